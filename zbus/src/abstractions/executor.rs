@@ -92,6 +92,20 @@ impl<'a> Executor<'a> {
         }
     }
 
+    /// Runs a single task, if one is scheduled, without waiting.
+    ///
+    /// Returns `true` if a task was run. This is useful for driving the executor from an external
+    /// event loop. With `tokio` feature enabled, it's a noop and always returns `false`.
+    pub fn try_tick(&self) -> bool {
+        #[cfg(not(feature = "tokio"))]
+        {
+            self.executor.try_tick()
+        }
+
+        #[cfg(feature = "tokio")]
+        false
+    }
+
     /// Create a new `Executor`.
     pub(crate) fn new() -> Self {
         #[cfg(not(feature = "tokio"))]
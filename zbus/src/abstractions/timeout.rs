@@ -0,0 +1,43 @@
+use std::{future::Future, time::Duration};
+
+use crate::{Error, Result};
+
+/// Await `future` for at most `duration`.
+///
+/// If `future` doesn't resolve in time, it's dropped and [`Error::Timeout`] is returned.
+pub(crate) async fn timeout<F, T>(future: F, duration: Duration) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    #[cfg(not(feature = "tokio"))]
+    {
+        use futures_util::{
+            future::{select, Either},
+            pin_mut,
+        };
+
+        let timer = async_io::Timer::after(duration);
+        pin_mut!(future);
+
+        match select(future, timer).await {
+            Either::Left((res, _)) => res,
+            Either::Right(_) => Err(Error::Timeout),
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    {
+        tokio::time::timeout(duration, future)
+            .await
+            .map_err(|_| Error::Timeout)?
+    }
+}
+
+/// Wait for `duration` to elapse.
+pub(crate) async fn sleep(duration: Duration) {
+    #[cfg(not(feature = "tokio"))]
+    async_io::Timer::after(duration).await;
+
+    #[cfg(feature = "tokio")]
+    tokio::time::sleep(duration).await;
+}
@@ -0,0 +1,86 @@
+use async_trait::async_trait;
+use std::fmt::Debug;
+
+use crate::{fdo, names::InterfaceName, Connection, Message};
+
+/// A hook deciding whether the method calls received by the [`ObjectServer`] are dispatched.
+///
+/// The policy is registered with [`ConnectionBuilder::access_policy`] and checked for each method
+/// call, once the method is found and right before it's called. This gives a single place to
+/// enforce access control in system services, rather than in every method. The header of the
+/// message gives the sender, the object path and the member called, and the connection can be
+/// used to query the credentials of the sender (see [`fdo::DBusProxy::get_connection_credentials`])
+/// or to ask an authorization agent such as polkit.
+///
+/// This applies to all the method calls, including the ones of the standard interfaces (e.g
+/// `org.freedesktop.DBus.Peer`). The policy is given the interface the call resolves to, which is
+/// not always the `INTERFACE` field of the message: that field is optional, and the `Get`, `Set`
+/// and `GetAll` methods of `org.freedesktop.DBus.Properties` are about the interface given in
+/// their arguments.
+///
+/// # Example
+///
+/// Only allow the `root` user to call the methods of a given interface:
+///
+/// ```no_run
+/// # use zbus::{
+/// #     block_on, export::async_trait::async_trait, fdo, names::InterfaceName, AccessPolicy,
+/// #     Connection, ConnectionBuilder, Message,
+/// # };
+/// #
+/// #[derive(Debug)]
+/// struct RootOnly;
+///
+/// #[async_trait]
+/// impl AccessPolicy for RootOnly {
+///     async fn check(
+///         &self,
+///         conn: &Connection,
+///         msg: &Message,
+///         interface: &InterfaceName<'_>,
+///     ) -> fdo::Result<()> {
+///         // This also covers the properties of the interface.
+///         if *interface != "org.example.Admin" {
+///             return Ok(());
+///         }
+///         let sender = msg.header()?.sender()?.cloned().ok_or(zbus::Error::MissingField)?;
+///         let uid = fdo::DBusProxy::new(conn)
+///             .await?
+///             .get_connection_unix_user(sender.into())
+///             .await?;
+///
+///         match uid {
+///             0 => Ok(()),
+///             _ => Err(fdo::Error::AccessDenied("only root is allowed".into())),
+///         }
+///     }
+/// }
+///
+/// # block_on(async {
+/// let conn = ConnectionBuilder::system()?
+///     .access_policy(RootOnly)
+///     .build()
+///     .await?;
+///
+/// // Serve something useful through `conn`..
+/// # drop(conn);
+/// # Ok::<_, zbus::Error>(())
+/// # }).unwrap();
+/// ```
+///
+/// [`ObjectServer`]: crate::ObjectServer
+/// [`ConnectionBuilder::access_policy`]: crate::ConnectionBuilder::access_policy
+#[async_trait]
+pub trait AccessPolicy: Debug + Send + Sync + 'static {
+    /// Check whether the method call `msg`, received on `conn` and targeting `interface`, is
+    /// allowed.
+    ///
+    /// Return `Ok(())` to dispatch the call. Otherwise, the error is sent back to the caller as
+    /// the reply (typically [`fdo::Error::AccessDenied`]) and the call isn't dispatched.
+    async fn check(
+        &self,
+        conn: &Connection,
+        msg: &Message,
+        interface: &InterfaceName<'_>,
+    ) -> fdo::Result<()>;
+}
@@ -186,6 +186,21 @@ pub(crate) enum Stream {
     Vsock(VsockStream),
 }
 
+impl From<Stream> for Box<dyn crate::Socket> {
+    fn from(stream: Stream) -> Self {
+        match stream {
+            #[cfg(any(unix, not(feature = "tokio")))]
+            Stream::Unix(stream) => Box::new(stream),
+            Stream::Tcp(stream) => Box::new(stream),
+            #[cfg(any(
+                all(feature = "vsock", not(feature = "tokio")),
+                feature = "tokio-vsock"
+            ))]
+            Stream::Vsock(stream) => Box::new(stream),
+        }
+    }
+}
+
 #[cfg(not(feature = "tokio"))]
 async fn connect_tcp(addr: TcpAddress) -> Result<Async<TcpStream>> {
     let addrs = crate::Task::spawn_blocking(
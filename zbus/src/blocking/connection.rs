@@ -1,15 +1,22 @@
 use enumflags2::BitFlags;
 use event_listener::EventListener;
+use futures_util::StreamExt;
 use static_assertions::assert_impl_all;
-use std::{convert::TryInto, io, ops::Deref, sync::Arc};
-use zbus_names::{BusName, ErrorName, InterfaceName, MemberName, OwnedUniqueName, WellKnownName};
+#[cfg(unix)]
+use std::os::unix::io::RawFd;
+use std::{collections::HashSet, convert::TryInto, io, ops::Deref, sync::Arc, time::Duration};
+use zbus_names::{
+    BusName, ErrorName, InterfaceName, MemberName, OwnedUniqueName, OwnedWellKnownName,
+    WellKnownName,
+};
 use zvariant::ObjectPath;
 
 use crate::{
-    blocking::ObjectServer,
-    fdo::{ConnectionCredentials, RequestNameFlags, RequestNameReply},
+    blocking::{MessageIterator, ObjectServer},
+    fdo::{ConnectionCredentials, ReleaseNameReply, RequestNameFlags, RequestNameReply},
     utils::block_on,
-    DBusError, Error, Message, Result,
+    ConnectionEvent, ConnectionStats, DBusError, Error, MatchRule, Message, MessageHeader,
+    MethodFlags, OwnedMatchRule, Result,
 };
 
 /// A blocking wrapper of [`zbus::Connection`].
@@ -38,6 +45,11 @@ impl Connection {
         block_on(crate::Connection::system()).map(Self::from)
     }
 
+    /// The default timeout for method calls, if any.
+    pub fn method_timeout(&self) -> Option<Duration> {
+        self.inner.method_timeout()
+    }
+
     /// The capacity of the main (unfiltered) queue.
     pub fn max_queued(&self) -> usize {
         self.inner.max_queued()
@@ -58,6 +70,13 @@ impl Connection {
         self.inner.unique_name()
     }
 
+    /// The unique name currently assigned to the connection.
+    ///
+    /// See [`crate::Connection::current_unique_name`] for details.
+    pub fn current_unique_name(&self) -> Option<OwnedUniqueName> {
+        self.inner.current_unique_name()
+    }
+
     /// Send `msg` to the peer.
     ///
     /// The connection sets a unique serial number on the message before sending it off.
@@ -67,6 +86,49 @@ impl Connection {
         block_on(self.inner.send_message(msg))
     }
 
+    /// Send `msg`, a method call, to the peer and wait for its reply.
+    ///
+    /// See [`crate::Connection::send_message_with_reply`] for details.
+    pub fn send_message_with_reply(&self, msg: Message) -> Result<Arc<Message>> {
+        block_on(async { self.inner.send_message_with_reply(msg).await?.reply().await })
+    }
+
+    /// Wait for all the queued outgoing messages to be written to the socket.
+    ///
+    /// See [`crate::Connection::flush`] for details.
+    pub fn flush(&self) -> Result<()> {
+        block_on(self.inner.flush())
+    }
+
+    /// Block until the connection is closed.
+    ///
+    /// See [`crate::Connection::closed`] for details.
+    pub fn closed(&self) {
+        block_on(self.inner.closed())
+    }
+
+    /// Write out as many of the queued outgoing messages as possible, without blocking.
+    ///
+    /// See [`crate::Connection::try_flush`] for details.
+    pub fn try_flush(&self) -> Result<bool> {
+        self.inner.try_flush()
+    }
+
+    /// The file descriptor of the underlying socket.
+    ///
+    /// See [`crate::Connection::raw_fd`] for details.
+    #[cfg(unix)]
+    pub fn raw_fd(&self) -> Option<RawFd> {
+        self.inner.raw_fd()
+    }
+
+    /// Gracefully close the connection.
+    ///
+    /// See [`crate::Connection::close`] for details.
+    pub fn close(self) -> Result<()> {
+        block_on(self.inner.close())
+    }
+
     /// Send a method call.
     ///
     /// Create a method-call message, send it over the connection, then wait for the reply. Incoming
@@ -103,6 +165,72 @@ impl Connection {
         )
     }
 
+    /// Send a method call and wait at most `timeout` for the reply.
+    ///
+    /// See [`crate::Connection::call_method_with_timeout`] for details.
+    pub fn call_method_with_timeout<'d, 'p, 'i, 'm, D, P, I, M, B>(
+        &self,
+        destination: Option<D>,
+        path: P,
+        iface: Option<I>,
+        method_name: M,
+        timeout: Duration,
+        body: &B,
+    ) -> Result<Arc<Message>>
+    where
+        D: TryInto<BusName<'d>>,
+        P: TryInto<ObjectPath<'p>>,
+        I: TryInto<InterfaceName<'i>>,
+        M: TryInto<MemberName<'m>>,
+        D::Error: Into<Error>,
+        P::Error: Into<Error>,
+        I::Error: Into<Error>,
+        M::Error: Into<Error>,
+        B: serde::ser::Serialize + zvariant::DynamicType,
+    {
+        block_on(self.inner.call_method_with_timeout(
+            destination,
+            path,
+            iface,
+            method_name,
+            timeout,
+            body,
+        ))
+    }
+
+    /// Send a method call, with the given flags.
+    ///
+    /// See [`crate::Connection::call_method_with_flags`] for details.
+    pub fn call_method_with_flags<'d, 'p, 'i, 'm, D, P, I, M, B>(
+        &self,
+        destination: Option<D>,
+        path: P,
+        iface: Option<I>,
+        method_name: M,
+        flags: BitFlags<MethodFlags>,
+        body: &B,
+    ) -> Result<Option<Arc<Message>>>
+    where
+        D: TryInto<BusName<'d>>,
+        P: TryInto<ObjectPath<'p>>,
+        I: TryInto<InterfaceName<'i>>,
+        M: TryInto<MemberName<'m>>,
+        D::Error: Into<Error>,
+        P::Error: Into<Error>,
+        I::Error: Into<Error>,
+        M::Error: Into<Error>,
+        B: serde::ser::Serialize + zvariant::DynamicType,
+    {
+        block_on(self.inner.call_method_with_flags(
+            destination,
+            path,
+            iface,
+            method_name,
+            flags,
+            body,
+        ))
+    }
+
     /// Emit a signal.
     ///
     /// Create a signal message, and send it over the connection.
@@ -217,6 +345,88 @@ impl Connection {
         block_on(self.inner.release_name(well_known_name))
     }
 
+    /// Deregister a well-known name for this service on the bus, and return the reply of the bus.
+    ///
+    /// See [`crate::Connection::release_name_with_reply`] for details.
+    pub fn release_name_with_reply<'w, W>(&self, well_known_name: W) -> Result<ReleaseNameReply>
+    where
+        W: TryInto<WellKnownName<'w>>,
+        W::Error: Into<Error>,
+    {
+        block_on(self.inner.release_name_with_reply(well_known_name))
+    }
+
+    /// The well-known names currently owned by this connection.
+    ///
+    /// See [`crate::Connection::owned_names`] for details.
+    pub fn owned_names(&self) -> HashSet<OwnedWellKnownName> {
+        self.inner.owned_names()
+    }
+
+    /// Introspect the object at `path` of `destination`, and return the XML description.
+    ///
+    /// See [`crate::Connection::introspect`] for details, including how the result is cached.
+    pub fn introspect<'d, 'p, D, P>(&self, destination: Option<D>, path: P) -> Result<String>
+    where
+        D: TryInto<BusName<'d>>,
+        P: TryInto<ObjectPath<'p>>,
+        D::Error: Into<Error>,
+        P::Error: Into<Error>,
+    {
+        block_on(self.inner.introspect(destination, path))
+    }
+
+    /// Drop the cached description of the object at `path` of `destination`, if any.
+    ///
+    /// See [`crate::Connection::invalidate_introspection`] for details.
+    pub fn invalidate_introspection<'d, 'p, D, P>(
+        &self,
+        destination: Option<D>,
+        path: P,
+    ) -> Result<bool>
+    where
+        D: TryInto<BusName<'d>>,
+        P: TryInto<ObjectPath<'p>>,
+        D::Error: Into<Error>,
+        P::Error: Into<Error>,
+    {
+        self.inner.invalidate_introspection(destination, path)
+    }
+
+    /// Drop all the object descriptions cached by [`Connection::introspect`].
+    pub fn clear_introspection_cache(&self) {
+        self.inner.clear_introspection_cache()
+    }
+
+    /// Add a match rule to this connection.
+    ///
+    /// See [`crate::Connection::add_match`] for details.
+    pub fn add_match<R>(&self, rule: R) -> Result<()>
+    where
+        R: Into<OwnedMatchRule>,
+    {
+        block_on(self.inner.add_match(rule))
+    }
+
+    /// Remove a match rule previously added to this connection.
+    ///
+    /// See [`crate::Connection::remove_match`] for details.
+    pub fn remove_match<R>(&self, rule: R) -> Result<bool>
+    where
+        R: Into<OwnedMatchRule>,
+    {
+        block_on(self.inner.remove_match(rule))
+    }
+
+    /// Turn `self` into a [monitor] connection and return an iterator over the captured messages.
+    ///
+    /// See [`crate::Connection::become_monitor`] for details.
+    ///
+    /// [monitor]: https://dbus.freedesktop.org/doc/dbus-specification.html#bus-messages-become-monitor
+    pub fn become_monitor(self, rules: &[MatchRule<'_>]) -> Result<MessageIterator> {
+        block_on(self.inner.become_monitor(rules)).map(MessageIterator::from)
+    }
+
     /// Checks if `self` is a connection to a message bus.
     ///
     /// This will return `false` for p2p connections.
@@ -231,6 +441,13 @@ impl Connection {
         self.inner.sync_object_server(true, None)
     }
 
+    /// Stop dispatching the incoming method calls to the [`ObjectServer`].
+    ///
+    /// See [`crate::Connection::stop_object_server`] for details.
+    pub fn stop_object_server(&self) {
+        block_on(self.inner.stop_object_server())
+    }
+
     /// Get a reference to the underlying async Connection.
     pub fn inner(&self) -> &crate::Connection {
         &self.inner
@@ -241,6 +458,22 @@ impl Connection {
         self.inner
     }
 
+    /// Statistics about the activity on this connection.
+    ///
+    /// See [`crate::Connection::stats`] for details.
+    pub fn stats(&self) -> ConnectionStats {
+        block_on(self.inner.stats())
+    }
+
+    /// An iterator over the [`ConnectionEvent`]s of this connection.
+    ///
+    /// See [`crate::Connection::receive_events`] for details.
+    pub fn receive_events(&self) -> impl Iterator<Item = ConnectionEvent> + Send + 'static {
+        let mut events = self.inner.receive_events();
+
+        std::iter::from_fn(move || block_on(events.next()))
+    }
+
     /// Returns a listener, notified on various connection activity.
     ///
     /// This function is meant for the caller to implement idle or timeout on inactivity.
@@ -259,6 +492,13 @@ impl Connection {
     pub fn peer_credentials(&self) -> io::Result<ConnectionCredentials> {
         block_on(self.inner.peer_credentials())
     }
+
+    /// Returns the credentials of the sender of a message.
+    ///
+    /// See [`crate::Connection::caller_credentials`] for details.
+    pub fn caller_credentials(&self, header: &MessageHeader<'_>) -> Result<ConnectionCredentials> {
+        block_on(self.inner.caller_credentials(header))
+    }
 }
 
 impl From<crate::Connection> for Connection {
@@ -1,9 +1,10 @@
+use enumflags2::BitFlags;
 use static_assertions::assert_impl_all;
-use std::convert::TryInto;
 #[cfg(not(feature = "tokio"))]
 use std::net::TcpStream;
 #[cfg(all(unix, not(feature = "tokio")))]
 use std::os::unix::net::UnixStream;
+use std::{convert::TryInto, time::Duration};
 #[cfg(feature = "tokio")]
 use tokio::net::TcpStream;
 #[cfg(all(unix, feature = "tokio"))]
@@ -16,9 +17,12 @@ use zvariant::{ObjectPath, Str};
 use crate::{
     address::Address,
     blocking::Connection,
+    fdo::RequestNameFlags,
     names::{UniqueName, WellKnownName},
+    raw::Socket,
     utils::block_on,
-    AuthMechanism, Error, Guid, Interface, Result,
+    AccessPolicy, AuthMechanism, DispatchObserver, Error, Guid, HandshakeOutcome, Interface,
+    MessageInterceptor, OutgoingQueuePolicy, Result,
 };
 
 /// A builder for [`zbus::blocking::Connection`].
@@ -68,11 +72,35 @@ impl<'a> ConnectionBuilder<'a> {
         Self(crate::ConnectionBuilder::tcp_stream(stream))
     }
 
+    /// Create a builder for connection that will use the given socket.
+    pub fn socket<S: Socket + 'static>(socket: S) -> Self {
+        Self(crate::ConnectionBuilder::socket(socket))
+    }
+
+    /// Create a builder for connection that will use the given, already authenticated, socket.
+    ///
+    /// See [`crate::ConnectionBuilder::authenticated_socket`] for details.
+    pub fn authenticated_socket<S: Socket + 'static>(socket: S, outcome: HandshakeOutcome) -> Self {
+        Self(crate::ConnectionBuilder::authenticated_socket(
+            socket, outcome,
+        ))
+    }
+
     /// Specify the mechanisms to use during authentication.
     pub fn auth_mechanisms(self, auth_mechanisms: &[AuthMechanism]) -> Self {
         Self(self.0.auth_mechanisms(auth_mechanisms))
     }
 
+    /// The identity to authenticate as, with the `EXTERNAL` mechanism.
+    ///
+    /// See [`zbus::ConnectionBuilder::external_id`] for details.
+    pub fn external_id<I>(self, id: I) -> Self
+    where
+        I: Into<String>,
+    {
+        Self(self.0.external_id(id))
+    }
+
     /// The cookie context to use during authentication.
     ///
     /// This is only used when the `cookie` authentication mechanism is enabled and only valid for
@@ -136,6 +164,116 @@ impl<'a> ConnectionBuilder<'a> {
         Self(self.0.max_queued(max))
     }
 
+    /// Set the capacity of the outgoing message queue.
+    ///
+    /// Once `max` messages are waiting to be written out, sending further messages blocks until
+    /// there is room in the queue again, unless another
+    /// [`ConnectionBuilder::outgoing_queue_policy`] is set. By default, the outgoing queue is
+    /// unbounded.
+    pub fn max_outgoing_queued(self, max: usize) -> Self {
+        Self(self.0.max_outgoing_queued(max))
+    }
+
+    /// Set what happens when sending a message while the outgoing queue is full.
+    ///
+    /// See [`zbus::ConnectionBuilder::outgoing_queue_policy`] for details.
+    pub fn outgoing_queue_policy(self, policy: OutgoingQueuePolicy) -> Self {
+        Self(self.0.outgoing_queue_policy(policy))
+    }
+
+    /// Set the maximum size (in bytes) of incoming messages.
+    ///
+    /// Receiving a larger message results in [`Error::MessageTooLarge`]. Values above the 128 MiB
+    /// maximum of the D-Bus specification, which is also the default, are capped to it.
+    pub fn max_message_size(self, max: usize) -> Self {
+        Self(self.0.max_message_size(max))
+    }
+
+    /// Set the maximum number of file descriptors an incoming message can carry.
+    ///
+    /// Receiving a message with more file descriptors results in [`Error::TooManyFds`]. By
+    /// default, there is no limit.
+    #[cfg(unix)]
+    pub fn max_unix_fds(self, max: usize) -> Self {
+        Self(self.0.max_unix_fds(max))
+    }
+
+    /// Set a timeout for the authentication handshake.
+    ///
+    /// If the peer doesn't complete the handshake within `timeout`, [`ConnectionBuilder::build`]
+    /// fails with [`Error::Timeout`].
+    ///
+    /// By default, there is no timeout.
+    pub fn handshake_timeout(self, timeout: Duration) -> Self {
+        Self(self.0.handshake_timeout(timeout))
+    }
+
+    /// Set a timeout for the whole connection setup.
+    ///
+    /// See [`zbus::ConnectionBuilder::connect_timeout`] for details.
+    ///
+    /// By default, there is no timeout.
+    pub fn connect_timeout(self, timeout: Duration) -> Self {
+        Self(self.0.connect_timeout(timeout))
+    }
+
+    /// Regularly check if the peer is still responsive.
+    ///
+    /// See [`zbus::ConnectionBuilder::keepalive`] for details.
+    ///
+    /// By default, keepalive is disabled.
+    pub fn keepalive(self, interval: Duration) -> Self {
+        Self(self.0.keepalive(interval))
+    }
+
+    /// Register a hook to observe or rewrite the messages going through the connection.
+    ///
+    /// See [`zbus::ConnectionBuilder::interceptor`] for details.
+    pub fn interceptor<I>(self, interceptor: I) -> Self
+    where
+        I: MessageInterceptor,
+    {
+        Self(self.0.interceptor(interceptor))
+    }
+
+    /// Set the policy deciding whether the method calls are dispatched to the object server.
+    ///
+    /// See [`zbus::ConnectionBuilder::access_policy`] for details.
+    pub fn access_policy<P>(self, policy: P) -> Self
+    where
+        P: AccessPolicy,
+    {
+        Self(self.0.access_policy(policy))
+    }
+
+    /// Set the hook notified of each method call handled by the object server.
+    ///
+    /// See [`zbus::ConnectionBuilder::dispatch_observer`] for details.
+    pub fn dispatch_observer<O>(self, observer: O) -> Self
+    where
+        O: DispatchObserver,
+    {
+        Self(self.0.dispatch_observer(observer))
+    }
+
+    /// Set the default timeout for method calls on the connection.
+    ///
+    /// See [`zbus::ConnectionBuilder::method_timeout`] for details.
+    ///
+    /// By default, there is no timeout.
+    pub fn method_timeout(self, timeout: Duration) -> Self {
+        Self(self.0.method_timeout(timeout))
+    }
+
+    /// Enable or disable automatic reconnection.
+    ///
+    /// See [`zbus::ConnectionBuilder::auto_reconnect`] for details.
+    ///
+    /// Disabled by default.
+    pub fn auto_reconnect(self, enabled: bool) -> Self {
+        Self(self.0.auto_reconnect(enabled))
+    }
+
     /// Register a D-Bus [`Interface`] to be served at a given path.
     ///
     /// This is similar to [`zbus::blocking::ObjectServer::at`], except that it allows you to have
@@ -165,6 +303,37 @@ impl<'a> ConnectionBuilder<'a> {
         self.0.name(well_known_name).map(Self)
     }
 
+    /// Register a well-known name for this connection on the bus, with the given flags.
+    ///
+    /// This is similar to [`zbus::blocking::Connection::request_name_with_flags`], except the name
+    /// is requested as part of the connection setup, just like with [`ConnectionBuilder::name`].
+    /// Unlike [`ConnectionBuilder::name`], no error is returned if the bus puts the connection in
+    /// the queue for the name.
+    pub fn name_with_flags<W>(
+        self,
+        well_known_name: W,
+        flags: BitFlags<RequestNameFlags>,
+    ) -> Result<Self>
+    where
+        W: TryInto<WellKnownName<'a>>,
+        W::Error: Into<Error>,
+    {
+        self.0.name_with_flags(well_known_name, flags).map(Self)
+    }
+
+    /// Register a well-known name on the bus, bound to the object server.
+    ///
+    /// This is similar to [`ConnectionBuilder::name`], except the name is requested through
+    /// [`zbus::blocking::ObjectServer::request_name`] and hence released once the last interface is
+    /// removed from the object server. See [`zbus::ConnectionBuilder::serve_name`] for details.
+    pub fn serve_name<W>(self, well_known_name: W) -> Result<Self>
+    where
+        W: TryInto<WellKnownName<'a>>,
+        W::Error: Into<Error>,
+    {
+        self.0.serve_name(well_known_name).map(Self)
+    }
+
     /// Sets the unique name of the connection.
     ///
     /// # Panics
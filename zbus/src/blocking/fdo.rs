@@ -4,7 +4,10 @@
 
 use enumflags2::BitFlags;
 use static_assertions::assert_impl_all;
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    convert::{TryFrom, TryInto},
+};
 use zbus_names::{
     BusName, InterfaceName, OwnedBusName, OwnedInterfaceName, OwnedUniqueName, UniqueName,
     WellKnownName,
@@ -14,10 +17,11 @@ use zvariant::{ObjectPath, Optional, OwnedValue, Value};
 use crate::{
     dbus_proxy,
     fdo::{
-        ConnectionCredentials, ManagedObjects, ReleaseNameReply, RequestNameFlags,
-        RequestNameReply, Result,
+        BusConnectionStats, BusStats, ConnectionCredentials, ManagedObjects, ReleaseNameReply,
+        RequestNameFlags, RequestNameReply, Result, StartServiceReply,
     },
-    Guid,
+    utils::block_on,
+    Guid, ProxyInterface,
 };
 
 gen_introspectable_proxy!(false, true);
@@ -26,9 +30,70 @@ assert_impl_all!(IntrospectableProxy<'_>: Send, Sync, Unpin);
 gen_properties_proxy!(false, true);
 assert_impl_all!(PropertiesProxy<'_>: Send, Sync, Unpin);
 
+impl<'a> PropertiesProxy<'a> {
+    /// Get the value of a property, converted to `T`.
+    ///
+    /// See [`crate::fdo::PropertiesProxy::get_as`] for details.
+    pub fn get_as<T>(
+        &self,
+        interface_name: InterfaceName<'_>,
+        property_name: &str,
+    ) -> zbus::Result<T>
+    where
+        T: TryFrom<OwnedValue>,
+        T::Error: Into<zbus::Error>,
+    {
+        let value = self.get(interface_name, property_name)?;
+
+        T::try_from(value).map_err(Into::into)
+    }
+
+    /// Set the value of a property, from anything convertible to a [`enum@Value`].
+    ///
+    /// See [`crate::fdo::PropertiesProxy::set_value`] for details.
+    pub fn set_value<'v, V>(
+        &self,
+        interface_name: InterfaceName<'_>,
+        property_name: &str,
+        value: V,
+    ) -> zbus::Result<()>
+    where
+        V: Into<Value<'v>>,
+    {
+        self.set(interface_name, property_name, &value.into())
+            .map_err(Into::into)
+    }
+}
+
 gen_object_manager_proxy!(false, true);
 assert_impl_all!(ObjectManagerProxy<'_>: Send, Sync, Unpin);
 
+impl<'a> ObjectManagerProxy<'a> {
+    /// Create a `P` proxy for each managed object implementing its interface.
+    ///
+    /// See [`crate::fdo::ObjectManagerProxy::managed_proxies`] for details.
+    pub fn managed_proxies<P>(&self) -> zbus::Result<Vec<P>>
+    where
+        P: ProxyInterface + From<crate::Proxy<'static>>,
+    {
+        let objects = self.get_managed_objects()?;
+
+        crate::fdo::managed_proxies(self.inner().inner(), objects)
+    }
+
+    /// Create a `P` proxy for the managed object at `path`.
+    ///
+    /// See [`crate::fdo::ObjectManagerProxy::managed_proxy`] for details.
+    pub fn managed_proxy<P, O>(&self, path: O) -> zbus::Result<P>
+    where
+        P: ProxyInterface + From<crate::Proxy<'static>>,
+        O: TryInto<ObjectPath<'static>>,
+        O::Error: Into<zbus::Error>,
+    {
+        crate::fdo::managed_proxy(self.inner().inner(), path.try_into().map_err(Into::into)?)
+    }
+}
+
 gen_peer_proxy!(false, true);
 assert_impl_all!(PeerProxy<'_>: Send, Sync, Unpin);
 
@@ -40,3 +105,71 @@ assert_impl_all!(StatsProxy<'_>: Send, Sync, Unpin);
 
 gen_dbus_proxy!(false, true);
 assert_impl_all!(DBusProxy<'_>: Send, Sync, Unpin);
+
+impl<'a> DBusProxy<'a> {
+    /// Create an iterator for the `NameOwnerChanged` signals about the name `name` only.
+    ///
+    /// See [`crate::fdo::DBusProxy::receive_name_owner_changed_for`] for details.
+    pub fn receive_name_owner_changed_for<'n, N>(
+        &self,
+        name: N,
+    ) -> zbus::Result<NameOwnerChangedIterator<'static>>
+    where
+        N: TryInto<BusName<'n>>,
+        N::Error: Into<zbus::Error>,
+    {
+        let name = name.try_into().map_err(Into::into)?;
+
+        self.receive_name_owner_changed_with_args(&[(0, name.as_str())])
+    }
+
+    /// Create an iterator for the `NameAcquired` signals about the name `name` only.
+    pub fn receive_name_acquired_for<'n, N>(
+        &self,
+        name: N,
+    ) -> zbus::Result<NameAcquiredIterator<'static>>
+    where
+        N: TryInto<BusName<'n>>,
+        N::Error: Into<zbus::Error>,
+    {
+        let name = name.try_into().map_err(Into::into)?;
+
+        self.receive_name_acquired_with_args(&[(0, name.as_str())])
+    }
+
+    /// Create an iterator for the `NameLost` signals about the name `name` only.
+    pub fn receive_name_lost_for<'n, N>(&self, name: N) -> zbus::Result<NameLostIterator<'static>>
+    where
+        N: TryInto<BusName<'n>>,
+        N::Error: Into<zbus::Error>,
+    {
+        let name = name.try_into().map_err(Into::into)?;
+
+        self.receive_name_lost_with_args(&[(0, name.as_str())])
+    }
+
+    /// Start the service owning the name `name` if needed, and wait for it to be on the bus.
+    ///
+    /// See [`crate::fdo::DBusProxy::ensure_activated`] for details.
+    pub fn ensure_activated<'n, N>(&self, name: N) -> zbus::Result<OwnedUniqueName>
+    where
+        N: TryInto<WellKnownName<'n>>,
+        N::Error: Into<zbus::Error>,
+    {
+        let proxy = crate::fdo::DBusProxy::from(self.inner().inner().clone());
+
+        block_on(proxy.ensure_activated(name))
+    }
+
+    /// Copy the environment variables `names` of the current process to the environment of the
+    /// services activated by the bus.
+    ///
+    /// See [`crate::fdo::DBusProxy::propagate_environment`] for details.
+    pub fn propagate_environment(&self, names: &[&str]) -> zbus::Result<()> {
+        let values = crate::fdo::environment(names);
+        let environment = values.iter().map(|(k, v)| (*k, v.as_str())).collect();
+
+        self.update_activation_environment(environment)
+            .map_err(Into::into)
+    }
+}
@@ -100,6 +100,16 @@ impl MessageIterator {
         .map(|s| Self { azync: s })
     }
 
+    /// Take the next message out of the queue, if any, without blocking.
+    ///
+    /// See [`crate::MessageStream::try_receive`] for details.
+    pub fn try_receive(&mut self) -> Option<Result<Arc<Message>>> {
+        self.azync
+            .as_mut()
+            .expect("Inner stream is `None`")
+            .try_receive()
+    }
+
     /// The associated match rule, if any.
     pub fn match_rule(&self) -> Option<MatchRule<'_>> {
         self.azync
@@ -125,6 +135,14 @@ impl From<Connection> for MessageIterator {
     }
 }
 
+impl From<crate::MessageStream> for MessageIterator {
+    fn from(stream: crate::MessageStream) -> Self {
+        Self {
+            azync: Some(stream),
+        }
+    }
+}
+
 impl From<&Connection> for MessageIterator {
     fn from(conn: &Connection) -> Self {
         Self::from(conn.clone())
@@ -5,6 +5,32 @@
 //! asynchronous counterparts on the underlying types and use [`async_io::block_on`] to turn them
 //! into blocking calls.
 //!
+//! This makes it possible to use zbus from non-async code, without having to set up an executor
+//! yourself. Proxies generated by [`crate::dbus_proxy`] come with a blocking variant as well,
+//! suffixed with `Blocking`:
+//!
+//! ```no_run
+//! use zbus::{blocking::Connection, dbus_proxy, Result};
+//!
+//! #[dbus_proxy(
+//!     interface = "org.freedesktop.systemd1.Manager",
+//!     default_service = "org.freedesktop.systemd1",
+//!     default_path = "/org/freedesktop/systemd1"
+//! )]
+//! trait SystemdManager {
+//!     #[dbus_proxy(property)]
+//!     fn architecture(&self) -> Result<String>;
+//! }
+//!
+//! fn main() -> Result<()> {
+//!     let connection = Connection::system()?;
+//!     let proxy = SystemdManagerProxyBlocking::new(&connection)?;
+//!     println!("Host architecture: {}", proxy.architecture()?);
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
 //! # Caveats
 //!
 //! Since methods provided by these types run their own little runtime (`block_on`), you must not
@@ -1,10 +1,12 @@
-use std::{convert::TryInto, ops::Deref};
+use std::{collections::HashMap, convert::TryInto, ops::Deref};
 
 use static_assertions::assert_impl_all;
+use zbus_names::{InterfaceName, WellKnownName};
 use zvariant::ObjectPath;
 
 use crate::{
-    utils::block_on, Error, Interface, InterfaceDeref, InterfaceDerefMut, Result, SignalContext,
+    blocking::Proxy, fdo, utils::block_on, DispatchStats, Error, FactoryPolicy, Interface,
+    InterfaceDeref, InterfaceDerefMut, Result, SignalContext,
 };
 
 /// Wrapper over an interface, along with its corresponding `SignalContext`
@@ -68,6 +70,9 @@ where
         block_on(self.azync.get_mut())
     }
 
+    /// The signal context for the object path of the interface.
+    ///
+    /// See [`crate::InterfaceRef::signal_context`] for details.
     pub fn signal_context(&self) -> &SignalContext<'static> {
         self.azync.signal_context()
     }
@@ -169,6 +174,121 @@ impl ObjectServer {
         block_on(self.azync.remove::<I, P>(path))
     }
 
+    /// Unregister all the interfaces of the object at the given path.
+    ///
+    /// See [`crate::ObjectServer::remove_object`] for details.
+    pub fn remove_object<'p, P>(&self, path: P) -> Result<bool>
+    where
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+    {
+        block_on(self.azync.remove_object(path))
+    }
+
+    /// Register a D-Bus [`Interface`] for the objects below a given path.
+    ///
+    /// See [`crate::ObjectServer::at_fallback`] for details.
+    pub fn at_fallback<'p, P, I>(&self, path: P, iface: I) -> Result<bool>
+    where
+        I: Interface,
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+    {
+        block_on(self.azync.at_fallback(path, iface))
+    }
+
+    /// Unregister a D-Bus [`Interface`] registered with [`ObjectServer::at_fallback`].
+    ///
+    /// See [`crate::ObjectServer::remove_fallback`] for details.
+    pub fn remove_fallback<'p, I, P>(&self, path: P) -> Result<bool>
+    where
+        I: Interface,
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+    {
+        block_on(self.azync.remove_fallback::<I, P>(path))
+    }
+
+    /// Forward the method calls for an interface at a given path to another object.
+    ///
+    /// See [`crate::ObjectServer::forward`] for details.
+    pub fn forward<'p, P>(&self, path: P, proxy: Proxy<'static>) -> Result<bool>
+    where
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+    {
+        block_on(self.azync.forward(path, proxy.into_inner()))
+    }
+
+    /// Stop forwarding an interface registered with [`ObjectServer::forward`].
+    ///
+    /// See [`crate::ObjectServer::remove_forward`] for details.
+    pub fn remove_forward<'p, 'i, P, I>(&self, path: P, interface: I) -> Result<bool>
+    where
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+        I: TryInto<InterfaceName<'i>>,
+        I::Error: Into<Error>,
+    {
+        block_on(self.azync.remove_forward(path, interface))
+    }
+
+    /// Register a factory, creating a D-Bus [`Interface`] for the objects whose path matches a
+    /// template.
+    ///
+    /// See [`crate::ObjectServer::at_factory`] for details.
+    pub fn at_factory<F, I>(
+        &self,
+        template: &str,
+        policy: FactoryPolicy,
+        factory: F,
+    ) -> Result<bool>
+    where
+        I: Interface,
+        F: Fn(&ObjectPath<'_>, &HashMap<&str, &str>) -> fdo::Result<I> + Send + Sync + 'static,
+    {
+        block_on(self.azync.at_factory(template, policy, factory))
+    }
+
+    /// Unregister a factory registered with [`ObjectServer::at_factory`].
+    ///
+    /// See [`crate::ObjectServer::remove_factory`] for details.
+    pub fn remove_factory<I>(&self, template: &str) -> Result<bool>
+    where
+        I: Interface,
+    {
+        block_on(self.azync.remove_factory::<I>(template))
+    }
+
+    /// Get the statistics about the method calls dispatched so far.
+    ///
+    /// See [`crate::ObjectServer::dispatch_stats`] for details.
+    pub fn dispatch_stats(&self) -> DispatchStats {
+        self.azync.dispatch_stats()
+    }
+
+    /// Register a well-known name on the bus, bound to the object server.
+    ///
+    /// See [`crate::ObjectServer::request_name`] for details.
+    pub fn request_name<'w, W>(&self, well_known_name: W) -> Result<()>
+    where
+        W: TryInto<WellKnownName<'w>>,
+        W::Error: Into<Error>,
+    {
+        block_on(self.azync.request_name(well_known_name))
+    }
+
+    /// Release a well-known name bound to the object server.
+    ///
+    /// See [`crate::ObjectServer::release_name`] for details.
+    pub fn release_name<'w, W>(&self, well_known_name: W) -> Result<bool>
+    where
+        W: TryInto<WellKnownName<'w>>,
+        W::Error: Into<Error>,
+    {
+        block_on(self.azync.release_name(well_known_name))
+    }
+
     /// Get the interface at the given path.
     ///
     /// # Errors
@@ -5,11 +5,14 @@ use std::{
     convert::{TryFrom, TryInto},
     ops::Deref,
     sync::Arc,
+    time::Duration,
 };
 use zbus_names::{BusName, InterfaceName, MemberName, UniqueName};
 use zvariant::{ObjectPath, OwnedValue, Value};
 
-use crate::{blocking::Connection, utils::block_on, Error, Message, MethodFlags, Result};
+use crate::{
+    blocking::Connection, utils::block_on, Error, MatchRule, Message, MethodFlags, Result,
+};
 
 use crate::fdo;
 
@@ -129,10 +132,22 @@ impl<'a> Proxy<'a> {
     }
 
     /// Get a reference to the destination service name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the proxy has no destination, which is only possible on peer-to-peer connections.
+    /// Use [`Proxy::optional_destination`] for such proxies.
     pub fn destination(&self) -> &BusName<'_> {
         self.inner().destination()
     }
 
+    /// Get a reference to the destination service name, if any.
+    ///
+    /// See [`crate::Proxy::optional_destination`] for details.
+    pub fn optional_destination(&self) -> Option<&BusName<'_>> {
+        self.inner().optional_destination()
+    }
+
     /// Get a reference to the object path.
     pub fn path(&self) -> &ObjectPath<'_> {
         self.inner().path()
@@ -143,6 +158,13 @@ impl<'a> Proxy<'a> {
         self.inner().interface()
     }
 
+    /// The timeout for the method calls made through the proxy, if any.
+    ///
+    /// See [`crate::Proxy::method_timeout`] for details.
+    pub fn method_timeout(&self) -> Option<Duration> {
+        self.inner().method_timeout()
+    }
+
     /// Introspect the associated object, and return the XML description.
     ///
     /// See the [xml](xml/index.html) module for parsing the result.
@@ -150,6 +172,14 @@ impl<'a> Proxy<'a> {
         block_on(self.inner().introspect())
     }
 
+    /// Introspect the associated object, and return the parsed description.
+    ///
+    /// See [`crate::Proxy::introspect_node`] for details.
+    #[cfg(feature = "quick-xml")]
+    pub fn introspect_node(&self) -> Result<crate::quick_xml::Node<'static>> {
+        block_on(self.inner().introspect_node())
+    }
+
     /// Get the cached value of the property `property_name`.
     ///
     /// This returns `None` if the property is not in the cache.  This could be because the cache
@@ -187,6 +217,16 @@ impl<'a> Proxy<'a> {
         block_on(self.inner().get_property(property_name))
     }
 
+    /// Get all the properties of the interface at once, as a `T`.
+    ///
+    /// See [`crate::Proxy::get_all_as`] for details.
+    pub fn get_all_as<T>(&self) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned + zvariant::Type,
+    {
+        block_on(self.inner().get_all_as())
+    }
+
     /// Set the property `property_name`.
     ///
     /// Effectively, call the `Set` method of the `org.freedesktop.DBus.Properties` interface.
@@ -213,6 +253,26 @@ impl<'a> Proxy<'a> {
         block_on(self.inner().call_method(method_name, body))
     }
 
+    /// Call a method and return the reply, waiting for it at most `timeout`.
+    ///
+    /// See [`crate::Proxy::call_method_with_timeout`] for details.
+    pub fn call_method_with_timeout<'m, M, B>(
+        &self,
+        method_name: M,
+        timeout: Duration,
+        body: &B,
+    ) -> Result<Arc<Message>>
+    where
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+        B: serde::ser::Serialize + zvariant::DynamicType,
+    {
+        block_on(
+            self.inner()
+                .call_method_with_timeout(method_name, timeout, body),
+        )
+    }
+
     /// Call a method and return the reply body.
     ///
     /// Use [`call_method`] instead if you need to deserialize the reply manually/separately.
@@ -228,6 +288,24 @@ impl<'a> Proxy<'a> {
         block_on(self.inner().call(method_name, body))
     }
 
+    /// Call a method and return the reply body, waiting for the reply at most `timeout`.
+    ///
+    /// See [`crate::Proxy::call_with_timeout`] for details.
+    pub fn call_with_timeout<'m, M, B, R>(
+        &self,
+        method_name: M,
+        timeout: Duration,
+        body: &B,
+    ) -> Result<R>
+    where
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+        B: serde::ser::Serialize + zvariant::DynamicType,
+        R: serde::de::DeserializeOwned + zvariant::Type,
+    {
+        block_on(self.inner().call_with_timeout(method_name, timeout, body))
+    }
+
     /// Call a method and return the reply body, optionally supplying a set of
     /// method flags to control the way the method call message is sent and handled.
     ///
@@ -301,6 +379,15 @@ impl<'a> Proxy<'a> {
             .map(SignalIterator)
     }
 
+    /// Same as [`Proxy::receive_signal`] but with all the filters of `rule`.
+    ///
+    /// See [`crate::Proxy::receive_signals_matching`] for details.
+    pub fn receive_signals_matching<'m>(&self, rule: MatchRule<'m>) -> Result<SignalIterator<'m>> {
+        block_on(self.inner().receive_signals_matching(rule))
+            .map(Some)
+            .map(SignalIterator)
+    }
+
     /// Create a stream for all signals emitted by this service.
     ///
     /// # Errors
@@ -314,14 +401,9 @@ impl<'a> Proxy<'a> {
             .map(SignalIterator)
     }
 
-    /// Get an iterator to receive owner changed events.
-    ///
-    /// If the proxy destination is a unique name, the stream will be notified of the peer
-    /// disconnection from the bus (with a `None` value).
+    /// Get an iterator to receive property changed events.
     ///
-    /// If the proxy destination is a well-known name, the stream will be notified whenever the name
-    /// owner is changed, either by a new peer being granted ownership (`Some` value) or when the
-    /// name is released (with a `None` value).
+    /// The first event is yielded right away, for the current value of the property.
     ///
     /// Note that zbus doesn't queue the updates. If the listener is slower than the receiver, it
     /// will only receive the last update.
@@ -332,7 +414,14 @@ impl<'a> Proxy<'a> {
         PropertyIterator(block_on(self.inner().receive_property_changed(name)))
     }
 
-    /// Get an iterator to receive property changed events.
+    /// Get an iterator to receive owner changed events.
+    ///
+    /// If the proxy destination is a unique name, the stream will be notified of the peer
+    /// disconnection from the bus (with a `None` value).
+    ///
+    /// If the proxy destination is a well-known name, the stream will be notified whenever the name
+    /// owner is changed, either by a new peer being granted ownership (`Some` value) or when the
+    /// name is released (with a `None` value).
     ///
     /// Note that zbus doesn't queue the updates. If the listener is slower than the receiver, it
     /// will only receive the last update.
@@ -340,6 +429,13 @@ impl<'a> Proxy<'a> {
         block_on(self.inner().receive_owner_changed()).map(OwnerChangedIterator)
     }
 
+    /// The unique name of the peer currently owning the destination, if any.
+    ///
+    /// See [`crate::Proxy::current_owner`] for details.
+    pub fn current_owner(&self) -> Result<Option<UniqueName<'static>>> {
+        block_on(self.inner().current_owner())
+    }
+
     /// Get a reference to the underlying async Proxy.
     pub fn inner(&self) -> &crate::Proxy<'a> {
         self.azync.as_ref().expect("Inner proxy is `None`")
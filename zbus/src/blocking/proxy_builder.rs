@@ -1,4 +1,4 @@
-use std::convert::TryInto;
+use std::{convert::TryInto, time::Duration};
 
 use static_assertions::assert_impl_all;
 use zbus_names::{BusName, InterfaceName};
@@ -6,7 +6,7 @@ use zvariant::ObjectPath;
 
 use crate::{blocking::Connection, utils::block_on, CacheProperties, Error, Result};
 
-pub use crate::ProxyDefault;
+pub use crate::{ProxyDefault, ProxyInterface};
 
 /// Builder for proxies.
 #[derive(Debug, Clone)]
@@ -62,6 +62,31 @@ impl<'a, T> ProxyBuilder<'a, T> {
         Self(self.0.uncached_properties(properties))
     }
 
+    /// Set the maximum number of signals to queue in each signal iterator of the proxy.
+    ///
+    /// See [`crate::ProxyBuilder::max_queued_signals`] for details.
+    #[must_use]
+    pub fn max_queued_signals(self, max: usize) -> Self {
+        Self(self.0.max_queued_signals(max))
+    }
+
+    /// Set the default timeout for the method calls made through the proxy.
+    ///
+    /// See [`crate::ProxyBuilder::method_timeout`] for details.
+    #[must_use]
+    pub fn method_timeout(self, timeout: Duration) -> Self {
+        Self(self.0.method_timeout(timeout))
+    }
+
+    /// Honor the `org.freedesktop.DBus.Property.EmitsChangedSignal` annotations of the properties.
+    ///
+    /// See [`crate::ProxyBuilder::honor_emits_changed_signal`] for details.
+    #[cfg(feature = "quick-xml")]
+    #[must_use]
+    pub fn honor_emits_changed_signal(self, honor: bool) -> Self {
+        Self(self.0.honor_emits_changed_signal(honor))
+    }
+
     /// Build a proxy from the builder.
     ///
     /// # Panics
@@ -73,6 +98,21 @@ impl<'a, T> ProxyBuilder<'a, T> {
     {
         block_on(self.0.build())
     }
+
+    /// Build a proxy from the builder, without any communication with the peer.
+    ///
+    /// See [`crate::ProxyBuilder::build_lazy`] for details.
+    ///
+    /// # Errors
+    ///
+    /// If the builder is lacking the necessary parameters to build a proxy,
+    /// [`Error::MissingParameter`](crate::Error::MissingParameter) is returned.
+    pub fn build_lazy(self) -> Result<T>
+    where
+        T: From<crate::Proxy<'a>>,
+    {
+        self.0.build_lazy()
+    }
 }
 
 impl<'a, T> ProxyBuilder<'a, T>
@@ -4,40 +4,53 @@ use event_listener::{Event, EventListener};
 use once_cell::sync::OnceCell;
 use ordered_stream::{OrderedFuture, OrderedStream, PollResult};
 use static_assertions::assert_impl_all;
+#[cfg(unix)]
+use std::os::unix::io::RawFd;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet, VecDeque},
     convert::TryInto,
     io::{self, ErrorKind},
     ops::Deref,
     pin::Pin,
     sync::{
         self,
-        atomic::{AtomicU32, Ordering::SeqCst},
+        atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering::SeqCst},
         Arc, Weak,
     },
     task::{Context, Poll},
+    time::Duration,
 };
 use tracing::{debug, info_span, instrument, trace, trace_span, warn, Instrument};
-use zbus_names::{BusName, ErrorName, InterfaceName, MemberName, OwnedUniqueName, WellKnownName};
-use zvariant::ObjectPath;
+use zbus_names::{
+    BusName, ErrorName, InterfaceName, MemberName, OwnedBusName, OwnedUniqueName,
+    OwnedWellKnownName, UniqueName, WellKnownName,
+};
+use zvariant::{ObjectPath, OwnedObjectPath};
 
-use futures_core::{ready, Future};
+use futures_core::{ready, Future, Stream};
 use futures_sink::Sink;
 use futures_util::{sink::SinkExt, StreamExt};
 
 use crate::{
     async_lock::Mutex,
-    blocking,
+    blocking, dispatch_stats,
     fdo::{self, ConnectionCredentials, RequestNameFlags, RequestNameReply},
+    interceptor::intercept_outgoing,
+    message_span,
     raw::{Connection as RawConnection, Socket},
     socket_reader::SocketReader,
-    Authenticated, CacheProperties, ConnectionBuilder, DBusError, Error, Executor, Guid, MatchRule,
-    Message, MessageBuilder, MessageFlags, MessageStream, MessageType, ObjectServer,
-    OwnedMatchRule, Result, Task,
+    timeout::{sleep, timeout},
+    AccessPolicy, Address, AuthMechanism, Authenticated, CacheProperties, ConnectionBuilder,
+    DBusError, DispatchObserver, Error, Executor, Guid, MatchRule, Message, MessageBuilder,
+    MessageFlags, MessageHeader, MessageInterceptor, MessageSequence, MessageStream, MessageType,
+    MethodFlags, ObjectServer, OwnedMatchRule, Result, Task,
 };
 
 const DEFAULT_MAX_QUEUED: usize = 64;
 const DEFAULT_MAX_METHOD_RETURN_QUEUED: usize = 8;
+const DEFAULT_MAX_EVENTS_QUEUED: usize = 8;
+const DEFAULT_RECONNECT_TIMEOUT: Duration = Duration::from_secs(25);
+const MAX_CACHED_INTROSPECTIONS: usize = 128;
 
 /// Inner state shared by Connection and WeakConnection
 #[derive(Debug)]
@@ -46,14 +59,37 @@ pub(crate) struct ConnectionInner {
     #[cfg(unix)]
     cap_unix_fd: bool,
     bus_conn: bool,
-    unique_name: OnceCell<OwnedUniqueName>,
-    registered_names: Mutex<HashMap<WellKnownName<'static>, NameStatus>>,
+    unique_names: UniqueNames,
+    // The flags are kept around for requesting the names again on reconnection.
+    registered_names:
+        Mutex<HashMap<WellKnownName<'static>, (NameStatus, BitFlags<RequestNameFlags>)>>,
+    // The well-known names we currently own, as told by the bus.
+    owned_names: sync::Mutex<OwnedNames>,
+    // The rule routing the bus signals about our names to the task tracking them.
+    owned_names_rule: sync::Mutex<Option<OwnedMatchRule>>,
+    // Introspection XML of the remote objects, by destination and path.
+    introspection_cache: sync::Mutex<IntrospectionCache>,
 
     raw_conn: Arc<sync::Mutex<RawConnection<Box<dyn Socket>>>>,
 
     // Serial number for next outgoing message
     serial: AtomicU32,
 
+    // Number of method calls awaiting a reply
+    method_calls_in_flight: Arc<AtomicUsize>,
+
+    // How long to wait for method replies by default
+    method_timeout: OnceCell<Duration>,
+
+    // Hooks run on all outgoing and incoming messages, in order
+    interceptors: OnceCell<Vec<Arc<dyn MessageInterceptor>>>,
+
+    // Checked before dispatching method calls to the object server
+    access_policy: OnceCell<Arc<dyn AccessPolicy>>,
+
+    // Notified of the method calls handled by the object server
+    dispatch_observer: OnceCell<Arc<dyn DispatchObserver>>,
+
     // Our executor
     executor: Executor<'static>,
 
@@ -61,6 +97,16 @@ pub(crate) struct ConnectionInner {
     #[allow(unused)]
     socket_reader_task: OnceCell<Task<()>>,
 
+    // Keepalive task, if enabled
+    #[allow(unused)]
+    keepalive_task: OnceCell<Task<()>>,
+
+    // Set once the connection is being closed on purpose, so it's not established again
+    closing: AtomicBool,
+    // Set once the socket reader task has stopped for good
+    closed: AtomicBool,
+    closed_event: Event,
+
     pub(crate) msg_receiver: InactiveReceiver<Result<Arc<Message>>>,
     pub(crate) method_return_receiver: InactiveReceiver<Result<Arc<Message>>>,
     msg_senders: Arc<Mutex<HashMap<Option<OwnedMatchRule>, MsgBroadcaster>>>,
@@ -68,7 +114,212 @@ pub(crate) struct ConnectionInner {
     subscriptions: Mutex<Subscriptions>,
 
     object_server: OnceCell<blocking::ObjectServer>,
-    object_server_dispatch_task: OnceCell<Task<()>>,
+    object_server_dispatch_task: sync::Mutex<Option<Task<()>>>,
+    // Set once the object server dispatching has been stopped for good
+    object_server_stopped: AtomicBool,
+    object_server_dispatches: Arc<Dispatches>,
+
+    events: Broadcaster<ConnectionEvent>,
+    events_receiver: InactiveReceiver<ConnectionEvent>,
+    reconnect: OnceCell<Reconnect>,
+}
+
+type IntrospectionKey = (Option<OwnedBusName>, OwnedObjectPath);
+
+// The cached descriptions, the oldest of which make room for the new ones once full.
+#[derive(Debug, Default)]
+struct IntrospectionCache {
+    entries: HashMap<IntrospectionKey, String>,
+    order: VecDeque<IntrospectionKey>,
+    // The destinations whose owner changes are tracked, with the number of their descriptions
+    // being fetched.
+    tracked: HashMap<OwnedBusName, usize>,
+}
+
+impl IntrospectionCache {
+    // Account for a description of `destination` being fetched, returning whether its owner
+    // changes need to be tracked.
+    fn fetching(&mut self, destination: &OwnedBusName) -> bool {
+        match self.tracked.get_mut(destination) {
+            Some(fetching) => {
+                *fetching += 1;
+
+                false
+            }
+            None => {
+                self.tracked.insert(destination.clone(), 1);
+
+                true
+            }
+        }
+    }
+
+    fn fetched(&mut self, destination: &OwnedBusName) {
+        if let Some(fetching) = self.tracked.get_mut(destination) {
+            *fetching = fetching.saturating_sub(1);
+        }
+    }
+
+    // Stop tracking the destinations with neither descriptions cached nor being fetched.
+    fn untrack_unused(&mut self) -> Vec<OwnedBusName> {
+        let order = &self.order;
+        let mut untracked = vec![];
+        self.tracked.retain(|destination, fetching| {
+            let used = *fetching > 0 || order.iter().any(|key| key.0.as_ref() == Some(destination));
+            if !used {
+                untracked.push(destination.clone());
+            }
+
+            used
+        });
+
+        untracked
+    }
+
+    fn insert(&mut self, key: IntrospectionKey, xml: String) {
+        if self.entries.insert(key.clone(), xml).is_some() {
+            return;
+        }
+        self.order.push_back(key);
+        if self.order.len() > MAX_CACHED_INTROSPECTIONS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+
+    fn remove(&mut self, key: &IntrospectionKey) -> bool {
+        self.order.retain(|k| k != key);
+
+        self.entries.remove(key).is_some()
+    }
+
+    fn remove_destination(&mut self, destination: &BusName<'_>) {
+        let entries = &mut self.entries;
+        self.order.retain(|key| match &key.0 {
+            Some(dest) if dest.as_ref() == *destination => {
+                entries.remove(key);
+
+                false
+            }
+            _ => true,
+        });
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+}
+
+// The rule matching the signals of the bus about `destination` changing owner.
+fn name_owner_changed_rule(destination: &BusName<'_>) -> Result<OwnedMatchRule> {
+    Ok(MatchRule::builder()
+        .msg_type(MessageType::Signal)
+        .sender("org.freedesktop.DBus")?
+        .interface("org.freedesktop.DBus")?
+        .member("NameOwnerChanged")?
+        .arg(0, destination.as_str())?
+        .build()
+        .to_owned()
+        .into())
+}
+
+// The well-known names we own, along with the position of the last message that told us about
+// each of them. The bus tells us through both the replies to our name calls and the signals the
+// tracking task handles in the background, so the latter could otherwise undo the former.
+#[derive(Debug, Default)]
+struct OwnedNames {
+    names: HashSet<OwnedWellKnownName>,
+    updated: HashMap<OwnedWellKnownName, MessageSequence>,
+}
+
+impl OwnedNames {
+    // If the message at `seq` is the latest news about `name`. Without a message, it always is.
+    fn is_latest(&mut self, name: &OwnedWellKnownName, seq: Option<MessageSequence>) -> bool {
+        let seq = match seq {
+            Some(seq) => seq,
+            None => return true,
+        };
+        match self.updated.get(name) {
+            Some(last) if *last > seq => false,
+            _ => {
+                self.updated.insert(name.clone(), seq);
+
+                true
+            }
+        }
+    }
+}
+
+// The unique names assigned to the connection. A new one is assigned on every reconnection but
+// the first one is handed out by reference, so it's kept along with the current one.
+#[derive(Debug, Default)]
+struct UniqueNames {
+    first: OnceCell<OwnedUniqueName>,
+    // The one assigned on the last reconnection, if any.
+    current: sync::Mutex<Option<OwnedUniqueName>>,
+}
+
+impl UniqueNames {
+    fn first(&self) -> Option<&OwnedUniqueName> {
+        self.first.get()
+    }
+
+    fn current(&self) -> Option<OwnedUniqueName> {
+        match &*self.current.lock().expect("poisoned lock") {
+            Some(name) => Some(name.clone()),
+            None => self.first.get().cloned(),
+        }
+    }
+
+    fn set(&self, name: OwnedUniqueName) -> std::result::Result<(), OwnedUniqueName> {
+        self.first.set(name)
+    }
+
+    fn replace(&self, name: OwnedUniqueName) {
+        if let Err(name) = self.first.set(name) {
+            *self.current.lock().expect("poisoned lock") = Some(name);
+        }
+    }
+}
+
+// What's needed to establish the connection again.
+#[derive(Debug)]
+struct Reconnect {
+    address: Address,
+    auth_mechanisms: Option<VecDeque<AuthMechanism>>,
+    external_id: Option<String>,
+    timeout: Duration,
+}
+
+/// An event in the life of a [`Connection`].
+///
+/// Use [`Connection::receive_events`] to get notified of these.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConnectionEvent {
+    /// The connection to the peer was lost.
+    ///
+    /// Unless [automatic reconnection] is enabled, this is the last event.
+    ///
+    /// [automatic reconnection]: ConnectionBuilder::auto_reconnect
+    Disconnected,
+    /// The connection to the peer was established again.
+    ///
+    /// For bus connections, this carries the new unique name assigned by the bus, and the
+    /// well-known names and match rules of the connection have been restored.
+    Reconnected(Option<OwnedUniqueName>),
+    /// The connection became the primary owner of a well-known name.
+    ///
+    /// See [`Connection::owned_names`].
+    NameAcquired(OwnedWellKnownName),
+    /// The connection is no longer the primary owner of a well-known name.
+    ///
+    /// Besides releasing the name, this happens when another connection replaces us as the owner
+    /// of a name requested with [`RequestNameFlags::AllowReplacement`] or when the connection to
+    /// the bus is lost. See [`Connection::owned_names`].
+    NameLost(OwnedWellKnownName),
 }
 
 type Subscriptions = HashMap<OwnedMatchRule, (u64, InactiveReceiver<Result<Arc<Message>>>)>;
@@ -103,6 +354,13 @@ pub(crate) type MsgBroadcaster = Broadcaster<Result<Arc<Message>>>;
 /// [`crate::blocking::MessageIterator`] instances are continuously polled and iterated on,
 /// respectively.
 ///
+/// Incoming messages are read from the socket, and dispatched to the streams, pending method calls
+/// and the [`ObjectServer`], by tasks running in the background. You don't need to poll anything
+/// for that to happen: by default, zbus runs these tasks in a thread of its own, or on the
+/// current runtime if the `tokio` feature is enabled. If you'd rather run them on your own
+/// executor, disable the thread through [`ConnectionBuilder::internal_executor`] and tick
+/// [`Connection::executor`] yourself.
+///
 /// For sending messages you can either use [`Connection::send_message`] method or make use of the
 /// [`Sink`] implementation. For latter, you might find [`SinkExt`] API very useful. Keep in mind
 /// that [`Connection`] will not manage the serial numbers (cookies) on the messages for you when
@@ -114,6 +372,12 @@ pub(crate) type MsgBroadcaster = Broadcaster<Result<Arc<Message>>>;
 /// Since you do not need exclusive access to a `zbus::Connection` to send messages on the bus,
 /// [`Sink`] is also implemented on `&Connection`.
 ///
+/// If the capacity of the outgoing queue is limited (see
+/// [`ConnectionBuilder::max_outgoing_queued`]), the [`Sink`] applies backpressure: it's not ready
+/// to accept more messages until the queued ones are written to the socket. This allows
+/// forwarding messages between connections with [`futures_util::StreamExt::forward`], without a
+/// slow peer causing unbounded memory growth.
+///
 /// # Caveats
 ///
 /// At the moment, a simultaneous [flush request] from multiple tasks/threads could
@@ -162,21 +426,11 @@ pub(crate) type MsgBroadcaster = Broadcaster<Result<Arc<Message>>>;
 /// ```rust,no_run
 /// # zbus::block_on(async {
 /// use futures_util::stream::TryStreamExt;
-/// use zbus::{Connection, MessageStream};
+/// use zbus::Connection;
 ///
 /// let connection = Connection::session().await?;
 ///
-/// connection
-///     .call_method(
-///         Some("org.freedesktop.DBus"),
-///         "/org/freedesktop/DBus",
-///         Some("org.freedesktop.DBus.Monitoring"),
-///         "BecomeMonitor",
-///         &(&[] as &[&str], 0u32),
-///     )
-///     .await?;
-///
-/// let mut stream = MessageStream::from(connection);
+/// let mut stream = connection.become_monitor(&[]).await?;
 /// while let Some(msg) = stream.try_next().await? {
 ///     println!("Got message: {}", msg);
 /// }
@@ -206,15 +460,95 @@ pub struct Connection {
 
 assert_impl_all!(Connection: Send, Sync, Unpin);
 
-/// A method call whose completion can be awaited or joined with other streams.
+/// The pending reply to a method call.
 ///
-/// This is useful for cache population method calls, where joining the [`JoinableStream`] with
-/// an update signal stream can be used to ensure that cache updates are not overwritten by a cache
-/// population whose task is scheduled later.
+/// Awaiting it resolves to the reply, or to the error the peer replied with. Use
+/// [`Connection::send_message_with_reply`] or [`Connection::expect_reply`] to get one.
+///
+/// This is also an [`OrderedFuture`], so it can be joined with other ordered streams. This is
+/// useful for cache population method calls, where joining the call with an update signal stream
+/// ensures that cache updates are not overwritten by a cache population whose task is scheduled
+/// later.
 #[derive(Debug)]
-pub(crate) struct PendingMethodCall {
+pub struct PendingMethodCall {
     stream: Option<MessageStream>,
     serial: u32,
+    in_flight: Arc<AtomicUsize>,
+    timeout: Option<Duration>,
+    span: tracing::Span,
+}
+
+assert_impl_all!(PendingMethodCall: Send, Sync, Unpin);
+
+impl PendingMethodCall {
+    /// The serial number of the method call this is the reply to.
+    pub fn serial(&self) -> u32 {
+        self.serial
+    }
+
+    // Use `span` instead of the generic one, for waiting for the reply.
+    fn in_span(mut self, span: tracing::Span) -> Self {
+        self.span = span;
+
+        self
+    }
+
+    fn finish(&mut self) {
+        if self.stream.take().is_some() {
+            self.in_flight.fetch_sub(1, SeqCst);
+        }
+    }
+
+    // Wait no longer than `timeout` in `reply`, instead of the default of the connection.
+    pub(crate) fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = Some(timeout);
+    }
+
+    /// Wait for the reply, but no longer than the [method timeout] of the connection, if any.
+    ///
+    /// Unlike awaiting `self` directly, this fails with [`Error::Timeout`] if the reply doesn't
+    /// arrive in time.
+    ///
+    /// [method timeout]: ConnectionBuilder::method_timeout
+    pub async fn reply(self) -> Result<Arc<Message>> {
+        match self.timeout {
+            Some(duration) => timeout(self, duration).await,
+            None => self.await,
+        }
+    }
+}
+
+impl Drop for PendingMethodCall {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+// The method calls being dispatched to the object server.
+#[derive(Debug, Default)]
+struct Dispatches {
+    count: AtomicUsize,
+    done: Event,
+}
+
+// Accounts for a method call while it's being dispatched.
+#[derive(Debug)]
+struct DispatchGuard(Arc<Dispatches>);
+
+impl DispatchGuard {
+    fn new(dispatches: &Arc<Dispatches>) -> Self {
+        dispatches.count.fetch_add(1, SeqCst);
+
+        Self(dispatches.clone())
+    }
+}
+
+impl Drop for DispatchGuard {
+    fn drop(&mut self) {
+        if self.0.count.fetch_sub(1, SeqCst) == 1 {
+            self.0.done.notify(usize::MAX);
+        }
+    }
 }
 
 impl Future for PendingMethodCall {
@@ -241,6 +575,8 @@ impl OrderedFuture for PendingMethodCall {
         before: Option<&Self::Ordering>,
     ) -> Poll<Option<(Self::Ordering, Self::Output)>> {
         let this = self.get_mut();
+        let span = this.span.clone();
+        let _span = span.enter();
         if let Some(stream) = &mut this.stream {
             loop {
                 match Pin::new(&mut *stream).poll_next_before(cx, before) {
@@ -256,7 +592,7 @@ impl OrderedFuture for PendingMethodCall {
                             MessageType::MethodReturn => Ok(msg),
                             _ => continue,
                         };
-                        this.stream = None;
+                        this.finish();
                         return Poll::Ready(Some((ordering, res)));
                     }
                     Poll::Ready(PollResult::Item {
@@ -280,6 +616,78 @@ impl OrderedFuture for PendingMethodCall {
     }
 }
 
+/// What to do when sending a message while the outgoing queue is full.
+///
+/// See [`ConnectionBuilder::outgoing_queue_policy`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OutgoingQueuePolicy {
+    /// Wait until there is room in the queue again (default).
+    ///
+    /// The senders are told there is room before they queue their message, so the queue can
+    /// exceed its capacity by one message per sender told so concurrently.
+    #[default]
+    Block,
+    /// Fail with [`Error::OutgoingQueueFull`].
+    Error,
+    /// Drop the oldest signal waiting in the queue, to make room for the new message.
+    ///
+    /// Other messages are never dropped, since their peers would wait for them. If no signal is
+    /// queued, sending waits for room in the queue, as with [`OutgoingQueuePolicy::Block`].
+    DropOldest,
+}
+
+/// Statistics about a [`Connection`], as returned by [`Connection::stats`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectionStats {
+    pub(crate) msgs_sent: u64,
+    pub(crate) bytes_sent: u64,
+    pub(crate) msgs_received: u64,
+    pub(crate) bytes_received: u64,
+    pub(crate) outgoing_queued: usize,
+    pub(crate) method_calls_in_flight: usize,
+    pub(crate) match_rules: usize,
+}
+
+assert_impl_all!(ConnectionStats: Send, Sync, Unpin);
+
+impl ConnectionStats {
+    /// The number of messages written to the socket.
+    pub fn msgs_sent(&self) -> u64 {
+        self.msgs_sent
+    }
+
+    /// The number of bytes written to the socket.
+    pub fn bytes_sent(&self) -> u64 {
+        self.bytes_sent
+    }
+
+    /// The number of messages received.
+    pub fn msgs_received(&self) -> u64 {
+        self.msgs_received
+    }
+
+    /// The number of bytes received, as part of complete messages.
+    pub fn bytes_received(&self) -> u64 {
+        self.bytes_received
+    }
+
+    /// The number of messages waiting in the outgoing queue.
+    pub fn outgoing_queued(&self) -> usize {
+        self.outgoing_queued
+    }
+
+    /// The number of method calls still waiting for their reply.
+    pub fn method_calls_in_flight(&self) -> usize {
+        self.method_calls_in_flight
+    }
+
+    /// The number of match rules installed by this connection.
+    pub fn match_rules(&self) -> usize {
+        self.match_rules
+    }
+}
+
 impl Connection {
     /// Send `msg` to the peer.
     ///
@@ -289,6 +697,9 @@ impl Connection {
     /// On successfully sending off `msg`, the assigned serial number is returned.
     pub async fn send_message(&self, mut msg: Message) -> Result<u32> {
         let serial = self.assign_serial_num(&mut msg)?;
+        if msg.message_type() == MessageType::Error {
+            dispatch_stats::error_replied(&msg);
+        }
 
         trace!("Sending message: {:?}", msg);
         (&mut &*self).send(msg).await?;
@@ -297,12 +708,150 @@ impl Connection {
         Ok(serial)
     }
 
+    /// Send `msg`, a method call, to the peer and return its pending reply.
+    ///
+    /// Like [`Connection::send_message`], this keeps the serial number already [assigned] to `msg`,
+    /// if any. The reply is watched for before sending, so it can't be missed, no matter how
+    /// quickly it arrives.
+    ///
+    /// Unless you need to build the message yourself, use [`Connection::call_method`] instead.
+    /// Don't use this for calls flagged with [`MessageFlags::NoReplyExpected`], as the reply would
+    /// never arrive.
+    ///
+    /// [assigned]: Connection::assign_serial_num
+    pub async fn send_message_with_reply(&self, mut msg: Message) -> Result<PendingMethodCall> {
+        let serial = self.assign_serial_num(&mut msg)?;
+        let reply = self.expect_reply(serial);
+        let reply = reply.in_span(message_span!("method call", &msg));
+        let send = self.send_message(msg);
+        let send = send.instrument(reply.span.clone());
+        send.await?;
+
+        Ok(reply)
+    }
+
+    /// Watch for the reply to the method call with the given serial number.
+    ///
+    /// This is meant for custom request/reply handling, e.g when relaying method calls between
+    /// connections: get a serial through [`Connection::assign_serial_num`] and call this method
+    /// before sending the call through any means, so the reply can't be missed. Just as for
+    /// [`Connection::call_method`], dropping the returned value stops watching for the reply.
+    pub fn expect_reply(&self, serial: u32) -> PendingMethodCall {
+        let msg_receiver = self.inner.method_return_receiver.activate_cloned();
+        let stream = Some(MessageStream::for_subscription_channel(
+            msg_receiver,
+            // This is a lie but we only use the stream internally so it's fine.
+            None,
+            self,
+        ));
+        self.inner.method_calls_in_flight.fetch_add(1, SeqCst);
+
+        PendingMethodCall {
+            stream,
+            serial,
+            in_flight: self.inner.method_calls_in_flight.clone(),
+            timeout: self.method_timeout(),
+            span: tracing::debug_span!("method reply", serial),
+        }
+    }
+
+    /// Wait for all the queued outgoing messages to be written to the socket.
+    ///
+    /// Messages sent through our [`Sink`] implementation are only queued until the sink is flushed.
+    /// This method flushes them (and anything else still pending), no matter which clone of the
+    /// connection queued them.
+    pub async fn flush(&self) -> Result<()> {
+        SinkExt::<Arc<Message>>::flush(&mut &*self).await
+    }
+
+    /// Write out as many of the queued outgoing messages as possible, without waiting.
+    ///
+    /// Returns `true` if all of them were written. If not, call this method again once the socket
+    /// is writable. This is meant for [driving the connection from an external event
+    /// loop][Connection::raw_fd]; async code should use [`Connection::flush`] instead.
+    pub fn try_flush(&self) -> Result<bool> {
+        let mut cx = Context::from_waker(futures_util::task::noop_waker_ref());
+        let mut raw_conn = self.inner.raw_conn.lock().expect("poisoned lock");
+
+        match raw_conn.flush(&mut cx) {
+            Poll::Ready(res) => res.map(|_| true),
+            Poll::Pending => Ok(false),
+        }
+    }
+
+    /// The file descriptor of the underlying socket.
+    ///
+    /// Returns `None` if the connection runs over a custom [`Socket`] implementation that doesn't
+    /// provide one.
+    ///
+    /// Together with [`ConnectionBuilder::internal_executor`], [`Executor::try_tick`],
+    /// [`MessageStream::try_receive`] and [`Connection::try_flush`], this allows driving the
+    /// connection from an external event loop (e.g GLib's or a custom `epoll` one), instead of a
+    /// dedicated thread:
+    ///
+    /// ```no_run
+    /// # use zbus::{block_on, ConnectionBuilder, MessageStream};
+    /// # fn wait_until_ready(_fd: std::os::unix::io::RawFd) {}
+    /// # block_on(async {
+    /// let conn = ConnectionBuilder::session()?
+    ///     .internal_executor(false)
+    ///     .build()
+    ///     .await?;
+    /// let fd = conn.raw_fd().unwrap();
+    /// let mut stream = MessageStream::from(&conn);
+    ///
+    /// loop {
+    ///     // Wait for `fd` to become readable (or writable, if `try_flush` returned `false`)
+    ///     // using your event loop, then run the tasks of the connection and handle the
+    ///     // messages received.
+    ///     wait_until_ready(fd);
+    ///     while conn.executor().try_tick() {}
+    ///     while let Some(msg) = stream.try_receive() {
+    ///         println!("Received: {}", msg?);
+    ///     }
+    ///     conn.try_flush()?;
+    /// #     break;
+    /// }
+    /// # Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    ///
+    /// Note that the socket is still registered with the reactor of the async runtime, which
+    /// wakes up the internal tasks whenever the socket is ready. The file descriptor must hence
+    /// only be polled for readiness and never read from or written to directly.
+    #[cfg(unix)]
+    pub fn raw_fd(&self) -> Option<RawFd> {
+        self.inner
+            .raw_conn
+            .lock()
+            .expect("poisoned lock")
+            .socket()
+            .raw_fd()
+    }
+
+    /// Gracefully close the connection.
+    ///
+    /// All the queued outgoing messages are written to the socket before it's shut down. This is
+    /// useful for short-lived programs, that would otherwise risk exiting before their last
+    /// messages (e.g a signal or a method reply) are sent out.
+    ///
+    /// Since all clones of a `Connection` share the same socket, the connection is closed for all
+    /// of them. Any subsequent operation on it will fail.
+    pub async fn close(self) -> Result<()> {
+        SinkExt::<Arc<Message>>::close(&mut &self).await
+    }
+
     /// Send a method call.
     ///
     /// Create a method-call message, send it over the connection, then wait for the reply.
     ///
     /// On successful reply, an `Ok(Message)` is returned. On error, an `Err` is returned. D-Bus
     /// error replies are returned as [`Error::MethodError`].
+    ///
+    /// Dropping the returned future stops waiting for the reply, which is then discarded once it
+    /// arrives. If you don't care about the reply in the first place, use
+    /// [`Connection::call_method_with_flags`] with [`MethodFlags::NoReplyExpected`] instead, so
+    /// the service doesn't even send one.
     pub async fn call_method<'d, 'p, 'i, 'm, D, P, I, M, B>(
         &self,
         destination: Option<D>,
@@ -332,9 +881,86 @@ impl Connection {
         )
         .await?
         .expect("no reply")
+        .reply()
         .await
     }
 
+    /// Send a method call and wait at most `timeout` for the reply.
+    ///
+    /// Same as [`Connection::call_method`], except that the given `timeout` is used instead of the
+    /// default one of the connection (see [`ConnectionBuilder::method_timeout`]). If no reply
+    /// arrives in time, [`Error::Timeout`] is returned.
+    pub async fn call_method_with_timeout<'d, 'p, 'i, 'm, D, P, I, M, B>(
+        &self,
+        destination: Option<D>,
+        path: P,
+        interface: Option<I>,
+        method_name: M,
+        timeout: Duration,
+        body: &B,
+    ) -> Result<Arc<Message>>
+    where
+        D: TryInto<BusName<'d>>,
+        P: TryInto<ObjectPath<'p>>,
+        I: TryInto<InterfaceName<'i>>,
+        M: TryInto<MemberName<'m>>,
+        D::Error: Into<Error>,
+        P::Error: Into<Error>,
+        I::Error: Into<Error>,
+        M::Error: Into<Error>,
+        B: serde::ser::Serialize + zvariant::DynamicType,
+    {
+        let mut call = self
+            .call_method_raw(
+                destination,
+                path,
+                interface,
+                method_name,
+                BitFlags::empty(),
+                body,
+            )
+            .await?
+            .expect("no reply");
+        call.set_timeout(timeout);
+
+        call.reply().await
+    }
+
+    /// Send a method call, with the given flags.
+    ///
+    /// Same as [`Connection::call_method`], except that `flags` control the way the method call
+    /// message is sent and handled. If `flags` contain [`MethodFlags::NoReplyExpected`], `Ok(None)`
+    /// is returned right after sending the call. Otherwise, the reply is awaited and returned.
+    pub async fn call_method_with_flags<'d, 'p, 'i, 'm, D, P, I, M, B>(
+        &self,
+        destination: Option<D>,
+        path: P,
+        interface: Option<I>,
+        method_name: M,
+        flags: BitFlags<MethodFlags>,
+        body: &B,
+    ) -> Result<Option<Arc<Message>>>
+    where
+        D: TryInto<BusName<'d>>,
+        P: TryInto<ObjectPath<'p>>,
+        I: TryInto<InterfaceName<'i>>,
+        M: TryInto<MemberName<'m>>,
+        D::Error: Into<Error>,
+        P::Error: Into<Error>,
+        I::Error: Into<Error>,
+        M::Error: Into<Error>,
+        B: serde::ser::Serialize + zvariant::DynamicType,
+    {
+        let flags = flags.iter().map(MessageFlags::from).collect();
+        match self
+            .call_method_raw(destination, path, interface, method_name, flags, body)
+            .await?
+        {
+            Some(call) => call.reply().await.map(Some),
+            None => Ok(None),
+        }
+    }
+
     /// Send a method call.
     ///
     /// Send the given message, which must be a method call, over the connection and return an
@@ -366,7 +992,7 @@ impl Connection {
         B: serde::ser::Serialize + zvariant::DynamicType,
     {
         let mut builder = MessageBuilder::method_call(path, method_name)?;
-        if let Some(sender) = self.unique_name() {
+        if let Some(sender) = self.current_unique_name() {
             builder = builder.sender(sender)?
         }
         if let Some(destination) = destination {
@@ -378,20 +1004,18 @@ impl Connection {
         for flag in flags {
             builder = builder.with_flags(flag)?;
         }
-        let msg = builder.build(body)?;
+        let mut msg = builder.build(body)?;
 
-        let msg_receiver = self.inner.method_return_receiver.activate_cloned();
-        let stream = Some(MessageStream::for_subscription_channel(
-            msg_receiver,
-            // This is a lie but we only use the stream internally so it's fine.
-            None,
-            self,
-        ));
-        let serial = self.send_message(msg).await?;
         if flags.contains(MessageFlags::NoReplyExpected) {
+            self.assign_serial_num(&mut msg)?;
+            let span = message_span!("method call", &msg);
+            let send = self.send_message(msg);
+            let send = send.instrument(span);
+            send.await?;
+
             Ok(None)
         } else {
-            Ok(Some(PendingMethodCall { stream, serial }))
+            self.send_message_with_reply(msg).await.map(Some)
         }
     }
 
@@ -418,7 +1042,7 @@ impl Connection {
         B: serde::ser::Serialize + zvariant::DynamicType,
     {
         let m = Message::signal(
-            self.unique_name(),
+            self.current_unique_name(),
             destination,
             path,
             interface,
@@ -439,7 +1063,7 @@ impl Connection {
     where
         B: serde::ser::Serialize + zvariant::DynamicType,
     {
-        let m = Message::method_reply(self.unique_name(), call, body)?;
+        let m = Message::method_reply(self.current_unique_name(), call, body)?;
         self.send_message(m).await
     }
 
@@ -448,6 +1072,10 @@ impl Connection {
     /// Given an existing message (likely a method call), send an error reply back to the caller
     /// with the given `error_name` and `body`.
     ///
+    /// The body can be of any type, for errors with structured details. By convention, its first
+    /// argument is a description of the error: if it's a string, the caller gets it as the
+    /// description of the resulting [`Error::MethodError`], along with the whole reply.
+    ///
     /// Returns the message serial number.
     pub async fn reply_error<'e, E, B>(
         &self,
@@ -460,7 +1088,7 @@ impl Connection {
         E: TryInto<ErrorName<'e>>,
         E::Error: Into<Error>,
     {
-        let m = Message::method_error(self.unique_name(), call, error_name, body)?;
+        let m = Message::method_error(self.current_unique_name(), call, error_name, body)?;
         self.send_message(m).await
     }
 
@@ -609,13 +1237,14 @@ impl Connection {
         let mut names = self.inner.registered_names.lock().await;
 
         match names.get(&well_known_name) {
-            Some(NameStatus::Owner(_)) => return Ok(RequestNameReply::AlreadyOwner),
-            Some(NameStatus::Queued(_)) => return Ok(RequestNameReply::InQueue),
+            Some((NameStatus::Owner(_), _)) => return Ok(RequestNameReply::AlreadyOwner),
+            Some((NameStatus::Queued(_), _)) => return Ok(RequestNameReply::InQueue),
             None => (),
         }
 
         if !self.is_bus() {
-            names.insert(well_known_name.to_owned(), NameStatus::Owner(None));
+            names.insert(well_known_name.to_owned(), (NameStatus::Owner(None), flags));
+            self.add_owned_name(well_known_name.into(), None);
 
             return Ok(RequestNameReply::PrimaryOwner);
         }
@@ -626,9 +1255,11 @@ impl Connection {
             .await?;
         let mut acquired_stream = dbus_proxy.receive_name_acquired().await?;
         let mut lost_stream = dbus_proxy.receive_name_lost().await?;
-        let reply = dbus_proxy
-            .request_name(well_known_name.clone(), flags)
+        // The reply message tells us where it stands relative to the name signals.
+        let reply_msg = dbus_proxy
+            .call_method("RequestName", &(well_known_name.clone(), flags))
             .await?;
+        let reply = reply_msg.body::<RequestNameReply>()?;
         let lost_task_name = format!("monitor name {well_known_name} lost");
         let name_lost_fut = if flags.contains(RequestNameFlags::AllowReplacement) {
             let weak_conn = WeakConnection::from(self);
@@ -649,7 +1280,7 @@ impl Connection {
                                         "Connection `{}` lost name `{}`",
                                         // SAFETY: This is bus connection so unique name can't be
                                         // None.
-                                        inner.unique_name.get().unwrap(),
+                                        inner.unique_names.current().unwrap(),
                                         well_known_name
                                     );
                                     inner.registered_names.lock().await.remove(&well_known_name);
@@ -696,7 +1327,7 @@ impl Connection {
                                 Some(signal) => match signal.args() {
                                     Ok(args) if args.name == well_known_name => {
                                         let mut names = inner.registered_names.lock().await;
-                                        if let Some(status) = names.get_mut(&well_known_name) {
+                                        if let Some((status, _)) = names.get_mut(&well_known_name) {
                                             let task = name_lost_fut.map(|fut| {
                                                 inner.executor.spawn(fut, &lost_task_name)
                                             });
@@ -726,13 +1357,17 @@ impl Connection {
             }
             RequestNameReply::PrimaryOwner | RequestNameReply::AlreadyOwner => {
                 let task = name_lost_fut.map(|fut| self.executor().spawn(fut, &lost_task_name));
+                self.add_owned_name(
+                    well_known_name.to_owned().into(),
+                    Some(reply_msg.recv_position()),
+                );
 
                 NameStatus::Owner(task)
             }
             RequestNameReply::Exists => return Err(Error::NameTaken),
         };
 
-        names.insert(well_known_name.to_owned(), status);
+        names.insert(well_known_name.to_owned(), (status, flags));
 
         Ok(reply)
     }
@@ -750,25 +1385,466 @@ impl Connection {
         W: TryInto<WellKnownName<'w>>,
         W::Error: Into<Error>,
     {
-        let well_known_name: WellKnownName<'w> = well_known_name.try_into().map_err(Into::into)?;
-        let mut names = self.inner.registered_names.lock().await;
-        // FIXME: Should be possible to avoid cloning/allocation here
-        if names.remove(&well_known_name.to_owned()).is_none() {
-            return Ok(false);
-        };
+        let well_known_name = well_known_name.try_into().map_err(Into::into)?;
+
+        self.release_name_inner(well_known_name, true)
+            .await
+            .map(|reply| reply.is_some())
+    }
+
+    /// Deregister a well-known name for this service on the bus, and return the reply of the bus.
+    ///
+    /// This is the same as [`Connection::release_name`], except that the bus is asked even if the
+    /// name wasn't registered through `self`. Its reply tells whether the connection owned (or was
+    /// queued for) the name, and whether the name exists at all. On peer-to-peer connections,
+    /// [`ReleaseNameReply::NonExistent`] is returned for the names not registered through `self`.
+    ///
+    /// [`ReleaseNameReply::NonExistent`]: fdo::ReleaseNameReply::NonExistent
+    pub async fn release_name_with_reply<'w, W>(
+        &self,
+        well_known_name: W,
+    ) -> Result<fdo::ReleaseNameReply>
+    where
+        W: TryInto<WellKnownName<'w>>,
+        W::Error: Into<Error>,
+    {
+        let well_known_name = well_known_name.try_into().map_err(Into::into)?;
+        let reply = self.release_name_inner(well_known_name, false).await?;
+
+        Ok(reply.unwrap_or(fdo::ReleaseNameReply::NonExistent))
+    }
+
+    // Release the name, returning the reply of the bus, or `None` if the name wasn't registered
+    // through `self` and either `registered_only` is set or this isn't a bus connection.
+    async fn release_name_inner(
+        &self,
+        well_known_name: WellKnownName<'_>,
+        registered_only: bool,
+    ) -> Result<Option<fdo::ReleaseNameReply>> {
+        let mut names = self.inner.registered_names.lock().await;
+        // FIXME: Should be possible to avoid cloning/allocation here
+        let registered = names.remove(&well_known_name.to_owned()).is_some();
+        if !registered && (registered_only || !self.is_bus()) {
+            return Ok(None);
+        }
 
         if !self.is_bus() {
-            return Ok(true);
+            self.remove_owned_name(&well_known_name.into(), None);
+
+            return Ok(Some(fdo::ReleaseNameReply::Released));
         }
 
-        fdo::DBusProxy::builder(self)
+        let reply_msg = fdo::DBusProxy::builder(self)
             .cache_properties(CacheProperties::No)
             .build()
             .await?
-            .release_name(well_known_name)
+            .call_method("ReleaseName", &(well_known_name.clone(),))
+            .await?;
+        let reply = reply_msg.body::<fdo::ReleaseNameReply>()?;
+        self.remove_owned_name(&well_known_name.into(), Some(reply_msg.recv_position()));
+
+        Ok(Some(reply))
+    }
+
+    /// The well-known names currently owned by this connection.
+    ///
+    /// Unlike the names requested through [`Connection::request_name`], this only includes the
+    /// names the connection is the primary owner of. It follows the `NameAcquired` and `NameLost`
+    /// signals of the bus, so a name queued for is only added once it's acquired and a name is
+    /// removed as soon as another connection replaces us as its owner. Use
+    /// [`Connection::receive_events`] to get notified of these changes, through
+    /// [`ConnectionEvent::NameAcquired`] and [`ConnectionEvent::NameLost`].
+    pub fn owned_names(&self) -> HashSet<OwnedWellKnownName> {
+        self.inner
+            .owned_names
+            .lock()
+            .expect("poisoned lock")
+            .names
+            .clone()
+    }
+
+    // Record that we own `name`, as told by the message at `seq`, unless we heard otherwise from a
+    // later message already.
+    fn add_owned_name(&self, name: OwnedWellKnownName, seq: Option<MessageSequence>) {
+        let added = {
+            let mut owned = self.inner.owned_names.lock().expect("poisoned lock");
+
+            owned.is_latest(&name, seq) && owned.names.insert(name.clone())
+        };
+        if added {
+            self.emit_event(ConnectionEvent::NameAcquired(name));
+        }
+    }
+
+    // The opposite of `add_owned_name`.
+    fn remove_owned_name(&self, name: &OwnedWellKnownName, seq: Option<MessageSequence>) {
+        let removed = {
+            let mut owned = self.inner.owned_names.lock().expect("poisoned lock");
+
+            owned.is_latest(name, seq) && owned.names.remove(name)
+        };
+        if removed {
+            self.emit_event(ConnectionEvent::NameLost(name.clone()));
+        }
+    }
+
+    // Keep `owned_names` up to date with the `NameAcquired` & `NameLost` signals the bus sends us.
+    //
+    // These are unicast to us, so no match rule needs to be added on the bus. We only need to
+    // route them to our own channel.
+    async fn track_owned_names(&self, unique_name: UniqueName<'_>) -> Result<()> {
+        let rule = MatchRule::builder()
+            .msg_type(MessageType::Signal)
+            .sender("org.freedesktop.DBus")?
+            .interface("org.freedesktop.DBus")?
+            .path("/org/freedesktop/DBus")?
+            .destination(unique_name)?
+            .build()
+            .to_owned();
+        let (sender, mut receiver) = broadcast(DEFAULT_MAX_QUEUED);
+        receiver.set_await_active(false);
+        {
+            let mut senders = self.inner.msg_senders.lock().await;
+            // Stop the task tracking the names of the previous connection to the bus, if any.
+            let previous = self
+                .inner
+                .owned_names_rule
+                .lock()
+                .expect("poisoned lock")
+                .replace(rule.clone().into());
+            if let Some(previous) = previous {
+                senders.remove(&Some(previous));
+            }
+            senders.insert(Some(rule.into()), sender);
+        }
+
+        let conn = WeakConnection::from(self);
+        let track = async move {
+            while let Some(msg) = receiver.next().await {
+                let conn = match conn.upgrade() {
+                    Some(conn) => conn,
+                    None => break,
+                };
+                if let Ok(msg) = msg {
+                    conn.handle_name_signal(&msg);
+                }
+            }
+        };
+        self.inner
+            .executor
+            .spawn(track, "track owned names")
+            .detach();
+
+        Ok(())
+    }
+
+    fn handle_name_signal(&self, msg: &Message) {
+        // Only trust the bus itself, since anyone could send us these signals.
+        let from_bus = msg
+            .header()
+            .ok()
+            .and_then(|h| {
+                h.sender()
+                    .ok()
+                    .flatten()
+                    .map(|s| s == "org.freedesktop.DBus")
+            })
+            .unwrap_or(false);
+        if !from_bus {
+            return;
+        }
+        // We get both signals for our unique name, which isn't of interest here.
+        let name = match msg.body::<BusName<'_>>() {
+            Ok(BusName::WellKnown(name)) => OwnedWellKnownName::from(name.to_owned()),
+            _ => return,
+        };
+
+        let seq = Some(msg.recv_position());
+        match msg.member().as_ref().map(|m| m.as_str()) {
+            Some("NameAcquired") => self.add_owned_name(name, seq),
+            Some("NameLost") => self.remove_owned_name(&name, seq),
+            _ => (),
+        }
+    }
+
+    // Forget about all the names we own, notifying the event listeners if `emit` is true.
+    fn clear_owned_names(&self, emit: bool) {
+        let names: Vec<_> = {
+            let mut owned = self.inner.owned_names.lock().expect("poisoned lock");
+            owned.updated.clear();
+
+            owned.names.drain().collect()
+        };
+        if emit {
+            for name in names {
+                self.emit_event(ConnectionEvent::NameLost(name));
+            }
+        }
+    }
+
+    /// Introspect the object at `path` of `destination`, and return the XML description.
+    ///
+    /// Unlike [`Proxy::introspect`], the description is cached on the connection: the following
+    /// calls for the same destination and path are answered right away, without asking the peer
+    /// again. This is meant for dynamic proxies and tools that repeatedly introspect the same
+    /// objects. The descriptions of a destination are dropped when its name changes owner on the
+    /// bus, and only the last 128 descriptions are kept. Other than that, the connection has no way
+    /// of telling when the objects of a peer change, so it's up to the caller to drop the stale
+    /// descriptions, through [`Connection::invalidate_introspection`] or
+    /// [`Connection::clear_introspection_cache`]. Failures are not cached.
+    ///
+    /// [`Proxy::introspect`]: crate::Proxy::introspect
+    pub async fn introspect<'d, 'p, D, P>(&self, destination: Option<D>, path: P) -> Result<String>
+    where
+        D: TryInto<BusName<'d>>,
+        P: TryInto<ObjectPath<'p>>,
+        D::Error: Into<Error>,
+        P::Error: Into<Error>,
+    {
+        let destination = destination
+            .map(|d| d.try_into().map_err(Into::into))
+            .transpose()?;
+        let path = path.try_into().map_err(Into::into)?;
+        let key = (
+            destination.as_ref().map(|d| d.to_owned().into()),
+            path.to_owned().into(),
+        );
+        if let Some(xml) = self
+            .inner
+            .introspection_cache
+            .lock()
+            .expect("poisoned lock")
+            .entries
+            .get(&key)
+        {
+            return Ok(xml.clone());
+        }
+        let tracked = match &key.0 {
+            Some(destination) if self.is_bus() => {
+                self.track_introspected_name(destination).await?;
+
+                Some(destination)
+            }
+            _ => None,
+        };
+
+        let xml = self
+            .call_method(
+                destination,
+                path,
+                Some("org.freedesktop.DBus.Introspectable"),
+                "Introspect",
+                &(),
+            )
             .await
-            .map(|_| true)
-            .map_err(Into::into)
+            .and_then(|reply| reply.body::<String>());
+        {
+            let mut cache = self
+                .inner
+                .introspection_cache
+                .lock()
+                .expect("poisoned lock");
+            if let Ok(xml) = &xml {
+                cache.insert(key.clone(), xml.clone());
+            }
+            if let Some(destination) = tracked {
+                cache.fetched(destination);
+            }
+        }
+        self.untrack_introspected_names();
+
+        xml
+    }
+
+    /// Drop the cached description of the object at `path` of `destination`, if any.
+    ///
+    /// The next [`Connection::introspect`] call for this object will ask the peer again. Returns
+    /// whether a description was cached.
+    pub fn invalidate_introspection<'d, 'p, D, P>(
+        &self,
+        destination: Option<D>,
+        path: P,
+    ) -> Result<bool>
+    where
+        D: TryInto<BusName<'d>>,
+        P: TryInto<ObjectPath<'p>>,
+        D::Error: Into<Error>,
+        P::Error: Into<Error>,
+    {
+        let destination = destination
+            .map(|d| d.try_into().map_err(Into::into))
+            .transpose()?;
+        let path = path.try_into().map_err(Into::into)?;
+        let key = (destination.map(Into::into), path.into());
+        let removed = self
+            .inner
+            .introspection_cache
+            .lock()
+            .expect("poisoned lock")
+            .remove(&key);
+        self.untrack_introspected_names();
+
+        Ok(removed)
+    }
+
+    /// Drop all the object descriptions cached by [`Connection::introspect`].
+    pub fn clear_introspection_cache(&self) {
+        self.inner
+            .introspection_cache
+            .lock()
+            .expect("poisoned lock")
+            .clear();
+        self.untrack_introspected_names();
+    }
+
+    // Drop the cached descriptions of `destination` once its name changes owner.
+    //
+    // The owner changes are matched by name, so that only the ones of the destinations having
+    // descriptions cached are received.
+    async fn track_introspected_name(&self, destination: &OwnedBusName) -> Result<()> {
+        let track = self
+            .inner
+            .introspection_cache
+            .lock()
+            .expect("poisoned lock")
+            .fetching(destination);
+        if !track {
+            return Ok(());
+        }
+        let subscribed = match name_owner_changed_rule(destination) {
+            Ok(rule) => self.subscribe(rule, None).await,
+            Err(e) => Err(e),
+        };
+        let mut receiver = match subscribed {
+            Ok(receiver) => receiver,
+            Err(e) => {
+                let mut cache = self
+                    .inner
+                    .introspection_cache
+                    .lock()
+                    .expect("poisoned lock");
+                cache.fetched(destination);
+                cache.untrack_unused();
+
+                return Err(e);
+            }
+        };
+
+        let conn = WeakConnection::from(self);
+        // The task ends once the rule is removed, when the destination is no longer tracked.
+        let track = async move {
+            while let Some(msg) = receiver.next().await {
+                let conn = match conn.upgrade() {
+                    Some(conn) => conn,
+                    None => break,
+                };
+                let signal = match msg.ok().and_then(fdo::NameOwnerChanged::from_message) {
+                    Some(signal) => signal,
+                    None => continue,
+                };
+                if let Ok(args) = signal.args() {
+                    conn.inner
+                        .introspection_cache
+                        .lock()
+                        .expect("poisoned lock")
+                        .remove_destination(args.name());
+                    conn.untrack_introspected_names();
+                }
+            }
+        };
+        self.inner
+            .executor
+            .spawn(track, "track introspected name")
+            .detach();
+
+        Ok(())
+    }
+
+    // Remove the rules tracking the destinations without any descriptions cached anymore.
+    fn untrack_introspected_names(&self) {
+        let untracked = self
+            .inner
+            .introspection_cache
+            .lock()
+            .expect("poisoned lock")
+            .untrack_unused();
+        for destination in untracked {
+            if let Ok(rule) = name_owner_changed_rule(&destination) {
+                self.queue_remove_match(rule);
+            }
+        }
+    }
+
+    /// Turn `self` into a [monitor] connection and return a stream of the captured messages.
+    ///
+    /// Only the messages matching any of the `rules` are captured. If `rules` is empty, all
+    /// messages on the bus are captured.
+    ///
+    /// The bus doesn't allow monitor connections to send any messages, hence why `self` is
+    /// consumed. The reply to the `BecomeMonitor` call itself is not part of the returned stream
+    /// but the messages the bus sends to the monitor itself (e.g the `NameLost` signal for its
+    /// unique name) are.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # zbus::block_on(async {
+    /// use futures_util::stream::TryStreamExt;
+    /// use zbus::{Connection, MatchRule, MessageType};
+    ///
+    /// let connection = Connection::session().await?;
+    /// let rule = MatchRule::builder()
+    ///     .msg_type(MessageType::Signal)
+    ///     .interface("org.freedesktop.DBus.Properties")?
+    ///     .build();
+    /// let mut stream = connection.become_monitor(&[rule]).await?;
+    /// while let Some(msg) = stream.try_next().await? {
+    ///     println!("Got message: {}", msg);
+    /// }
+    ///
+    /// # Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::Unsupported`] for p2p connections.
+    ///
+    /// [monitor]: https://dbus.freedesktop.org/doc/dbus-specification.html#bus-messages-become-monitor
+    pub async fn become_monitor(self, rules: &[MatchRule<'_>]) -> Result<MessageStream> {
+        if !self.is_bus() {
+            return Err(Error::Unsupported);
+        }
+
+        let rules: Vec<String> = rules.iter().map(ToString::to_string).collect();
+        // Create the stream before the call so that we don't miss any message arriving right
+        // after the reply.
+        let mut stream = MessageStream::from(&self);
+        let reply = self
+            .call_method(
+                Some("org.freedesktop.DBus"),
+                "/org/freedesktop/DBus",
+                Some("org.freedesktop.DBus.Monitoring"),
+                "BecomeMonitor",
+                &(rules, 0u32),
+            )
+            .await?;
+        let reply_serial = reply.primary_header().serial_num().cloned();
+
+        // Skip everything up to (and including) the reply.
+        while let Some(msg) = stream.next().await {
+            let msg = msg?;
+            if msg.message_type() == MessageType::MethodReturn
+                && msg.primary_header().serial_num().cloned() == reply_serial
+            {
+                return Ok(stream);
+            }
+        }
+
+        Err(Error::InputOutput(Arc::new(io::Error::new(
+            ErrorKind::BrokenPipe,
+            "Connection closed before becoming a monitor",
+        ))))
     }
 
     /// Checks if `self` is a connection to a message bus.
@@ -795,8 +1871,23 @@ impl Connection {
     ///
     /// The unique name is assigned by the message bus or set manually using
     /// [`Connection::set_unique_name`].
+    ///
+    /// If [automatic reconnection] is enabled, this is the name assigned on the first connection.
+    /// Use [`Connection::current_unique_name`] for the one assigned on the last connection.
+    ///
+    /// [automatic reconnection]: ConnectionBuilder::auto_reconnect
     pub fn unique_name(&self) -> Option<&OwnedUniqueName> {
-        self.inner.unique_name.get()
+        self.inner.unique_names.first()
+    }
+
+    /// The unique name currently assigned to the connection, if set/applicable.
+    ///
+    /// This is the same as [`Connection::unique_name`], unless [automatic reconnection] is enabled,
+    /// in which case the bus assigns a new unique name on every reconnection.
+    ///
+    /// [automatic reconnection]: ConnectionBuilder::auto_reconnect
+    pub fn current_unique_name(&self) -> Option<OwnedUniqueName> {
+        self.inner.unique_names.current()
     }
 
     /// Sets the unique name of the connection (if not already set).
@@ -813,13 +1904,64 @@ impl Connection {
     {
         let name = unique_name.try_into().map_err(Into::into)?;
         self.inner
-            .unique_name
+            .unique_names
             .set(name)
             .expect("unique name already set");
 
         Ok(())
     }
 
+    /// The default timeout for method calls, if any.
+    ///
+    /// See [`ConnectionBuilder::method_timeout`] for details.
+    pub fn method_timeout(&self) -> Option<Duration> {
+        self.inner.method_timeout.get().copied()
+    }
+
+    pub(crate) fn set_method_timeout(&self, timeout: Duration) {
+        self.inner
+            .method_timeout
+            .set(timeout)
+            .expect("method timeout already set");
+    }
+
+    pub(crate) fn set_interceptors(&self, interceptors: Vec<Arc<dyn MessageInterceptor>>) {
+        self.inner
+            .interceptors
+            .set(interceptors)
+            .expect("interceptors already set");
+    }
+
+    pub(crate) fn set_access_policy(&self, policy: Arc<dyn AccessPolicy>) {
+        self.inner
+            .access_policy
+            .set(policy)
+            .expect("access policy already set");
+    }
+
+    pub(crate) fn access_policy(&self) -> Option<&Arc<dyn AccessPolicy>> {
+        self.inner.access_policy.get()
+    }
+
+    pub(crate) fn set_dispatch_observer(&self, observer: Arc<dyn DispatchObserver>) {
+        self.inner
+            .dispatch_observer
+            .set(observer)
+            .expect("dispatch observer already set");
+    }
+
+    pub(crate) fn dispatch_observer(&self) -> Option<&Arc<dyn DispatchObserver>> {
+        self.inner.dispatch_observer.get()
+    }
+
+    fn interceptors(&self) -> &[Arc<dyn MessageInterceptor>] {
+        self.inner
+            .interceptors
+            .get()
+            .map(|i| i.as_slice())
+            .unwrap_or_default()
+    }
+
     /// The capacity of the main (unfiltered) queue.
     pub fn max_queued(&self) -> usize {
         self.inner.msg_receiver.capacity()
@@ -895,6 +2037,15 @@ impl Connection {
         &self.inner.executor
     }
 
+    /// Create a weak reference to this connection.
+    ///
+    /// Use this instead of keeping a clone of the connection in the interfaces served by its
+    /// [`ObjectServer`], as that would create a reference cycle, keeping the connection and its
+    /// objects from ever being released. See [`WeakConnection`] for details.
+    pub fn downgrade(&self) -> WeakConnection {
+        WeakConnection::from(self)
+    }
+
     /// Get a reference to the associated [`ObjectServer`].
     ///
     /// The `ObjectServer` is created on-demand.
@@ -941,7 +2092,15 @@ impl Connection {
 
     #[instrument(skip(self))]
     pub(crate) fn start_object_server(&self, started_event: Option<Event>) {
-        self.inner.object_server_dispatch_task.get_or_init(|| {
+        let mut dispatch_task = self
+            .inner
+            .object_server_dispatch_task
+            .lock()
+            .expect("poisoned lock");
+        if dispatch_task.is_some() || self.inner.object_server_stopped.load(SeqCst) {
+            return;
+        }
+        *dispatch_task = Some({
             trace!("starting ObjectServer task");
             let weak_conn = WeakConnection::from(self);
 
@@ -951,11 +2110,15 @@ impl Connection {
                     let mut stream = match weak_conn.upgrade() {
                         Some(conn) => {
                             let mut builder = MatchRule::builder().msg_type(MessageType::MethodCall);
-                            if let Some(unique_name) = conn.unique_name() {
+                            // The unique name changes on reconnection so the match rule can't
+                            // filter on it then. It's checked below instead.
+                            if let (Some(unique_name), None) =
+                                (conn.unique_name(), conn.inner.reconnect.get())
+                            {
                                 builder = builder.destination(&**unique_name).expect("unique name");
                             }
                             let rule = builder.build();
-                            match conn.add_match(rule.into(), None).await {
+                            match conn.subscribe(rule.into(), None).await {
                                 Ok(stream) => stream,
                                 Err(e) => {
                                     // Very unlikely but can happen I guess if connection is closed.
@@ -992,8 +2155,14 @@ impl Connection {
                                 }
                             };
                             match hdr.destination() {
-                                // Unique name is already checked by the match rule.
-                                Ok(Some(BusName::Unique(_))) | Ok(None) => (),
+                                Ok(Some(BusName::Unique(dest))) => {
+                                    if conn.current_unique_name().map(|n| *n != *dest).unwrap_or(false) {
+                                        trace!("Got a method call for a different destination: {}", dest);
+
+                                        continue;
+                                    }
+                                }
+                                Ok(None) => (),
                                 Ok(Some(BusName::WellKnown(dest))) => {
                                     let names = conn.inner.registered_names.lock().await;
                                     // destination doesn't matter if no name has been registered
@@ -1018,12 +2187,27 @@ impl Connection {
                                     continue;
                                 }
                             };
+                            // Counted before checking if we're stopped, so stopping either
+                            // waits for this dispatch or prevents it.
+                            let dispatch = DispatchGuard::new(&conn.inner.object_server_dispatches);
+                            if conn.inner.object_server_stopped.load(SeqCst) {
+                                drop(dispatch);
+                                if !hdr.primary().flags().contains(MessageFlags::NoReplyExpected) {
+                                    let e = fdo::Error::Failed("Object server stopped".to_string());
+                                    if let Err(e) = conn.reply_dbus_error(&hdr, e).await {
+                                        debug!("Failed to reply to `{}`: {}", msg, e);
+                                    }
+                                }
+
+                                continue;
+                            }
                             trace!("Got `{}`. Will spawn a task for dispatch..", msg);
                             let executor = conn.inner.executor.clone();
                             let task_name = format!("`{member}` method dispatcher");
                             executor
                                 .spawn(
                                     async move {
+                                        let _dispatch = dispatch;
                                         trace!("spawned a task to dispatch `{}`.", msg);
                                         let server = conn.object_server();
                                         if let Err(e) = server.dispatch_message(&msg).await {
@@ -1050,7 +2234,76 @@ impl Connection {
         });
     }
 
-    pub(crate) async fn add_match(
+    /// Stop dispatching the incoming method calls to the [`ObjectServer`].
+    ///
+    /// Once the associated [`ObjectServer`] is set up (e.g through [`Connection::object_server`]
+    /// or [`ConnectionBuilder::serve_at`]), the method calls received on the connection are
+    /// dispatched to it in the background, with no need for the application to handle the incoming
+    /// messages. This stops that, for good: the method calls received afterwards are replied to
+    /// with an [`fdo::Error::Failed`] error. The returned future resolves once the method calls
+    /// that were being dispatched are all handled and replied to.
+    ///
+    /// This is typically used for a graceful shutdown of a service, before closing the connection
+    /// (see [`Connection::close`]).
+    pub async fn stop_object_server(&self) {
+        // The dispatching task keeps running, to reply to the method calls with an error.
+        trace!("stopping ObjectServer dispatching");
+        self.inner.object_server_stopped.store(true, SeqCst);
+
+        let dispatches = &self.inner.object_server_dispatches;
+        loop {
+            if dispatches.count.load(SeqCst) == 0 {
+                return;
+            }
+            let listener = dispatches.done.listen();
+            if dispatches.count.load(SeqCst) == 0 {
+                return;
+            }
+
+            listener.await;
+        }
+    }
+
+    /// Add a match rule to this connection.
+    ///
+    /// When connected to a bus, signal match rules are also installed on the bus side (using the
+    /// `AddMatch` method of the bus), so that the matching signals are routed to this connection.
+    /// The messages matching the rule are delivered to the streams of this connection (e.g
+    /// [`MessageStream`]).
+    ///
+    /// Rules are reference counted: adding the same rule multiple times (including through
+    /// [`MessageStream::for_match_rule`] or signal streams of proxies) only installs it once, and
+    /// it's only uninstalled once it has been removed (using [`Connection::remove_match`]) as many
+    /// times as it was added.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # zbus::block_on(async {
+    /// use zbus::{Connection, MatchRule, MessageType};
+    ///
+    /// let conn = Connection::session().await?;
+    /// let rule = MatchRule::builder()
+    ///     .msg_type(MessageType::Signal)
+    ///     .sender("org.freedesktop.DBus")?
+    ///     .member("NameOwnerChanged")?
+    ///     .build();
+    /// conn.add_match(rule.clone()).await?;
+    ///
+    /// // Receive the signals through a `MessageStream`..
+    ///
+    /// assert!(conn.remove_match(rule).await?);
+    /// # Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    pub async fn add_match<R>(&self, rule: R) -> Result<()>
+    where
+        R: Into<OwnedMatchRule>,
+    {
+        self.subscribe(rule.into(), None).await.map(|_| ())
+    }
+
+    pub(crate) async fn subscribe(
         &self,
         rule: OwnedMatchRule,
         max_queued: Option<usize>,
@@ -1103,11 +2356,21 @@ impl Connection {
         }
     }
 
-    pub(crate) async fn remove_match(&self, rule: OwnedMatchRule) -> Result<bool> {
+    /// Remove a match rule previously added to this connection.
+    ///
+    /// See [`Connection::add_match`] for details on how rules are reference counted.
+    ///
+    /// Returns `Ok(true)` if the rule was previously added and has now been removed, `Ok(false)`
+    /// otherwise.
+    pub async fn remove_match<R>(&self, rule: R) -> Result<bool>
+    where
+        R: Into<OwnedMatchRule>,
+    {
         use std::collections::hash_map::Entry;
+        let rule = rule.into();
         let mut subscriptions = self.inner.subscriptions.lock().await;
         // TODO when it becomes stable, use HashMap::raw_entry and only require expr: &str
-        // (both here and in add_match)
+        // (both here and in subscribe)
         let msg_type = rule.msg_type().unwrap_or(MessageType::Signal);
         match subscriptions.entry(rule) {
             Entry::Vacant(_) => Ok(false),
@@ -1115,20 +2378,22 @@ impl Connection {
                 let rule = e.key().inner().clone();
                 e.get_mut().0 -= 1;
                 if e.get().0 == 0 {
+                    // Forget about the rule, even if the bus fails to remove it. Otherwise, we'd
+                    // keep routing messages to a subscription no one is listening to.
+                    e.remove();
+                    self.inner
+                        .msg_senders
+                        .lock()
+                        .await
+                        .remove(&Some(rule.clone().into()));
                     if self.is_bus() && msg_type == MessageType::Signal {
                         fdo::DBusProxy::builder(self)
                             .cache_properties(CacheProperties::No)
                             .build()
                             .await?
-                            .remove_match_rule(rule.clone())
+                            .remove_match_rule(rule)
                             .await?;
                     }
-                    e.remove();
-                    self.inner
-                        .msg_senders
-                        .lock()
-                        .await
-                        .remove(&Some(rule.into()));
                 }
                 Ok(true)
             }
@@ -1149,9 +2414,10 @@ impl Connection {
             .build()
             .await?;
         let name = dbus_proxy.hello().await?;
+        self.track_owned_names(name.as_ref()).await?;
 
         self.inner
-            .unique_name
+            .unique_names
             .set(name)
             // programmer (probably our) error if this fails.
             .expect("Attempted to set unique_name twice");
@@ -1199,6 +2465,10 @@ impl Connection {
 
         let raw_conn = Arc::new(sync::Mutex::new(auth.conn));
 
+        let (mut events, events_receiver) = broadcast(DEFAULT_MAX_EVENTS_QUEUED);
+        events.set_overflow(true);
+        let events_receiver = events_receiver.deactivate();
+
         let connection = Self {
             inner: Arc::new(ConnectionInner {
                 raw_conn,
@@ -1207,16 +2477,33 @@ impl Connection {
                 cap_unix_fd,
                 bus_conn: bus_connection,
                 serial: AtomicU32::new(1),
-                unique_name: OnceCell::new(),
+                method_calls_in_flight: Arc::new(AtomicUsize::new(0)),
+                method_timeout: OnceCell::new(),
+                interceptors: OnceCell::new(),
+                access_policy: OnceCell::new(),
+                dispatch_observer: OnceCell::new(),
+                unique_names: UniqueNames::default(),
                 subscriptions,
                 object_server: OnceCell::new(),
-                object_server_dispatch_task: OnceCell::new(),
+                object_server_dispatch_task: sync::Mutex::new(None),
+                object_server_stopped: AtomicBool::new(false),
+                object_server_dispatches: Arc::new(Dispatches::default()),
                 executor,
                 socket_reader_task: OnceCell::new(),
+                keepalive_task: OnceCell::new(),
+                closed: AtomicBool::new(false),
+                closing: AtomicBool::new(false),
+                closed_event: Event::new(),
                 msg_senders,
                 msg_receiver,
                 method_return_receiver,
                 registered_names: Mutex::new(HashMap::new()),
+                owned_names: sync::Mutex::new(OwnedNames::default()),
+                owned_names_rule: sync::Mutex::new(None),
+                introspection_cache: sync::Mutex::new(IntrospectionCache::default()),
+                events,
+                events_receiver,
+                reconnect: OnceCell::new(),
             }),
         };
 
@@ -1237,15 +2524,212 @@ impl Connection {
         ConnectionBuilder::system()?.build().await
     }
 
-    /// Returns a listener, notified on various connection activity.
+    /// Statistics about the activity on this connection.
     ///
-    /// This function is meant for the caller to implement idle or timeout on inactivity.
-    pub fn monitor_activity(&self) -> EventListener {
+    /// The counters are shared by all clones of the connection and are never reset, which makes
+    /// them suitable for exporting as health metrics of long-running services.
+    pub async fn stats(&self) -> ConnectionStats {
+        let mut stats = self.inner.raw_conn.lock().expect("poisoned lock").stats();
+        stats.method_calls_in_flight = self.inner.method_calls_in_flight.load(SeqCst);
+        stats.match_rules = self.inner.subscriptions.lock().await.len();
+
+        stats
+    }
+
+    /// A stream of the [`ConnectionEvent`]s of this connection.
+    ///
+    /// Only the events happening after this call are received. If the stream isn't read from,
+    /// the oldest events are dropped to make room for new ones.
+    pub fn receive_events(&self) -> impl Stream<Item = ConnectionEvent> + Send + Unpin + 'static {
+        self.inner.events_receiver.activate_cloned()
+    }
+
+    pub(crate) fn emit_event(&self, event: ConnectionEvent) {
+        // Failure only means no one is listening.
+        let _ = self.inner.events.try_broadcast(event);
+    }
+
+    pub(crate) fn enable_auto_reconnect(
+        &self,
+        address: Address,
+        auth_mechanisms: Option<VecDeque<AuthMechanism>>,
+        external_id: Option<String>,
+        timeout: Option<Duration>,
+    ) {
         self.inner
-            .raw_conn
-            .lock()
-            .expect("poisoned lock")
-            .monitor_activity()
+            .reconnect
+            .set(Reconnect {
+                address,
+                auth_mechanisms,
+                external_id,
+                timeout: timeout.unwrap_or(DEFAULT_RECONNECT_TIMEOUT),
+            })
+            .expect("Attempted to enable auto-reconnect twice");
+    }
+
+    /// Establish the connection to the peer again, after the socket reader lost it.
+    ///
+    /// Returns `Ok(false)` if automatic reconnection isn't enabled or the connection is being
+    /// closed through [`Connection::close`]. Once the new socket is in place, the rest of the
+    /// connection state is restored in the background.
+    pub(crate) async fn reconnect(&self) -> Result<bool> {
+        let reconnect = match self.inner.reconnect.get() {
+            Some(reconnect) if !self.inner.closing.load(SeqCst) => reconnect,
+            _ => return Ok(false),
+        };
+        // Whatever names we owned were released by the bus along with the old connection.
+        self.clear_owned_names(true);
+        // The peers we'll talk to may well not be the same anymore.
+        self.clear_introspection_cache();
+        let handshake = async {
+            let socket = reconnect.address.clone().connect().await?.into();
+
+            Authenticated::client(
+                socket,
+                reconnect.auth_mechanisms.clone(),
+                reconnect.external_id.clone(),
+            )
+            .await
+        };
+        let auth = timeout(handshake, reconnect.timeout).await?;
+
+        // The `Hello` call has to be the first message on the new connection, so we queue it
+        // along with the socket replacement, before any other task gets to send anything.
+        let hello = if self.is_bus() {
+            let mut msg = MessageBuilder::method_call("/org/freedesktop/DBus", "Hello")?
+                .destination("org.freedesktop.DBus")?
+                .interface("org.freedesktop.DBus")?
+                .build(&())?;
+            let serial = self.assign_serial_num(&mut msg)?;
+            let reply = self.expect_reply(serial);
+            let reply = reply.in_span(message_span!("method call", &msg));
+            // This doesn't go through our `Sink` implementation, so run the interceptors here.
+            let msg = intercept_outgoing(self.interceptors(), Arc::new(msg))?;
+
+            Some((msg, reply))
+        } else {
+            None
+        };
+        let (reply, unsent) = {
+            let mut raw_conn = self.inner.raw_conn.lock().expect("poisoned lock");
+            // The connection may have been closed while we were establishing it again.
+            if self.inner.closing.load(SeqCst) {
+                return Ok(false);
+            }
+            let unsent = raw_conn.replace_socket(auth.conn);
+            let reply = hello.map(|(msg, reply)| {
+                if let Some(msg) = msg {
+                    raw_conn.enqueue_message(msg);
+                }
+
+                reply
+            });
+
+            (reply, unsent)
+        };
+        self.fail_unsent_calls(unsent).await;
+        self.flush().await?;
+
+        let conn = self.clone();
+        self.inner
+            .executor
+            .spawn(
+                async move {
+                    let unique_name = match reply {
+                        Some(reply) => match conn.restore_bus_state(reply).await {
+                            Ok(unique_name) => Some(unique_name),
+                            Err(e) => {
+                                warn!("Failed to restore the connection state: {}", e);
+
+                                return;
+                            }
+                        },
+                        None => None,
+                    };
+                    debug!("Reconnected");
+                    conn.emit_event(ConnectionEvent::Reconnected(unique_name));
+                },
+                "restore connection state",
+            )
+            .detach();
+
+        Ok(true)
+    }
+
+    // Fail the method calls that were still queued when the connection was lost, as if the peer
+    // replied with a `org.freedesktop.DBus.Error.Disconnected` error. Otherwise, their callers
+    // would wait for a reply that never comes. The other messages are dropped.
+    async fn fail_unsent_calls(&self, unsent: VecDeque<Arc<Message>>) {
+        let rule = MatchRule::builder().msg_type(MessageType::Error).build();
+        let sender = match self.inner.msg_senders.lock().await.get(&Some(rule.into())) {
+            Some(sender) => sender.clone(),
+            None => return,
+        };
+        let calls = unsent.into_iter().filter(|msg| {
+            msg.message_type() == MessageType::MethodCall
+                && !msg.flags().contains(MessageFlags::NoReplyExpected)
+        });
+        for call in calls {
+            let error = fdo::Error::Disconnected("The connection was lost before sending".into());
+            let description = error.description().unwrap_or_default();
+            match Message::method_error(None::<()>, &call, error.name(), &description) {
+                // Failure only means no one is waiting for the reply anymore.
+                Ok(reply) => drop(sender.broadcast(Ok(Arc::new(reply))).await),
+                Err(e) => debug!("Failed to create the error reply to an unsent call: {}", e),
+            }
+        }
+    }
+
+    // Complete the `Hello` call and request the names & install the match rules once again.
+    async fn restore_bus_state(&self, hello_reply: PendingMethodCall) -> Result<OwnedUniqueName> {
+        let unique_name = hello_reply.reply().await?.body::<OwnedUniqueName>()?;
+        self.inner.unique_names.replace(unique_name.clone());
+        self.track_owned_names(unique_name.as_ref()).await?;
+
+        let rules: Vec<_> = self
+            .inner
+            .subscriptions
+            .lock()
+            .await
+            .keys()
+            .cloned()
+            .collect();
+        if !rules.is_empty() {
+            let dbus_proxy = fdo::DBusProxy::builder(self)
+                .cache_properties(CacheProperties::No)
+                .build()
+                .await?;
+            for rule in rules {
+                dbus_proxy.add_match_rule(rule.into_inner()).await?;
+            }
+        }
+
+        let names: Vec<_> = self
+            .inner
+            .registered_names
+            .lock()
+            .await
+            .drain()
+            .map(|(name, (_, flags))| (name, flags))
+            .collect();
+        for (name, flags) in names {
+            if let Err(e) = self.request_name_with_flags(name.clone(), flags).await {
+                warn!("Failed to request name `{}` again: {}", name, e);
+            }
+        }
+
+        Ok(unique_name)
+    }
+
+    /// Returns a listener, notified on various connection activity.
+    ///
+    /// This function is meant for the caller to implement idle or timeout on inactivity.
+    pub fn monitor_activity(&self) -> EventListener {
+        self.inner
+            .raw_conn
+            .lock()
+            .expect("poisoned lock")
+            .monitor_activity()
     }
 
     /// Returns the peer process ID, or Ok(None) if it cannot be returned for the associated socket.
@@ -1270,7 +2754,7 @@ impl Connection {
     ///
     /// # Caveats
     ///
-    /// Currently `unix_group_ids` and `linux_security_label` fields are not populated.
+    /// Currently the `unix_group_ids` field is not populated.
     #[allow(deprecated)]
     pub async fn peer_credentials(&self) -> io::Result<ConnectionCredentials> {
         let raw_conn = self.inner.raw_conn.lock().expect("poisoned lock");
@@ -1288,18 +2772,153 @@ impl Connection {
             windows_sid: socket.peer_sid(),
             #[cfg(not(windows))]
             windows_sid: None,
-            // TODO: Populate this field (see the field docs for pointers).
-            linux_security_label: None,
+            linux_security_label: socket.peer_security_label()?,
         })
     }
 
+    /// Returns the credentials of the sender of a message.
+    ///
+    /// This is typically used by services to identify the caller of a method, from the header of
+    /// the method call (see the `#[zbus(header)]` attribute of [`dbus_interface`]). On bus
+    /// connections, the credentials are asked to the bus (see
+    /// [`fdo::DBusProxy::get_connection_credentials`]). On peer-to-peer connections, the sender is
+    /// the peer, so these are the [peer credentials](Connection::peer_credentials).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use zbus::{dbus_interface, fdo, Connection, MessageHeader};
+    /// #
+    /// struct Vault;
+    ///
+    /// #[dbus_interface(name = "org.example.Vault")]
+    /// impl Vault {
+    ///     async fn open(
+    ///         &self,
+    ///         #[zbus(connection)] conn: &Connection,
+    ///         #[zbus(header)] hdr: MessageHeader<'_>,
+    ///     ) -> fdo::Result<()> {
+    ///         let credentials = conn.caller_credentials(&hdr).await?;
+    ///         if credentials.unix_user_id() != Some(0) {
+    ///             return Err(fdo::Error::AccessDenied("only root can open the vault".into()));
+    ///         }
+    ///
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// # zbus::block_on(async {
+    /// let connection = Connection::system().await?;
+    /// connection
+    ///     .object_server()
+    ///     .at("/org/example/Vault", Vault)
+    ///     .await?;
+    /// # Ok::<_, zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    ///
+    /// [`dbus_interface`]: crate::dbus_interface
+    pub async fn caller_credentials(
+        &self,
+        header: &MessageHeader<'_>,
+    ) -> Result<ConnectionCredentials> {
+        if !self.is_bus() {
+            return self.peer_credentials().await.map_err(Into::into);
+        }
+
+        let sender = header.sender()?.ok_or(Error::MissingField)?;
+        fdo::DBusProxy::new(self)
+            .await?
+            .get_connection_credentials(sender.clone().into())
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Wait until the connection is closed.
+    ///
+    /// This resolves once the connection can no longer be used, because it was closed through
+    /// [`Connection::close`], the peer went away or it stopped responding to keepalive pings (see
+    /// [`ConnectionBuilder::keepalive`]). If [automatic reconnection] is enabled, that only happens
+    /// once the connection is closed through [`Connection::close`].
+    ///
+    /// [automatic reconnection]: ConnectionBuilder::auto_reconnect
+    pub async fn closed(&self) {
+        loop {
+            if self.is_closed() {
+                return;
+            }
+            let listener = self.inner.closed_event.listen();
+            if self.is_closed() {
+                return;
+            }
+
+            listener.await;
+        }
+    }
+
+    fn is_closed(&self) -> bool {
+        self.inner.closed.load(SeqCst)
+    }
+
+    pub(crate) fn set_closed(&self) {
+        self.inner.closed.store(true, SeqCst);
+        self.inner.closed_event.notify(usize::MAX);
+        self.clear_owned_names(false);
+    }
+
+    /// Ping the peer every `interval` and close the socket if it doesn't respond in time.
+    pub(crate) fn start_keepalive(&self, interval: Duration) {
+        let conn = WeakConnection::from(self);
+        let keepalive = async move {
+            loop {
+                sleep(interval).await;
+                let conn = match conn.upgrade() {
+                    Some(conn) if !conn.is_closed() => conn,
+                    _ => return,
+                };
+                let destination = conn.is_bus().then_some("org.freedesktop.DBus");
+                let res = conn
+                    .call_method_with_timeout(
+                        destination,
+                        "/",
+                        Some("org.freedesktop.DBus.Peer"),
+                        "Ping",
+                        interval,
+                        &(),
+                    )
+                    .await;
+                match res {
+                    // An error reply is still a sign of life.
+                    Ok(_) | Err(Error::MethodError(..)) => (),
+                    Err(e) => {
+                        warn!("Peer failed to respond to keepalive ping: {}", e);
+                        // The socket reader takes it from there.
+                        let raw_conn = conn.inner.raw_conn.lock().expect("poisoned lock");
+                        if let Err(e) = raw_conn.close() {
+                            debug!("Failed to close the socket: {}", e);
+                        }
+                    }
+                }
+            }
+        };
+        self.inner
+            .keepalive_task
+            .set(self.inner.executor.spawn(keepalive, "keepalive"))
+            .expect("Attempted to enable keepalive twice");
+    }
+
     pub(crate) fn init_socket_reader(&self) {
         let inner = &self.inner;
         inner
             .socket_reader_task
             .set(
-                SocketReader::new(inner.raw_conn.clone(), inner.msg_senders.clone())
-                    .spawn(&inner.executor),
+                SocketReader::new(
+                    inner.raw_conn.clone(),
+                    inner.msg_senders.clone(),
+                    self.interceptors().to_vec(),
+                    WeakConnection::from(self),
+                )
+                .spawn(&inner.executor),
             )
             .expect("Attempted to set `socket_reader_task` twice");
     }
@@ -1334,24 +2953,51 @@ where
 {
     type Error = Error;
 
-    fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<()>> {
-        // TODO: We should have a max queue length in raw::Socket for outgoing messages.
-        Poll::Ready(Ok(()))
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let mut raw_conn = self.inner.raw_conn.lock().expect("poisoned lock");
+        if !raw_conn.is_out_queue_full() {
+            return Poll::Ready(Ok(()));
+        }
+
+        // Make room by writing out queued messages. Even if the socket can't take all of them,
+        // that may be enough.
+        match (raw_conn.flush(cx), raw_conn.out_queue_policy()) {
+            (Poll::Ready(Err(e)), _) => Poll::Ready(Err(e)),
+            _ if !raw_conn.is_out_queue_full() => Poll::Ready(Ok(())),
+            (_, OutgoingQueuePolicy::Block) => Poll::Pending,
+            (_, OutgoingQueuePolicy::Error) => Poll::Ready(Err(Error::OutgoingQueueFull)),
+            // `start_send` makes room, if there's a signal to drop.
+            (_, OutgoingQueuePolicy::DropOldest) if raw_conn.has_queued_signal() => {
+                Poll::Ready(Ok(()))
+            }
+            (_, OutgoingQueuePolicy::DropOldest) => Poll::Pending,
+        }
     }
 
     fn start_send(self: Pin<&mut Self>, msg: T) -> Result<()> {
-        let msg = msg.into();
+        let msg = match intercept_outgoing(self.interceptors(), msg.into())? {
+            Some(msg) => msg,
+            None => return Ok(()),
+        };
 
         #[cfg(unix)]
         if !msg.fds().is_empty() && !self.inner.cap_unix_fd {
             return Err(Error::Unsupported);
         }
 
-        self.inner
-            .raw_conn
-            .lock()
-            .expect("poisoned lock")
-            .enqueue_message(msg);
+        let mut raw_conn = self.inner.raw_conn.lock().expect("poisoned lock");
+        // Other senders may have filled the queue since `poll_ready` told us there is room. Unless
+        // the policy is to fail, the message is queued anyway, as the sender was told it can be.
+        if raw_conn.is_out_queue_full() {
+            match raw_conn.out_queue_policy() {
+                OutgoingQueuePolicy::Error => return Err(Error::OutgoingQueueFull),
+                OutgoingQueuePolicy::DropOldest => {
+                    raw_conn.drop_oldest_queued_signal();
+                }
+                OutgoingQueuePolicy::Block => (),
+            }
+        }
+        raw_conn.enqueue_message(msg);
 
         Ok(())
     }
@@ -1362,6 +3008,8 @@ where
 
     fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
         let mut raw_conn = self.inner.raw_conn.lock().expect("poisoned lock");
+        // Set under the lock, so that a reconnection in progress doesn't replace the socket.
+        self.inner.closing.store(true, SeqCst);
         let res = raw_conn.flush(cx);
         match ready!(res) {
             Ok(_) => (),
@@ -1378,14 +3026,27 @@ impl From<crate::blocking::Connection> for Connection {
     }
 }
 
-// Internal API that allows keeping a weak connection ref around.
-#[derive(Debug)]
-pub(crate) struct WeakConnection {
+/// A weak reference to a [`Connection`].
+///
+/// It doesn't keep the connection alive, which is what you want when the connection holds the
+/// owner of the reference itself, and would otherwise never be released. This is typically the case
+/// of interfaces served by the [`ObjectServer`] of a connection, and keeping it around to emit
+/// signals (see [`WeakSignalContext`]) or call other peers.
+///
+/// It's created with [`Connection::downgrade`].
+///
+/// [`WeakSignalContext`]: crate::WeakSignalContext
+#[derive(Clone, Debug)]
+pub struct WeakConnection {
     inner: Weak<ConnectionInner>,
 }
 
+assert_impl_all!(WeakConnection: Send, Sync, Unpin);
+
 impl WeakConnection {
     /// Upgrade to a Connection.
+    ///
+    /// Returns `None` if the connection has been dropped.
     pub fn upgrade(&self) -> Option<Connection> {
         self.inner.upgrade().map(|inner| Connection { inner })
     }
@@ -1413,6 +3074,8 @@ mod tests {
     use ntest::timeout;
     use test_log::test;
 
+    #[cfg(unix)]
+    use crate::utils::{unix_p2p_pipe, unix_p2p_pipe_with};
     use crate::{fdo::DBusProxy, AuthMechanism};
 
     use super::*;
@@ -1544,26 +3207,1353 @@ mod tests {
         test_p2p(server1, client1, server2, client2).await
     }
 
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[test]
+    #[timeout(15000)]
+    fn unix_p2p_peer_credentials() {
+        crate::utils::block_on(test_unix_p2p_peer_credentials()).unwrap();
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    async fn test_unix_p2p_peer_credentials() -> Result<()> {
+        let (server, client) = unix_p2p_pipe().await?;
+
+        // Both ends are in this process.
+        for conn in [server, client] {
+            let creds = conn.peer_credentials().await?;
+            assert_eq!(creds.process_id(), Some(std::process::id()));
+            assert_eq!(
+                creds.unix_user_id(),
+                Some(nix::unistd::Uid::effective().as_raw())
+            );
+            if let Some(label) = creds.linux_security_label() {
+                assert_eq!(label.last(), Some(&0));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn unix_p2p_handshake_timeout() {
+        crate::utils::block_on(test_unix_p2p_handshake_timeout());
+    }
+
     #[cfg(unix)]
-    async fn unix_p2p_pipe() -> Result<(Connection, Connection)> {
+    async fn test_unix_p2p_handshake_timeout() {
         #[cfg(not(feature = "tokio"))]
         use std::os::unix::net::UnixStream;
         #[cfg(feature = "tokio")]
         use tokio::net::UnixStream;
-        #[cfg(all(windows, not(feature = "tokio")))]
-        use uds_windows::UnixStream;
 
-        let guid = Guid::generate();
+        // Keep the server side open but never reply to the client's `AUTH`.
+        let (_p0, p1) = UnixStream::pair().unwrap();
 
-        let (p0, p1) = UnixStream::pair().unwrap();
+        let res = ConnectionBuilder::unix_stream(p1)
+            .p2p()
+            .handshake_timeout(std::time::Duration::from_millis(100))
+            .build()
+            .await;
+        assert_eq!(res.unwrap_err(), Error::Timeout);
+    }
 
-        futures_util::try_join!(
-            ConnectionBuilder::unix_stream(p1).p2p().build(),
-            ConnectionBuilder::unix_stream(p0)
-                .server(&guid)
-                .p2p()
-                .build(),
-        )
+    #[test]
+    #[timeout(15000)]
+    fn builder_name_with_flags() {
+        crate::utils::block_on(test_builder_name_with_flags()).unwrap();
+    }
+
+    async fn test_builder_name_with_flags() -> Result<()> {
+        let name = "org.zbus.BuilderNameWithFlagsTest";
+        let _conn1 = ConnectionBuilder::session()?
+            .name_with_flags(name, RequestNameFlags::AllowReplacement.into())?
+            .build()
+            .await?;
+
+        // The first connection allowed replacement, so we can take over.
+        let conn2 = Connection::session().await?;
+        let reply = conn2
+            .request_name_with_flags(name, RequestNameFlags::ReplaceExisting.into())
+            .await?;
+        assert_eq!(reply, RequestNameReply::PrimaryOwner);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn owned_names() {
+        crate::utils::block_on(test_owned_names()).unwrap();
+    }
+
+    async fn test_owned_names() -> Result<()> {
+        let name: OwnedWellKnownName =
+            WellKnownName::from_static_str("org.zbus.OwnedNamesTest")?.into();
+        let conn1 = Connection::session().await?;
+        let mut events = conn1.receive_events();
+        assert!(conn1.owned_names().is_empty());
+
+        conn1
+            .request_name_with_flags(name.as_ref(), RequestNameFlags::AllowReplacement.into())
+            .await?;
+        assert!(conn1.owned_names().contains(&name));
+        assert_eq!(
+            events.next().await,
+            Some(ConnectionEvent::NameAcquired(name.clone()))
+        );
+
+        // Getting replaced by another connection is noticed.
+        let conn2 = Connection::session().await?;
+        conn2
+            .request_name_with_flags(name.as_ref(), RequestNameFlags::ReplaceExisting.into())
+            .await?;
+        assert_eq!(
+            events.next().await,
+            Some(ConnectionEvent::NameLost(name.clone()))
+        );
+        assert!(conn1.owned_names().is_empty());
+        assert!(conn2.owned_names().contains(&name));
+
+        let mut events = conn2.receive_events();
+        assert!(conn2.release_name(name.as_ref()).await?);
+        assert!(conn2.owned_names().is_empty());
+        assert_eq!(
+            events.next().await,
+            Some(ConnectionEvent::NameLost(name.clone()))
+        );
+
+        // A signal received before the release reply doesn't bring the name back.
+        let stale = MessageBuilder::signal(
+            "/org/freedesktop/DBus",
+            "org.freedesktop.DBus",
+            "NameAcquired",
+        )?
+        .sender("org.freedesktop.DBus")?
+        .build(&name)?;
+        conn2.handle_name_signal(&stale);
+        assert!(conn2.owned_names().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn release_name_with_reply() {
+        crate::utils::block_on(test_release_name_with_reply()).unwrap();
+    }
+
+    async fn test_release_name_with_reply() -> Result<()> {
+        let name = WellKnownName::from_static_str("org.zbus.ReleaseNameWithReplyTest")?;
+        let conn1 = Connection::session().await?;
+        let conn2 = Connection::session().await?;
+
+        assert_eq!(
+            conn1.release_name_with_reply(name.as_ref()).await?,
+            fdo::ReleaseNameReply::NonExistent
+        );
+        conn1.request_name(name.as_ref()).await?;
+        // Unlike `release_name`, the bus is asked even for the names not requested through `self`.
+        assert!(!conn2.release_name(name.as_ref()).await?);
+        assert_eq!(
+            conn2.release_name_with_reply(name.as_ref()).await?,
+            fdo::ReleaseNameReply::NotOwner
+        );
+        assert_eq!(
+            conn1.release_name_with_reply(name.as_ref()).await?,
+            fdo::ReleaseNameReply::Released
+        );
+        assert!(!conn1.release_name(name.as_ref()).await?);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn introspection_cache() {
+        crate::utils::block_on(test_introspection_cache()).unwrap();
+    }
+
+    async fn test_introspection_cache() -> Result<()> {
+        struct Cached;
+
+        #[crate::dbus_interface(name = "org.zbus.IntrospectionCache")]
+        impl Cached {
+            fn ping(&self) {}
+        }
+
+        let path = "/org/zbus/IntrospectionCache";
+        let service = ConnectionBuilder::session()?
+            .serve_at(path, Cached)?
+            .build()
+            .await?;
+        let dest = service.unique_name().unwrap().to_owned();
+        let client = Connection::session().await?;
+
+        let xml = client.introspect(Some(&dest), path).await?;
+        assert!(xml.contains("org.zbus.IntrospectionCache"));
+        // Only the owner changes of the destination are matched, and only while it has
+        // descriptions cached.
+        let rule = name_owner_changed_rule(&BusName::from(dest.as_ref()))?;
+        assert_eq!(rule.args(), [(0, dest.as_str().into())]);
+        assert!(client.inner.subscriptions.lock().await.contains_key(&rule));
+        client.introspect(Some(&dest), "/").await?;
+        assert!(client.invalidate_introspection(Some(&dest), "/")?);
+        assert!(client.inner.subscriptions.lock().await.contains_key(&rule));
+
+        // The service changed but we keep getting the cached description.
+        service.object_server().remove::<Cached, _>(path).await?;
+        assert_eq!(client.introspect(Some(&dest), path).await?, xml);
+
+        assert!(client.invalidate_introspection(Some(&dest), path)?);
+        assert!(!client.invalidate_introspection(Some(&dest), path)?);
+        // The object is gone now, and failures are not cached.
+        assert!(client.introspect(Some(&dest), path).await.is_err());
+        assert!(!client.invalidate_introspection(Some(&dest), path)?);
+
+        client.introspect(Some(&dest), "/").await?;
+        client.clear_introspection_cache();
+        assert!(!client.invalidate_introspection(Some(&dest), "/")?);
+        while client.inner.subscriptions.lock().await.contains_key(&rule) {
+            sleep(Duration::from_millis(10)).await;
+        }
+
+        // The descriptions of a name are dropped once it changes owner.
+        let name = "org.zbus.IntrospectionCache";
+        service.request_name(name).await?;
+        client.introspect(Some(name), "/").await?;
+        service.release_name(name).await?;
+        while client
+            .inner
+            .introspection_cache
+            .lock()
+            .unwrap()
+            .entries
+            .contains_key(&(Some(name.try_into()?), "/".try_into()?))
+        {
+            sleep(Duration::from_millis(10)).await;
+        }
+
+        // Only the latest descriptions are kept.
+        let bus = "org.freedesktop.DBus";
+        for i in 0..=MAX_CACHED_INTROSPECTIONS {
+            let path = format!("/org/zbus/IntrospectionCache/{i}");
+            client.introspect(Some(bus), path.as_str()).await?;
+        }
+        assert!(!client.invalidate_introspection(Some(bus), "/org/zbus/IntrospectionCache/0")?);
+        assert!(client.invalidate_introspection(Some(bus), "/org/zbus/IntrospectionCache/1")?);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn add_remove_match() {
+        crate::utils::block_on(test_add_remove_match()).unwrap();
+    }
+
+    async fn test_add_remove_match() -> Result<()> {
+        let rule = MatchRule::builder()
+            .msg_type(MessageType::Signal)
+            .interface("org.zbus.MatchTest")?
+            .member("Ping")?
+            .build();
+        let conn = Connection::session().await?;
+        let mut stream = MessageStream::from(&conn);
+        conn.add_match(rule.clone()).await?;
+        conn.add_match(rule.clone()).await?;
+
+        let emitter = Connection::session().await?;
+        emitter
+            .emit_signal(None::<()>, "/", "org.zbus.MatchTest", "Ping", &())
+            .await?;
+        let msg = loop {
+            let msg = stream.try_next().await?.unwrap();
+            if msg.member().map(|m| m == "Ping").unwrap_or(false) {
+                break msg;
+            }
+        };
+        assert_eq!(
+            msg.header()?.sender()?.map(|s| s.as_str()),
+            emitter.unique_name().map(|n| n.as_str())
+        );
+
+        // Reference counted, so it has to be removed as many times as it was added.
+        assert!(conn.remove_match(rule.clone()).await?);
+        assert!(conn.remove_match(rule.clone()).await?);
+        assert!(!conn.remove_match(rule).await?);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn become_monitor() {
+        crate::utils::block_on(test_become_monitor()).unwrap();
+    }
+
+    async fn test_become_monitor() -> Result<()> {
+        let rule = MatchRule::builder()
+            .msg_type(MessageType::Signal)
+            .interface("org.zbus.MonitorTest")?
+            .build();
+        let mut stream = Connection::session().await?.become_monitor(&[rule]).await?;
+
+        let conn = Connection::session().await?;
+        conn.emit_signal(None::<()>, "/", "org.zbus.MonitorTest", "Ping", &())
+            .await?;
+
+        // Skip the signals the bus sends to the monitor itself.
+        let msg = loop {
+            let msg = stream.try_next().await?.unwrap();
+            if msg.member().map(|m| m == "Ping").unwrap_or(false) {
+                break msg;
+            }
+        };
+        assert_eq!(
+            msg.header()?.sender()?.map(|s| s.as_str()),
+            conn.unique_name().map(|n| n.as_str())
+        );
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn unix_p2p_close() {
+        crate::utils::block_on(test_unix_p2p_close()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_unix_p2p_close() -> Result<()> {
+        let (server, client) = unix_p2p_pipe().await?;
+        let mut stream = MessageStream::from(&server);
+
+        // Only queue the message, `close` has to flush it.
+        let mut msg = Message::signal(None::<()>, None::<()>, "/", "org.zbus.p2p", "Bye", &())?;
+        client.assign_serial_num(&mut msg)?;
+        (&mut &client).feed(msg).await?;
+        client.clone().close().await?;
+
+        let msg = stream.try_next().await?.unwrap();
+        assert_eq!(msg.to_string(), "Signal Bye");
+        // Any operation on the closed connection fails.
+        client
+            .emit_signal(None::<()>, "/", "org.zbus.p2p", "Bye", &())
+            .await
+            .unwrap_err();
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn stop_object_server() {
+        crate::utils::block_on(test_stop_object_server()).unwrap();
+    }
+
+    async fn test_stop_object_server() -> Result<()> {
+        struct Slow {
+            started: Arc<Event>,
+            done: Arc<AtomicBool>,
+        }
+
+        #[crate::dbus_interface(name = "org.zbus.Slow")]
+        impl Slow {
+            async fn nap(&self) {
+                self.started.notify(1);
+                sleep(Duration::from_millis(100)).await;
+                self.done.store(true, SeqCst);
+            }
+        }
+
+        let started = Arc::new(Event::new());
+        let done = Arc::new(AtomicBool::new(false));
+        let service = ConnectionBuilder::session()?
+            .serve_at(
+                "/org/zbus/Slow",
+                Slow {
+                    started: started.clone(),
+                    done: done.clone(),
+                },
+            )?
+            .build()
+            .await?;
+        let destination = service.unique_name().unwrap().to_owned();
+        let client = Connection::session().await?;
+
+        // The call being dispatched when stopping is still handled.
+        let nap_started = started.listen();
+        let (reply, _) = futures_util::join!(
+            client.call_method(
+                Some(destination.as_ref()),
+                "/org/zbus/Slow",
+                Some("org.zbus.Slow"),
+                "Nap",
+                &(),
+            ),
+            async {
+                nap_started.await;
+                service.stop_object_server().await;
+                assert!(done.load(SeqCst));
+            }
+        );
+        reply?;
+        done.store(false, SeqCst);
+
+        // But not the ones received afterwards, which get an error.
+        let res = client
+            .call_method(
+                Some(destination.as_ref()),
+                "/org/zbus/Slow",
+                Some("org.zbus.Slow"),
+                "Nap",
+                &(),
+            )
+            .await;
+        match res.unwrap_err() {
+            Error::MethodError(name, _, _) => {
+                assert_eq!(name, "org.freedesktop.DBus.Error.Failed")
+            }
+            e => panic!("unexpected error: {}", e),
+        }
+        assert!(!done.load(SeqCst));
+
+        Ok(())
+    }
+
+    #[test]
+    fn unique_names() {
+        use std::convert::TryFrom;
+
+        let names = UniqueNames::default();
+        assert_eq!(names.current(), None);
+        names
+            .set(OwnedUniqueName::try_from(":1.1").unwrap())
+            .unwrap();
+        let first = names.first().unwrap();
+        assert_eq!(names.current().as_ref(), Some(first));
+
+        // Reconnections assign new names, only the last of which is kept along the first one.
+        names.replace(OwnedUniqueName::try_from(":1.2").unwrap());
+        names.replace(OwnedUniqueName::try_from(":1.3").unwrap());
+        assert_eq!(names.current().unwrap().as_str(), ":1.3");
+        assert_eq!(first.as_str(), ":1.1");
+        assert!(names
+            .set(OwnedUniqueName::try_from(":1.4").unwrap())
+            .is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn unix_p2p_auto_reconnect() {
+        crate::utils::block_on(test_unix_p2p_auto_reconnect()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_unix_p2p_auto_reconnect() -> Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let address = format!("unix:path={}", dir.path().join("zbus-reconnect").display());
+        let server = crate::Server::bind(address.as_str()).await?;
+
+        let client = ConnectionBuilder::address(address.as_str())?
+            .p2p()
+            .auto_reconnect(true)
+            .build();
+        let (client, server_conn) = futures_util::try_join!(client, server.accept())?;
+        let mut events = client.receive_events();
+
+        // Drop the client from the server side and accept it again.
+        server_conn.close().await?;
+        assert_eq!(events.next().await, Some(ConnectionEvent::Disconnected));
+        // A call still queued meanwhile isn't sent over the new connection but fails, instead of
+        // waiting for a reply forever.
+        let mut call = MessageBuilder::method_call("/", "Lost")?.build(&())?;
+        let serial = client.assign_serial_num(&mut call)?;
+        let reply = client.expect_reply(serial);
+        let mut sink = &client;
+        futures_util::future::poll_fn(|cx| Sink::<Message>::poll_ready(Pin::new(&mut sink), cx))
+            .await?;
+        Pin::new(&mut sink).start_send(call)?;
+        let server_conn = server.accept().await?;
+        match reply.reply().await {
+            Err(Error::MethodError(name, _, _)) => {
+                assert_eq!(name, "org.freedesktop.DBus.Error.Disconnected")
+            }
+            res => panic!("unexpected reply: {:?}", res),
+        }
+        assert_eq!(
+            events.next().await,
+            Some(ConnectionEvent::Reconnected(None))
+        );
+
+        let mut stream = MessageStream::from(&server_conn);
+        client
+            .emit_signal(None::<()>, "/", "org.zbus.p2p", "Back", &())
+            .await?;
+        let msg = stream.try_next().await?.unwrap();
+        assert_eq!(msg.to_string(), "Signal Back");
+
+        // Closing the connection on purpose doesn't establish it again.
+        client.clone().close().await?;
+        crate::abstractions::timeout::timeout(
+            async {
+                client.closed().await;
+
+                Ok(())
+            },
+            Duration::from_secs(5),
+        )
+        .await?;
+        assert_eq!(events.next().await, Some(ConnectionEvent::Disconnected));
+
+        // Only address-based connections can be reconnected.
+        #[cfg(not(feature = "tokio"))]
+        use std::os::unix::net::UnixStream;
+        #[cfg(feature = "tokio")]
+        use tokio::net::UnixStream;
+        let (p0, _p1) = UnixStream::pair().unwrap();
+        let res = ConnectionBuilder::unix_stream(p0)
+            .p2p()
+            .auto_reconnect(true)
+            .build()
+            .await;
+        assert!(matches!(res, Err(Error::Unsupported)));
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn unix_p2p_stats() {
+        crate::utils::block_on(test_unix_p2p_stats()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_unix_p2p_stats() -> Result<()> {
+        let (server, client) = unix_p2p_pipe().await?;
+        assert_eq!(client.stats().await, ConnectionStats::default());
+        let mut stream = MessageStream::from(&server);
+
+        client
+            .emit_signal(None::<()>, "/", "org.zbus.p2p", "Hi", &())
+            .await?;
+        let msg = stream.try_next().await?.unwrap();
+        let stats = client.stats().await;
+        assert_eq!(stats.msgs_sent(), 1);
+        assert_eq!(stats.bytes_sent(), msg.as_bytes().len() as u64);
+        assert_eq!(stats.outgoing_queued(), 0);
+        let stats = server.stats().await;
+        assert_eq!(stats.msgs_received(), 1);
+        assert_eq!(stats.bytes_received(), msg.as_bytes().len() as u64);
+
+        // The server doesn't serve anything, so the call stays in flight until dropped.
+        let call = client
+            .call_method_raw(
+                None::<()>,
+                "/",
+                Some("org.zbus.p2p"),
+                "Test",
+                BitFlags::empty(),
+                &(),
+            )
+            .await?
+            .unwrap();
+        assert_eq!(client.stats().await.method_calls_in_flight(), 1);
+        drop(call);
+        assert_eq!(client.stats().await.method_calls_in_flight(), 0);
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn unix_p2p_max_message_size() {
+        crate::utils::block_on(test_unix_p2p_max_message_size()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_unix_p2p_max_message_size() -> Result<()> {
+        let (client, server) =
+            unix_p2p_pipe_with(Ok, |server| Ok(server.max_message_size(1024))).await?;
+        let mut stream = MessageStream::from(&server);
+
+        client
+            .emit_signal(None::<()>, "/", "org.zbus.p2p", "Small", &"small")
+            .await?;
+        let msg = stream.try_next().await?.unwrap();
+        assert_eq!(msg.to_string(), "Signal Small");
+
+        let big = "a".repeat(2048);
+        client
+            .emit_signal(None::<()>, "/", "org.zbus.p2p", "Big", &big)
+            .await?;
+        match stream.try_next().await {
+            Err(Error::MessageTooLarge(size)) => assert!(size > 2048),
+            res => panic!("unexpected result: {:?}", res),
+        }
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn unix_p2p_sink_backpressure() {
+        crate::utils::block_on(test_unix_p2p_sink_backpressure()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_unix_p2p_sink_backpressure() -> Result<()> {
+        use futures_util::sink::SinkExt;
+        #[cfg(not(feature = "tokio"))]
+        use std::os::unix::net::UnixStream;
+        #[cfg(feature = "tokio")]
+        use tokio::net::UnixStream;
+
+        for policy in [
+            OutgoingQueuePolicy::Block,
+            OutgoingQueuePolicy::Error,
+            OutgoingQueuePolicy::DropOldest,
+        ] {
+            let (p0, p1) = UnixStream::pair().unwrap();
+            #[cfg(not(feature = "tokio"))]
+            let p0 = async_io::Async::new(p0)?;
+            // The peer only does the handshake and never reads anything afterwards.
+            let (client, _peer) = futures_util::try_join!(
+                ConnectionBuilder::unix_stream(p1)
+                    .p2p()
+                    .max_outgoing_queued(2)
+                    .outgoing_queue_policy(policy)
+                    .build(),
+                crate::Authenticated::server(
+                    p0,
+                    Guid::generate(),
+                    Some(nix::unistd::Uid::effective().into()),
+                    None,
+                    None,
+                    crate::CookieContext::default(),
+                ),
+            )?;
+
+            // Feeding doesn't flush, so without backpressure the queue would be full after two
+            // messages. Instead, once the socket buffer is full as well, the policy kicks in.
+            let body = "a".repeat(64 * 1024);
+            let mut sink = &client;
+            for i in 0.. {
+                let msg =
+                    Message::signal(None::<()>, None::<()>, "/", "org.zbus.p2p", "Big", &body)?;
+                let res = crate::abstractions::timeout::timeout(
+                    sink.feed(msg),
+                    std::time::Duration::from_millis(100),
+                )
+                .await;
+                match (policy, res) {
+                    (OutgoingQueuePolicy::DropOldest, Ok(())) if i == 64 => break,
+                    (_, Ok(())) => continue,
+                    (OutgoingQueuePolicy::Block, Err(Error::Timeout)) => break,
+                    (OutgoingQueuePolicy::Error, Err(Error::OutgoingQueueFull)) => break,
+                    (_, Err(e)) => panic!("unexpected error with {:?}: {:?}", policy, e),
+                }
+            }
+            let stats = client.stats().await;
+            assert!(stats.outgoing_queued() <= 2);
+            if policy == OutgoingQueuePolicy::DropOldest {
+                // The 4 MiB fed can't possibly fit in the socket buffer, so some were dropped.
+                assert!(stats.msgs_sent() + (stats.outgoing_queued() as u64) < 65);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn unix_p2p_concurrent_senders() {
+        crate::utils::block_on(test_unix_p2p_concurrent_senders()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_unix_p2p_concurrent_senders() -> Result<()> {
+        use futures_util::future::poll_fn;
+
+        let (client, server) =
+            crate::utils::unix_p2p_pipe_with(|builder| Ok(builder.max_outgoing_queued(1)), Ok)
+                .await?;
+        let mut stream = MessageStream::from(&server);
+
+        // The senders are all told there is room in the queue before any of them sends, as can
+        // happen with concurrent tasks. They still get to send instead of failing.
+        let mut senders = [&client; 4];
+        for sender in &mut senders {
+            poll_fn(|cx| Sink::<Message>::poll_ready(Pin::new(&mut *sender), cx)).await?;
+        }
+        for (i, sender) in senders.iter_mut().enumerate() {
+            let member = format!("Signal{i}");
+            let msg = Message::signal(None::<()>, None::<()>, "/", "org.zbus.p2p", member, &())?;
+            Pin::new(&mut *sender).start_send(msg)?;
+        }
+        client.flush().await?;
+        for i in 0..senders.len() {
+            let msg = stream.try_next().await?.unwrap();
+            assert_eq!(msg.to_string(), format!("Signal Signal{i}"));
+        }
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn unix_p2p_method_timeout() {
+        crate::utils::block_on(test_unix_p2p_method_timeout()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_unix_p2p_method_timeout() -> Result<()> {
+        use std::time::Duration;
+
+        // The server doesn't serve any objects, so it never replies to method calls.
+        let (client, _server) = unix_p2p_pipe_with(
+            |client| Ok(client.method_timeout(Duration::from_millis(100))),
+            Ok,
+        )
+        .await?;
+        assert_eq!(client.method_timeout(), Some(Duration::from_millis(100)));
+
+        let res = client
+            .call_method(None::<()>, "/", Some("org.zbus.p2p"), "Silent", &())
+            .await;
+        assert_eq!(res.unwrap_err(), Error::Timeout);
+
+        let res = client
+            .call_method_with_timeout(
+                None::<()>,
+                "/",
+                Some("org.zbus.p2p"),
+                "Silent",
+                Duration::from_millis(10),
+                &(),
+            )
+            .await;
+        assert_eq!(res.unwrap_err(), Error::Timeout);
+
+        // Calls that timed out don't linger around.
+        assert_eq!(client.stats().await.method_calls_in_flight(), 0);
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn custom_socket() {
+        crate::utils::block_on(test_custom_socket()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_custom_socket() -> Result<()> {
+        #[cfg(not(feature = "tokio"))]
+        use std::os::unix::net::UnixStream;
+        use std::os::unix::prelude::RawFd;
+        #[cfg(feature = "tokio")]
+        use tokio::net::UnixStream;
+
+        // A transport that can't pass FDs or tell who the peer is, and counts the bytes it sends.
+        #[derive(Debug)]
+        struct Channel {
+            inner: Box<dyn Socket>,
+            sent: Arc<AtomicUsize>,
+        }
+
+        impl Socket for Channel {
+            fn can_pass_unix_fd(&self) -> bool {
+                false
+            }
+
+            fn poll_recvmsg(
+                &mut self,
+                cx: &mut Context<'_>,
+                buf: &mut [u8],
+            ) -> Poll<io::Result<(usize, Vec<zvariant::OwnedFd>)>> {
+                self.inner.poll_recvmsg(cx, buf)
+            }
+
+            fn poll_sendmsg(
+                &mut self,
+                cx: &mut Context<'_>,
+                buffer: &[u8],
+                fds: &[RawFd],
+            ) -> Poll<io::Result<usize>> {
+                if !fds.is_empty() {
+                    return Poll::Ready(Err(io::Error::new(
+                        ErrorKind::InvalidInput,
+                        "fds cannot be sent",
+                    )));
+                }
+                let res = ready!(self.inner.poll_sendmsg(cx, buffer, fds));
+                if let Ok(len) = &res {
+                    self.sent.fetch_add(*len, SeqCst);
+                }
+
+                Poll::Ready(res)
+            }
+
+            fn close(&self) -> io::Result<()> {
+                self.inner.close()
+            }
+        }
+
+        let (p0, p1) = UnixStream::pair().unwrap();
+        #[cfg(not(feature = "tokio"))]
+        let (p0, p1) = (async_io::Async::new(p0)?, async_io::Async::new(p1)?);
+        let channel = |stream, sent: &Arc<AtomicUsize>| Channel {
+            inner: Box::new(stream),
+            sent: sent.clone(),
+        };
+        let client_sent = Arc::new(AtomicUsize::new(0));
+
+        // Without peer credentials, `EXTERNAL` can't be used.
+        let guid = Guid::generate();
+        let (client, server) = futures_util::try_join!(
+            ConnectionBuilder::socket(channel(p1, &client_sent))
+                .auth_mechanisms(&[AuthMechanism::Anonymous])
+                .p2p()
+                .build(),
+            ConnectionBuilder::socket(channel(p0, &Arc::default()))
+                .server(&guid)
+                .auth_mechanisms(&[AuthMechanism::Anonymous])
+                .p2p()
+                .build(),
+        )?;
+        let mut stream = MessageStream::from(&server);
+
+        client
+            .emit_signal(None::<()>, "/", "org.zbus.p2p", "Custom", &"hi")
+            .await?;
+        let msg = stream.try_next().await?.unwrap();
+        assert_eq!(msg.body::<&str>()?, "hi");
+        // Everything, including the handshake, went through the channel.
+        let stats = client.stats().await;
+        assert!(stats.bytes_sent() > 0);
+        assert!(client_sent.load(SeqCst) as u64 > stats.bytes_sent());
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn unix_p2p_concurrent_calls() {
+        crate::utils::block_on(test_unix_p2p_concurrent_calls()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_unix_p2p_concurrent_calls() -> Result<()> {
+        struct Echo;
+
+        #[crate::dbus_interface(name = "org.zbus.p2p.Echo")]
+        impl Echo {
+            fn echo(&self, n: u32) -> u32 {
+                n
+            }
+        }
+
+        let (server, client) = unix_p2p_pipe().await?;
+        server.object_server().at("/org/zbus/Echo", Echo).await?;
+
+        // Clones of the connection can be used from multiple tasks at once, without any locking
+        // on our side, and each call gets its own reply.
+        let tasks = (0..8u32).map(|i| {
+            let client = client.clone();
+            async move {
+                for j in 0..10 {
+                    let n = i * 100 + j;
+                    let reply = client
+                        .call_method(
+                            None::<()>,
+                            "/org/zbus/Echo",
+                            Some("org.zbus.p2p.Echo"),
+                            "Echo",
+                            &n,
+                        )
+                        .await?;
+                    assert_eq!(reply.body::<u32>()?, n);
+                }
+
+                Ok::<_, Error>(())
+            }
+        });
+        futures_util::future::try_join_all(tasks).await?;
+        assert_eq!(client.stats().await.msgs_sent(), 80);
+
+        Ok(())
+    }
+
+    #[cfg(all(unix, not(feature = "tokio")))]
+    #[test]
+    #[timeout(15000)]
+    fn unix_p2p_external_event_loop() {
+        crate::utils::block_on(test_unix_p2p_external_event_loop()).unwrap();
+    }
+
+    #[cfg(all(unix, not(feature = "tokio")))]
+    async fn test_unix_p2p_external_event_loop() -> Result<()> {
+        use futures_util::FutureExt;
+
+        let (client, server) =
+            unix_p2p_pipe_with(|client| Ok(client.internal_executor(false)), Ok).await?;
+        assert!(client.raw_fd().is_some());
+        let mut client_stream = MessageStream::from(&client);
+        let mut server_stream = MessageStream::from(&server);
+
+        // Queue a message without waiting for it to be sent, and write it out by hand.
+        let msg = Message::signal(None::<()>, None::<()>, "/", "org.zbus.p2p", "Ping", &())?;
+        (&mut &client).feed(msg).now_or_never().unwrap()?;
+        assert!(client.try_flush()?);
+        let msg = server_stream.try_next().await?.unwrap();
+        assert_eq!(msg.to_string(), "Signal Ping");
+
+        // Incoming messages are dispatched by the tasks of the connection, which we run by hand.
+        assert!(client_stream.try_receive().is_none());
+        server
+            .emit_signal(None::<()>, "/", "org.zbus.p2p", "Pong", &())
+            .await?;
+        let msg = loop {
+            client.executor().try_tick();
+            match client_stream.try_receive() {
+                Some(msg) => break msg?,
+                None => std::thread::sleep(std::time::Duration::from_millis(1)),
+            }
+        };
+        assert_eq!(msg.to_string(), "Signal Pong");
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn unix_p2p_cancelled_call() {
+        crate::utils::block_on(test_unix_p2p_cancelled_call()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_unix_p2p_cancelled_call() -> Result<()> {
+        let (server, client) = unix_p2p_pipe().await?;
+        let mut server_stream = MessageStream::from(&server);
+        let call =
+            |n: &'static u32| client.call_method(None::<()>, "/", Some("org.zbus.p2p"), "Call", n);
+
+        // Send off the call and give up on it before the reply arrives.
+        {
+            let cancelled = call(&1);
+            futures_util::pin_mut!(cancelled);
+            assert!(futures_util::poll!(cancelled).is_pending());
+            assert_eq!(client.stats().await.method_calls_in_flight(), 1);
+        }
+        assert_eq!(client.stats().await.method_calls_in_flight(), 0);
+
+        // The late reply is simply discarded and doesn't get in the way of the next call.
+        let msg = server_stream.try_next().await?.unwrap();
+        server.reply(&msg, &msg.body::<u32>()?).await?;
+        let (reply, _) = futures_util::try_join!(call(&2), async {
+            let msg = server_stream.try_next().await?.unwrap();
+            server.reply(&msg, &msg.body::<u32>()?).await
+        })?;
+        assert_eq!(reply.body::<u32>()?, 2);
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn unix_p2p_manual_reply() {
+        crate::utils::block_on(test_unix_p2p_manual_reply()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_unix_p2p_manual_reply() -> Result<()> {
+        let (server, client) = unix_p2p_pipe().await?;
+        let mut server_stream = MessageStream::from(&server);
+        let call = |n: u32| {
+            MessageBuilder::method_call("/", "Call")
+                .unwrap()
+                .interface("org.zbus.p2p")
+                .unwrap()
+                .build(&n)
+                .unwrap()
+        };
+
+        // Allocate the serial ourselves and only send the call once we watch for its reply.
+        let mut msg = call(1);
+        let serial = client.assign_serial_num(&mut msg)?;
+        let pending = client.expect_reply(serial);
+        assert_eq!(pending.serial(), serial);
+        assert_eq!(client.send_message(msg).await?, serial);
+        // Replies to other serials are not taken for ours.
+        let other = client.send_message_with_reply(call(2)).await?;
+        assert_ne!(other.serial(), serial);
+        for _ in 0..2 {
+            let msg = server_stream.try_next().await?.unwrap();
+            server.reply(&msg, &(msg.body::<u32>()? * 10)).await?;
+        }
+        assert_eq!(other.await?.body::<u32>()?, 20);
+        assert_eq!(pending.await?.body::<u32>()?, 10);
+
+        // Error replies resolve to an error.
+        let pending = client.send_message_with_reply(call(3)).await?;
+        let msg = server_stream.try_next().await?.unwrap();
+        server
+            .reply_error(&msg, "org.zbus.p2p.Error", &"nope")
+            .await?;
+        assert!(matches!(pending.await.unwrap_err(), Error::MethodError(..)));
+        assert_eq!(client.stats().await.method_calls_in_flight(), 0);
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn unix_p2p_interceptors() {
+        crate::utils::block_on(test_unix_p2p_interceptors()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_unix_p2p_interceptors() -> Result<()> {
+        type Log = Arc<sync::Mutex<Vec<(usize, String)>>>;
+
+        // Logs the members of the messages going through and applies some policy.
+        #[derive(Debug)]
+        struct Policy {
+            id: usize,
+            log: Log,
+        }
+
+        impl MessageInterceptor for Policy {
+            fn outgoing(&self, msg: Arc<Message>) -> Result<Option<Arc<Message>>> {
+                let member = msg.member().map(|m| m.to_string()).unwrap_or_default();
+                self.log.lock().unwrap().push((self.id, member.clone()));
+
+                match member.as_str() {
+                    "Deny" => Err(Error::Unsupported),
+                    "Drop" => Ok(None),
+                    _ => Ok(Some(msg)),
+                }
+            }
+
+            fn incoming(&self, msg: Arc<Message>) -> Option<Arc<Message>> {
+                let member = msg.member().map(|m| m.to_string()).unwrap_or_default();
+                self.log.lock().unwrap().push((self.id, member.clone()));
+
+                match member.as_str() {
+                    "Muted" => None,
+                    "Old" => {
+                        let header = msg.header().unwrap();
+                        let renamed = MessageBuilder::from(header.clone())
+                            .member("New")
+                            .unwrap()
+                            .build(&msg.body::<u32>().unwrap())
+                            .unwrap();
+
+                        Some(Arc::new(renamed))
+                    }
+                    _ => Some(msg),
+                }
+            }
+        }
+
+        let log = Log::default();
+        let (client, server) = unix_p2p_pipe_with(
+            |client| {
+                Ok(client
+                    .interceptor(Policy {
+                        id: 1,
+                        log: log.clone(),
+                    })
+                    .interceptor(Policy {
+                        id: 2,
+                        log: log.clone(),
+                    }))
+            },
+            Ok,
+        )
+        .await?;
+        let mut client_stream = MessageStream::from(&client);
+        let mut server_stream = MessageStream::from(&server);
+
+        // Interceptors run in order, and a dropped message doesn't reach the next one.
+        client
+            .emit_signal(None::<()>, "/", "org.zbus.p2p", "Drop", &())
+            .await?;
+        assert_eq!(*log.lock().unwrap(), vec![(1, "Drop".to_string())]);
+        assert!(matches!(
+            client
+                .emit_signal(None::<()>, "/", "org.zbus.p2p", "Deny", &())
+                .await
+                .unwrap_err(),
+            Error::Unsupported
+        ));
+        client
+            .emit_signal(None::<()>, "/", "org.zbus.p2p", "Keep", &())
+            .await?;
+        let msg = server_stream.try_next().await?.unwrap();
+        assert_eq!(msg.member().unwrap(), "Keep");
+
+        // Dropping a method call fails it, rather than leaving the caller waiting for a reply.
+        let res = client
+            .call_method(None::<()>, "/", Some("org.zbus.p2p"), "Drop", &())
+            .await;
+        assert!(matches!(res.unwrap_err(), Error::Failure(_)));
+        log.lock().unwrap().clear();
+
+        // Replies are intercepted too.
+        let (reply, _) = futures_util::try_join!(
+            client.call_method(None::<()>, "/", Some("org.zbus.p2p"), "Call", &()),
+            async {
+                let msg = server_stream.try_next().await?.unwrap();
+                server.reply(&msg, &()).await
+            },
+        )?;
+        assert_eq!(reply.message_type(), MessageType::MethodReturn);
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                (1, "Call".to_string()),
+                (2, "Call".to_string()),
+                (1, String::new()),
+                (2, String::new()),
+            ]
+        );
+
+        // Incoming messages can be dropped and rewritten.
+        for member in ["Muted", "Old"] {
+            server
+                .emit_signal(None::<()>, "/", "org.zbus.p2p", member, &7u32)
+                .await?;
+        }
+        let msg = loop {
+            let msg = client_stream.try_next().await?.unwrap();
+            if msg.message_type() == MessageType::Signal {
+                break msg;
+            }
+        };
+        assert_eq!(msg.member().unwrap(), "New");
+        assert_eq!(msg.body::<u32>()?, 7);
+        assert!(msg.recv_position() > reply.recv_position());
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn unix_p2p_tracing_spans() {
+        use std::fmt;
+        use tracing::{
+            field::{Field, Visit},
+            span::{Attributes, Id},
+            Subscriber,
+        };
+        use tracing_subscriber::{
+            layer::{Context, SubscriberExt},
+            Layer, Registry,
+        };
+
+        type Span = (&'static str, Option<u64>);
+
+        // Records the name and the serial of all the spans created.
+        #[derive(Clone, Default)]
+        struct Spans(Arc<sync::Mutex<Vec<Span>>>);
+
+        impl<S: Subscriber> Layer<S> for Spans {
+            fn on_new_span(&self, attrs: &Attributes<'_>, _: &Id, _: Context<'_, S>) {
+                struct Serial(Option<u64>);
+
+                impl Visit for Serial {
+                    fn record_u64(&mut self, field: &Field, value: u64) {
+                        if field.name() == "serial" {
+                            self.0 = Some(value);
+                        }
+                    }
+
+                    fn record_debug(&mut self, _: &Field, _: &dyn fmt::Debug) {}
+                }
+
+                let mut serial = Serial(None);
+                attrs.record(&mut serial);
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((attrs.metadata().name(), serial.0));
+            }
+        }
+
+        let spans = Spans::default();
+        let _guard = tracing::subscriber::set_default(Registry::default().with(spans.clone()));
+
+        let reply = crate::utils::block_on(async {
+            let (server, client) = unix_p2p_pipe().await?;
+            let mut server_stream = MessageStream::from(&server);
+            let (reply, _) = futures_util::try_join!(
+                client.call_method(None::<()>, "/", Some("org.zbus.p2p"), "Call", &()),
+                async {
+                    let msg = server_stream.try_next().await?.unwrap();
+                    server.reply(&msg, &()).await
+                },
+            )?;
+
+            Ok::<_, Error>(reply)
+        })
+        .unwrap();
+
+        let spans = spans.0.lock().unwrap();
+        assert_eq!(
+            spans
+                .iter()
+                .filter(|(name, _)| *name == "handshake")
+                .count(),
+            2
+        );
+        // The reply can be correlated with the call through the serial.
+        let serial = reply.reply_serial().map(u64::from);
+        assert!(spans.contains(&("method call", serial)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn unix_p2p_keepalive() {
+        crate::utils::block_on(test_unix_p2p_keepalive()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_unix_p2p_keepalive() -> Result<()> {
+        use crate::abstractions::timeout::timeout;
+        #[cfg(not(feature = "tokio"))]
+        use std::os::unix::net::UnixStream;
+        use std::time::Duration;
+        #[cfg(feature = "tokio")]
+        use tokio::net::UnixStream;
+
+        let interval = Duration::from_millis(50);
+
+        // A responsive peer keeps the connection alive.
+        let (client, server) =
+            unix_p2p_pipe_with(|client| Ok(client.keepalive(interval)), Ok).await?;
+        let _ = server.object_server();
+        let closed = async {
+            client.closed().await;
+
+            Ok(())
+        };
+        assert_eq!(timeout(closed, interval * 6).await, Err(Error::Timeout));
+        assert!(client.stats().await.msgs_sent() >= 2);
+
+        // A peer that doesn't respond gets the connection closed.
+        let (p0, p1) = UnixStream::pair().unwrap();
+        #[cfg(not(feature = "tokio"))]
+        let p0 = async_io::Async::new(p0)?;
+        let (client, _peer) = futures_util::try_join!(
+            ConnectionBuilder::unix_stream(p1)
+                .p2p()
+                .keepalive(interval)
+                .build(),
+            crate::Authenticated::server(
+                p0,
+                Guid::generate(),
+                Some(nix::unistd::Uid::effective().into()),
+                None,
+                None,
+                crate::CookieContext::default(),
+            ),
+        )?;
+        let mut events = client.receive_events();
+        let closed = async {
+            client.closed().await;
+
+            Ok(())
+        };
+        timeout(closed, interval * 20).await?;
+        assert_eq!(events.next().await, Some(ConnectionEvent::Disconnected));
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn unix_p2p_object_server() {
+        crate::utils::block_on(test_unix_p2p_object_server()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_unix_p2p_object_server() -> Result<()> {
+        struct Greeter(&'static str);
+
+        #[crate::dbus_interface(name = "org.zbus.p2p.Greeter")]
+        impl Greeter {
+            fn greet(&self, name: &str) -> String {
+                format!("{} {name}", self.0)
+            }
+        }
+
+        // Both peers serve objects and neither has a unique name.
+        let (client, server) = unix_p2p_pipe_with(
+            |client| client.serve_at("/org/zbus/Greeter", Greeter("Hi")),
+            |server| server.serve_at("/org/zbus/Greeter", Greeter("Hello")),
+        )
+        .await?;
+        assert!(server.unique_name().is_none());
+        assert!(client.unique_name().is_none());
+
+        for (conn, greeting) in [(&client, "Hello p2p"), (&server, "Hi p2p")] {
+            let reply = conn
+                .call_method(
+                    None::<()>,
+                    "/org/zbus/Greeter",
+                    Some("org.zbus.p2p.Greeter"),
+                    "Greet",
+                    &"p2p",
+                )
+                .await?;
+            assert_eq!(reply.body::<String>()?, greeting);
+        }
+
+        // A fire-and-forget call doesn't wait for the reply.
+        let reply = client
+            .call_method_with_flags(
+                None::<()>,
+                "/org/zbus/Greeter",
+                Some("org.zbus.p2p.Greeter"),
+                "Greet",
+                MethodFlags::NoReplyExpected | MethodFlags::NoAutoStart,
+                &"p2p",
+            )
+            .await?;
+        assert!(reply.is_none());
+        let reply = client
+            .call_method_with_flags(
+                None::<()>,
+                "/org/zbus/Greeter",
+                Some("org.zbus.p2p.Greeter"),
+                "Greet",
+                MethodFlags::AllowInteractiveAuth.into(),
+                &"p2p",
+            )
+            .await?
+            .unwrap();
+        assert_eq!(reply.body::<String>()?, "Hello p2p");
+
+        Ok(())
     }
 
     // Compile-test only since we don't have a VM setup to run this with/in.
@@ -1,5 +1,6 @@
 #[cfg(not(feature = "tokio"))]
 use async_io::Async;
+use enumflags2::BitFlags;
 use event_listener::Event;
 use static_assertions::assert_impl_all;
 #[cfg(not(feature = "tokio"))]
@@ -10,6 +11,7 @@ use std::{
     collections::{HashMap, HashSet, VecDeque},
     convert::TryInto,
     sync::Arc,
+    time::Duration,
 };
 #[cfg(feature = "tokio")]
 use tokio::net::TcpStream;
@@ -17,6 +19,7 @@ use tokio::net::TcpStream;
 use tokio::net::UnixStream;
 #[cfg(feature = "tokio-vsock")]
 use tokio_vsock::VsockStream;
+use tracing::Instrument;
 #[cfg(windows)]
 use uds_windows::UnixStream;
 #[cfg(all(feature = "vsock", not(feature = "tokio")))]
@@ -25,12 +28,15 @@ use vsock::VsockStream;
 use zvariant::{ObjectPath, Str};
 
 use crate::{
-    address::{self, Address},
+    address::Address,
     async_lock::RwLock,
+    fdo::RequestNameFlags,
     handshake,
     names::{InterfaceName, UniqueName, WellKnownName},
     raw::Socket,
-    AuthMechanism, Authenticated, Connection, Error, Executor, Guid, Interface, Result,
+    timeout::timeout,
+    AccessPolicy, AuthMechanism, Authenticated, Connection, DispatchObserver, Error, Executor,
+    Guid, HandshakeOutcome, Interface, MessageInterceptor, OutgoingQueuePolicy, Result,
 };
 
 const DEFAULT_MAX_QUEUED: usize = 64;
@@ -46,28 +52,70 @@ enum Target {
     VsockStream(VsockStream),
     Address(Address),
     Socket(Box<dyn Socket>),
+    Authenticated(Box<dyn Socket>, HandshakeOutcome),
 }
 
 type Interfaces<'a> =
     HashMap<ObjectPath<'a>, HashMap<InterfaceName<'static>, Arc<RwLock<dyn Interface>>>>;
 
 /// A builder for [`zbus::Connection`].
+///
+/// The builder gathers all the settings that need to be known before the connection is
+/// established: the bus (or peer) to connect to, the authentication mechanisms, timeouts, queue
+/// sizes, the interfaces to serve and the names to request.
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use zbus::{AuthMechanism, ConnectionBuilder};
+/// # use zbus::block_on;
+/// #
+/// # block_on(async {
+/// let conn = ConnectionBuilder::system()?
+///     .auth_mechanisms(&[AuthMechanism::External])
+///     .handshake_timeout(Duration::from_secs(5))
+///     .max_queued(128)
+///     .name("org.zbus.MyService")?
+///     .build()
+///     .await?;
+///
+/// // Do something useful with `conn`..
+/// #     drop(conn);
+/// #     Ok::<(), zbus::Error>(())
+/// # }).unwrap();
+/// ```
 #[derive(derivative::Derivative)]
 #[derivative(Debug)]
 #[must_use]
 pub struct ConnectionBuilder<'a> {
     target: Target,
     max_queued: Option<usize>,
-    guid: Option<&'a Guid>,
+    max_outgoing_queued: Option<usize>,
+    outgoing_queue_policy: OutgoingQueuePolicy,
+    max_message_size: Option<usize>,
+    #[cfg(unix)]
+    max_unix_fds: Option<usize>,
+    guid: Option<Guid>,
     p2p: bool,
     internal_executor: bool,
     #[derivative(Debug = "ignore")]
     interfaces: Interfaces<'a>,
-    names: HashSet<WellKnownName<'a>>,
+    names: HashMap<WellKnownName<'a>, BitFlags<RequestNameFlags>>,
+    served_names: HashSet<WellKnownName<'a>>,
     auth_mechanisms: Option<VecDeque<AuthMechanism>>,
     unique_name: Option<UniqueName<'a>>,
     cookie_context: Option<handshake::CookieContext<'a>>,
     cookie_id: Option<usize>,
+    external_id: Option<String>,
+    handshake_timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    method_timeout: Option<Duration>,
+    keepalive: Option<Duration>,
+    interceptors: Vec<Arc<dyn MessageInterceptor>>,
+    access_policy: Option<Arc<dyn AccessPolicy>>,
+    dispatch_observer: Option<Arc<dyn DispatchObserver>>,
+    auto_reconnect: bool,
 }
 
 assert_impl_all!(ConnectionBuilder<'_>: Send, Sync, Unpin);
@@ -160,6 +208,15 @@ impl<'a> ConnectionBuilder<'a> {
         Self::new(Target::Socket(Box::new(socket)))
     }
 
+    /// Create a builder for connection that will use the given, already authenticated, socket.
+    ///
+    /// This is useful if you performed the handshake yourself, using [`crate::ClientHandshake`] or
+    /// [`crate::ServerHandshake`]. The handshake-related settings of the builder (e.g
+    /// [`ConnectionBuilder::auth_mechanisms`]) are ignored in this case.
+    pub fn authenticated_socket<S: Socket + 'static>(socket: S, outcome: HandshakeOutcome) -> Self {
+        Self::new(Target::Authenticated(Box::new(socket), outcome))
+    }
+
     /// Specify the mechanisms to use during authentication.
     pub fn auth_mechanisms(mut self, auth_mechanisms: &[AuthMechanism]) -> Self {
         self.auth_mechanisms = Some(VecDeque::from(auth_mechanisms.to_vec()));
@@ -167,6 +224,23 @@ impl<'a> ConnectionBuilder<'a> {
         self
     }
 
+    /// The identity to authenticate as, with the `EXTERNAL` mechanism.
+    ///
+    /// By default, the effective user ID of the process is used on Unix and the SID of the process
+    /// owner on Windows. Overriding it is needed when the bus sees the client under a different
+    /// identity, e.g when connecting across UID-mapping user namespaces, or when a privileged
+    /// process connects on behalf of another user.
+    ///
+    /// This is only used for client connections.
+    pub fn external_id<I>(mut self, id: I) -> Self
+    where
+        I: Into<String>,
+    {
+        self.external_id = Some(id.into());
+
+        self
+    }
+
     /// The cookie context to use during authentication.
     ///
     /// This is only used when the `cookie` authentication mechanism is enabled and only valid for
@@ -199,6 +273,11 @@ impl<'a> ConnectionBuilder<'a> {
     }
 
     /// The to-be-created connection will be a peer-to-peer connection.
+    ///
+    /// No bus is involved, so the `Hello` call is skipped and the connection won't have a unique
+    /// name. Messages can be sent without a destination and both peers can serve objects (see
+    /// [`ConnectionBuilder::serve_at`]). One of the peers must be the server side of the handshake
+    /// (see [`ConnectionBuilder::server`]).
     pub fn p2p(mut self) -> Self {
         self.p2p = true;
 
@@ -209,8 +288,8 @@ impl<'a> ConnectionBuilder<'a> {
     ///
     /// The to-be-created connection will wait for incoming client authentication handshake and
     /// negotiation messages, for peer-to-peer communications after successful creation.
-    pub fn server(mut self, guid: &'a Guid) -> Self {
-        self.guid = Some(guid);
+    pub fn server(mut self, guid: &Guid) -> Self {
+        self.guid = Some(guid.clone());
 
         self
     }
@@ -246,6 +325,189 @@ impl<'a> ConnectionBuilder<'a> {
         self
     }
 
+    /// Set the capacity of the outgoing message queue.
+    ///
+    /// Messages are queued while the socket isn't ready to be written to. Once `max` messages
+    /// are waiting to be written out, what happens to further messages depends on the
+    /// [`ConnectionBuilder::outgoing_queue_policy`]. By default, sending waits until there is room
+    /// in the queue again.
+    ///
+    /// By default, the outgoing queue is unbounded. A `max` of `0` is treated as `1`.
+    pub fn max_outgoing_queued(mut self, max: usize) -> Self {
+        self.max_outgoing_queued = Some(max);
+
+        self
+    }
+
+    /// Set what happens when sending a message while the outgoing queue is full.
+    ///
+    /// This only matters if the capacity of the queue is limited through
+    /// [`ConnectionBuilder::max_outgoing_queued`]. By default, sending waits for room in the queue
+    /// ([`OutgoingQueuePolicy::Block`]), which slows down producers to the pace of the peer.
+    /// Producers that must never wait can instead choose to fail
+    /// ([`OutgoingQueuePolicy::Error`]) or to sacrifice the oldest queued signals
+    /// ([`OutgoingQueuePolicy::DropOldest`]), e.g for periodic status signals.
+    pub fn outgoing_queue_policy(mut self, policy: OutgoingQueuePolicy) -> Self {
+        self.outgoing_queue_policy = policy;
+
+        self
+    }
+
+    /// Set the maximum size (in bytes) of incoming messages.
+    ///
+    /// Receiving a larger message results in [`Error::MessageTooLarge`] and the connection is
+    /// no longer read from. Values above the 128 MiB maximum of the D-Bus specification, which is
+    /// also the default, are capped to it.
+    pub fn max_message_size(mut self, max: usize) -> Self {
+        self.max_message_size = Some(max);
+
+        self
+    }
+
+    /// Set the maximum number of file descriptors an incoming message can carry.
+    ///
+    /// Receiving a message with more file descriptors results in [`Error::TooManyFds`] and the
+    /// connection is no longer read from. The file descriptors received are closed.
+    ///
+    /// By default, there is no limit.
+    #[cfg(unix)]
+    pub fn max_unix_fds(mut self, max: usize) -> Self {
+        self.max_unix_fds = Some(max);
+
+        self
+    }
+
+    /// Set a timeout for the authentication handshake.
+    ///
+    /// If the peer doesn't complete the handshake within `timeout`, [`ConnectionBuilder::build`]
+    /// fails with [`Error::Timeout`]. This is useful to guard against servers that accept the
+    /// connection but never reply to the `AUTH` command.
+    ///
+    /// By default, there is no timeout.
+    pub fn handshake_timeout(mut self, timeout: Duration) -> Self {
+        self.handshake_timeout = Some(timeout);
+
+        self
+    }
+
+    /// Set a timeout for the whole connection setup.
+    ///
+    /// This covers everything [`ConnectionBuilder::build`] does: connecting to the address, the
+    /// authentication handshake, the bus `Hello` call and the requesting of well-known names. If
+    /// all of that doesn't complete within `timeout`, [`ConnectionBuilder::build`] fails with
+    /// [`Error::Timeout`].
+    ///
+    /// By default, there is no timeout.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+
+        self
+    }
+
+    /// Regularly check if the peer is still responsive.
+    ///
+    /// Every `interval`, the peer (or the bus) is pinged through the standard
+    /// `org.freedesktop.DBus.Peer` interface. If it doesn't respond within `interval`, the
+    /// connection is considered dead and closed, which is signaled through
+    /// [`ConnectionEvent::Disconnected`] and [`Connection::closed`] (unless
+    /// [`ConnectionBuilder::auto_reconnect`] is enabled, in which case the connection is
+    /// established again). This allows detecting half-open connections, e.g over TCP, that
+    /// would otherwise go unnoticed until the next time a message is sent.
+    ///
+    /// On peer-to-peer connections, the peer must reply to the pings. zbus peers do so as long as
+    /// their [`crate::ObjectServer`] is in use.
+    ///
+    /// By default, keepalive is disabled.
+    ///
+    /// [`ConnectionEvent::Disconnected`]: crate::ConnectionEvent::Disconnected
+    pub fn keepalive(mut self, interval: Duration) -> Self {
+        self.keepalive = Some(interval);
+
+        self
+    }
+
+    /// Register a hook to observe or rewrite the messages going through the connection.
+    ///
+    /// This can be called multiple times. The interceptors run in the order they were registered,
+    /// for all the messages sent and received, from the very first one. See [`MessageInterceptor`]
+    /// for details.
+    pub fn interceptor<I>(mut self, interceptor: I) -> Self
+    where
+        I: MessageInterceptor,
+    {
+        self.interceptors.push(Arc::new(interceptor));
+
+        self
+    }
+
+    /// Set the policy deciding whether the method calls are dispatched to the object server.
+    ///
+    /// The policy is checked for each method call received, before it's dispatched to the served
+    /// interfaces. See [`AccessPolicy`] for details.
+    pub fn access_policy<P>(mut self, policy: P) -> Self
+    where
+        P: AccessPolicy,
+    {
+        self.access_policy = Some(Arc::new(policy));
+
+        self
+    }
+
+    /// Set the hook notified of each method call handled by the object server.
+    ///
+    /// See [`DispatchObserver`] for details.
+    pub fn dispatch_observer<O>(mut self, observer: O) -> Self
+    where
+        O: DispatchObserver,
+    {
+        self.dispatch_observer = Some(Arc::new(observer));
+
+        self
+    }
+
+    /// Set the default timeout for method calls on the connection.
+    ///
+    /// If no reply arrives within `timeout`, method calls (e.g. [`Connection::call_method`] and
+    /// calls through proxies) fail with [`Error::Timeout`] instead of waiting forever for services
+    /// that never answer. Use [`Connection::call_method_with_timeout`] to override the timeout for
+    /// individual calls.
+    ///
+    /// By default, there is no timeout.
+    pub fn method_timeout(mut self, timeout: Duration) -> Self {
+        self.method_timeout = Some(timeout);
+
+        self
+    }
+
+    /// Enable or disable automatic reconnection.
+    ///
+    /// When enabled and the connection is lost (e.g because the bus was restarted), the connection
+    /// is established again to the same address, including the authentication handshake. For bus
+    /// connections, the `Hello` call is made again, the well-known names owned or queued for are
+    /// requested again and the match rules are installed again. Use
+    /// [`Connection::receive_events`] to be notified of the disconnection and reconnection.
+    ///
+    /// Messages that were queued for sending when the connection was lost are not sent again.
+    /// Method calls among them fail with [`crate::fdo::Error::Disconnected`] instead, while the
+    /// ones that were already sent and waiting for a reply fail with the error that broke the
+    /// connection. Since the bus assigns a new unique name on every connection,
+    /// [`Connection::current_unique_name`] returns the new one once the `Hello` call is made
+    /// again, while [`Connection::unique_name`] keeps returning the first one. It's also carried
+    /// by [`crate::ConnectionEvent::Reconnected`].
+    ///
+    /// Each attempt to connect and authenticate again is bound by the
+    /// [`ConnectionBuilder::handshake_timeout`], or 25 seconds if none is set.
+    ///
+    /// Only connections created from an address (e.g [`ConnectionBuilder::session`]) can be
+    /// reconnected. [`ConnectionBuilder::build`] returns [`Error::Unsupported`] for others.
+    ///
+    /// Disabled by default.
+    pub fn auto_reconnect(mut self, enabled: bool) -> Self {
+        self.auto_reconnect = enabled;
+
+        self
+    }
+
     /// Enable or disable the internal executor thread.
     ///
     /// The thread is enabled by default.
@@ -282,13 +544,75 @@ impl<'a> ConnectionBuilder<'a> {
     /// of the connection setup ([`ConnectionBuilder::build`]), immediately after interfaces
     /// registered (through [`ConnectionBuilder::serve_at`]) are advertised. Typically this is
     /// exactly what you want.
-    pub fn name<W>(mut self, well_known_name: W) -> Result<Self>
+    pub fn name<W>(self, well_known_name: W) -> Result<Self>
+    where
+        W: TryInto<WellKnownName<'a>>,
+        W::Error: Into<Error>,
+    {
+        self.name_with_flags(
+            well_known_name,
+            RequestNameFlags::ReplaceExisting | RequestNameFlags::DoNotQueue,
+        )
+    }
+
+    /// Register a well-known name for this connection on the bus, with the given flags.
+    ///
+    /// This is similar to [`zbus::Connection::request_name_with_flags`], except the name is
+    /// requested as part of the connection setup, just like with [`ConnectionBuilder::name`].
+    /// Unlike [`ConnectionBuilder::name`], no error is returned if the bus puts the connection in
+    /// the queue for the name.
+    ///
+    /// # Example
+    ///
+    /// Allow other instances of the service to take over the name:
+    ///
+    /// ```no_run
+    /// # use zbus::{ConnectionBuilder, fdo::RequestNameFlags};
+    /// # use zbus::block_on;
+    /// #
+    /// # block_on(async {
+    /// let conn = ConnectionBuilder::session()?
+    ///     .name_with_flags(
+    ///         "org.zbus.MyService",
+    ///         RequestNameFlags::AllowReplacement | RequestNameFlags::ReplaceExisting,
+    ///     )?
+    ///     .build()
+    ///     .await?;
+    ///
+    /// // Do something useful with `conn`..
+    /// #     drop(conn);
+    /// #     Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    pub fn name_with_flags<W>(
+        mut self,
+        well_known_name: W,
+        flags: BitFlags<RequestNameFlags>,
+    ) -> Result<Self>
     where
         W: TryInto<WellKnownName<'a>>,
         W::Error: Into<Error>,
     {
         let well_known_name = well_known_name.try_into().map_err(Into::into)?;
-        self.names.insert(well_known_name);
+        self.names.insert(well_known_name, flags);
+
+        Ok(self)
+    }
+
+    /// Register a well-known name on the bus, bound to the object server.
+    ///
+    /// This is similar to [`ConnectionBuilder::name`], except the name is requested through
+    /// [`zbus::ObjectServer::request_name`] and hence released once the last interface is removed
+    /// from the object server. If the name is also given to [`ConnectionBuilder::name`] or
+    /// [`ConnectionBuilder::name_with_flags`], it's only requested once, with the flags given
+    /// there.
+    pub fn serve_name<W>(mut self, well_known_name: W) -> Result<Self>
+    where
+        W: TryInto<WellKnownName<'a>>,
+        W::Error: Into<Error>,
+    {
+        let well_known_name = well_known_name.try_into().map_err(Into::into)?;
+        self.served_names.insert(well_known_name);
 
         Ok(self)
     }
@@ -321,12 +645,19 @@ impl<'a> ConnectionBuilder<'a> {
     ///
     /// Until server-side bus connection is supported, attempting to build such a connection will
     /// result in [`Error::Unsupported`] error.
+    ///
+    /// If a handshake or connect timeout was set and it elapses, [`Error::Timeout`] is returned.
     pub async fn build(self) -> Result<Connection> {
         let executor = Executor::new();
         #[cfg(not(feature = "tokio"))]
         let internal_executor = self.internal_executor;
+        let connect_timeout = self.connect_timeout;
         // Box the future as it's large and can cause stack overflow.
-        let conn = Box::pin(executor.run(self.build_(executor.clone()))).await?;
+        let build = Box::pin(executor.run(self.build_(executor.clone())));
+        let conn = match connect_timeout {
+            Some(connect_timeout) => timeout(build, connect_timeout).await?,
+            None => build.await?,
+        };
 
         #[cfg(not(feature = "tokio"))]
         start_internal_executor(&executor, internal_executor)?;
@@ -335,6 +666,12 @@ impl<'a> ConnectionBuilder<'a> {
     }
 
     async fn build_(self, executor: Executor<'static>) -> Result<Connection> {
+        let reconnect_address = match (&self.target, self.auto_reconnect) {
+            (_, false) => None,
+            (Target::Address(address), true) if self.guid.is_none() => Some(address.clone()),
+            (_, true) => return Err(Error::Unsupported),
+        };
+        let mut outcome = None;
         let stream = match self.target {
             #[cfg(not(feature = "tokio"))]
             Target::UnixStream(stream) => Box::new(Async::new(stream)?) as Box<dyn Socket>,
@@ -350,51 +687,98 @@ impl<'a> ConnectionBuilder<'a> {
             Target::VsockStream(stream) => Box::new(Async::new(stream)?) as Box<dyn Socket>,
             #[cfg(feature = "tokio-vsock")]
             Target::VsockStream(stream) => Box::new(stream) as Box<dyn Socket>,
-            Target::Address(address) => match address.connect().await? {
-                #[cfg(any(unix, not(feature = "tokio")))]
-                address::Stream::Unix(stream) => Box::new(stream) as Box<dyn Socket>,
-                address::Stream::Tcp(stream) => Box::new(stream) as Box<dyn Socket>,
-                #[cfg(any(
-                    all(feature = "vsock", not(feature = "tokio")),
-                    feature = "tokio-vsock"
-                ))]
-                address::Stream::Vsock(stream) => Box::new(stream) as Box<dyn Socket>,
-            },
+            Target::Address(address) => address.connect().await?.into(),
             Target::Socket(stream) => stream,
+            Target::Authenticated(stream, o) => {
+                outcome = Some(o);
+
+                stream
+            }
         };
-        let auth = match self.guid {
-            None => {
-                // SASL Handshake
-                Authenticated::client(stream, self.auth_mechanisms).await?
+        let guid = self.guid;
+        let p2p = self.p2p;
+        let auth_mechanisms = self.auth_mechanisms;
+        let cookie_id = self.cookie_id;
+        let cookie_context = self.cookie_context;
+        let external_id = self.external_id;
+        let reconnect = reconnect_address
+            .map(|address| (address, auth_mechanisms.clone(), external_id.clone()));
+        let span = tracing::debug_span!("handshake", p2p, server = guid.is_some());
+        let handshake = async move {
+            if let Some(outcome) = outcome {
+                return Ok(Authenticated::new(stream, outcome));
             }
-            Some(guid) => {
-                if !self.p2p {
-                    return Err(Error::Unsupported);
-                }
-
-                #[cfg(unix)]
-                let client_uid = stream.uid()?;
 
-                #[cfg(windows)]
-                let client_sid = stream.peer_sid();
+            match guid {
+                None => {
+                    // SASL Handshake
+                    Authenticated::client(stream, auth_mechanisms, external_id).await
+                }
+                Some(guid) => {
+                    if !p2p {
+                        return Err(Error::Unsupported);
+                    }
 
-                Authenticated::server(
-                    stream,
-                    guid.clone(),
                     #[cfg(unix)]
-                    client_uid,
+                    let client_uid = stream.uid()?;
+
                     #[cfg(windows)]
-                    client_sid,
-                    self.auth_mechanisms,
-                    self.cookie_id,
-                    self.cookie_context.unwrap_or_default(),
-                )
-                .await?
+                    let client_sid = stream.peer_sid();
+
+                    Authenticated::server(
+                        stream,
+                        guid,
+                        #[cfg(unix)]
+                        client_uid,
+                        #[cfg(windows)]
+                        client_sid,
+                        auth_mechanisms,
+                        cookie_id,
+                        cookie_context.unwrap_or_default(),
+                    )
+                    .await
+                }
             }
         };
+        let handshake = handshake.instrument(span);
+        let mut auth = match self.handshake_timeout {
+            Some(handshake_timeout) => timeout(handshake, handshake_timeout).await?,
+            None => handshake.await?,
+        };
+        if let Some(max) = self.max_message_size {
+            auth.conn.set_max_message_size(max);
+        }
+        #[cfg(unix)]
+        if let Some(max) = self.max_unix_fds {
+            auth.conn.set_max_fds(max);
+        }
+        if let Some(max) = self.max_outgoing_queued {
+            auth.conn.set_max_out_queued(max);
+        }
+        auth.conn.set_out_queue_policy(self.outgoing_queue_policy);
 
         let mut conn = Connection::new(auth, !self.p2p, executor).await?;
         conn.set_max_queued(self.max_queued.unwrap_or(DEFAULT_MAX_QUEUED));
+        if let Some(timeout) = self.method_timeout {
+            conn.set_method_timeout(timeout);
+        }
+        if !self.interceptors.is_empty() {
+            conn.set_interceptors(self.interceptors);
+        }
+        if let Some(policy) = self.access_policy {
+            conn.set_access_policy(policy);
+        }
+        if let Some(observer) = self.dispatch_observer {
+            conn.set_dispatch_observer(observer);
+        }
+        if let Some((address, auth_mechanisms, external_id)) = reconnect {
+            conn.enable_auto_reconnect(
+                address,
+                auth_mechanisms,
+                external_id,
+                self.handshake_timeout,
+            );
+        }
         if let Some(unique_name) = self.unique_name {
             conn.set_unique_name(unique_name)?;
         }
@@ -425,8 +809,22 @@ impl<'a> ConnectionBuilder<'a> {
             conn.hello_bus().await?;
         }
 
-        for name in self.names {
-            conn.request_name(name).await?;
+        let mut served_names = self.served_names;
+        for (name, flags) in self.names {
+            if served_names.remove(&name) {
+                conn.object_server()
+                    .request_name_with_flags(name, flags)
+                    .await?;
+            } else {
+                conn.request_name_with_flags(name, flags).await?;
+            }
+        }
+        for name in served_names {
+            conn.object_server().request_name(name).await?;
+        }
+
+        if let Some(interval) = self.keepalive {
+            conn.start_keepalive(interval);
         }
 
         Ok(conn)
@@ -437,14 +835,29 @@ impl<'a> ConnectionBuilder<'a> {
             target,
             p2p: false,
             max_queued: None,
+            max_outgoing_queued: None,
+            outgoing_queue_policy: OutgoingQueuePolicy::default(),
+            max_message_size: None,
+            #[cfg(unix)]
+            max_unix_fds: None,
             guid: None,
             internal_executor: true,
             interfaces: HashMap::new(),
-            names: HashSet::new(),
+            names: HashMap::new(),
+            served_names: HashSet::new(),
             auth_mechanisms: None,
             unique_name: None,
             cookie_id: None,
             cookie_context: None,
+            external_id: None,
+            handshake_timeout: None,
+            auto_reconnect: false,
+            connect_timeout: None,
+            method_timeout: None,
+            keepalive: None,
+            interceptors: vec![],
+            access_policy: None,
+            dispatch_observer: None,
         }
     }
 }
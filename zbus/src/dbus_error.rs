@@ -1,4 +1,4 @@
-use crate::{names::ErrorName, Message, MessageHeader, Result};
+use crate::{names::ErrorName, Message, MessageBuilder, MessageHeader, Result};
 
 /// A trait that needs to be implemented by error types to be returned from D-Bus methods.
 ///
@@ -7,16 +7,54 @@ use crate::{names::ErrorName, Message, MessageHeader, Result};
 /// implement this trait for your error type. The easiest way to achieve that is to make use of the
 /// [`DBusError` macro][dm].
 ///
+/// A method of an interface (see [`dbus_interface`]) returning a `Result<T, E>` where `E`
+/// implements this trait, replies its errors with their name and body, as given by
+/// [`DBusError::create_reply`].
+///
+/// # Example
+///
+/// Implementing the trait by hand, with the description as the body of the error replies:
+///
+/// ```
+/// use zbus::{names::ErrorName, DBusError};
+///
+/// #[derive(Debug)]
+/// struct OutOfStock(String);
+///
+/// impl DBusError for OutOfStock {
+///     fn name(&self) -> ErrorName<'_> {
+///         ErrorName::from_static_str_unchecked("org.example.Shop.Error.OutOfStock")
+///     }
+///
+///     fn description(&self) -> Option<&str> {
+///         Some(&self.0)
+///     }
+/// }
+///
+/// let err = OutOfStock("bread".to_string());
+/// assert_eq!(err.name(), "org.example.Shop.Error.OutOfStock");
+/// ```
+///
 /// [dm]: derive.DBusError.html
+/// [`dbus_interface`]: crate::dbus_interface
 pub trait DBusError {
     /// Generate an error reply message for the given method call.
-    fn create_reply(&self, msg: &MessageHeader<'_>) -> Result<Message>;
+    ///
+    /// By default, the reply has the [name](DBusError::name) of the error and the
+    /// [description](DBusError::description) of it (if any) as its body.
+    fn create_reply(&self, msg: &MessageHeader<'_>) -> Result<Message> {
+        let builder = MessageBuilder::error(msg, self.name())?;
+        match self.description() {
+            Some(description) => builder.build(&(description,)),
+            None => builder.build(&()),
+        }
+    }
 
-    // The name of the error.
-    //
-    // Every D-Bus error must have a name. See [`ErrorName`] for more information.
+    /// The name of the error.
+    ///
+    /// Every D-Bus error must have a name. See [`ErrorName`] for more information.
     fn name(&self) -> ErrorName<'_>;
 
-    // The optional description for the error.
+    /// The optional description for the error.
     fn description(&self) -> Option<&str>;
 }
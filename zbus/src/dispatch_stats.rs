@@ -0,0 +1,345 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt::Debug,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use zbus_names::{ErrorName, InterfaceName, MemberName};
+
+use crate::Message;
+
+/// Statistics about the method calls dispatched by an [`ObjectServer`].
+///
+/// This is a snapshot, as returned by [`ObjectServer::dispatch_stats`]. The calls are accounted for
+/// once they have been handled, i.e once the handler returned, except for [`in_flight`].
+///
+/// The per-member statistics only cover the calls that were dispatched to an interface, so that
+/// calls to members that don't exist can't grow them.
+///
+/// [`ObjectServer`]: crate::ObjectServer
+/// [`ObjectServer::dispatch_stats`]: crate::ObjectServer::dispatch_stats
+/// [`in_flight`]: DispatchStats::in_flight
+#[derive(Clone, Debug, Default)]
+pub struct DispatchStats {
+    calls: u64,
+    errors: u64,
+    in_flight: usize,
+    members: HashMap<(InterfaceName<'static>, MemberName<'static>), MemberStats>,
+}
+
+impl DispatchStats {
+    /// The number of method calls handled.
+    pub fn calls(&self) -> u64 {
+        self.calls
+    }
+
+    /// The number of method calls that failed, i.e that got an error as reply, or would have if a
+    /// reply was expected.
+    pub fn errors(&self) -> u64 {
+        self.errors
+    }
+
+    /// The number of method calls being dispatched.
+    ///
+    /// The method calls are dispatched concurrently, as soon as they're received, so this is the
+    /// number of calls whose handler is running (or waiting for the interface to be available).
+    pub fn in_flight(&self) -> usize {
+        self.in_flight
+    }
+
+    /// The statistics of the calls to the member `member` of the interface `interface`.
+    pub fn member(
+        &self,
+        interface: &InterfaceName<'_>,
+        member: &MemberName<'_>,
+    ) -> Option<&MemberStats> {
+        self.members.get(&(interface.to_owned(), member.to_owned()))
+    }
+
+    /// The statistics of all the members called, by interface and member.
+    pub fn members(
+        &self,
+    ) -> impl Iterator<Item = (&InterfaceName<'static>, &MemberName<'static>, &MemberStats)> {
+        self.members.iter().map(|((i, m), s)| (i, m, s))
+    }
+}
+
+/// Statistics about the calls to a member of an interface.
+///
+/// See [`DispatchStats::member`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemberStats {
+    calls: u64,
+    errors: u64,
+    total_time: Duration,
+    max_time: Duration,
+}
+
+impl MemberStats {
+    /// The number of calls handled.
+    pub fn calls(&self) -> u64 {
+        self.calls
+    }
+
+    /// The number of calls that failed.
+    pub fn errors(&self) -> u64 {
+        self.errors
+    }
+
+    /// The time spent handling the calls, in total.
+    pub fn total_time(&self) -> Duration {
+        self.total_time
+    }
+
+    /// The longest time spent handling a call.
+    pub fn max_time(&self) -> Duration {
+        self.max_time
+    }
+}
+
+/// A hook notified of each method call handled by the [`ObjectServer`].
+///
+/// The observer is registered with [`ConnectionBuilder::dispatch_observer`]. It's meant for
+/// monitoring the health of services, e.g to feed metrics to a monitoring system or log the slow
+/// calls. Since it runs for every method call, it should be quick and must not block. See also
+/// [`ObjectServer::dispatch_stats`] for the statistics the object server keeps itself.
+///
+/// # Example
+///
+/// Log the calls taking longer than a second:
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use zbus::{block_on, ConnectionBuilder, DispatchObserver, DispatchOutcome, Message};
+/// #
+/// #[derive(Debug)]
+/// struct SlowCalls;
+///
+/// impl DispatchObserver for SlowCalls {
+///     fn dispatched(&self, msg: &Message, outcome: &DispatchOutcome<'_>) {
+///         if outcome.elapsed() > Duration::from_secs(1) {
+///             eprintln!("`{:?}` took {:?}", msg.member(), outcome.elapsed());
+///         }
+///     }
+/// }
+///
+/// # block_on(async {
+/// let conn = ConnectionBuilder::session()?
+///     .dispatch_observer(SlowCalls)
+///     .build()
+///     .await?;
+///
+/// // Serve something useful through `conn`..
+/// # drop(conn);
+/// # Ok::<_, zbus::Error>(())
+/// # }).unwrap();
+/// ```
+///
+/// [`ObjectServer`]: crate::ObjectServer
+/// [`ObjectServer::dispatch_stats`]: crate::ObjectServer::dispatch_stats
+/// [`ConnectionBuilder::dispatch_observer`]: crate::ConnectionBuilder::dispatch_observer
+pub trait DispatchObserver: Debug + Send + Sync + 'static {
+    /// Called once the method call `msg` has been handled.
+    fn dispatched(&self, msg: &Message, outcome: &DispatchOutcome<'_>);
+}
+
+/// How a method call was handled by the [`ObjectServer`].
+///
+/// See [`DispatchObserver::dispatched`].
+///
+/// [`ObjectServer`]: crate::ObjectServer
+#[derive(Debug)]
+pub struct DispatchOutcome<'o> {
+    interface: Option<&'o InterfaceName<'o>>,
+    error: Option<&'o ErrorName<'o>>,
+    elapsed: Duration,
+}
+
+impl<'o> DispatchOutcome<'o> {
+    pub(crate) fn new(
+        interface: Option<&'o InterfaceName<'o>>,
+        error: Option<&'o ErrorName<'o>>,
+        elapsed: Duration,
+    ) -> Self {
+        Self {
+            interface,
+            error,
+            elapsed,
+        }
+    }
+
+    /// The interface the call was dispatched to, if any.
+    pub fn interface(&self) -> Option<&InterfaceName<'o>> {
+        self.interface
+    }
+
+    /// The name of the error the call failed with, if any.
+    pub fn error(&self) -> Option<&ErrorName<'o>> {
+        self.error
+    }
+
+    /// The time spent handling the call.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+// Identifies a method call: its sender and serial number.
+type CallId = (Option<String>, u32);
+
+thread_local! {
+    // The method call being dispatched by the future being polled on this thread, if any.
+    static DISPATCHED: RefCell<Option<Arc<DispatchedCall>>> = const { RefCell::new(None) };
+}
+
+// Keeps the dispatch statistics of an `ObjectServer`.
+#[derive(Debug, Default)]
+pub(crate) struct DispatchTracker {
+    stats: Mutex<DispatchStats>,
+}
+
+// A method call being dispatched, and the error replied to it, if any.
+#[derive(Debug)]
+struct DispatchedCall {
+    id: Option<CallId>,
+    replied: Mutex<Option<ErrorName<'static>>>,
+}
+
+// A method call being dispatched.
+#[derive(Debug)]
+pub(crate) struct TrackedCall {
+    call: Arc<DispatchedCall>,
+    start: Instant,
+    // The error the dispatch failed with, if any.
+    error: Option<ErrorName<'static>>,
+}
+
+impl TrackedCall {
+    // Record the error the dispatch failed with, whether it's replied or not.
+    pub(crate) fn failed(&mut self, name: ErrorName<'_>) {
+        self.error = Some(name.to_owned());
+    }
+
+    // Dispatch the call through `future`.
+    //
+    // The errors replied to the call while `future` is polled are recorded, so that only the
+    // replies of the dispatch itself are accounted for, not the ones sent from elsewhere.
+    pub(crate) fn dispatch<'f, F>(&self, future: F) -> Dispatch<'f, F::Output>
+    where
+        F: Future + Send + 'f,
+    {
+        Dispatch {
+            future: Box::pin(future),
+            call: self.call.clone(),
+        }
+    }
+}
+
+// The future dispatching a tracked call, see `TrackedCall::dispatch`.
+pub(crate) struct Dispatch<'f, T> {
+    future: Pin<Box<dyn Future<Output = T> + Send + 'f>>,
+    call: Arc<DispatchedCall>,
+}
+
+impl<T> Future for Dispatch<'_, T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        struct Restore(Option<Arc<DispatchedCall>>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                DISPATCHED.with(|d| *d.borrow_mut() = self.0.take());
+            }
+        }
+
+        let this = self.get_mut();
+        let _restore = Restore(DISPATCHED.with(|d| d.replace(Some(this.call.clone()))));
+
+        this.future.as_mut().poll(cx)
+    }
+}
+
+// Record the error replied to the call being dispatched, if `reply` is one.
+//
+// This is a no-op unless called from the dispatch of the call, see `TrackedCall::dispatch`.
+pub(crate) fn error_replied(reply: &Message) {
+    let call = match DISPATCHED.with(|d| d.borrow().clone()) {
+        Some(call) => call,
+        None => return,
+    };
+    let (hdr, serial) = match (reply.header(), reply.reply_serial()) {
+        (Ok(hdr), Some(serial)) => (hdr, serial),
+        _ => return,
+    };
+    let (destination, name) = match (hdr.destination(), hdr.error_name()) {
+        (Ok(destination), Ok(Some(name))) => (destination, name),
+        _ => return,
+    };
+    if call.id == Some((destination.map(|d| d.to_string()), serial)) {
+        *call.replied.lock().expect("poisoned lock") = Some(name.to_owned());
+    }
+}
+
+impl DispatchTracker {
+    pub(crate) fn stats(&self) -> DispatchStats {
+        self.stats.lock().expect("poisoned lock").clone()
+    }
+
+    pub(crate) fn begin(&self, msg: &Message) -> TrackedCall {
+        self.stats.lock().expect("poisoned lock").in_flight += 1;
+
+        TrackedCall {
+            call: Arc::new(DispatchedCall {
+                id: call_id(msg),
+                replied: Mutex::new(None),
+            }),
+            start: Instant::now(),
+            error: None,
+        }
+    }
+
+    // Account for the call once handled, returning the outcome of it.
+    pub(crate) fn end(
+        &self,
+        call: TrackedCall,
+        interface: Option<&InterfaceName<'_>>,
+        member: Option<&MemberName<'_>>,
+    ) -> (Option<ErrorName<'static>>, Duration) {
+        let elapsed = call.start.elapsed();
+        let replied = call.call.replied.lock().expect("poisoned lock").take();
+        let error = call.error.or(replied);
+        let mut stats = self.stats.lock().expect("poisoned lock");
+        stats.in_flight -= 1;
+        stats.calls += 1;
+        if error.is_some() {
+            stats.errors += 1;
+        }
+        if let (Some(interface), Some(member)) = (interface, member) {
+            let member = stats
+                .members
+                .entry((interface.to_owned(), member.to_owned()))
+                .or_default();
+            member.calls += 1;
+            if error.is_some() {
+                member.errors += 1;
+            }
+            member.total_time += elapsed;
+            member.max_time = member.max_time.max(elapsed);
+        }
+
+        (error, elapsed)
+    }
+}
+
+fn call_id(msg: &Message) -> Option<CallId> {
+    let serial = *msg.primary_header().serial_num()?;
+    let sender = msg.header().ok()?.sender().ok()?.map(|s| s.to_string());
+
+    Some((sender, serial))
+}
@@ -5,7 +5,7 @@ use std::{convert::Infallible, error, fmt, io, sync::Arc};
 use zbus_names::{Error as NamesError, OwnedErrorName};
 use zvariant::Error as VariantError;
 
-use crate::{fdo, Message, MessageType};
+use crate::{fdo, Message, MessageFieldCode, MessageType};
 
 /// The error type for `zbus`.
 ///
@@ -66,6 +66,25 @@ pub enum Error {
     Failure(String),
     /// A required parameter was missing.
     MissingParameter(&'static str),
+    /// The operation did not complete in the allotted time.
+    Timeout,
+    /// A received message (of the given size) exceeds the maximum message size.
+    ///
+    /// That is the one configured through [`crate::ConnectionBuilder::max_message_size`], or else
+    /// the 128 MiB allowed by the D-Bus specification.
+    MessageTooLarge(usize),
+    /// A received message carries more file descriptors (the given count) than the configured
+    /// maximum.
+    TooManyFds(usize),
+    /// The outgoing message queue is full.
+    OutgoingQueueFull,
+    /// A header field of a message (given by its code) has a type or value not valid for it.
+    InvalidHeaderField(MessageFieldCode),
+    /// A header field required by the type of a message (given by its code) is missing.
+    MissingHeaderField(MessageFieldCode),
+    /// The header fields of a received message (of the given length) exceed the maximum array
+    /// length allowed by the specification.
+    HeaderFieldsTooLarge(usize),
 }
 
 assert_impl_all!(Error: Send, Sync, Unpin);
@@ -98,6 +117,13 @@ impl PartialEq for Error {
             #[cfg(feature = "quick-xml")]
             (Self::QuickXml(_), Self::QuickXml(_)) => false,
             (Self::Failure(s1), Self::Failure(s2)) => s1 == s2,
+            (Self::Timeout, Self::Timeout) => true,
+            (Self::MessageTooLarge(s), Self::MessageTooLarge(o)) => s == o,
+            (Self::TooManyFds(s), Self::TooManyFds(o)) => s == o,
+            (Self::OutgoingQueueFull, Self::OutgoingQueueFull) => true,
+            (Self::InvalidHeaderField(s), Self::InvalidHeaderField(o)) => s == o,
+            (Self::MissingHeaderField(s), Self::MissingHeaderField(o)) => s == o,
+            (Self::HeaderFieldsTooLarge(s), Self::HeaderFieldsTooLarge(o)) => s == o,
             (_, _) => false,
         }
     }
@@ -110,7 +136,7 @@ impl error::Error for Error {
             Error::Address(_) => None,
             #[allow(deprecated)]
             Error::Io(e) => Some(e),
-            Error::InputOutput(e) => Some(e),
+            Error::InputOutput(e) => Some(&**e),
             Error::ExcessData => None,
             Error::Handshake(_) => None,
             Error::IncorrectEndian => None,
@@ -120,7 +146,7 @@ impl error::Error for Error {
             Error::MethodError(_, _, _) => None,
             Error::InvalidGUID => None,
             Error::Unsupported => None,
-            Error::FDO(e) => Some(e),
+            Error::FDO(e) => Some(&**e),
             #[cfg(feature = "xml")]
             Error::SerdeXml(e) => Some(e),
             #[cfg(feature = "quick-xml")]
@@ -132,6 +158,13 @@ impl error::Error for Error {
             Error::InvalidMatchRule => None,
             Error::Failure(_) => None,
             Error::MissingParameter(_) => None,
+            Error::Timeout => None,
+            Error::MessageTooLarge(_) => None,
+            Error::TooManyFds(_) => None,
+            Error::OutgoingQueueFull => None,
+            Error::InvalidHeaderField(_) => None,
+            Error::MissingHeaderField(_) => None,
+            Error::HeaderFieldsTooLarge(_) => None,
         }
     }
 }
@@ -172,6 +205,26 @@ impl fmt::Display for Error {
             Error::MissingParameter(p) => {
                 write!(f, "Parameter `{}` was not specified but it is required", p)
             }
+            Error::Timeout => write!(f, "operation timed out"),
+            Error::MessageTooLarge(size) => {
+                write!(
+                    f,
+                    "message of {size} bytes exceeds the maximum message size"
+                )
+            }
+            Error::TooManyFds(count) => write!(
+                f,
+                "message with {count} file descriptors exceeds the maximum allowed"
+            ),
+            Error::OutgoingQueueFull => write!(f, "outgoing message queue is full"),
+            Error::InvalidHeaderField(code) => write!(f, "invalid `{code:?}` message header field"),
+            Error::MissingHeaderField(code) => {
+                write!(f, "missing `{code:?}` message header field")
+            }
+            Error::HeaderFieldsTooLarge(len) => write!(
+                f,
+                "message header fields of {len} bytes exceed the maximum allowed by the specification"
+            ),
         }
     }
 }
@@ -208,6 +261,13 @@ impl Clone for Error {
             Error::InvalidMatchRule => Error::InvalidMatchRule,
             Error::Failure(e) => Error::Failure(e.clone()),
             Error::MissingParameter(p) => Error::MissingParameter(p),
+            Error::Timeout => Error::Timeout,
+            Error::MessageTooLarge(size) => Error::MessageTooLarge(*size),
+            Error::TooManyFds(count) => Error::TooManyFds(*count),
+            Error::OutgoingQueueFull => Error::OutgoingQueueFull,
+            Error::InvalidHeaderField(code) => Error::InvalidHeaderField(*code),
+            Error::MissingHeaderField(code) => Error::MissingHeaderField(*code),
+            Error::HeaderFieldsTooLarge(len) => Error::HeaderFieldsTooLarge(*len),
         }
     }
 }
@@ -292,10 +352,16 @@ impl From<Arc<Message>> for Error {
 
         if let Ok(Some(name)) = header.error_name() {
             let name = name.to_owned().into();
-            match message.body_unchecked::<&str>() {
-                Ok(detail) => Error::MethodError(name, Some(String::from(detail)), message),
-                Err(_) => Error::MethodError(name, None, message),
-            }
+            // By convention, the description is the first argument, if it's a string. The whole
+            // body is still available through the message, for errors with structured details.
+            let detail = match header.signature() {
+                Ok(Some(signature)) if signature.starts_with('s') => {
+                    message.body_unchecked::<&str>().ok().map(String::from)
+                }
+                _ => None,
+            };
+
+            Error::MethodError(name, detail, message)
         } else {
             Error::InvalidReply
         }
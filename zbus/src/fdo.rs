@@ -4,10 +4,14 @@
 //! be useful across various D-Bus applications. This module provides their proxy.
 
 use enumflags2::{bitflags, BitFlags};
+use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use static_assertions::assert_impl_all;
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    convert::{TryFrom, TryInto},
+};
 use zbus_names::{
     BusName, InterfaceName, OwnedBusName, OwnedInterfaceName, OwnedUniqueName, UniqueName,
     WellKnownName,
@@ -17,7 +21,8 @@ use zvariant::{
 };
 
 use crate::{
-    dbus_interface, dbus_proxy, DBusError, Guid, MessageHeader, ObjectServer, SignalContext,
+    dbus_interface, dbus_proxy, DBusError, Guid, MessageHeader, ObjectServer, Proxy, ProxyBuilder,
+    ProxyInterface, SignalContext,
 };
 
 #[rustfmt::skip]
@@ -55,11 +60,8 @@ impl Introspectable {
     ) -> Result<String> {
         let path = header.path()?.ok_or(crate::Error::MissingField)?;
         let root = server.root().read().await;
-        let node = root
-            .get_child(path)
-            .ok_or_else(|| Error::UnknownObject(format!("Unknown object '{path}'")))?;
 
-        Ok(node.introspect().await)
+        root.introspect(path).await
     }
 }
 
@@ -109,6 +111,58 @@ macro_rules! gen_properties_proxy {
 gen_properties_proxy!(true, false);
 assert_impl_all!(PropertiesProxy<'_>: Send, Sync, Unpin);
 
+impl<'a> PropertiesProxy<'a> {
+    /// Get the value of a property, converted to `T`.
+    ///
+    /// This is handy to access the properties of an interface you don't have a generated proxy
+    /// for:
+    ///
+    /// ```no_run
+    /// # zbus::block_on(async {
+    /// use zbus::{fdo::PropertiesProxy, names::InterfaceName, Connection};
+    ///
+    /// let conn = Connection::system().await?;
+    /// let properties = PropertiesProxy::builder(&conn)
+    ///     .destination("org.freedesktop.hostname1")?
+    ///     .path("/org/freedesktop/hostname1")?
+    ///     .build()
+    ///     .await?;
+    /// let interface = InterfaceName::from_static_str("org.freedesktop.hostname1")?;
+    /// let hostname: String = properties.get_as(interface, "Hostname").await?;
+    /// println!("{}", hostname);
+    /// # Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    pub async fn get_as<T>(
+        &self,
+        interface_name: InterfaceName<'_>,
+        property_name: &str,
+    ) -> zbus::Result<T>
+    where
+        T: TryFrom<OwnedValue>,
+        T::Error: Into<zbus::Error>,
+    {
+        let value = self.get(interface_name, property_name).await?;
+
+        T::try_from(value).map_err(Into::into)
+    }
+
+    /// Set the value of a property, from anything convertible to a [`enum@Value`].
+    pub async fn set_value<'v, V>(
+        &self,
+        interface_name: InterfaceName<'_>,
+        property_name: &str,
+        value: V,
+    ) -> zbus::Result<()>
+    where
+        V: Into<Value<'v>>,
+    {
+        self.set(interface_name, property_name, &value.into())
+            .await
+            .map_err(Into::into)
+    }
+}
+
 /// Server-side implementation for the `org.freedesktop.DBus.Properties` interface.
 /// This interface is implemented automatically for any object registered to the
 /// [ObjectServer](crate::ObjectServer).
@@ -127,17 +181,17 @@ impl Properties {
     ) -> Result<OwnedValue> {
         let path = header.path()?.ok_or(crate::Error::MissingField)?;
         let root = server.root().read().await;
-        let iface = root
-            .get_child(path)
-            .and_then(|node| node.interface_lock(interface_name.as_ref()))
-            .ok_or_else(|| {
-                Error::UnknownInterface(format!("Unknown interface '{interface_name}'"))
-            })?;
+        if let Some(forwarder) = root.lookup_forward(path, &interface_name) {
+            drop(root);
+
+            return forwarder.get(property_name).await;
+        }
+        let iface = root.lookup_interface(path, interface_name.as_ref())?;
 
         let res = iface.read().await.get(property_name).await;
         res.unwrap_or_else(|| {
             Err(Error::UnknownProperty(format!(
-                "Unknown property '{property_name}'"
+                "Unknown property '{property_name}' on interface '{interface_name}'"
             )))
         })
     }
@@ -153,22 +207,25 @@ impl Properties {
     ) -> Result<()> {
         let path = header.path()?.ok_or(crate::Error::MissingField)?;
         let root = server.root().read().await;
-        let iface = root
-            .get_child(path)
-            .and_then(|node| node.interface_lock(interface_name.as_ref()))
-            .ok_or_else(|| {
-                Error::UnknownInterface(format!("Unknown interface '{interface_name}'"))
-            })?;
+        if let Some(forwarder) = root.lookup_forward(path, &interface_name) {
+            drop(root);
+
+            return forwarder.set(property_name, &value).await;
+        }
+        let iface = root.lookup_interface(path, interface_name.as_ref())?;
 
         match iface.read().await.set(property_name, &value, &ctxt) {
             zbus::DispatchResult::RequiresMut => {}
             zbus::DispatchResult::NotFound => {
                 return Err(Error::UnknownProperty(format!(
-                    "Unknown property '{property_name}'"
+                    "Unknown property '{property_name}' on interface '{interface_name}'"
                 )));
             }
             zbus::DispatchResult::Async(f) => {
-                return f.await.map_err(Into::into);
+                return f.await.map_err(|e| match e {
+                    zbus::Error::FDO(e) => *e,
+                    e => e.into(),
+                });
             }
         }
         let res = iface
@@ -178,7 +235,7 @@ impl Properties {
             .await;
         res.unwrap_or_else(|| {
             Err(Error::UnknownProperty(format!(
-                "Unknown property '{property_name}'"
+                "Unknown property '{property_name}' on interface '{interface_name}'"
             )))
         })
     }
@@ -191,12 +248,12 @@ impl Properties {
     ) -> Result<HashMap<String, OwnedValue>> {
         let path = header.path()?.ok_or(crate::Error::MissingField)?;
         let root = server.root().read().await;
-        let iface = root
-            .get_child(path)
-            .and_then(|node| node.interface_lock(interface_name.as_ref()))
-            .ok_or_else(|| {
-                Error::UnknownInterface(format!("Unknown interface '{interface_name}'"))
-            })?;
+        if let Some(forwarder) = root.lookup_forward(path, &interface_name) {
+            drop(root);
+
+            return forwarder.get_all().await;
+        }
+        let iface = root.lookup_interface(path, interface_name.as_ref())?;
 
         let res = iface.read().await.get_all().await;
         Ok(res)
@@ -261,12 +318,126 @@ macro_rules! gen_object_manager_proxy {
                 interfaces: Vec<&str>,
             ) -> Result<()>;
         }
+
+        impl<'s> InterfacesAddedArgs<'s> {
+            /// The properties of the interfaces added, by interface name.
+            ///
+            /// This is the same as `interfaces_and_properties`, except for the typed interface
+            /// names. The interfaces with an invalid name are left out.
+            pub fn properties_by_interface(
+                &self,
+            ) -> HashMap<InterfaceName<'s>, &HashMap<&'s str, Value<'s>>> {
+                self.interfaces_and_properties
+                    .iter()
+                    .filter_map(|(name, props)| {
+                        InterfaceName::try_from(*name).ok().map(|name| (name, props))
+                    })
+                    .collect()
+            }
+        }
+
+        impl<'s> InterfacesRemovedArgs<'s> {
+            /// The names of the interfaces removed.
+            ///
+            /// This is the same as `interfaces`, except for the typed interface names. The
+            /// interfaces with an invalid name are left out.
+            pub fn interface_names(&self) -> Vec<InterfaceName<'s>> {
+                self.interfaces
+                    .iter()
+                    .filter_map(|name| InterfaceName::try_from(*name).ok())
+                    .collect()
+            }
+        }
     };
 }
 
 gen_object_manager_proxy!(true, false);
 assert_impl_all!(ObjectManagerProxy<'_>: Send, Sync, Unpin);
 
+impl<'a> ObjectManagerProxy<'a> {
+    /// Create a `P` proxy for each managed object implementing its interface.
+    ///
+    /// The proxies are for the same peer as `self` and are sorted by object path. This is typically
+    /// how you discover the devices of services like BlueZ or NetworkManager:
+    ///
+    /// ```no_run
+    /// # zbus::block_on(async {
+    /// use zbus::{dbus_proxy, fdo::ObjectManagerProxy, Connection};
+    ///
+    /// #[dbus_proxy(interface = "org.bluez.Device1", default_service = "org.bluez")]
+    /// trait Device1 {
+    ///     #[dbus_proxy(property)]
+    ///     fn address(&self) -> zbus::Result<String>;
+    /// }
+    ///
+    /// let conn = Connection::system().await?;
+    /// let manager = ObjectManagerProxy::builder(&conn)
+    ///     .destination("org.bluez")?
+    ///     .path("/")?
+    ///     .build()
+    ///     .await?;
+    /// for device in manager.managed_proxies::<Device1Proxy<'_>>().await? {
+    ///     println!("{}: {}", device.path(), device.address().await?);
+    /// }
+    /// # Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    pub async fn managed_proxies<P>(&self) -> zbus::Result<Vec<P>>
+    where
+        P: ProxyInterface + From<Proxy<'static>>,
+    {
+        let objects = self.get_managed_objects().await?;
+
+        managed_proxies(self.inner(), objects)
+    }
+
+    /// Create a `P` proxy for the managed object at `path`.
+    ///
+    /// The proxy is for the same peer as `self`. No check is made whether the object exists or
+    /// implements the interface of `P`.
+    pub fn managed_proxy<P, O>(&self, path: O) -> zbus::Result<P>
+    where
+        P: ProxyInterface + From<Proxy<'static>>,
+        O: TryInto<ObjectPath<'static>>,
+        O::Error: Into<zbus::Error>,
+    {
+        managed_proxy(self.inner(), path.try_into().map_err(Into::into)?)
+    }
+}
+
+pub(crate) fn managed_proxies<P>(
+    manager: &Proxy<'_>,
+    objects: ManagedObjects,
+) -> zbus::Result<Vec<P>>
+where
+    P: ProxyInterface + From<Proxy<'static>>,
+{
+    let mut paths: Vec<_> = objects
+        .into_iter()
+        .filter(|(_, interfaces)| interfaces.keys().any(|i| i.as_str() == P::INTERFACE))
+        .map(|(path, _)| path)
+        .collect();
+    paths.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+
+    paths
+        .into_iter()
+        .map(|path| managed_proxy(manager, path.into()))
+        .collect()
+}
+
+pub(crate) fn managed_proxy<P>(manager: &Proxy<'_>, path: ObjectPath<'static>) -> zbus::Result<P>
+where
+    P: ProxyInterface + From<Proxy<'static>>,
+{
+    ProxyBuilder::<Proxy<'static>>::new_bare(manager.connection())
+        .same_destination_as(manager)
+        .path(path)?
+        .interface(P::INTERFACE)?
+        .uncached_properties(P::UNCACHED_PROPERTIES)
+        .build_internal()
+        .map(Into::into)
+}
+
 /// Service-side [Object Manager][om] interface implementation.
 ///
 /// The recommended path to add this interface at is the path form of the well-known name of a D-Bus
@@ -280,6 +451,11 @@ assert_impl_all!(ObjectManagerProxy<'_>: Send, Sync, Unpin);
 /// the `path` its added at. You can use this fact to minimize the signal emissions by populating
 /// the entire (sub)tree under `path` before registering an object manager.
 ///
+/// From then on, `InterfacesAdded` and `InterfacesRemoved` signals are emitted as interfaces are
+/// added to or removed from the [`ObjectServer`] under `path`, and `GetManagedObjects` replies
+/// with the objects currently under `path`. Nodes that only exist on the way to other objects
+/// (i-e with no interface of their own) are not reported as objects.
+///
 /// [om]: https://dbus.freedesktop.org/doc/dbus-specification.html#standard-interfaces-objectmanager
 #[derive(Debug, Clone)]
 pub struct ObjectManager;
@@ -350,31 +526,39 @@ assert_impl_all!(PeerProxy<'_>: Send, Sync, Unpin);
 
 pub(crate) struct Peer;
 
+// The ID of the machine, read once needed.
+static MACHINE_ID: OnceCell<String> = OnceCell::new();
+
 /// Server-side implementation for the `org.freedesktop.DBus.Peer` interface.
 /// This interface is implemented automatically for any object registered to the
-/// [ObjectServer](crate::ObjectServer).
+/// [ObjectServer](crate::ObjectServer), and answered on any path, whether an object is registered
+/// at it or not.
 #[dbus_interface(name = "org.freedesktop.DBus.Peer")]
 impl Peer {
     fn ping(&self) {}
 
     fn get_machine_id(&self) -> Result<String> {
-        let mut id = match std::fs::read_to_string("/var/lib/dbus/machine-id") {
-            Ok(id) => id,
-            Err(e) => {
-                if let Ok(id) = std::fs::read_to_string("/etc/machine-id") {
-                    id
-                } else {
-                    return Err(Error::IOError(format!(
-                        "Failed to read from /var/lib/dbus/machine-id or /etc/machine-id: {e}"
-                    )));
-                }
+        MACHINE_ID.get_or_try_init(read_machine_id).cloned()
+    }
+}
+
+fn read_machine_id() -> Result<String> {
+    let mut id = match std::fs::read_to_string("/var/lib/dbus/machine-id") {
+        Ok(id) => id,
+        Err(e) => {
+            if let Ok(id) = std::fs::read_to_string("/etc/machine-id") {
+                id
+            } else {
+                return Err(Error::IOError(format!(
+                    "Failed to read from /var/lib/dbus/machine-id or /etc/machine-id: {e}"
+                )));
             }
-        };
+        }
+    };
 
-        let len = id.trim_end().len();
-        id.truncate(len);
-        Ok(id)
-    }
+    let len = id.trim_end().len();
+    id.truncate(len);
+    Ok(id)
 }
 
 #[rustfmt::skip]
@@ -401,22 +585,27 @@ assert_impl_all!(MonitoringProxy<'_>: Send, Sync, Unpin);
 #[rustfmt::skip]
 macro_rules! gen_stats_proxy {
     ($gen_async:literal, $gen_blocking:literal) => {
-        /// Proxy for the `org.freedesktop.DBus.Stats` interface.
+        /// Proxy for the `org.freedesktop.DBus.Debug.Stats` interface.
+        ///
+        /// This interface is only provided by message buses built with statistics support, and is
+        /// typically restricted to the user the bus runs as. Check
+        /// [`DBusProxy::interfaces`](crate::fdo::DBusProxy::interfaces) for its availability.
         #[dbus_proxy(
             interface = "org.freedesktop.DBus.Debug.Stats",
-            assume_defaults = true,
+            default_service = "org.freedesktop.DBus",
+            default_path = "/org/freedesktop/DBus",
             gen_async = $gen_async,
             gen_blocking = $gen_blocking,
         )]
         trait Stats {
-            /// GetStats (undocumented)
-            fn get_stats(&self) -> Result<Vec<HashMap<String, OwnedValue>>>;
+            /// Returns the statistics of the message bus itself.
+            fn get_stats(&self) -> Result<BusStats>;
 
-            /// GetConnectionStats (undocumented)
-            fn get_connection_stats(&self, n1: &str) -> Result<Vec<HashMap<String, OwnedValue>>>;
+            /// Returns the statistics of the connection owning `name`.
+            fn get_connection_stats(&self, name: BusName<'_>) -> Result<BusConnectionStats>;
 
-            /// GetAllMatchRules (undocumented)
-            fn get_all_match_rules(&self) -> Result<Vec<HashMap<String, Vec<String>>>>;
+            /// Returns the match rules of all the connections, by their unique name.
+            fn get_all_match_rules(&self) -> Result<HashMap<OwnedUniqueName, Vec<String>>>;
         }
     };
 }
@@ -424,6 +613,208 @@ macro_rules! gen_stats_proxy {
 gen_stats_proxy!(true, false);
 assert_impl_all!(StatsProxy<'_>: Send, Sync, Unpin);
 
+/// Statistics of a message bus, as returned by [`StatsProxy::get_stats`].
+///
+/// The statistics the bus doesn't provide are `None`. Unknown keys are ignored.
+#[derive(Debug, Default, DeserializeDict, PartialEq, Eq, SerializeDict, Type)]
+#[zvariant(signature = "a{sv}")]
+pub struct BusStats {
+    #[zvariant(rename = "Serial")]
+    serial: Option<u32>,
+    #[zvariant(rename = "ActiveConnections")]
+    active_connections: Option<u32>,
+    #[zvariant(rename = "IncompleteConnections")]
+    incomplete_connections: Option<u32>,
+    #[zvariant(rename = "MatchRules")]
+    match_rules: Option<u32>,
+    #[zvariant(rename = "PeakMatchRules")]
+    peak_match_rules: Option<u32>,
+    #[zvariant(rename = "PeakMatchRulesPerConnection")]
+    peak_match_rules_per_connection: Option<u32>,
+    #[zvariant(rename = "BusNames")]
+    bus_names: Option<u32>,
+    #[zvariant(rename = "PeakBusNames")]
+    peak_bus_names: Option<u32>,
+    #[zvariant(rename = "PeakBusNamesPerConnection")]
+    peak_bus_names_per_connection: Option<u32>,
+}
+
+assert_impl_all!(BusStats: Send, Sync, Unpin);
+
+impl BusStats {
+    /// The serial number of the statistics, incremented by the bus each time they're queried.
+    pub fn serial(&self) -> Option<u32> {
+        self.serial
+    }
+
+    /// The number of connections that completed authentication.
+    pub fn active_connections(&self) -> Option<u32> {
+        self.active_connections
+    }
+
+    /// The number of connections still authenticating.
+    pub fn incomplete_connections(&self) -> Option<u32> {
+        self.incomplete_connections
+    }
+
+    /// The number of match rules of all the connections.
+    pub fn match_rules(&self) -> Option<u32> {
+        self.match_rules
+    }
+
+    /// The highest number of match rules of all the connections, so far.
+    pub fn peak_match_rules(&self) -> Option<u32> {
+        self.peak_match_rules
+    }
+
+    /// The highest number of match rules of a single connection, so far.
+    pub fn peak_match_rules_per_connection(&self) -> Option<u32> {
+        self.peak_match_rules_per_connection
+    }
+
+    /// The number of names owned on the bus, unique names included.
+    pub fn bus_names(&self) -> Option<u32> {
+        self.bus_names
+    }
+
+    /// The highest number of names owned on the bus, so far.
+    pub fn peak_bus_names(&self) -> Option<u32> {
+        self.peak_bus_names
+    }
+
+    /// The highest number of names owned by a single connection, so far.
+    pub fn peak_bus_names_per_connection(&self) -> Option<u32> {
+        self.peak_bus_names_per_connection
+    }
+}
+
+/// Statistics of a connection to a message bus, as returned by
+/// [`StatsProxy::get_connection_stats`].
+///
+/// The incoming messages are the ones received by the bus from the connection, queued until the
+/// bus routes them, while the outgoing ones are those queued by the bus for the connection to read.
+/// The statistics the bus doesn't provide are `None`. Unknown keys are ignored.
+#[derive(Debug, Default, DeserializeDict, PartialEq, Eq, SerializeDict, Type)]
+#[zvariant(signature = "a{sv}")]
+pub struct BusConnectionStats {
+    #[zvariant(rename = "Serial")]
+    serial: Option<u32>,
+    #[zvariant(rename = "UniqueName")]
+    unique_name: Option<OwnedUniqueName>,
+    #[zvariant(rename = "MatchRules")]
+    match_rules: Option<u32>,
+    #[zvariant(rename = "PeakMatchRules")]
+    peak_match_rules: Option<u32>,
+    #[zvariant(rename = "BusNames")]
+    bus_names: Option<u32>,
+    #[zvariant(rename = "PeakBusNames")]
+    peak_bus_names: Option<u32>,
+    #[zvariant(rename = "IncomingMessages")]
+    incoming_messages: Option<u32>,
+    #[zvariant(rename = "IncomingBytes")]
+    incoming_bytes: Option<u32>,
+    #[zvariant(rename = "IncomingFDs")]
+    incoming_fds: Option<u32>,
+    #[zvariant(rename = "PeakIncomingBytes")]
+    peak_incoming_bytes: Option<u32>,
+    #[zvariant(rename = "PeakIncomingFDs")]
+    peak_incoming_fds: Option<u32>,
+    #[zvariant(rename = "OutgoingMessages")]
+    outgoing_messages: Option<u32>,
+    #[zvariant(rename = "OutgoingBytes")]
+    outgoing_bytes: Option<u32>,
+    #[zvariant(rename = "OutgoingFDs")]
+    outgoing_fds: Option<u32>,
+    #[zvariant(rename = "PeakOutgoingBytes")]
+    peak_outgoing_bytes: Option<u32>,
+    #[zvariant(rename = "PeakOutgoingFDs")]
+    peak_outgoing_fds: Option<u32>,
+}
+
+assert_impl_all!(BusConnectionStats: Send, Sync, Unpin);
+
+impl BusConnectionStats {
+    /// The serial number of the statistics, incremented by the bus each time they're queried.
+    pub fn serial(&self) -> Option<u32> {
+        self.serial
+    }
+
+    /// The unique name of the connection.
+    pub fn unique_name(&self) -> Option<&OwnedUniqueName> {
+        self.unique_name.as_ref()
+    }
+
+    /// The number of match rules of the connection.
+    pub fn match_rules(&self) -> Option<u32> {
+        self.match_rules
+    }
+
+    /// The highest number of match rules of the connection, so far.
+    pub fn peak_match_rules(&self) -> Option<u32> {
+        self.peak_match_rules
+    }
+
+    /// The number of names owned by the connection, its unique name included.
+    pub fn bus_names(&self) -> Option<u32> {
+        self.bus_names
+    }
+
+    /// The highest number of names owned by the connection, so far.
+    pub fn peak_bus_names(&self) -> Option<u32> {
+        self.peak_bus_names
+    }
+
+    /// The number of messages from the connection, queued by the bus.
+    pub fn incoming_messages(&self) -> Option<u32> {
+        self.incoming_messages
+    }
+
+    /// The size in bytes of the messages from the connection, queued by the bus.
+    pub fn incoming_bytes(&self) -> Option<u32> {
+        self.incoming_bytes
+    }
+
+    /// The number of file descriptors from the connection, queued by the bus.
+    pub fn incoming_fds(&self) -> Option<u32> {
+        self.incoming_fds
+    }
+
+    /// The highest size in bytes of the messages from the connection queued by the bus, so far.
+    pub fn peak_incoming_bytes(&self) -> Option<u32> {
+        self.peak_incoming_bytes
+    }
+
+    /// The highest number of file descriptors from the connection queued by the bus, so far.
+    pub fn peak_incoming_fds(&self) -> Option<u32> {
+        self.peak_incoming_fds
+    }
+
+    /// The number of messages queued by the bus for the connection.
+    pub fn outgoing_messages(&self) -> Option<u32> {
+        self.outgoing_messages
+    }
+
+    /// The size in bytes of the messages queued by the bus for the connection.
+    pub fn outgoing_bytes(&self) -> Option<u32> {
+        self.outgoing_bytes
+    }
+
+    /// The number of file descriptors queued by the bus for the connection.
+    pub fn outgoing_fds(&self) -> Option<u32> {
+        self.outgoing_fds
+    }
+
+    /// The highest size in bytes of the messages queued by the bus for the connection, so far.
+    pub fn peak_outgoing_bytes(&self) -> Option<u32> {
+        self.peak_outgoing_bytes
+    }
+
+    /// The highest number of file descriptors queued by the bus for the connection, so far.
+    pub fn peak_outgoing_fds(&self) -> Option<u32> {
+        self.peak_outgoing_fds
+    }
+}
+
 /// The flags used by the bus [`request_name`] method.
 ///
 /// [`request_name`]: struct.DBusProxy.html#method.request_name
@@ -465,7 +856,7 @@ assert_impl_all!(RequestNameFlags: Send, Sync, Unpin);
 ///
 /// [`request_name`]: struct.DBusProxy.html#method.request_name
 #[repr(u32)]
-#[derive(Deserialize_repr, Serialize_repr, Type, Debug, PartialEq, Eq)]
+#[derive(Deserialize_repr, Serialize_repr, Type, Debug, PartialEq, Eq, Copy, Clone, Hash)]
 pub enum RequestNameReply {
     /// The caller is now the primary owner of the name, replacing any previous owner. Either the
     /// name had no owner before, or the caller specified [`ReplaceExisting`] and the current owner
@@ -500,7 +891,7 @@ assert_impl_all!(RequestNameReply: Send, Sync, Unpin);
 ///
 /// [`release_name`]: struct.DBusProxy.html#method.release_name
 #[repr(u32)]
-#[derive(Deserialize_repr, Serialize_repr, Type, Debug, PartialEq, Eq)]
+#[derive(Deserialize_repr, Serialize_repr, Type, Debug, PartialEq, Eq, Copy, Clone, Hash)]
 pub enum ReleaseNameReply {
     /// The caller has released their claim on the given name. Either the caller was the primary
     /// owner of the name, and the name is now unused or taken by somebody waiting in the queue for
@@ -516,6 +907,20 @@ pub enum ReleaseNameReply {
 
 assert_impl_all!(ReleaseNameReply: Send, Sync, Unpin);
 
+/// The return code of the [`start_service`] method.
+///
+/// [`start_service`]: struct.DBusProxy.html#method.start_service
+#[repr(u32)]
+#[derive(Deserialize_repr, Serialize_repr, Type, Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum StartServiceReply {
+    /// The service was successfully started.
+    Success = 0x01,
+    /// A connection already owns the given name.
+    AlreadyRunning = 0x02,
+}
+
+assert_impl_all!(StartServiceReply: Send, Sync, Unpin);
+
 /// Credentials of a process connected to a bus server.
 ///
 /// If unable to determine certain credentials (for instance, because the process is not on the same
@@ -748,8 +1153,16 @@ macro_rules! gen_dbus_proxy {
 
             /// Tries to launch the executable associated with a name (service
             /// activation), as an explicit request.
+            #[deprecated(since = "3.15.0", note = "Use `start_service` instead")]
             fn start_service_by_name(&self, name: WellKnownName<'_>, flags: u32) -> Result<u32>;
 
+            /// Tries to launch the executable associated with a name (service
+            /// activation), as an explicit request. The flags are currently unused and must be 0.
+            ///
+            /// See also [`DBusProxy::ensure_activated`], to wait for the service to own the name.
+            #[dbus_proxy(name = "StartServiceByName")]
+            fn start_service(&self, name: WellKnownName<'_>, flags: u32) -> Result<StartServiceReply>;
+
             /// This method adds to or modifies that environment when activating services.
             fn update_activation_environment(&self, environment: HashMap<&str, &str>)
                 -> Result<()>;
@@ -775,6 +1188,11 @@ macro_rules! gen_dbus_proxy {
 
             /// This property lists abstract “features” provided by the message bus, and can be used by
             /// clients to detect the capabilities of the message bus with which they are communicating.
+            /// This property was added in version 1.11.x of the reference implementation of the message
+            /// bus.
+            ///
+            /// Known features include `AppArmor`, `SELinux`, `SystemdActivation` and
+            /// `HeaderFiltering`.
             #[dbus_proxy(property)]
             fn features(&self) -> Result<Vec<String>>;
 
@@ -799,6 +1217,166 @@ macro_rules! gen_dbus_proxy {
 gen_dbus_proxy!(true, false);
 assert_impl_all!(DBusProxy<'_>: Send, Sync, Unpin);
 
+impl<'a> DBusProxy<'a> {
+    /// Create a stream for the `NameOwnerChanged` signals about the name `name` only.
+    ///
+    /// The filtering is done by the bus. This is typically how you track a service (re)starting or
+    /// going away:
+    ///
+    /// ```no_run
+    /// # zbus::block_on(async {
+    /// use futures_util::StreamExt;
+    /// use zbus::{fdo::DBusProxy, Connection};
+    ///
+    /// let conn = Connection::session().await?;
+    /// let proxy = DBusProxy::new(&conn).await?;
+    /// let mut changes = proxy
+    ///     .receive_name_owner_changed_for("org.freedesktop.Notifications")
+    ///     .await?;
+    /// while let Some(change) = changes.next().await {
+    ///     let args = change.args()?;
+    ///     match args.new_owner().as_ref() {
+    ///         Some(owner) => println!("{} is now owned by {}", args.name(), owner),
+    ///         None => println!("{} went away", args.name()),
+    ///     }
+    /// }
+    /// # Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    pub async fn receive_name_owner_changed_for<'n, N>(
+        &self,
+        name: N,
+    ) -> zbus::Result<NameOwnerChangedStream<'static>>
+    where
+        N: TryInto<BusName<'n>>,
+        N::Error: Into<zbus::Error>,
+    {
+        let name = name.try_into().map_err(Into::into)?;
+
+        self.receive_name_owner_changed_with_args(&[(0, name.as_str())])
+            .await
+    }
+
+    /// Create a stream for the `NameAcquired` signals about the name `name` only.
+    pub async fn receive_name_acquired_for<'n, N>(
+        &self,
+        name: N,
+    ) -> zbus::Result<NameAcquiredStream<'static>>
+    where
+        N: TryInto<BusName<'n>>,
+        N::Error: Into<zbus::Error>,
+    {
+        let name = name.try_into().map_err(Into::into)?;
+
+        self.receive_name_acquired_with_args(&[(0, name.as_str())])
+            .await
+    }
+
+    /// Create a stream for the `NameLost` signals about the name `name` only.
+    pub async fn receive_name_lost_for<'n, N>(
+        &self,
+        name: N,
+    ) -> zbus::Result<NameLostStream<'static>>
+    where
+        N: TryInto<BusName<'n>>,
+        N::Error: Into<zbus::Error>,
+    {
+        let name = name.try_into().map_err(Into::into)?;
+
+        self.receive_name_lost_with_args(&[(0, name.as_str())])
+            .await
+    }
+
+    /// Start the service owning the name `name` if needed, and wait for it to be on the bus.
+    ///
+    /// This requests the activation of the service, unless the name already has an owner, and
+    /// returns the unique name of the owner once the service acquired `name`. Talking to the
+    /// service only once it's there avoids the errors you'd get if it's still starting up or if
+    /// it's quitting while you call it.
+    ///
+    /// ```no_run
+    /// # zbus::block_on(async {
+    /// use zbus::{fdo::DBusProxy, Connection};
+    ///
+    /// let conn = Connection::session().await?;
+    /// let owner = DBusProxy::new(&conn)
+    ///     .await?
+    ///     .ensure_activated("org.freedesktop.Notifications")
+    ///     .await?;
+    /// println!("The notification service is {}", owner);
+    /// # Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    pub async fn ensure_activated<'n, N>(&self, name: N) -> zbus::Result<OwnedUniqueName>
+    where
+        N: TryInto<WellKnownName<'n>>,
+        N::Error: Into<zbus::Error>,
+    {
+        use futures_util::StreamExt;
+
+        let name = name.try_into().map_err(Into::into)?;
+        // Subscribe first, so the service can't appear unnoticed in the meantime.
+        let mut changes = self.receive_name_owner_changed_for(name.as_ref()).await?;
+        match self.get_name_owner(name.as_ref().into()).await {
+            Ok(owner) => return Ok(owner),
+            Err(Error::NameHasNoOwner(_)) => (),
+            Err(e) => return Err(e.into()),
+        }
+
+        self.start_service(name.as_ref(), 0).await?;
+        match self.get_name_owner(name.as_ref().into()).await {
+            Ok(owner) => return Ok(owner),
+            Err(Error::NameHasNoOwner(_)) => (),
+            Err(e) => return Err(e.into()),
+        }
+        while let Some(change) = changes.next().await {
+            if let Some(owner) = change.args()?.new_owner().as_ref() {
+                return Ok(owner.to_owned().into());
+            }
+        }
+
+        Err(zbus::Error::Failure(format!(
+            "Connection closed before `{name}` was activated"
+        )))
+    }
+
+    /// Copy the environment variables `names` of the current process to the environment of the
+    /// services activated by the bus.
+    ///
+    /// The variables that aren't set (or aren't valid UTF-8) are skipped. This is typically what
+    /// session managers do once the graphical session is up, so the services activated afterwards
+    /// can connect to it:
+    ///
+    /// ```no_run
+    /// # zbus::block_on(async {
+    /// use zbus::{fdo::DBusProxy, Connection};
+    ///
+    /// let conn = Connection::session().await?;
+    /// DBusProxy::new(&conn)
+    ///     .await?
+    ///     .propagate_environment(&["DISPLAY", "WAYLAND_DISPLAY"])
+    ///     .await?;
+    /// # Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    pub async fn propagate_environment(&self, names: &[&str]) -> zbus::Result<()> {
+        let values = environment(names);
+        let environment = values.iter().map(|(k, v)| (*k, v.as_str())).collect();
+
+        self.update_activation_environment(environment)
+            .await
+            .map_err(Into::into)
+    }
+}
+
+// The values of the environment variables `names` that are set.
+pub(crate) fn environment<'n>(names: &[&'n str]) -> HashMap<&'n str, String> {
+    names
+        .iter()
+        .filter_map(|name| std::env::var(name).ok().map(|value| (*name, value)))
+        .collect()
+}
+
 /// Errors from <https://gitlab.freedesktop.org/dbus/dbus/-/blob/master/dbus/dbus-protocol.h>
 #[derive(Clone, Debug, DBusError, PartialEq)]
 #[dbus_error(prefix = "org.freedesktop.DBus.Error", impl_display = true)]
@@ -1002,6 +1580,64 @@ mod tests {
         assert_eq!(e.description(), Some("so long"));
     }
 
+    #[test]
+    fn error_source_chain() {
+        use std::error::Error as _;
+
+        let io = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "reset by peer");
+        let e = fdo::Error::from(Error::from(io));
+        let zbus_error = e.source().unwrap();
+        assert!(matches!(
+            zbus_error.downcast_ref::<Error>(),
+            Some(Error::InputOutput(_))
+        ));
+        let io = zbus_error.source().unwrap();
+        assert_eq!(
+            io.downcast_ref::<std::io::Error>().unwrap().kind(),
+            std::io::ErrorKind::ConnectionReset
+        );
+
+        // Wrapped back in a `zbus::Error`, the `fdo::Error` is part of the chain.
+        let e = Error::from(fdo::Error::AccessDenied("not allowed".into()));
+        let fdo_error = e.source().unwrap().downcast_ref::<fdo::Error>().unwrap();
+        assert_eq!(fdo_error, &fdo::Error::AccessDenied("not allowed".into()));
+        assert!(fdo_error.source().is_none());
+    }
+
+    #[test]
+    fn error_reply_round_trip() {
+        let call = Message::method(Some(":1.2"), None::<()>, "/", None::<()>, "foo", &()).unwrap();
+        let hdr = call.header().unwrap();
+        for e in [
+            fdo::Error::ServiceUnknown("no such service".into()),
+            fdo::Error::AccessDenied("not allowed".into()),
+            fdo::Error::LimitsExceeded("too many".into()),
+            fdo::Error::TimedOut("so long".into()),
+            fdo::Error::NotSupported("nope".into()),
+            fdo::Error::SpawnChildExited("exited with 1".into()),
+        ] {
+            let reply = e.create_reply(&hdr).unwrap();
+            assert_eq!(
+                reply.header().unwrap().error_name().unwrap().unwrap(),
+                &e.name()
+            );
+            let back: fdo::Error = Error::from(reply).into();
+            assert_eq!(back, e);
+        }
+
+        // Non-standard errors are kept as they are.
+        let reply =
+            Message::method_error(None::<()>, &call, "org.zbus.Error.Custom", &("hm")).unwrap();
+        let e: fdo::Error = Error::from(reply).into();
+        match e {
+            fdo::Error::ZBus(Error::MethodError(name, description, _)) => {
+                assert_eq!(name, "org.zbus.Error.Custom");
+                assert_eq!(description.as_deref(), Some("hm"));
+            }
+            e => panic!("unexpected error: {}", e),
+        }
+    }
+
     #[test]
     #[timeout(15000)]
     fn signal() {
@@ -1071,4 +1707,357 @@ mod tests {
                 dbg!(v)
             });
     }
+
+    #[test]
+    #[timeout(15000)]
+    fn name_signals_for() {
+        runtime::Runtime::new()
+            .unwrap()
+            .block_on(test_name_signals_for());
+    }
+
+    async fn test_name_signals_for() {
+        let conn = crate::Connection::session().await.unwrap();
+        let proxy = fdo::DBusProxy::new(&conn).await.unwrap();
+        let well_known = "org.freedesktop.zbus.NameSignalsFor";
+        let other = "org.freedesktop.zbus.NameSignalsFor.Other";
+        let mut owner_changes = proxy
+            .receive_name_owner_changed_for(well_known)
+            .await
+            .unwrap();
+        let mut acquired = proxy.receive_name_acquired_for(well_known).await.unwrap();
+        let mut lost = proxy.receive_name_lost_for(well_known).await.unwrap();
+
+        // Signals about other names are filtered out.
+        conn.request_name(other).await.unwrap();
+        conn.request_name(well_known).await.unwrap();
+        let change = owner_changes.next().await.unwrap();
+        let args = change.args().unwrap();
+        assert_eq!(args.name(), &well_known);
+        assert_eq!(args.old_owner().as_ref(), None);
+        assert_eq!(args.new_owner().as_ref(), conn.unique_name().map(|n| &**n));
+        let name_acquired = acquired.next().await.unwrap();
+        assert_eq!(name_acquired.args().unwrap().name(), &well_known);
+
+        conn.release_name(other).await.unwrap();
+        conn.release_name(well_known).await.unwrap();
+        let change = owner_changes.next().await.unwrap();
+        let args = change.args().unwrap();
+        assert_eq!(args.name(), &well_known);
+        assert_eq!(args.new_owner().as_ref(), None);
+        let name_lost = lost.next().await.unwrap();
+        assert_eq!(name_lost.args().unwrap().name(), &well_known);
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn ensure_activated() {
+        runtime::Runtime::new()
+            .unwrap()
+            .block_on(test_ensure_activated());
+    }
+
+    async fn test_ensure_activated() {
+        let service = crate::Connection::session().await.unwrap();
+        let well_known = "org.freedesktop.zbus.EnsureActivated";
+        service.request_name(well_known).await.unwrap();
+        let conn = crate::Connection::session().await.unwrap();
+        let proxy = fdo::DBusProxy::new(&conn).await.unwrap();
+
+        let owner = proxy.ensure_activated(well_known).await.unwrap();
+        assert_eq!(&owner, service.unique_name().unwrap());
+
+        // Nothing can be activated for this one.
+        let res = proxy
+            .ensure_activated("org.freedesktop.zbus.NotActivatable")
+            .await;
+        assert!(matches!(
+            res,
+            Err(Error::FDO(e)) if matches!(*e, fdo::Error::ServiceUnknown(_))
+        ));
+    }
+
+    #[test]
+    fn environment() {
+        // The environment isn't modified, as the tests run in parallel.
+        let (name, value) = std::env::vars().next().expect("empty environment");
+        let mut unset = String::from("ZBUS_TEST_ENVIRONMENT_UNSET");
+        while std::env::var_os(&unset).is_some() {
+            unset.push('_');
+        }
+
+        let environment = fdo::environment(&[&name, &unset]);
+        assert_eq!(environment.len(), 1);
+        assert_eq!(environment[name.as_str()], value);
+        assert!(fdo::environment(&[]).is_empty());
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn propagate_environment() {
+        runtime::Runtime::new()
+            .unwrap()
+            .block_on(test_propagate_environment());
+    }
+
+    async fn test_propagate_environment() {
+        let conn = crate::Connection::session().await.unwrap();
+        let proxy = fdo::DBusProxy::new(&conn).await.unwrap();
+
+        // Only with nothing to propagate, not to change the activation environment of the bus.
+        proxy.propagate_environment(&[]).await.unwrap();
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn managed_proxies() {
+        crate::block_on(test_managed_proxies()).unwrap();
+    }
+
+    async fn test_managed_proxies() -> zbus::Result<()> {
+        use crate::{dbus_interface, dbus_proxy, ProxyInterface};
+
+        struct Thing(u32);
+
+        #[dbus_interface(name = "org.zbus.ManagedThing")]
+        impl Thing {
+            #[dbus_interface(property)]
+            fn serial(&self) -> u32 {
+                self.0
+            }
+        }
+
+        struct Other;
+
+        #[dbus_interface(name = "org.zbus.ManagedOther")]
+        impl Other {}
+
+        #[dbus_proxy(interface = "org.zbus.ManagedThing", assume_defaults = false)]
+        trait Thing {
+            #[dbus_proxy(property(emits_changed_signal = "false"))]
+            fn serial(&self) -> zbus::Result<u32>;
+        }
+
+        assert_eq!(
+            <ThingProxy<'_> as ProxyInterface>::INTERFACE,
+            "org.zbus.ManagedThing"
+        );
+        assert_eq!(
+            <ThingProxy<'_> as ProxyInterface>::UNCACHED_PROPERTIES,
+            ["Serial"]
+        );
+
+        let service = crate::ConnectionBuilder::session()?
+            .serve_at("/org/zbus/Managed", fdo::ObjectManager)?
+            .build()
+            .await?;
+        let server = service.object_server();
+        server.at("/org/zbus/Managed/b", Thing(2)).await?;
+        server.at("/org/zbus/Managed/a", Thing(1)).await?;
+        server.at("/org/zbus/Managed/c", Other).await?;
+        let conn = crate::Connection::session().await?;
+        let manager = fdo::ObjectManagerProxy::builder(&conn)
+            .destination(service.unique_name().unwrap().to_owned())?
+            .path("/org/zbus/Managed")?
+            .build()
+            .await?;
+
+        let things = manager.managed_proxies::<ThingProxy<'_>>().await?;
+        let paths: Vec<_> = things.iter().map(|t| t.path().as_str()).collect();
+        assert_eq!(paths, ["/org/zbus/Managed/a", "/org/zbus/Managed/b"]);
+        for (thing, serial) in things.iter().zip(1..) {
+            assert_eq!(thing.destination(), manager.destination());
+            assert_eq!(thing.serial().await?, serial);
+        }
+
+        let thing: ThingProxy<'_> = manager.managed_proxy("/org/zbus/Managed/b")?;
+        assert_eq!(thing.serial().await?, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn object_manager() {
+        crate::block_on(test_object_manager()).unwrap();
+    }
+
+    async fn test_object_manager() -> zbus::Result<()> {
+        use crate::dbus_interface;
+
+        struct Thing;
+
+        #[dbus_interface(name = "org.zbus.ManagedThing")]
+        impl Thing {}
+
+        let service = crate::ConnectionBuilder::session()?
+            .serve_at("/org/zbus/Manager", fdo::ObjectManager)?
+            .build()
+            .await?;
+        let server = service.object_server();
+        let conn = crate::Connection::session().await?;
+        let manager = fdo::ObjectManagerProxy::builder(&conn)
+            .destination(service.unique_name().unwrap().to_owned())?
+            .path("/org/zbus/Manager")?
+            .build()
+            .await?;
+        let mut added = manager.receive_interfaces_added().await?;
+        let mut removed = manager.receive_interfaces_removed().await?;
+        let managed_paths = || async {
+            let mut paths: Vec<_> = manager
+                .get_managed_objects()
+                .await?
+                .into_keys()
+                .map(|p| p.to_string())
+                .collect();
+            paths.sort();
+
+            Ok::<_, fdo::Error>(paths)
+        };
+
+        // The intermediate node at `/org/zbus/Manager/a` is not an object.
+        server.at("/org/zbus/Manager/a/b", Thing).await?;
+        let signal = added.next().await.unwrap();
+        assert_eq!(signal.args()?.object_path(), "/org/zbus/Manager/a/b");
+        assert_eq!(managed_paths().await?, ["/org/zbus/Manager/a/b"]);
+
+        server.at("/org/zbus/Manager/a", Thing).await?;
+        let signal = added.next().await.unwrap();
+        assert_eq!(signal.args()?.object_path(), "/org/zbus/Manager/a");
+        assert_eq!(
+            managed_paths().await?,
+            ["/org/zbus/Manager/a", "/org/zbus/Manager/a/b"]
+        );
+
+        // Removing an object leaves the ones below it alone.
+        assert!(server.remove::<Thing, _>("/org/zbus/Manager/a").await?);
+        let signal = removed.next().await.unwrap();
+        assert_eq!(signal.args()?.object_path(), "/org/zbus/Manager/a");
+        assert_eq!(managed_paths().await?, ["/org/zbus/Manager/a/b"]);
+
+        assert!(server.remove::<Thing, _>("/org/zbus/Manager/a/b").await?);
+        let signal = removed.next().await.unwrap();
+        assert_eq!(signal.args()?.object_path(), "/org/zbus/Manager/a/b");
+        assert!(managed_paths().await?.is_empty());
+        let root = server.root().read().await;
+        assert!(root.get_child(&"/org/zbus/Manager/a".try_into()?).is_none());
+        assert!(root.get_child(&"/org/zbus/Manager".try_into()?).is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn interfaces_added_removed_args() {
+        crate::block_on(test_interfaces_added_removed_args()).unwrap();
+    }
+
+    async fn test_interfaces_added_removed_args() -> zbus::Result<()> {
+        use crate::dbus_interface;
+        use zvariant::Value;
+
+        struct Device(&'static str);
+
+        #[dbus_interface(name = "org.zbus.Device1")]
+        impl Device {
+            #[dbus_interface(property)]
+            fn address(&self) -> &str {
+                self.0
+            }
+        }
+
+        let service = crate::ConnectionBuilder::session()?
+            .serve_at("/org/zbus/Adapter", fdo::ObjectManager)?
+            .build()
+            .await?;
+        let conn = crate::Connection::session().await?;
+        let manager = fdo::ObjectManagerProxy::builder(&conn)
+            .destination(service.unique_name().unwrap().to_owned())?
+            .path("/org/zbus/Adapter")?
+            .build()
+            .await?;
+        let mut added = manager.receive_interfaces_added().await?;
+        let mut removed = manager.receive_interfaces_removed().await?;
+        let device1 = zbus_names::InterfaceName::from_static_str("org.zbus.Device1")?;
+
+        service
+            .object_server()
+            .at("/org/zbus/Adapter/dev_0", Device("00:11:22:33:44:55"))
+            .await?;
+        let signal = added.next().await.unwrap();
+        let args = signal.args()?;
+        assert_eq!(args.object_path(), "/org/zbus/Adapter/dev_0");
+        let properties = &args.interfaces_and_properties()["org.zbus.Device1"];
+        assert_eq!(properties["Address"], Value::from("00:11:22:33:44:55"));
+        assert_eq!(
+            args.properties_by_interface()[&device1]["Address"],
+            Value::from("00:11:22:33:44:55")
+        );
+
+        service
+            .object_server()
+            .remove::<Device, _>("/org/zbus/Adapter/dev_0")
+            .await?;
+        let signal = removed.next().await.unwrap();
+        let args = signal.args()?;
+        assert_eq!(args.object_path(), "/org/zbus/Adapter/dev_0");
+        assert_eq!(args.interfaces(), &["org.zbus.Device1"]);
+        assert_eq!(args.interface_names(), [device1]);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn remove_object() {
+        crate::block_on(test_remove_object()).unwrap();
+    }
+
+    async fn test_remove_object() -> zbus::Result<()> {
+        use crate::dbus_interface;
+
+        struct Device;
+
+        #[dbus_interface(name = "org.zbus.Device")]
+        impl Device {}
+
+        struct Battery;
+
+        #[dbus_interface(name = "org.zbus.Battery")]
+        impl Battery {}
+
+        let service = crate::ConnectionBuilder::session()?
+            .serve_at("/org/zbus/Devices", fdo::ObjectManager)?
+            .build()
+            .await?;
+        let server = service.object_server();
+        let conn = crate::Connection::session().await?;
+        let manager = fdo::ObjectManagerProxy::builder(&conn)
+            .destination(service.unique_name().unwrap().to_owned())?
+            .path("/org/zbus/Devices")?
+            .build()
+            .await?;
+        let mut removed = manager.receive_interfaces_removed().await?;
+
+        server.at("/org/zbus/Devices/0", Device).await?;
+        server.at("/org/zbus/Devices/0", Battery).await?;
+        server.at("/org/zbus/Devices/0/1", Device).await?;
+
+        assert!(server.remove_object("/org/zbus/Devices/0").await?);
+        let signal = removed.next().await.unwrap();
+        let args = signal.args()?;
+        assert_eq!(args.object_path(), "/org/zbus/Devices/0");
+        let mut interfaces: Vec<_> = args.interfaces().iter().map(|i| i.to_string()).collect();
+        interfaces.sort_unstable();
+        assert_eq!(interfaces, ["org.zbus.Battery", "org.zbus.Device"]);
+        let objects = manager.get_managed_objects().await?;
+        let paths: Vec<_> = objects.keys().map(|p| p.as_str()).collect();
+        assert_eq!(paths, ["/org/zbus/Devices/0/1"]);
+
+        assert!(!server.remove_object("/org/zbus/Devices/0").await?);
+        assert!(server.remove_object("/org/zbus/Devices/0/1").await?);
+        assert!(manager.get_managed_objects().await?.is_empty());
+
+        Ok(())
+    }
 }
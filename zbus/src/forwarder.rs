@@ -0,0 +1,164 @@
+use std::{collections::HashMap, fmt::Write};
+
+use tracing::{debug, trace};
+use zbus_names::InterfaceName;
+use zvariant::{OwnedValue, Value};
+
+use crate::{fdo, Connection, Error, Message, MessageBuilder, MessageFlags, Proxy, Result};
+
+// Forwards everything received for an interface to the object of a proxy, relaying the replies
+// back. Held by the nodes aside from their interfaces, see `ObjectServer::forward`.
+#[derive(Debug)]
+pub(crate) struct Forwarder {
+    proxy: Proxy<'static>,
+    // The introspection XML of the interface, as given by the forwarded object.
+    introspection: Option<String>,
+}
+
+impl Forwarder {
+    pub(crate) async fn new(proxy: Proxy<'static>) -> Self {
+        let introspection = match proxy.introspect().await {
+            Ok(xml) => interface_xml(&xml, proxy.interface()),
+            Err(e) => {
+                debug!("Failed to introspect the forwarded object: {}", e);
+
+                None
+            }
+        };
+
+        Self {
+            proxy,
+            introspection,
+        }
+    }
+
+    pub(crate) fn interface_name(&self) -> InterfaceName<'static> {
+        self.proxy.interface().to_owned()
+    }
+
+    pub(crate) async fn get(&self, property_name: &str) -> fdo::Result<OwnedValue> {
+        self.proxy
+            .get_property::<OwnedValue>(property_name)
+            .await
+            .map_err(Into::into)
+    }
+
+    pub(crate) async fn get_all(&self) -> fdo::Result<HashMap<String, OwnedValue>> {
+        self.proxy
+            .properties_proxy()
+            .get_all(self.proxy.interface().as_ref())
+            .await
+    }
+
+    pub(crate) async fn set(&self, property_name: &str, value: &Value<'_>) -> fdo::Result<()> {
+        self.proxy.set_property(property_name, value.clone()).await
+    }
+
+    pub(crate) async fn forward_call(&self, connection: &Connection, msg: &Message) -> Result<()> {
+        let hdr = msg.header()?;
+        let no_reply = hdr
+            .primary()
+            .flags()
+            .contains(MessageFlags::NoReplyExpected);
+        let member = msg.member().ok_or(Error::MissingField)?;
+        let conn = self.proxy.connection();
+
+        let mut builder = MessageBuilder::method_call(self.proxy.path(), member)?
+            .interface(self.proxy.interface())?;
+        if let Some(sender) = conn.current_unique_name() {
+            builder = builder.sender(sender)?;
+        }
+        if let Some(destination) = self.proxy.optional_destination() {
+            builder = builder.destination(destination)?;
+        }
+        if no_reply {
+            builder = builder.with_flags(MessageFlags::NoReplyExpected)?;
+        }
+        let call = build_with_body_of(builder, msg)?;
+        if no_reply {
+            trace!("No reply expected for {:?} by the caller.", msg);
+            return conn.send_message(call).await.map(|_| ());
+        }
+
+        let (builder, reply) = match conn.send_message_with_reply(call).await?.reply().await {
+            Ok(reply) => (MessageBuilder::method_return(&hdr)?, reply),
+            Err(Error::MethodError(name, _, reply)) => (MessageBuilder::error(&hdr, name)?, reply),
+            Err(e) => {
+                return connection
+                    .reply_dbus_error(&hdr, fdo::Error::from(e))
+                    .await
+                    .map(|_| ())
+            }
+        };
+        let builder = match connection.current_unique_name() {
+            Some(sender) => builder.sender(sender)?,
+            None => builder,
+        };
+        let reply = build_with_body_of(builder, &reply)?;
+
+        connection.send_message(reply).await.map(|_| ())
+    }
+
+    pub(crate) fn introspect_to_writer(&self, writer: &mut dyn Write, level: usize) {
+        match &self.introspection {
+            Some(xml) => {
+                for line in xml.lines() {
+                    writeln!(writer, "{:indent$}{}", "", line, indent = level).unwrap();
+                }
+            }
+            None => {
+                writeln!(
+                    writer,
+                    r#"{:indent$}<interface name="{}">"#,
+                    "",
+                    self.proxy.interface(),
+                    indent = level
+                )
+                .unwrap();
+                writeln!(writer, "{:indent$}</interface>", "", indent = level).unwrap();
+            }
+        }
+    }
+}
+
+// Build the message, with a copy of the body (and the FDs) of `msg` as its body.
+fn build_with_body_of(builder: MessageBuilder<'_>, msg: &Message) -> Result<Message> {
+    let signature = match msg.body_signature() {
+        // Wrapped in STRUCT delimiters, since `build_raw_body` strips the leading and trailing
+        // ones, which would break signatures like `(s)(s)`.
+        Ok(signature) => format!("({signature})"),
+        Err(Error::NoBodySignature) => String::new(),
+        Err(e) => return Err(e),
+    };
+    let builder = builder.endian(msg.primary_header().endian_sig());
+
+    // SAFETY: The body comes from a valid message, with the same signature and endianness.
+    unsafe {
+        builder.build_raw_body(
+            msg.body_as_bytes()?,
+            signature.as_str(),
+            #[cfg(unix)]
+            msg.fds(),
+        )
+    }
+}
+
+// Extract the (unindented) XML of the given interface out of the introspection XML of an object.
+fn interface_xml(xml: &str, interface_name: &InterfaceName<'_>) -> Option<String> {
+    let start = format!(r#"<interface name="{interface_name}">"#);
+    let end = "</interface>";
+    let start_index = xml.find(&start)?;
+    let end_index = start_index + xml[start_index..].find(end)? + end.len();
+    let mut lines = xml[start_index..end_index].lines();
+    let first = lines.next()?;
+    // The indentation of the lines, relative to the one of the `interface` element.
+    let base = xml[..start_index].len() - xml[..start_index].trim_end_matches(' ').len();
+    let mut interface = String::from(first);
+    for line in lines {
+        interface.push('\n');
+        let indent = line.len() - line.trim_start_matches(' ').len();
+        interface.push_str(&line[indent.min(base)..]);
+    }
+
+    Some(interface)
+}
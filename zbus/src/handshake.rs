@@ -1,4 +1,3 @@
-use async_trait::async_trait;
 use futures_util::{future::poll_fn, StreamExt};
 #[cfg(unix)]
 use nix::unistd::Uid;
@@ -6,6 +5,7 @@ use std::{
     collections::VecDeque,
     convert::{TryFrom, TryInto},
     fmt::{self, Debug},
+    mem,
     path::PathBuf,
     str::FromStr,
 };
@@ -44,12 +44,8 @@ pub enum AuthMechanism {
 
 /// The result of a finalized handshake
 ///
-/// The result of a finalized [`ClientHandshake`] or [`ServerHandshake`]. It can be passed to
-/// [`Connection::new_authenticated`] to initialize a connection.
-///
-/// [`ClientHandshake`]: struct.ClientHandshake.html
-/// [`ServerHandshake`]: struct.ServerHandshake.html
-/// [`Connection::new_authenticated`]: ../struct.Connection.html#method.new_authenticated
+/// The result of a finalized [`ClientHandshake`] or [`ServerHandshake`], bound to the socket the
+/// handshake was performed on.
 #[derive(Debug)]
 pub struct Authenticated<S> {
     pub(crate) conn: Connection<S>,
@@ -65,8 +61,19 @@ where
     S: Socket + Unpin,
 {
     /// Create a client-side `Authenticated` for the given `socket`.
-    pub async fn client(socket: S, mechanisms: Option<VecDeque<AuthMechanism>>) -> Result<Self> {
-        ClientHandshake::new(socket, mechanisms).perform().await
+    ///
+    /// If given, `external_id` overrides the identity used for the `EXTERNAL` mechanism.
+    pub async fn client(
+        socket: S,
+        mechanisms: Option<VecDeque<AuthMechanism>>,
+        external_id: Option<String>,
+    ) -> Result<Self> {
+        let mut handshake = ClientHandshake::new(mechanisms, socket.can_pass_unix_fd());
+        if let Some(id) = external_id {
+            handshake.set_external_id(id);
+        }
+
+        Self::perform(socket, handshake).await
     }
 
     /// Create a server-side `Authenticated` for the given `socket`.
@@ -81,8 +88,7 @@ where
         cookie_id: Option<usize>,
         cookie_context: CookieContext<'_>,
     ) -> Result<Self> {
-        ServerHandshake::new(
-            socket,
+        let handshake = ServerHandshake::new(
             guid,
             #[cfg(unix)]
             client_uid,
@@ -91,9 +97,255 @@ where
             auth_mechanisms,
             cookie_id,
             cookie_context,
-        )?
-        .perform()
-        .await
+        )?;
+
+        Self::perform(socket, handshake).await
+    }
+
+    /// Create an `Authenticated` from a handshake that was driven to completion by the caller.
+    pub(crate) fn new(socket: S, outcome: HandshakeOutcome) -> Self {
+        Authenticated {
+            conn: Connection::new(socket, outcome.remaining),
+            server_guid: outcome.server_guid,
+            #[cfg(unix)]
+            cap_unix_fd: outcome.cap_unix_fd,
+        }
+    }
+
+    /// Drive `handshake` to completion over `socket`.
+    #[instrument(skip(socket, handshake))]
+    async fn perform<H: Handshake>(mut socket: S, mut handshake: H) -> Result<Self> {
+        let mut buf = [0; 64];
+        let mut read = None;
+        let mut cookie = None;
+        #[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
+        let mut first_write = true;
+
+        loop {
+            let input = match (read.take(), cookie.take()) {
+                (Some(len), _) => HandshakeInput::Received(&buf[..len]),
+                (None, Some((id, cookie))) => HandshakeInput::Cookie { id, cookie },
+                (None, None) => HandshakeInput::None,
+            };
+
+            match handshake.advance(input)? {
+                HandshakeOutput::Send(data) => {
+                    // The dbus daemon on some platforms requires sending the zero byte as a
+                    // separate message with SCM_CREDS.
+                    #[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
+                    if first_write && data == [b'\0'] {
+                        first_write = false;
+                        match socket.send_zero_byte() {
+                            Ok(Some(1)) => continue,
+                            Ok(_) => {
+                                return Err(Error::Handshake(
+                                    "Could not send zero byte with credentials".to_string(),
+                                ))
+                            }
+                            Err(e) => {
+                                return Err(Error::Handshake(format!(
+                                    "Could not send zero byte with credentials: {e}"
+                                )))
+                            }
+                        }
+                    }
+
+                    write_all(&mut socket, &data).await?;
+                }
+                HandshakeOutput::NeedInput => {
+                    let res = poll_fn(|cx| socket.poll_recvmsg(cx, &mut buf)).await?;
+                    let len = {
+                        #[cfg(unix)]
+                        {
+                            let (len, fds) = res;
+                            if !fds.is_empty() {
+                                return Err(Error::Handshake(
+                                    "Unexpected FDs during handshake".into(),
+                                ));
+                            }
+                            len
+                        }
+                        #[cfg(not(unix))]
+                        {
+                            res
+                        }
+                    };
+                    if len == 0 {
+                        return Err(Error::Handshake("Unexpected EOF during handshake".into()));
+                    }
+                    read = Some(len);
+                }
+                HandshakeOutput::LookupCookie { context, id } => {
+                    let found = match id {
+                        Some(id) => Cookie::lookup(&context, id).await?,
+                        None => Cookie::first(&context).await?,
+                    };
+                    cookie = Some((found.id, found.cookie));
+                }
+                HandshakeOutput::Done(outcome) => {
+                    trace!("Handshake done");
+
+                    return Ok(Self::new(socket, outcome));
+                }
+            }
+        }
+    }
+}
+
+#[instrument(skip(socket))]
+async fn write_all<S: Socket>(socket: &mut S, mut data: &[u8]) -> Result<()> {
+    while !data.is_empty() {
+        let written = poll_fn(|cx| {
+            socket.poll_sendmsg(
+                cx,
+                data,
+                #[cfg(unix)]
+                &[],
+            )
+        })
+        .await?;
+        data = &data[written..];
+    }
+
+    Ok(())
+}
+
+/// An I/O-free SASL handshake.
+///
+/// This is implemented by [`ClientHandshake`] and [`ServerHandshake`], the client and server sides
+/// of the [authentication protocol] respectively. The handshake doesn't perform any I/O itself.
+/// Instead, the caller feeds it the bytes received from the peer and performs the actions it
+/// requests through [`HandshakeOutput`], until [`HandshakeOutput::Done`] is returned. This allows
+/// authenticating over any transport.
+///
+/// # Example
+///
+/// Here we connect a client and a server handshake to each other in memory:
+///
+/// ```
+/// use zbus::{
+///     AuthMechanism, ClientHandshake, CookieContext, Guid, Handshake, HandshakeInput,
+///     HandshakeOutput, ServerHandshake,
+/// };
+///
+/// let guid = Guid::generate();
+/// let mechanisms = Some(vec![AuthMechanism::Anonymous].into());
+/// let mut client = ClientHandshake::new(mechanisms.clone(), false);
+/// let mut server = ServerHandshake::new(
+///     guid.clone(),
+///     None,
+///     mechanisms,
+///     None,
+///     CookieContext::default(),
+/// )?;
+///
+/// // Run the client until it needs to hear back from the server and collect what it sends.
+/// fn run(handshake: &mut impl Handshake, input: &[u8]) -> zbus::Result<(Vec<u8>, bool)> {
+///     let mut sent = vec![];
+///     let mut input = HandshakeInput::Received(input);
+///     loop {
+///         match handshake.advance(input)? {
+///             HandshakeOutput::Send(data) => sent.extend(data),
+///             HandshakeOutput::NeedInput => return Ok((sent, false)),
+///             HandshakeOutput::Done(_) => return Ok((sent, true)),
+///             HandshakeOutput::LookupCookie { .. } => unreachable!(),
+///         }
+///         input = HandshakeInput::None;
+///     }
+/// }
+///
+/// let (mut to_server, _) = run(&mut client, &[])?;
+/// loop {
+///     let (to_client, server_done) = run(&mut server, &to_server)?;
+///     let (data, client_done) = run(&mut client, &to_client)?;
+///     to_server = data;
+///     if client_done {
+///         assert!(run(&mut server, &to_server)?.1);
+///         break;
+///     }
+///     assert!(!server_done);
+/// }
+/// # Ok::<_, zbus::Error>(())
+/// ```
+///
+/// [authentication protocol]: https://dbus.freedesktop.org/doc/dbus-specification.html#auth-protocol
+pub trait Handshake {
+    /// Advance the handshake with the given `input`.
+    ///
+    /// The returned [`HandshakeOutput`] tells the caller what to do next. After having done so,
+    /// the caller should call this method again, passing the requested input (if any), or
+    /// [`HandshakeInput::None`] otherwise.
+    fn advance(&mut self, input: HandshakeInput<'_>) -> Result<HandshakeOutput>;
+}
+
+/// The input to [`Handshake::advance`].
+#[derive(Debug)]
+pub enum HandshakeInput<'a> {
+    /// No new input.
+    None,
+    /// Bytes received from the peer.
+    ///
+    /// These don't need to be complete lines. The handshake buffers incomplete data internally.
+    Received(&'a [u8]),
+    /// The cookie requested through [`HandshakeOutput::LookupCookie`].
+    Cookie {
+        /// The ID of the cookie.
+        id: usize,
+        /// The cookie data.
+        cookie: String,
+    },
+}
+
+/// The output of [`Handshake::advance`].
+#[derive(Debug)]
+pub enum HandshakeOutput {
+    /// Send the given bytes to the peer.
+    ///
+    /// The very first output of a [`ClientHandshake`] is always the single NUL byte that starts
+    /// the protocol. Transports that pass credentials out-of-band (e.g `SCM_CREDS`) should do
+    /// so along with this byte.
+    Send(Vec<u8>),
+    /// More data from the peer is needed, pass it with [`HandshakeInput::Received`].
+    NeedInput,
+    /// The `DBUS_COOKIE_SHA1` mechanism needs a cookie from the user's keyring.
+    ///
+    /// The cookie should be passed with [`HandshakeInput::Cookie`]. If `id` is `None`, the first
+    /// cookie in the keyring is to be used.
+    LookupCookie {
+        /// The cookie context (i.e the keyring) to look the cookie up in.
+        context: CookieContext<'static>,
+        /// The ID of the cookie.
+        id: Option<usize>,
+    },
+    /// The handshake has successfully completed.
+    Done(HandshakeOutcome),
+}
+
+/// The outcome of a successful handshake.
+#[derive(Debug)]
+pub struct HandshakeOutcome {
+    server_guid: Guid,
+    cap_unix_fd: bool,
+    remaining: Vec<u8>,
+}
+
+impl HandshakeOutcome {
+    /// The GUID of the server.
+    pub fn server_guid(&self) -> &Guid {
+        &self.server_guid
+    }
+
+    /// Whether both sides agreed to pass file descriptors.
+    pub fn cap_unix_fd(&self) -> bool {
+        self.cap_unix_fd
+    }
+
+    /// Bytes received after the end of the handshake.
+    ///
+    /// These are the start of the D-Bus message stream and must be handed over to the message
+    /// layer along with the socket (see [`crate::ConnectionBuilder::authenticated_socket`]).
+    pub fn remaining(&self) -> &[u8] {
+        &self.remaining
     }
 }
 
@@ -101,12 +353,13 @@ where
  * Client-side handshake logic
  */
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq)]
 #[allow(clippy::upper_case_acronyms)]
 enum ClientHandshakeStep {
     Init,
     MechanismInit,
     WaitingForData,
+    WaitingForCookie { server_challenge: String },
     WaitingForOK,
     WaitingForAgreeUnixFD,
     Done,
@@ -130,37 +383,23 @@ enum Command {
     AgreeUnixFD,
 }
 
-/// A representation of an in-progress handshake, client-side
-///
-/// This struct is an async-compatible representation of the initial handshake that must be
-/// performed before a D-Bus connection can be used. To use it, you should call the
-/// [`advance_handshake`] method whenever the underlying socket becomes ready (tracking the
-/// readiness itself is not managed by this abstraction) until it returns `Ok(())`, at which point
-/// you can invoke the [`try_finish`] method to get an [`Authenticated`], which can be given to
-/// [`Connection::new_authenticated`].
+/// The client side of the handshake.
 ///
-/// [`advance_handshake`]: struct.ClientHandshake.html#method.advance_handshake
-/// [`try_finish`]: struct.ClientHandshake.html#method.try_finish
-/// [`Authenticated`]: struct.AUthenticated.html
-/// [`Connection::new_authenticated`]: ../struct.Connection.html#method.new_authenticated
+/// See [`Handshake`] for details on how to drive it.
 #[derive(Debug)]
-pub struct ClientHandshake<S> {
-    common: HandshakeCommon<S>,
+pub struct ClientHandshake {
+    common: HandshakeCommon,
     step: ClientHandshakeStep,
+    unix_fd: bool,
+    external_id: Option<String>,
 }
 
-#[async_trait]
-pub trait Handshake<S> {
-    /// Perform the handshake.
+impl ClientHandshake {
+    /// Start a client handshake.
     ///
-    /// On a successful handshake, you get an `Authenticated`. If you need to send a Bus Hello,
-    /// this remains to be done.
-    async fn perform(mut self) -> Result<Authenticated<S>>;
-}
-
-impl<S: Socket> ClientHandshake<S> {
-    /// Start a handshake on this client socket
-    pub fn new(socket: S, mechanisms: Option<VecDeque<AuthMechanism>>) -> ClientHandshake<S> {
+    /// The `mechanisms` are tried in order. If `None`, all the supported mechanisms are tried.
+    /// If `unix_fd` is `true`, the client will negotiate file descriptor passing with the server.
+    pub fn new(mechanisms: Option<VecDeque<AuthMechanism>>, unix_fd: bool) -> ClientHandshake {
         let mechanisms = mechanisms.unwrap_or_else(|| {
             let mut mechanisms = VecDeque::new();
             mechanisms.push_back(AuthMechanism::External);
@@ -170,31 +409,49 @@ impl<S: Socket> ClientHandshake<S> {
         });
 
         ClientHandshake {
-            common: HandshakeCommon::new(socket, mechanisms, None),
+            common: HandshakeCommon::new(mechanisms, None),
             step: ClientHandshakeStep::Init,
+            unix_fd,
+            external_id: None,
         }
     }
 
-    fn mechanism_init(&mut self) -> Result<(ClientHandshakeStep, Command)> {
+    /// Use `id` as the identity to authenticate as, with the `EXTERNAL` mechanism.
+    ///
+    /// See [`ConnectionBuilder::external_id`](crate::ConnectionBuilder::external_id) for when this
+    /// is needed.
+    pub fn set_external_id(&mut self, id: String) {
+        self.external_id = Some(id);
+    }
+
+    fn mechanism_init(&mut self) -> Result<HandshakeOutput> {
         use ClientHandshakeStep::*;
-        let mech = self.common.mechanism()?;
-        match mech {
-            AuthMechanism::Anonymous => Ok((
-                WaitingForOK,
-                Command::Auth(Some(*mech), Some("zbus".into())),
-            )),
-            AuthMechanism::External => Ok((
-                WaitingForOK,
-                Command::Auth(Some(*mech), Some(sasl_auth_id()?.into_bytes())),
-            )),
-            AuthMechanism::Cookie => Ok((
+        let mech = *self.common.mechanism()?;
+        let (step, cmd) = match mech {
+            AuthMechanism::Anonymous => {
+                (WaitingForOK, Command::Auth(Some(mech), Some("zbus".into())))
+            }
+            AuthMechanism::External => {
+                let id = match &self.external_id {
+                    Some(id) => id.clone(),
+                    None => sasl_auth_id()?,
+                };
+
+                (
+                    WaitingForOK,
+                    Command::Auth(Some(mech), Some(id.into_bytes())),
+                )
+            }
+            AuthMechanism::Cookie => (
                 WaitingForData,
-                Command::Auth(Some(*mech), Some(sasl_auth_id()?.into_bytes())),
-            )),
-        }
+                Command::Auth(Some(mech), Some(sasl_auth_id()?.into_bytes())),
+            ),
+        };
+
+        Ok(self.send(step, cmd))
     }
 
-    async fn mechanism_data(&mut self, data: Vec<u8>) -> Result<(ClientHandshakeStep, Command)> {
+    fn mechanism_data(&mut self, data: Vec<u8>) -> Result<HandshakeOutput> {
         let mech = self.common.mechanism()?;
         match mech {
             AuthMechanism::Cookie => {
@@ -204,7 +461,7 @@ impl<S: Socket> ClientHandshake<S> {
                 let context = split
                     .next()
                     .ok_or_else(|| Error::Handshake("Missing cookie context name".into()))?;
-                let context = Str::from(context).try_into()?;
+                let context = Str::from(context.to_string()).try_into()?;
                 let id = split
                     .next()
                     .ok_or_else(|| Error::Handshake("Missing cookie ID".into()))?;
@@ -213,21 +470,124 @@ impl<S: Socket> ClientHandshake<S> {
                     .map_err(|e| Error::Handshake(format!("Invalid cookie ID `{id}`: {e}")))?;
                 let server_challenge = split
                     .next()
-                    .ok_or_else(|| Error::Handshake("Missing cookie challenge".into()))?;
-
-                let cookie = Cookie::lookup(&context, id).await?.cookie;
-                let client_challenge = random_ascii(16);
-                let _sec = format!("{server_challenge}:{client_challenge}:{cookie}");
-                let sha1 = ""; // SHA1 disabled 
-                let data = format!("{client_challenge} {sha1}");
-                Ok((
-                    ClientHandshakeStep::WaitingForOK,
-                    Command::Data(Some(data.into())),
-                ))
+                    .ok_or_else(|| Error::Handshake("Missing cookie challenge".into()))?
+                    .to_string();
+
+                self.step = ClientHandshakeStep::WaitingForCookie { server_challenge };
+
+                Ok(HandshakeOutput::LookupCookie {
+                    context,
+                    id: Some(id),
+                })
             }
             _ => Err(Error::Handshake("Unexpected mechanism DATA".into())),
         }
     }
+
+    fn send(&mut self, step: ClientHandshakeStep, cmd: Command) -> HandshakeOutput {
+        self.step = step;
+
+        HandshakeOutput::Send(cmd.into())
+    }
+}
+
+impl Handshake for ClientHandshake {
+    #[instrument(skip(self))]
+    fn advance(&mut self, input: HandshakeInput<'_>) -> Result<HandshakeOutput> {
+        use ClientHandshakeStep::*;
+
+        let cookie = self.common.receive(input);
+        loop {
+            match &self.step {
+                Init => {
+                    trace!("Initializing");
+                    self.step = MechanismInit;
+
+                    return Ok(HandshakeOutput::Send(vec![b'\0']));
+                }
+                MechanismInit => {
+                    trace!("Initializing auth mechanisms");
+
+                    return self.mechanism_init();
+                }
+                WaitingForCookie { server_challenge } => {
+                    let server_challenge = server_challenge.clone();
+                    let cookie = match cookie {
+                        Some((_, cookie)) => cookie,
+                        None => return Err(Error::Handshake("Expected a cookie".into())),
+                    };
+                    let client_challenge = random_ascii(16);
+                    let _sec = format!("{server_challenge}:{client_challenge}:{cookie}");
+                    let sha1 = ""; // SHA1 disabled
+                    let data = format!("{client_challenge} {sha1}");
+
+                    return Ok(self.send(WaitingForOK, Command::Data(Some(data.into()))));
+                }
+                WaitingForData | WaitingForOK => {
+                    trace!("Waiting for DATA or OK from server");
+                    let reply = match self.common.read_command()? {
+                        Some(reply) => reply,
+                        None => return Ok(HandshakeOutput::NeedInput),
+                    };
+                    match (&self.step, reply) {
+                        (_, Command::Data(data)) => {
+                            trace!("Received DATA from server");
+                            let data = data.ok_or_else(|| {
+                                Error::Handshake("Received DATA with no data from server".into())
+                            })?;
+
+                            return self.mechanism_data(data);
+                        }
+                        (_, Command::Rejected(_)) => {
+                            trace!("Received REJECT from server. Will try next auth mechanism..");
+                            self.common.mechanisms.pop_front();
+                            self.step = MechanismInit;
+                        }
+                        (WaitingForOK, Command::Ok(guid)) => {
+                            trace!("Received OK from server");
+                            self.common.server_guid = Some(guid);
+
+                            return Ok(if self.unix_fd {
+                                self.send(WaitingForAgreeUnixFD, Command::NegotiateUnixFD)
+                            } else {
+                                self.send(Done, Command::Begin)
+                            });
+                        }
+                        (_, reply) => {
+                            return Err(Error::Handshake(format!(
+                                "Unexpected server AUTH OK reply: {reply}"
+                            )));
+                        }
+                    }
+                }
+                WaitingForAgreeUnixFD => {
+                    trace!("Waiting for Unix FD passing agreement from server");
+                    let reply = match self.common.read_command()? {
+                        Some(reply) => reply,
+                        None => return Ok(HandshakeOutput::NeedInput),
+                    };
+                    match reply {
+                        Command::AgreeUnixFD => {
+                            trace!("Unix FD passing agreed by server");
+                            self.common.cap_unix_fd = true
+                        }
+                        Command::Error(_) => {
+                            trace!("Unix FD passing rejected by server");
+                            self.common.cap_unix_fd = false
+                        }
+                        _ => {
+                            return Err(Error::Handshake(format!(
+                                "Unexpected server UNIX_FD reply: {reply}"
+                            )));
+                        }
+                    }
+
+                    return Ok(self.send(Done, Command::Begin));
+                }
+                Done => return self.common.outcome().map(HandshakeOutput::Done),
+            }
+        }
+    }
 }
 
 fn random_ascii(len: usize) -> String {
@@ -350,9 +710,22 @@ impl Cookie {
     }
 }
 
-#[derive(Debug)]
+/// The context (i.e keyring name) of `DBUS_COOKIE_SHA1` cookies.
+#[derive(Clone, Debug)]
 pub struct CookieContext<'c>(Str<'c>);
 
+impl CookieContext<'_> {
+    /// The context as a string.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// Creates an owned clone of `self`.
+    pub fn to_owned(&self) -> CookieContext<'static> {
+        CookieContext(self.0.to_owned())
+    }
+}
+
 impl<'c> TryFrom<Str<'c>> for CookieContext<'c> {
     type Error = Error;
 
@@ -375,130 +748,6 @@ impl Default for CookieContext<'_> {
     }
 }
 
-#[async_trait]
-impl<S: Socket> Handshake<S> for ClientHandshake<S> {
-    #[instrument(skip(self))]
-    async fn perform(mut self) -> Result<Authenticated<S>> {
-        use ClientHandshakeStep::*;
-        loop {
-            let (next_step, cmd) = match self.step {
-                Init => {
-                    trace!("Initializing");
-                    #[allow(clippy::let_and_return)]
-                    let ret = self.mechanism_init()?;
-                    // The dbus daemon on some platforms requires sending the zero byte as a
-                    // separate message with SCM_CREDS.
-                    #[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
-                    let written = self
-                        .common
-                        .socket
-                        .send_zero_byte()
-                        .map_err(|e| {
-                            Error::Handshake(format!(
-                                "Could not send zero byte with credentials: {}",
-                                e
-                            ))
-                        })
-                        .and_then(|n| match n {
-                            None => Err(Error::Handshake(
-                                "Could not send zero byte with credentials".to_string(),
-                            )),
-                            Some(n) => Ok(n),
-                        })?;
-
-                    // leading 0 is sent separately already for `freebsd` and `dragonfly` above.
-                    #[cfg(not(any(target_os = "freebsd", target_os = "dragonfly")))]
-                    let written = poll_fn(|cx| {
-                        self.common.socket.poll_sendmsg(
-                            cx,
-                            &[b'\0'],
-                            #[cfg(unix)]
-                            &[],
-                        )
-                    })
-                    .await?;
-
-                    if written != 1 {
-                        return Err(Error::Handshake(
-                            "Could not send zero byte with credentials".to_string(),
-                        ));
-                    }
-
-                    ret
-                }
-                MechanismInit => {
-                    trace!("Initializing auth mechanisms");
-                    self.mechanism_init()?
-                }
-                WaitingForData | WaitingForOK => {
-                    trace!("Waiting for DATA or OK from server");
-                    let reply = self.common.read_command().await?;
-                    match (self.step, reply) {
-                        (_, Command::Data(data)) => {
-                            trace!("Received DATA from server");
-                            let data = data.ok_or_else(|| {
-                                Error::Handshake("Received DATA with no data from server".into())
-                            })?;
-                            self.mechanism_data(data).await?
-                        }
-                        (_, Command::Rejected(_)) => {
-                            trace!("Received REJECT from server. Will try next auth mechanism..");
-                            self.common.mechanisms.pop_front();
-                            self.step = MechanismInit;
-                            continue;
-                        }
-                        (WaitingForOK, Command::Ok(guid)) => {
-                            trace!("Received OK from server");
-                            self.common.server_guid = Some(guid);
-                            if self.common.socket.can_pass_unix_fd() {
-                                (WaitingForAgreeUnixFD, Command::NegotiateUnixFD)
-                            } else {
-                                (Done, Command::Begin)
-                            }
-                        }
-                        (_, reply) => {
-                            return Err(Error::Handshake(format!(
-                                "Unexpected server AUTH OK reply: {reply}"
-                            )));
-                        }
-                    }
-                }
-                WaitingForAgreeUnixFD => {
-                    trace!("Waiting for Unix FD passing agreement from server");
-                    let reply = self.common.read_command().await?;
-                    match reply {
-                        Command::AgreeUnixFD => {
-                            trace!("Unix FD passing agreed by server");
-                            self.common.cap_unix_fd = true
-                        }
-                        Command::Error(_) => {
-                            trace!("Unix FD passing rejected by server");
-                            self.common.cap_unix_fd = false
-                        }
-                        _ => {
-                            return Err(Error::Handshake(format!(
-                                "Unexpected server UNIX_FD reply: {reply}"
-                            )));
-                        }
-                    }
-                    (Done, Command::Begin)
-                }
-                Done => {
-                    trace!("Handshake done");
-                    return Ok(Authenticated {
-                        conn: Connection::new(self.common.socket, self.common.recv_buffer),
-                        server_guid: self.common.server_guid.unwrap(),
-                        #[cfg(unix)]
-                        cap_unix_fd: self.common.cap_unix_fd,
-                    });
-                }
-            };
-            self.common.write_command(cmd).await?;
-            self.step = next_step;
-        }
-    }
-}
-
 /*
  * Server-side handshake logic
  */
@@ -509,28 +758,23 @@ enum ServerHandshakeStep {
     WaitingForNull,
     WaitingForAuth,
     WaitingForData(AuthMechanism),
+    WaitingForCookie,
+    WaitingForCookieResponse {
+        cookie: String,
+        server_challenge: String,
+    },
     WaitingForBegin,
     Done,
 }
 
-/// A representation of an in-progress handshake, server-side
+/// The server side of the handshake.
 ///
 /// This would typically be used to implement a D-Bus broker, or in the context of a P2P connection.
 ///
-/// This struct is an async-compatible representation of the initial handshake that must be
-/// performed before a D-Bus connection can be used. To use it, you should call the
-/// [`advance_handshake`] method whenever the underlying socket becomes ready (tracking the
-/// readiness itself is not managed by this abstraction) until it returns `Ok(())`, at which point
-/// you can invoke the [`try_finish`] method to get an [`Authenticated`], which can be given to
-/// [`Connection::new_authenticated`].
-///
-/// [`advance_handshake`]: struct.ServerHandshake.html#method.advance_handshake
-/// [`try_finish`]: struct.ServerHandshake.html#method.try_finish
-/// [`Authenticated`]: struct.Authenticated.html
-/// [`Connection::new_authenticated`]: ../struct.Connection.html#method.new_authenticated
+/// See [`Handshake`] for details on how to drive it.
 #[derive(Debug)]
-pub struct ServerHandshake<'s, S> {
-    common: HandshakeCommon<S>,
+pub struct ServerHandshake<'s> {
+    common: HandshakeCommon,
     step: ServerHandshakeStep,
     #[cfg(unix)]
     client_uid: Option<u32>,
@@ -540,16 +784,20 @@ pub struct ServerHandshake<'s, S> {
     cookie_context: CookieContext<'s>,
 }
 
-impl<'s, S: Socket> ServerHandshake<'s, S> {
+impl<'s> ServerHandshake<'s> {
+    /// Start a server handshake.
+    ///
+    /// For the `EXTERNAL` mechanism, the client is authenticated against `client_uid` on Unix and
+    /// against `client_sid` on Windows. If `mechanisms` is `None`, only `EXTERNAL` is accepted.
+    /// The `cookie_id` and `cookie_context` are only used for the `DBUS_COOKIE_SHA1` mechanism.
     pub fn new(
-        socket: S,
         guid: Guid,
         #[cfg(unix)] client_uid: Option<u32>,
         #[cfg(windows)] client_sid: Option<String>,
         mechanisms: Option<VecDeque<AuthMechanism>>,
         cookie_id: Option<usize>,
         cookie_context: CookieContext<'s>,
-    ) -> Result<ServerHandshake<'s, S>> {
+    ) -> Result<ServerHandshake<'s>> {
         let mechanisms = match mechanisms {
             Some(mechanisms) => mechanisms,
             None => {
@@ -561,7 +809,7 @@ impl<'s, S: Socket> ServerHandshake<'s, S> {
         };
 
         Ok(ServerHandshake {
-            common: HandshakeCommon::new(socket, mechanisms, Some(guid)),
+            common: HandshakeCommon::new(mechanisms, Some(guid)),
             step: ServerHandshakeStep::WaitingForNull,
             #[cfg(unix)]
             client_uid,
@@ -572,16 +820,20 @@ impl<'s, S: Socket> ServerHandshake<'s, S> {
         })
     }
 
-    async fn auth_ok(&mut self) -> Result<()> {
+    fn send(&mut self, step: ServerHandshakeStep, cmd: Command) -> HandshakeOutput {
+        self.step = step;
+
+        HandshakeOutput::Send(cmd.into())
+    }
+
+    fn auth_ok(&mut self) -> HandshakeOutput {
         let cmd = Command::Ok(self.guid().clone());
         trace!("Sending authentication OK");
-        self.common.write_command(cmd).await?;
-        self.step = ServerHandshakeStep::WaitingForBegin;
 
-        Ok(())
+        self.send(ServerHandshakeStep::WaitingForBegin, cmd)
     }
 
-    async fn check_external_auth(&mut self, sasl_id: &[u8]) -> Result<()> {
+    fn check_external_auth(&mut self, sasl_id: &[u8]) -> Result<HandshakeOutput> {
         let auth_ok = {
             let id = std::str::from_utf8(sasl_id)
                 .map_err(|e| Error::Handshake(format!("Invalid ID: {e}")))?;
@@ -599,17 +851,13 @@ impl<'s, S: Socket> ServerHandshake<'s, S> {
         };
 
         if auth_ok {
-            self.auth_ok().await
+            Ok(self.auth_ok())
         } else {
-            self.rejected_error().await
+            Ok(self.rejected_error())
         }
     }
 
-    async fn check_cookie_auth(&mut self, sasl_id: &[u8]) -> Result<()> {
-        let cookie = match self.cookie_id {
-            Some(cookie_id) => Cookie::lookup(&self.cookie_context, cookie_id).await?,
-            None => Cookie::first(&self.cookie_context).await?,
-        };
+    fn check_cookie_auth(&mut self, sasl_id: &[u8]) -> Result<HandshakeOutput> {
         let id = std::str::from_utf8(sasl_id)
             .map_err(|e| Error::Handshake(format!("Invalid ID: {e}")))?;
         if sasl_auth_id()? != id {
@@ -618,16 +866,38 @@ impl<'s, S: Socket> ServerHandshake<'s, S> {
             // contents in random users' home directories.
             //
             // The dbus reference implementation also has the same limitation/behavior.
-            self.rejected_error().await?;
-            return Ok(());
+            return Ok(self.rejected_error());
         }
+        self.step = ServerHandshakeStep::WaitingForCookie;
+
+        Ok(HandshakeOutput::LookupCookie {
+            context: self.cookie_context.to_owned(),
+            id: self.cookie_id,
+        })
+    }
+
+    fn cookie_challenge(&mut self, cookie_id: usize, cookie: String) -> HandshakeOutput {
         let server_challenge = random_ascii(16);
-        let data = format!("{} {} {server_challenge}", self.cookie_context.0, cookie.id);
+        let data = format!("{} {} {server_challenge}", self.cookie_context.0, cookie_id);
         let cmd = Command::Data(Some(data.into_bytes()));
         trace!("Sending DBUS_COOKIE_SHA1 authentication challenge");
-        self.common.write_command(cmd).await?;
 
-        let auth_data = match self.common.read_command().await? {
+        self.send(
+            ServerHandshakeStep::WaitingForCookieResponse {
+                cookie,
+                server_challenge,
+            },
+            cmd,
+        )
+    }
+
+    fn check_cookie_response(
+        &mut self,
+        response: Command,
+        cookie: &str,
+        server_challenge: &str,
+    ) -> Result<HandshakeOutput> {
+        let auth_data = match response {
             Command::Data(data) => data,
             _ => None,
         };
@@ -643,33 +913,29 @@ impl<'s, S: Socket> ServerHandshake<'s, S> {
         let client_sha1 = split
             .next()
             .ok_or_else(|| Error::Handshake("Missing client cookie data".into()))?;
-        let _sec = format!("{server_challenge}:{client_challenge}:{}", cookie.cookie);
+        let _sec = format!("{server_challenge}:{client_challenge}:{cookie}");
         let sha1 = ""; // SHA1 disabled
 
         if sha1 == client_sha1 {
-            self.auth_ok().await
+            Ok(self.auth_ok())
         } else {
-            self.rejected_error().await
+            Ok(self.rejected_error())
         }
     }
 
-    async fn unsupported_command_error(&mut self) -> Result<()> {
+    fn unsupported_command_error(&mut self) -> HandshakeOutput {
         let cmd = Command::Error("Unsupported command".to_string());
         trace!("Sending authentication error");
-        self.common.write_command(cmd).await?;
-        self.step = ServerHandshakeStep::WaitingForAuth;
 
-        Ok(())
+        self.send(ServerHandshakeStep::WaitingForAuth, cmd)
     }
 
-    async fn rejected_error(&mut self) -> Result<()> {
+    fn rejected_error(&mut self) -> HandshakeOutput {
         let mechanisms = self.common.mechanisms.iter().cloned().collect();
         let cmd = Command::Rejected(mechanisms);
         trace!("Sending authentication error");
-        self.common.write_command(cmd).await?;
-        self.step = ServerHandshakeStep::WaitingForAuth;
 
-        Ok(())
+        self.send(ServerHandshakeStep::WaitingForAuth, cmd)
     }
 
     fn guid(&self) -> &Guid {
@@ -681,79 +947,107 @@ impl<'s, S: Socket> ServerHandshake<'s, S> {
     }
 }
 
-#[async_trait]
-impl<S: Socket> Handshake<S> for ServerHandshake<'_, S> {
+impl Handshake for ServerHandshake<'_> {
     #[instrument(skip(self))]
-    async fn perform(mut self) -> Result<Authenticated<S>> {
+    fn advance(&mut self, input: HandshakeInput<'_>) -> Result<HandshakeOutput> {
+        let cookie = self.common.receive(input);
         loop {
-            match self.step {
+            match &self.step {
                 ServerHandshakeStep::WaitingForNull => {
                     trace!("Waiting for NULL");
-                    let mut buffer = [0; 1];
-                    let read =
-                        poll_fn(|cx| self.common.socket.poll_recvmsg(cx, &mut buffer)).await?;
-                    #[cfg(unix)]
-                    let read = read.0;
-                    // recvmsg cannot return anything else than Ok(1) or Err
-                    debug_assert!(read == 1);
-                    if buffer[0] != 0 {
+                    let byte = match self.common.recv_buffer.first() {
+                        Some(byte) => *byte,
+                        None => return Ok(HandshakeOutput::NeedInput),
+                    };
+                    if byte != 0 {
                         return Err(Error::Handshake(
                             "First client byte is not NUL!".to_string(),
                         ));
                     }
+                    self.common.recv_buffer.remove(0);
                     trace!("Received NULL from client");
                     self.step = ServerHandshakeStep::WaitingForAuth;
                 }
                 ServerHandshakeStep::WaitingForAuth => {
                     trace!("Waiting for authentication");
-                    let reply = self.common.read_command().await?;
-                    match reply {
+                    let reply = match self.common.read_command()? {
+                        Some(reply) => reply,
+                        None => return Ok(HandshakeOutput::NeedInput),
+                    };
+                    return match reply {
                         Command::Auth(mech, resp) => {
                             let mech = mech.filter(|m| self.common.mechanisms.contains(m));
 
                             match (mech, &resp) {
                                 (Some(mech), None) => {
                                     trace!("Sending data request");
-                                    self.common.write_command(Command::Data(None)).await?;
-                                    self.step = ServerHandshakeStep::WaitingForData(mech);
-                                }
-                                (Some(AuthMechanism::Anonymous), Some(_)) => {
-                                    self.auth_ok().await?;
+                                    Ok(self.send(
+                                        ServerHandshakeStep::WaitingForData(mech),
+                                        Command::Data(None),
+                                    ))
                                 }
+                                (Some(AuthMechanism::Anonymous), Some(_)) => Ok(self.auth_ok()),
                                 (Some(AuthMechanism::External), Some(sasl_id)) => {
-                                    self.check_external_auth(sasl_id).await?;
+                                    self.check_external_auth(sasl_id)
                                 }
                                 (Some(AuthMechanism::Cookie), Some(sasl_id)) => {
-                                    self.check_cookie_auth(sasl_id).await?;
+                                    self.check_cookie_auth(sasl_id)
                                 }
-                                _ => self.rejected_error().await?,
+                                _ => Ok(self.rejected_error()),
                             }
                         }
-                        Command::Error(_) => self.rejected_error().await?,
-                        Command::Begin => {
-                            return Err(Error::Handshake(
-                                "Received BEGIN while not authenticated".to_string(),
-                            ));
-                        }
-                        _ => self.unsupported_command_error().await?,
-                    }
+                        Command::Error(_) => Ok(self.rejected_error()),
+                        Command::Begin => Err(Error::Handshake(
+                            "Received BEGIN while not authenticated".to_string(),
+                        )),
+                        _ => Ok(self.unsupported_command_error()),
+                    };
                 }
                 ServerHandshakeStep::WaitingForData(mech) => {
                     trace!("Waiting for authentication");
-                    let reply = self.common.read_command().await?;
-                    match (mech, reply) {
-                        (AuthMechanism::External, Command::Data(None)) => self.auth_ok().await?,
+                    let mech = *mech;
+                    let reply = match self.common.read_command()? {
+                        Some(reply) => reply,
+                        None => return Ok(HandshakeOutput::NeedInput),
+                    };
+                    return match (mech, reply) {
+                        (AuthMechanism::External, Command::Data(None)) => Ok(self.auth_ok()),
                         (AuthMechanism::External, Command::Data(Some(data))) => {
-                            self.check_external_auth(&data).await?;
+                            self.check_external_auth(&data)
                         }
-                        (AuthMechanism::Anonymous, Command::Data(_)) => self.auth_ok().await?,
-                        (_, Command::Data(_)) => self.rejected_error().await?,
-                        (_, _) => self.unsupported_command_error().await?,
-                    }
+                        (AuthMechanism::Anonymous, Command::Data(_)) => Ok(self.auth_ok()),
+                        (_, Command::Data(_)) => Ok(self.rejected_error()),
+                        (_, _) => Ok(self.unsupported_command_error()),
+                    };
+                }
+                ServerHandshakeStep::WaitingForCookie => {
+                    return match cookie {
+                        Some((id, cookie)) => Ok(self.cookie_challenge(id, cookie)),
+                        None => Err(Error::Handshake("Expected a cookie".into())),
+                    };
+                }
+                ServerHandshakeStep::WaitingForCookieResponse { .. } => {
+                    let response = match self.common.read_command()? {
+                        Some(response) => response,
+                        None => return Ok(HandshakeOutput::NeedInput),
+                    };
+                    let (cookie, server_challenge) =
+                        match mem::replace(&mut self.step, ServerHandshakeStep::WaitingForAuth) {
+                            ServerHandshakeStep::WaitingForCookieResponse {
+                                cookie,
+                                server_challenge,
+                            } => (cookie, server_challenge),
+                            _ => unreachable!(),
+                        };
+
+                    return self.check_cookie_response(response, &cookie, &server_challenge);
                 }
                 ServerHandshakeStep::WaitingForBegin => {
                     trace!("Waiting for Begin command from the client");
-                    let reply = self.common.read_command().await?;
+                    let reply = match self.common.read_command()? {
+                        Some(reply) => reply,
+                        None => return Ok(HandshakeOutput::NeedInput),
+                    };
                     match reply {
                         Command::Begin => {
                             trace!("Received Begin command from the client");
@@ -761,29 +1055,21 @@ impl<S: Socket> Handshake<S> for ServerHandshake<'_, S> {
                         }
                         Command::Cancel | Command::Error(_) => {
                             trace!("Received CANCEL or ERROR command from the client");
-                            self.rejected_error().await?;
+                            return Ok(self.rejected_error());
                         }
                         #[cfg(unix)]
                         Command::NegotiateUnixFD => {
                             trace!("Received NEGOTIATE_UNIX_FD command from the client");
                             self.common.cap_unix_fd = true;
                             trace!("Sending AGREE_UNIX_FD to the client");
-                            self.common.write_command(Command::AgreeUnixFD).await?;
-                            self.step = ServerHandshakeStep::WaitingForBegin;
+                            return Ok(self
+                                .send(ServerHandshakeStep::WaitingForBegin, Command::AgreeUnixFD));
                         }
-                        _ => self.unsupported_command_error().await?,
+                        _ => return Ok(self.unsupported_command_error()),
                     }
                 }
                 ServerHandshakeStep::Done => {
-                    trace!("Handshake done");
-                    return Ok(Authenticated {
-                        conn: Connection::new(self.common.socket, self.common.recv_buffer),
-                        // SAFETY: We know that the server GUID is set because we set it in the
-                        // constructor.
-                        server_guid: self.common.server_guid.expect("Server GUID not set"),
-                        #[cfg(unix)]
-                        cap_unix_fd: self.common.cap_unix_fd,
-                    });
+                    return self.common.outcome().map(HandshakeOutput::Done);
                 }
             }
         }
@@ -909,8 +1195,7 @@ impl FromStr for Command {
 
 // Common code for the client and server side of the handshake.
 #[derive(Debug)]
-pub struct HandshakeCommon<S> {
-    socket: S,
+struct HandshakeCommon {
     recv_buffer: Vec<u8>,
     server_guid: Option<Guid>,
     cap_unix_fd: bool,
@@ -918,11 +1203,9 @@ pub struct HandshakeCommon<S> {
     mechanisms: VecDeque<AuthMechanism>,
 }
 
-impl<S: Socket> HandshakeCommon<S> {
-    /// Start a handshake on this client socket
-    pub fn new(socket: S, mechanisms: VecDeque<AuthMechanism>, server_guid: Option<Guid>) -> Self {
+impl HandshakeCommon {
+    fn new(mechanisms: VecDeque<AuthMechanism>, server_guid: Option<Guid>) -> Self {
         Self {
-            socket,
             recv_buffer: Vec::new(),
             server_guid,
             cap_unix_fd: false,
@@ -930,66 +1213,38 @@ impl<S: Socket> HandshakeCommon<S> {
         }
     }
 
-    #[instrument(skip(self))]
-    async fn write_command(&mut self, command: Command) -> Result<()> {
-        let mut send_buffer = Vec::<u8>::from(command);
-        while !send_buffer.is_empty() {
-            let written = poll_fn(|cx| {
-                self.socket.poll_sendmsg(
-                    cx,
-                    &send_buffer,
-                    #[cfg(unix)]
-                    &[],
-                )
-            })
-            .await?;
-            send_buffer.drain(..written);
+    /// Buffer the received data, if any, and return the cookie, if given.
+    fn receive(&mut self, input: HandshakeInput<'_>) -> Option<(usize, String)> {
+        match input {
+            HandshakeInput::None => None,
+            HandshakeInput::Received(data) => {
+                self.recv_buffer.extend_from_slice(data);
+
+                None
+            }
+            HandshakeInput::Cookie { id, cookie } => Some((id, cookie)),
         }
-        Ok(())
     }
 
+    /// Parse the next command out of the receive buffer, if a complete line was received.
     #[instrument(skip(self))]
-    async fn read_command(&mut self) -> Result<Command> {
-        let mut cmd_end = 0;
-        loop {
-            if let Some(i) = self.recv_buffer[cmd_end..].iter().position(|b| *b == b'\n') {
-                if cmd_end + i == 0 || self.recv_buffer.get(cmd_end + i - 1) != Some(&b'\r') {
+    fn read_command(&mut self) -> Result<Option<Command>> {
+        let cmd_end = match self.recv_buffer.iter().position(|b| *b == b'\n') {
+            Some(i) => {
+                if i == 0 || self.recv_buffer[i - 1] != b'\r' {
                     return Err(Error::Handshake("Invalid line ending in handshake".into()));
                 }
-                cmd_end += i + 1;
 
-                break;
-            } else {
-                cmd_end = self.recv_buffer.len();
-            }
-
-            let mut buf = [0; 64];
-            let res = poll_fn(|cx| self.socket.poll_recvmsg(cx, &mut buf)).await?;
-            let read = {
-                #[cfg(unix)]
-                {
-                    let (read, fds) = res;
-                    if !fds.is_empty() {
-                        return Err(Error::Handshake("Unexpected FDs during handshake".into()));
-                    }
-                    read
-                }
-                #[cfg(not(unix))]
-                {
-                    res
-                }
-            };
-            if read == 0 {
-                return Err(Error::Handshake("Unexpected EOF during handshake".into()));
+                i + 1
             }
-            self.recv_buffer.extend(&buf[..read]);
-        }
+            None => return Ok(None),
+        };
 
         let line_bytes = self.recv_buffer.drain(..cmd_end);
         let line = std::str::from_utf8(line_bytes.as_slice())
             .map_err(|e| Error::Handshake(e.to_string()))?;
 
-        line.parse()
+        line.parse().map(Some)
     }
 
     fn mechanism(&self) -> Result<&AuthMechanism> {
@@ -997,6 +1252,20 @@ impl<S: Socket> HandshakeCommon<S> {
             .front()
             .ok_or_else(|| Error::Handshake("Exhausted available AUTH mechanisms".into()))
     }
+
+    fn outcome(&mut self) -> Result<HandshakeOutcome> {
+        // The GUID is only taken once, so advancing a finished handshake again is an error.
+        let server_guid = self
+            .server_guid
+            .take()
+            .ok_or_else(|| Error::Handshake("Handshake already finished".into()))?;
+
+        Ok(HandshakeOutcome {
+            server_guid,
+            cap_unix_fd: self.cap_unix_fd,
+            remaining: mem::take(&mut self.recv_buffer),
+        })
+    }
 }
 
 #[cfg(unix)]
@@ -1038,25 +1307,28 @@ mod tests {
         (p0, p1)
     }
 
-    #[test]
-    fn handshake() {
-        let (p0, p1) = create_async_socket_pair();
-
-        let client = ClientHandshake::new(p0, None);
-        let server = ServerHandshake::new(
-            p1,
+    fn server<S: Socket + Unpin>(
+        socket: S,
+        mechanisms: Option<VecDeque<AuthMechanism>>,
+    ) -> impl std::future::Future<Output = Result<Authenticated<S>>> {
+        Authenticated::server(
+            socket,
             Guid::generate(),
             Some(Uid::effective().into()),
-            None,
+            mechanisms,
             None,
             CookieContext::default(),
         )
-        .unwrap();
+    }
+
+    #[test]
+    fn handshake() {
+        let (p0, p1) = create_async_socket_pair();
 
         // proceed to the handshakes
         let (client, server) = crate::utils::block_on(join(
-            async move { client.perform().await.unwrap() },
-            async move { server.perform().await.unwrap() },
+            async move { Authenticated::client(p0, None, None).await.unwrap() },
+            async move { server(p1, None).await.unwrap() },
         ));
 
         assert_eq!(client.server_guid, server.server_guid);
@@ -1067,15 +1339,6 @@ mod tests {
     #[timeout(15000)]
     fn pipelined_handshake() {
         let (mut p0, p1) = create_async_socket_pair();
-        let server = ServerHandshake::new(
-            p1,
-            Guid::generate(),
-            Some(Uid::effective().into()),
-            None,
-            None,
-            CookieContext::default(),
-        )
-        .unwrap();
 
         crate::utils::block_on(
             p0.write_all(
@@ -1087,7 +1350,7 @@ mod tests {
             ),
         )
         .unwrap();
-        let server = crate::utils::block_on(server.perform()).unwrap();
+        let server = crate::utils::block_on(server(p1, None)).unwrap();
 
         assert!(server.cap_unix_fd);
     }
@@ -1096,15 +1359,6 @@ mod tests {
     #[timeout(15000)]
     fn separate_external_data() {
         let (mut p0, p1) = create_async_socket_pair();
-        let server = ServerHandshake::new(
-            p1,
-            Guid::generate(),
-            Some(Uid::effective().into()),
-            None,
-            None,
-            CookieContext::default(),
-        )
-        .unwrap();
 
         crate::utils::block_on(
             p0.write_all(
@@ -1116,61 +1370,126 @@ mod tests {
             ),
         )
         .unwrap();
-        crate::utils::block_on(server.perform()).unwrap();
+        crate::utils::block_on(server(p1, None)).unwrap();
     }
 
     #[test]
     #[timeout(15000)]
     fn missing_external_data() {
         let (mut p0, p1) = create_async_socket_pair();
-        let server = ServerHandshake::new(
-            p1,
-            Guid::generate(),
-            Some(Uid::effective().into()),
-            None,
-            None,
-            CookieContext::default(),
-        )
-        .unwrap();
 
         crate::utils::block_on(p0.write_all(b"\0AUTH EXTERNAL\r\nDATA\r\nBEGIN\r\n")).unwrap();
-        crate::utils::block_on(server.perform()).unwrap();
+        crate::utils::block_on(server(p1, None)).unwrap();
     }
 
     #[test]
     #[timeout(15000)]
     fn anonymous_handshake() {
         let (mut p0, p1) = create_async_socket_pair();
-        let server = ServerHandshake::new(
-            p1,
-            Guid::generate(),
-            Some(Uid::effective().into()),
-            Some(vec![AuthMechanism::Anonymous].into()),
-            None,
-            CookieContext::default(),
-        )
-        .unwrap();
 
         crate::utils::block_on(p0.write_all(b"\0AUTH ANONYMOUS abcd\r\nBEGIN\r\n")).unwrap();
-        crate::utils::block_on(server.perform()).unwrap();
+        crate::utils::block_on(server(p1, Some(vec![AuthMechanism::Anonymous].into()))).unwrap();
     }
 
     #[test]
     #[timeout(15000)]
     fn separate_anonymous_data() {
         let (mut p0, p1) = create_async_socket_pair();
-        let server = ServerHandshake::new(
-            p1,
+
+        crate::utils::block_on(p0.write_all(b"\0AUTH ANONYMOUS\r\nDATA abcd\r\nBEGIN\r\n"))
+            .unwrap();
+        crate::utils::block_on(server(p1, Some(vec![AuthMechanism::Anonymous].into()))).unwrap();
+    }
+
+    // Run a client and server handshake against each other in memory, feeding each side what the
+    // other sends one byte at a time.
+    fn run_sans_io(
+        client: &mut ClientHandshake,
+        server: &mut ServerHandshake<'_>,
+    ) -> Result<(HandshakeOutcome, HandshakeOutcome)> {
+        let mut to_server = vec![];
+        let mut to_client = vec![];
+        let (mut client_outcome, mut server_outcome) = (None, None);
+        while client_outcome.is_none() || server_outcome.is_none() {
+            if client_outcome.is_none() {
+                let input = match to_client.is_empty() {
+                    true => HandshakeInput::None,
+                    false => HandshakeInput::Received(&to_client[..1]),
+                };
+                let consumed = matches!(input, HandshakeInput::Received(_));
+                match client.advance(input)? {
+                    HandshakeOutput::Send(data) => to_server.extend(data),
+                    HandshakeOutput::NeedInput => (),
+                    HandshakeOutput::Done(outcome) => client_outcome = Some(outcome),
+                    output => panic!("Unexpected client output: {:?}", output),
+                }
+                if consumed {
+                    to_client.remove(0);
+                }
+            }
+
+            if server_outcome.is_none() {
+                let input = match to_server.is_empty() {
+                    true => HandshakeInput::None,
+                    false => HandshakeInput::Received(&to_server[..1]),
+                };
+                let consumed = matches!(input, HandshakeInput::Received(_));
+                match server.advance(input)? {
+                    HandshakeOutput::Send(data) => to_client.extend(data),
+                    HandshakeOutput::NeedInput => (),
+                    HandshakeOutput::Done(outcome) => server_outcome = Some(outcome),
+                    output => panic!("Unexpected server output: {:?}", output),
+                }
+                if consumed {
+                    to_server.remove(0);
+                }
+            }
+        }
+
+        Ok((client_outcome.unwrap(), server_outcome.unwrap()))
+    }
+
+    fn sans_io_server<'s>(client_uid: u32) -> ServerHandshake<'s> {
+        ServerHandshake::new(
             Guid::generate(),
-            Some(Uid::effective().into()),
-            Some(vec![AuthMechanism::Anonymous].into()),
+            Some(client_uid),
+            None,
             None,
             CookieContext::default(),
         )
-        .unwrap();
+        .unwrap()
+    }
 
-        crate::utils::block_on(p0.write_all(b"\0AUTH ANONYMOUS\r\nDATA abcd\r\nBEGIN\r\n"))
-            .unwrap();
-        crate::utils::block_on(server.perform()).unwrap();
+    #[test]
+    fn sans_io_handshake() {
+        let mut client = ClientHandshake::new(None, true);
+        let mut server = sans_io_server(Uid::effective().into());
+
+        let (client_outcome, server_outcome) = run_sans_io(&mut client, &mut server).unwrap();
+        assert_eq!(client_outcome.server_guid(), server_outcome.server_guid());
+        assert!(client_outcome.cap_unix_fd());
+        assert!(server_outcome.cap_unix_fd());
+        assert!(client_outcome.remaining().is_empty());
+        assert!(server_outcome.remaining().is_empty());
+
+        // A finished handshake can't be advanced any further.
+        client.advance(HandshakeInput::None).unwrap_err();
+        server.advance(HandshakeInput::None).unwrap_err();
+    }
+
+    #[test]
+    fn external_id_override() {
+        let uid = u32::from(Uid::effective()).wrapping_add(1);
+        let mechanisms = Some(vec![AuthMechanism::External].into());
+
+        // Without the override, the server rejects us.
+        let mut client = ClientHandshake::new(mechanisms.clone(), false);
+        let mut server = sans_io_server(uid);
+        run_sans_io(&mut client, &mut server).unwrap_err();
+
+        let mut client = ClientHandshake::new(mechanisms, false);
+        client.set_external_id(uid.to_string());
+        let mut server = sans_io_server(uid);
+        run_sans_io(&mut client, &mut server).unwrap();
     }
 }
@@ -0,0 +1,127 @@
+use std::{fmt::Debug, sync::Arc};
+
+use crate::{Error, Message, MessageFlags, MessageType, Result};
+
+/// A hook observing or rewriting the messages going through a [`Connection`].
+///
+/// Interceptors are registered with [`ConnectionBuilder::interceptor`] and run on the I/O path of
+/// the connection, for all the messages it sends and receives. This includes the traffic of
+/// proxies, the [`ObjectServer`] and the connection itself (e.g the bus `Hello`). Both methods
+/// are no-ops by default, so you only need to implement the direction you care about.
+///
+/// The interceptors of a connection run one after the other in the order they were registered,
+/// each getting the message returned by the previous one. Since they run for every single message,
+/// they should be quick and must not block.
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use zbus::{
+/// #     block_on, ConnectionBuilder, Error, Message, MessageInterceptor, MessageType, Result,
+/// # };
+/// #
+/// #[derive(Debug)]
+/// struct Policy;
+///
+/// impl MessageInterceptor for Policy {
+///     fn outgoing(&self, msg: Arc<Message>) -> Result<Option<Arc<Message>>> {
+///         if msg.member().map(|m| m == "PowerOff").unwrap_or(false) {
+///             return Err(Error::Unsupported);
+///         }
+///
+///         Ok(Some(msg))
+///     }
+///
+///     fn incoming(&self, msg: Arc<Message>) -> Option<Arc<Message>> {
+///         let noisy = msg.message_type() == MessageType::Signal
+///             && msg.interface().map(|i| i == "org.example.Noisy").unwrap_or(false);
+///
+///         (!noisy).then_some(msg)
+///     }
+/// }
+///
+/// # block_on(async {
+/// let conn = ConnectionBuilder::session()?
+///     .interceptor(Policy)
+///     .build()
+///     .await?;
+///
+/// // Do something useful with `conn`..
+/// # drop(conn);
+/// # Ok::<_, Error>(())
+/// # }).unwrap();
+/// ```
+///
+/// # Rewriting messages
+///
+/// To rewrite a message, build a new one with the [`MessageBuilder`] created from its header.
+/// This keeps the serial number of the original, which is required for the reply to an outgoing
+/// method call to be matched with it. The [receive position] of incoming messages is always
+/// preserved.
+///
+/// [`Connection`]: crate::Connection
+/// [`ConnectionBuilder::interceptor`]: crate::ConnectionBuilder::interceptor
+/// [`ObjectServer`]: crate::ObjectServer
+/// [`MessageBuilder`]: crate::MessageBuilder
+/// [receive position]: Message::recv_position
+pub trait MessageInterceptor: Debug + Send + Sync + 'static {
+    /// Called for each message about to be queued for sending.
+    ///
+    /// Return `Ok(None)` to silently drop the message. An error is returned to the sender instead
+    /// of sending the message (e.g. by [`Connection::send_message`]).
+    ///
+    /// Method calls expecting a reply can't be dropped silently, since the caller would wait for
+    /// the reply forever. Dropping one results in [`Error::Failure`] for the sender.
+    ///
+    /// [`Connection::send_message`]: crate::Connection::send_message
+    fn outgoing(&self, msg: Arc<Message>) -> Result<Option<Arc<Message>>> {
+        Ok(Some(msg))
+    }
+
+    /// Called for each message received, before it's dispatched to any stream.
+    ///
+    /// Return `None` to drop the message, as if it never arrived.
+    fn incoming(&self, msg: Arc<Message>) -> Option<Arc<Message>> {
+        Some(msg)
+    }
+}
+
+/// Run the outgoing hooks of `interceptors` on `msg`, in order.
+pub(crate) fn intercept_outgoing(
+    interceptors: &[Arc<dyn MessageInterceptor>],
+    msg: Arc<Message>,
+) -> Result<Option<Arc<Message>>> {
+    let expects_reply = msg.message_type() == MessageType::MethodCall
+        && !msg.flags().contains(MessageFlags::NoReplyExpected);
+    let msg = interceptors
+        .iter()
+        .try_fold(Some(msg), |msg, interceptor| match msg {
+            Some(msg) => interceptor.outgoing(msg),
+            None => Ok(None),
+        })?;
+    if msg.is_none() && expects_reply {
+        return Err(Error::Failure(
+            "method call expecting a reply dropped by an interceptor".into(),
+        ));
+    }
+
+    Ok(msg)
+}
+
+/// Run the incoming hooks of `interceptors` on `msg`, in order.
+pub(crate) fn intercept_incoming(
+    interceptors: &[Arc<dyn MessageInterceptor>],
+    msg: Arc<Message>,
+) -> Option<Arc<Message>> {
+    let recv_seq = msg.recv_seq;
+    let mut msg = interceptors
+        .iter()
+        .try_fold(msg, |msg, interceptor| interceptor.incoming(msg))?;
+    // Rewritten messages must keep their position in the stream of received messages.
+    if msg.recv_seq != recv_seq {
+        Arc::make_mut(&mut msg).recv_seq = recv_seq;
+    }
+
+    Some(msg)
+}
@@ -62,18 +62,38 @@ pub use message_field::*;
 mod message_fields;
 pub use message_fields::*;
 
+mod message_parser;
+pub use message_parser::*;
+
+mod interceptor;
+pub use interceptor::*;
+
+mod access_policy;
+pub use access_policy::*;
+
+mod dispatch_stats;
+pub use dispatch_stats::*;
+
 mod handshake;
-pub use handshake::AuthMechanism;
 pub(crate) use handshake::*;
+pub use handshake::{
+    AuthMechanism, ClientHandshake, CookieContext, Handshake, HandshakeInput, HandshakeOutcome,
+    HandshakeOutput, ServerHandshake,
+};
 
 mod connection;
 pub use connection::*;
 mod connection_builder;
 pub use connection_builder::*;
+mod server;
+pub use server::*;
 mod message_stream;
 pub use message_stream::*;
 mod object_server;
 pub use object_server::*;
+mod forwarder;
+mod object_factory;
+pub use object_factory::FactoryPolicy;
 mod proxy;
 pub use proxy::*;
 mod proxy_builder;
@@ -107,6 +127,11 @@ pub mod xml;
 #[cfg(feature = "quick-xml")]
 pub mod quick_xml;
 
+#[cfg(feature = "quick-xml")]
+mod tree_walker;
+#[cfg(feature = "quick-xml")]
+pub use tree_walker::*;
+
 pub use zbus_macros::{dbus_interface, dbus_proxy, DBusError};
 
 // Required for the macros to function within this crate.
@@ -278,6 +303,188 @@ mod tests {
         f.metadata().unwrap();
     }
 
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn fdpass_p2p() {
+        block_on(test_fdpass_p2p()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_fdpass_p2p() -> Result<()> {
+        use std::{
+            io::{Read, Write},
+            mem::ManuallyDrop,
+            os::unix::io::IntoRawFd,
+        };
+
+        use crate::{fdo, utils::unix_p2p_pipe_with};
+        use futures_util::StreamExt;
+        use zvariant::OwnedFd;
+
+        #[derive(Default)]
+        struct FdService {
+            log: Option<OwnedFd>,
+        }
+
+        #[crate::dbus_interface(name = "org.zbus.FdPasser")]
+        impl FdService {
+            fn write_to(&self, fd: Fd, data: &str) -> fdo::Result<()> {
+                // The message owns the FD so we mustn't close it.
+                let file = ManuallyDrop::new(unsafe { File::from_raw_fd(fd.as_raw_fd()) });
+
+                (&*file)
+                    .write_all(data.as_bytes())
+                    .map_err(|e| fdo::Error::IOError(e.to_string()))
+            }
+
+            fn pipe_with(&self, data: &str) -> fdo::Result<OwnedFd> {
+                let (read, write) =
+                    nix::unistd::pipe().map_err(|e| fdo::Error::IOError(e.to_string()))?;
+                let mut write = unsafe { File::from_raw_fd(write) };
+                write
+                    .write_all(data.as_bytes())
+                    .map_err(|e| fdo::Error::IOError(e.to_string()))?;
+
+                Ok(unsafe { OwnedFd::from_raw_fd(read) })
+            }
+
+            fn pipe_with_len(&self, data: &str) -> fdo::Result<(OwnedFd, u32)> {
+                let fd = self.pipe_with(data)?;
+
+                Ok((fd, data.len() as u32))
+            }
+
+            // The service keeps the ownership of the FD, hence getters return a `Fd`.
+            #[dbus_interface(property)]
+            fn log(&self) -> fdo::Result<Fd> {
+                self.log
+                    .as_ref()
+                    .map(|fd| fd.as_raw_fd().into())
+                    .ok_or_else(|| fdo::Error::Failed("no log".into()))
+            }
+
+            #[dbus_interface(property)]
+            fn set_log(&mut self, fd: OwnedFd) {
+                self.log = Some(fd);
+            }
+        }
+
+        #[crate::dbus_proxy(
+            interface = "org.zbus.FdPasser",
+            default_service = "org.zbus.FdPasser",
+            default_path = "/org/zbus/FdPasser",
+            gen_blocking = false
+        )]
+        trait FdPasser {
+            fn write_to(&self, fd: Fd, data: &str) -> Result<()>;
+
+            fn pipe_with(&self, data: &str) -> Result<OwnedFd>;
+
+            #[dbus_proxy(name = "WriteTo")]
+            fn write_owned_to(&self, fd: &OwnedFd, data: &str) -> Result<()>;
+
+            fn pipe_with_len(&self, data: &str) -> Result<(OwnedFd, u32)>;
+
+            #[dbus_proxy(signal)]
+            fn piped(&self, fd: OwnedFd) -> Result<()>;
+        }
+
+        let (client, server) = unix_p2p_pipe_with(Ok, |server| {
+            server.serve_at("/org/zbus/FdPasser", FdService::default())
+        })
+        .await?;
+        let proxy = FdPasserProxy::new(&client).await?;
+
+        // FD in a method call.
+        let (read, write) = nix::unistd::pipe().unwrap();
+        let mut read = unsafe { File::from_raw_fd(read) };
+        let write = unsafe { OwnedFd::from_raw_fd(write) };
+        proxy
+            .write_to(write.as_raw_fd().into(), "to service")
+            .await?;
+        drop(write);
+        let mut received = String::new();
+        read.read_to_string(&mut received).unwrap();
+        assert_eq!(received, "to service");
+
+        // FD in a method reply.
+        let fd = proxy.pipe_with("from service").await?;
+        let mut read = unsafe { File::from_raw_fd(fd.into_raw_fd()) };
+        let mut received = String::new();
+        read.read_to_string(&mut received).unwrap();
+        assert_eq!(received, "from service");
+
+        // Owned FDs work just as well, in arguments and in composite return values.
+        let (read, write) = nix::unistd::pipe().unwrap();
+        let mut read = unsafe { File::from_raw_fd(read) };
+        let write = unsafe { OwnedFd::from_raw_fd(write) };
+        proxy.write_owned_to(&write, "owned").await?;
+        drop(write);
+        let mut received = String::new();
+        read.read_to_string(&mut received).unwrap();
+        assert_eq!(received, "owned");
+
+        let (fd, len) = proxy.pipe_with_len("with len").await?;
+        let mut read = unsafe { File::from_raw_fd(fd.into_raw_fd()) };
+        let mut received = String::new();
+        read.read_to_string(&mut received).unwrap();
+        assert_eq!(received.len() as u32, len);
+
+        // FD as a property value.
+        let (read, write) = nix::unistd::pipe().unwrap();
+        let mut read = unsafe { File::from_raw_fd(read) };
+        let write = unsafe { OwnedFd::from_raw_fd(write) };
+        proxy
+            .inner()
+            .set_property("Log", Fd::from(write.as_raw_fd()))
+            .await?;
+        drop(write);
+        let reply = client
+            .call_method(
+                None::<()>,
+                "/org/zbus/FdPasser",
+                Some("org.freedesktop.DBus.Properties"),
+                "Get",
+                &("org.zbus.FdPasser", "Log"),
+            )
+            .await?;
+        // The reply owns the FD so we duplicate it.
+        let log = OwnedFd::try_from(&reply.body::<zvariant::Value<'_>>()?)?;
+        drop(reply);
+        let mut log = unsafe { File::from_raw_fd(log.into_raw_fd()) };
+        log.write_all(b"logged").unwrap();
+        drop(log);
+        let mut received = [0; 6];
+        read.read_exact(&mut received).unwrap();
+        assert_eq!(&received, b"logged");
+
+        // FD in a signal.
+        let mut piped = proxy.receive_piped().await?;
+        let (read, write) = nix::unistd::pipe().unwrap();
+        let read = unsafe { OwnedFd::from_raw_fd(read) };
+        let mut write = unsafe { File::from_raw_fd(write) };
+        server
+            .emit_signal(
+                None::<()>,
+                "/org/zbus/FdPasser",
+                "org.zbus.FdPasser",
+                "Piped",
+                &(&read,),
+            )
+            .await?;
+        drop(read);
+        let signal = piped.next().await.unwrap();
+        let mut read = unsafe { File::from_raw_fd(signal.args()?.fd.into_raw_fd()) };
+        write.write_all(b"through signal").unwrap();
+        drop(write);
+        let mut received = String::new();
+        read.read_to_string(&mut received).unwrap();
+        assert_eq!(received, "through signal");
+
+        Ok(())
+    }
+
     #[test]
     #[instrument]
     #[timeout(15000)]
@@ -849,6 +1056,1888 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[timeout(15000)]
+    fn async_method_dispatch() {
+        block_on(test_async_method_dispatch()).unwrap();
+    }
+
+    async fn test_async_method_dispatch() -> Result<()> {
+        use std::sync::atomic::{AtomicBool, Ordering::SeqCst};
+
+        #[derive(Default)]
+        struct Gate {
+            open: AtomicBool,
+            opened: event_listener::Event,
+        }
+
+        #[zbus::dbus_interface(name = "org.zbus.AsyncDispatch")]
+        impl Gate {
+            // Waits on another bus call, and then on another call to this interface.
+            async fn pass(
+                &self,
+                #[zbus(connection)] conn: &Connection,
+            ) -> zbus::fdo::Result<String> {
+                let id = zbus::fdo::DBusProxy::new(conn).await?.get_id().await?;
+                while !self.open.load(SeqCst) {
+                    let listener = self.opened.listen();
+                    if self.open.load(SeqCst) {
+                        break;
+                    }
+                    listener.await;
+                }
+
+                Ok(id.to_string())
+            }
+
+            fn open(&self) {
+                self.open.store(true, SeqCst);
+                self.opened.notify(usize::MAX);
+            }
+        }
+
+        let service = crate::ConnectionBuilder::session()?
+            .serve_at("/org/zbus/AsyncDispatch", Gate::default())?
+            .build()
+            .await?;
+        let client = Connection::session().await?;
+        let proxy = zbus::Proxy::new(
+            &client,
+            service.unique_name().unwrap(),
+            "/org/zbus/AsyncDispatch",
+            "org.zbus.AsyncDispatch",
+        )
+        .await?;
+
+        // `Pass` can only complete once `Open` is dispatched while it's still pending.
+        let (id, ()) = futures_util::try_join!(
+            proxy.call::<_, _, String>("Pass", &()),
+            proxy.call("Open", &()),
+        )?;
+        let bus_id = zbus::fdo::DBusProxy::new(&client).await?.get_id().await?;
+        assert_eq!(id, bus_id.to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn write_only_property() {
+        block_on(test_write_only_property()).unwrap();
+    }
+
+    async fn test_write_only_property() -> Result<()> {
+        use futures_util::StreamExt;
+
+        #[derive(Default)]
+        struct Secrets {
+            password: String,
+            hint: String,
+        }
+
+        #[zbus::dbus_interface(name = "org.zbus.Secrets")]
+        impl Secrets {
+            #[dbus_interface(property)]
+            fn set_password(&mut self, password: &str) {
+                self.password = password.to_string();
+            }
+
+            #[dbus_interface(property)]
+            fn hint(&self) -> String {
+                self.hint.clone()
+            }
+
+            #[dbus_interface(property)]
+            fn set_hint(&mut self, hint: String) {
+                self.hint = hint;
+            }
+        }
+
+        let service = crate::ConnectionBuilder::session()?
+            .serve_at("/org/zbus/Secrets", Secrets::default())?
+            .build()
+            .await?;
+        let client = Connection::session().await?;
+        let properties = crate::fdo::PropertiesProxy::builder(&client)
+            .destination(service.unique_name().unwrap().to_owned())?
+            .path("/org/zbus/Secrets")?
+            .build()
+            .await?;
+        let mut changes = properties.receive_properties_changed().await?;
+        let iface = zbus_names::InterfaceName::try_from("org.zbus.Secrets")?;
+
+        properties
+            .set(iface.as_ref(), "Password", &"hunter2".into())
+            .await?;
+        properties
+            .set(iface.as_ref(), "Hint", &"hunter".into())
+            .await?;
+        let iface_ref = service
+            .object_server()
+            .interface::<_, Secrets>("/org/zbus/Secrets")
+            .await?;
+        assert_eq!(iface_ref.get().await.password, "hunter2");
+        properties
+            .get(iface.as_ref(), "Password")
+            .await
+            .unwrap_err();
+
+        // Only the change of the readable property is signaled.
+        let signal = changes.next().await.unwrap();
+        let args = signal.args()?;
+        let changed: Vec<_> = args.changed_properties().keys().collect();
+        assert_eq!(changed, [&"Hint"]);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn served_name() {
+        block_on(test_served_name()).unwrap();
+    }
+
+    async fn test_served_name() -> Result<()> {
+        struct Dummy;
+
+        #[zbus::dbus_interface(name = "org.zbus.Dummy")]
+        impl Dummy {
+            fn ping(&self) {}
+        }
+
+        let name = "org.zbus.ServedName";
+        let conn = crate::ConnectionBuilder::session()?
+            .serve_at("/org/zbus/Dummy/a", Dummy)?
+            .serve_at("/org/zbus/Dummy/b", Dummy)?
+            .serve_name(name)?
+            .build()
+            .await?;
+        let dbus = crate::fdo::DBusProxy::new(&conn).await?;
+        let has_owner = || async { dbus.name_has_owner(name.try_into().unwrap()).await };
+        assert!(has_owner().await?);
+
+        // The name is kept as long as there's something to serve.
+        let server = conn.object_server();
+        server.remove::<Dummy, _>("/org/zbus/Dummy/a").await?;
+        assert!(has_owner().await?);
+        server.remove_object("/org/zbus/Dummy/b").await?;
+        assert!(!has_owner().await?);
+
+        server.at("/org/zbus/Dummy/a", Dummy).await?;
+        server.request_name(name).await?;
+        assert!(has_owner().await?);
+        assert!(server.release_name(name).await?);
+        assert!(!server.release_name(name).await?);
+        assert!(!has_owner().await?);
+
+        // A name both requested and served is requested once, and released with the objects.
+        let conn = crate::ConnectionBuilder::session()?
+            .serve_at("/org/zbus/Dummy", Dummy)?
+            .name(name)?
+            .serve_name(name)?
+            .build()
+            .await?;
+        assert!(has_owner().await?);
+        conn.object_server()
+            .remove::<Dummy, _>("/org/zbus/Dummy")
+            .await?;
+        assert!(!has_owner().await?);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn fallback() {
+        block_on(test_fallback()).unwrap();
+    }
+
+    async fn test_fallback() -> Result<()> {
+        struct Device;
+
+        #[zbus::dbus_interface(name = "org.zbus.Device")]
+        impl Device {
+            fn name(
+                &self,
+                #[zbus(header)] hdr: &crate::MessageHeader<'_>,
+            ) -> crate::fdo::Result<String> {
+                let path = hdr.path()?.ok_or(crate::Error::MissingField)?;
+
+                Ok(path.rsplit('/').next().unwrap().to_string())
+            }
+
+            #[dbus_interface(property)]
+            fn kind(&self) -> &str {
+                "disk"
+            }
+        }
+
+        struct Special;
+
+        #[zbus::dbus_interface(name = "org.zbus.Device")]
+        impl Special {
+            fn name(&self) -> String {
+                "special".to_string()
+            }
+        }
+
+        let service = crate::ConnectionBuilder::session()?
+            .serve_at("/org/zbus/Devices/special", Special)?
+            .build()
+            .await?;
+        let server = service.object_server();
+        assert!(server.at_fallback("/org/zbus/Devices", Device).await?);
+        assert!(!server.at_fallback("/org/zbus/Devices", Device).await?);
+
+        let client = crate::Connection::session().await?;
+        let proxy = |path| {
+            crate::ProxyBuilder::<'_, crate::Proxy<'_>>::new_bare(&client)
+                .destination(service.unique_name().unwrap().to_owned())
+                .unwrap()
+                .path(path)
+                .unwrap()
+                .interface("org.zbus.Device")
+                .unwrap()
+                .cache_properties(crate::CacheProperties::No)
+                .build()
+        };
+
+        // Virtual objects, and the registered object overriding the fallback.
+        let sda = proxy("/org/zbus/Devices/sda").await?;
+        let name: String = sda.call("Name", &()).await?;
+        assert_eq!(name, "sda");
+        let kind: String = sda.get_property("Kind").await?;
+        assert_eq!(kind, "disk");
+        let name: String = proxy("/org/zbus/Devices/sdb/part1")
+            .await?
+            .call("Name", &())
+            .await?;
+        assert_eq!(name, "part1");
+        let special = proxy("/org/zbus/Devices/special").await?;
+        let name: String = special.call("Name", &()).await?;
+        assert_eq!(name, "special");
+
+        let xml = crate::fdo::IntrospectableProxy::builder(&client)
+            .destination(service.unique_name().unwrap().to_owned())?
+            .path("/org/zbus/Devices/sda")?
+            .build()
+            .await?
+            .introspect()
+            .await?;
+        for iface in ["org.freedesktop.DBus.Properties", "org.zbus.Device"] {
+            assert!(xml.contains(&format!("<interface name=\"{iface}\">")));
+        }
+
+        // Objects outside of the subtree are still unknown.
+        let err = proxy("/org/zbus/Disks/sda")
+            .await?
+            .call::<_, _, String>("Name", &())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            crate::Error::MethodError(ref name, _, _)
+                if name.as_str() == "org.freedesktop.DBus.Error.UnknownObject"
+        ));
+
+        assert!(
+            server
+                .remove_fallback::<Device, _>("/org/zbus/Devices")
+                .await?
+        );
+        assert!(
+            !server
+                .remove_fallback::<Device, _>("/org/zbus/Devices")
+                .await?
+        );
+        assert!(sda.call::<_, _, String>("Name", &()).await.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn object_factory() {
+        block_on(test_object_factory()).unwrap();
+    }
+
+    async fn test_object_factory() -> Result<()> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct Item {
+            id: u32,
+            calls: u32,
+        }
+
+        #[zbus::dbus_interface(name = "org.zbus.Item")]
+        impl Item {
+            fn call(&mut self) -> (u32, u32) {
+                self.calls += 1;
+
+                (self.id, self.calls)
+            }
+        }
+
+        let created = Arc::new(AtomicUsize::new(0));
+        let factory = |created: Arc<AtomicUsize>| {
+            move |path: &zvariant::ObjectPath<'_>, params: &HashMap<&str, &str>| {
+                let id = params["id"]
+                    .parse()
+                    .map_err(|_| crate::fdo::Error::UnknownObject(format!("No '{}'", path)))?;
+                created.fetch_add(1, Ordering::SeqCst);
+
+                Ok(Item { id, calls: 0 })
+            }
+        };
+
+        let service = crate::Connection::session().await?;
+        let server = service.object_server();
+        assert!(
+            server
+                .at_factory(
+                    "/org/zbus/Cached/{id}",
+                    crate::FactoryPolicy::Cache,
+                    factory(created.clone()),
+                )
+                .await?
+        );
+        assert!(
+            !server
+                .at_factory(
+                    "/org/zbus/Cached/{id}",
+                    crate::FactoryPolicy::Cache,
+                    factory(created.clone()),
+                )
+                .await?
+        );
+        assert!(
+            server
+                .at_factory(
+                    "/org/zbus/Discarded/{id}/item",
+                    crate::FactoryPolicy::Discard,
+                    factory(created.clone()),
+                )
+                .await?
+        );
+        assert!(server
+            .at_factory(
+                "/org/zbus/{id",
+                crate::FactoryPolicy::Cache,
+                factory(created.clone())
+            )
+            .await
+            .is_err());
+
+        let client = crate::Connection::session().await?;
+        let call = |path: &'static str| {
+            let client = &client;
+            let service = &service;
+
+            async move {
+                client
+                    .call_method(
+                        service.unique_name(),
+                        path,
+                        Some("org.zbus.Item"),
+                        "Call",
+                        &(),
+                    )
+                    .await?
+                    .body::<(u32, u32)>()
+            }
+        };
+
+        // Cached objects are only created once.
+        assert_eq!(call("/org/zbus/Cached/7").await?, (7, 1));
+        assert_eq!(call("/org/zbus/Cached/7").await?, (7, 2));
+        assert_eq!(call("/org/zbus/Cached/8").await?, (8, 1));
+        assert_eq!(created.load(Ordering::SeqCst), 2);
+        server.interface::<_, Item>("/org/zbus/Cached/7").await?;
+
+        // Discarded ones are created for each call, and dropped right after the reply is sent.
+        let path = "/org/zbus/Discarded/3/item";
+        for count in 3..=4 {
+            assert_eq!(call(path).await?, (3, 1));
+            assert_eq!(created.load(Ordering::SeqCst), count);
+            while server.interface::<_, Item>(path).await.is_ok() {
+                crate::abstractions::timeout::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        }
+
+        // Errors of the factory, and paths not matching the template.
+        for path in [
+            "/org/zbus/Cached/seven",
+            "/org/zbus/Cached/7/item",
+            "/org/zbus/Discarded/3",
+        ] {
+            let err = call(path).await.unwrap_err();
+            assert!(matches!(
+                err,
+                crate::Error::MethodError(ref name, _, _)
+                    if name.as_str() == "org.freedesktop.DBus.Error.UnknownObject"
+            ));
+        }
+
+        assert!(
+            server
+                .remove_factory::<Item>("/org/zbus/Cached/{id}")
+                .await?
+        );
+        assert!(
+            !server
+                .remove_factory::<Item>("/org/zbus/Cached/{id}")
+                .await?
+        );
+        assert_eq!(call("/org/zbus/Cached/7").await?, (7, 3));
+        assert!(call("/org/zbus/Cached/9").await.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn forward() {
+        block_on(test_forward()).unwrap();
+    }
+
+    async fn test_forward() -> Result<()> {
+        struct Backend {
+            count: u32,
+        }
+
+        #[zbus::dbus_interface(name = "org.zbus.Backend")]
+        impl Backend {
+            fn greet(&self, name: &str, times: u8) -> crate::fdo::Result<(String, u8)> {
+                if name.is_empty() {
+                    return Err(crate::fdo::Error::InvalidArgs("no name".into()));
+                }
+
+                Ok((format!("Hello {name}!"), times))
+            }
+
+            #[dbus_interface(property)]
+            fn count(&self) -> u32 {
+                self.count
+            }
+
+            #[dbus_interface(property)]
+            fn set_count(&mut self, count: u32) {
+                self.count = count;
+            }
+        }
+
+        let backend = crate::ConnectionBuilder::session()?
+            .serve_at("/org/zbus/Backend", Backend { count: 1 })?
+            .build()
+            .await?;
+        let gateway = crate::Connection::session().await?;
+        let remote = crate::ProxyBuilder::new_bare(&gateway)
+            .destination(backend.unique_name().unwrap().to_owned())?
+            .path("/org/zbus/Backend")?
+            .interface("org.zbus.Backend")?
+            .cache_properties(crate::CacheProperties::No)
+            .build()
+            .await?;
+        let server = gateway.object_server();
+        assert!(server.forward("/org/zbus/Gateway", remote).await?);
+
+        let client = crate::Connection::session().await?;
+        let proxy: crate::Proxy<'_> = crate::ProxyBuilder::new_bare(&client)
+            .destination(gateway.unique_name().unwrap().to_owned())?
+            .path("/org/zbus/Gateway")?
+            .interface("org.zbus.Backend")?
+            .cache_properties(crate::CacheProperties::No)
+            .build()
+            .await?;
+
+        let reply: (String, u8) = proxy.call("Greet", &("zbus", 3u8)).await?;
+        assert_eq!(reply, ("Hello zbus!".to_string(), 3));
+        let err = proxy
+            .call::<_, _, (String, u8)>("Greet", &("", 3u8))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            crate::Error::MethodError(ref name, Some(ref desc), _)
+                if name.as_str() == "org.freedesktop.DBus.Error.InvalidArgs" && desc == "no name"
+        ));
+
+        assert_eq!(proxy.get_property::<u32>("Count").await?, 1);
+        proxy.set_property("Count", 5u32).await?;
+        assert_eq!(proxy.get_property::<u32>("Count").await?, 5);
+        let backend_iface = backend
+            .object_server()
+            .interface::<_, Backend>("/org/zbus/Backend")
+            .await?;
+        assert_eq!(backend_iface.get().await.count, 5);
+
+        let xml = proxy.introspect().await?;
+        assert!(xml.contains(r#"<method name="Greet">"#));
+
+        // Forwarded interfaces are not served locally.
+        let remote = crate::ProxyBuilder::new_bare(&gateway)
+            .destination(backend.unique_name().unwrap().to_owned())?
+            .path("/org/zbus/Backend")?
+            .interface("org.zbus.Backend")?
+            .cache_properties(crate::CacheProperties::No)
+            .build()
+            .await?;
+        assert!(!server.forward("/org/zbus/Gateway", remote).await?);
+        assert!(!server.at("/org/zbus/Gateway", Backend { count: 0 }).await?);
+        assert!(matches!(
+            server.remove::<Backend, _>("/org/zbus/Gateway").await,
+            Err(crate::Error::InterfaceNotFound)
+        ));
+        assert_eq!(proxy.get_property::<u32>("Count").await?, 5);
+
+        assert!(
+            server
+                .remove_forward("/org/zbus/Gateway", "org.zbus.Backend")
+                .await?
+        );
+        assert!(proxy
+            .call::<_, _, (String, u8)>("Greet", &("zbus", 3u8))
+            .await
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn unknown_call_errors() {
+        block_on(test_unknown_call_errors()).unwrap();
+    }
+
+    async fn test_unknown_call_errors() -> Result<()> {
+        struct Greeter;
+
+        #[zbus::dbus_interface(name = "org.zbus.Greeter")]
+        impl Greeter {
+            fn greet(&self) -> &str {
+                "Hello!"
+            }
+        }
+
+        let service = crate::ConnectionBuilder::session()?
+            .serve_at("/org/zbus/Greeter", Greeter)?
+            .build()
+            .await?;
+        let client = crate::Connection::session().await?;
+        let call = |path, iface: Option<&'static str>, method| {
+            client.call_method(
+                service.unique_name().map(|n| n.to_owned()),
+                path,
+                iface,
+                method,
+                &(),
+            )
+        };
+        let error = |res: Result<Arc<crate::Message>>| match res.unwrap_err() {
+            crate::Error::MethodError(name, desc, _) => (name.to_string(), desc.unwrap()),
+            e => panic!("unexpected error: {}", e),
+        };
+
+        let greeting: String = call("/org/zbus/Greeter", Some("org.zbus.Greeter"), "Greet")
+            .await?
+            .body()?;
+        assert_eq!(greeting, "Hello!");
+        // The interface is optional in method calls.
+        let greeting: String = call("/org/zbus/Greeter", None, "Greet").await?.body()?;
+        assert_eq!(greeting, "Hello!");
+
+        assert_eq!(
+            error(call("/org/zbus/Nothing", Some("org.zbus.Greeter"), "Greet").await),
+            (
+                "org.freedesktop.DBus.Error.UnknownObject".to_string(),
+                "Unknown object '/org/zbus/Nothing'".to_string()
+            )
+        );
+        assert_eq!(
+            error(call("/org/zbus/Greeter", Some("org.zbus.Nothing"), "Greet").await),
+            (
+                "org.freedesktop.DBus.Error.UnknownInterface".to_string(),
+                "Unknown interface 'org.zbus.Nothing' at '/org/zbus/Greeter'".to_string()
+            )
+        );
+        assert_eq!(
+            error(call("/org/zbus/Greeter", Some("org.zbus.Greeter"), "Wave").await),
+            (
+                "org.freedesktop.DBus.Error.UnknownMethod".to_string(),
+                "Unknown method 'Wave' on interface 'org.zbus.Greeter'".to_string()
+            )
+        );
+        assert_eq!(
+            error(call("/org/zbus/Greeter", None, "Wave").await),
+            (
+                "org.freedesktop.DBus.Error.UnknownMethod".to_string(),
+                "Unknown method 'Wave' at '/org/zbus/Greeter'".to_string()
+            )
+        );
+
+        let properties = crate::fdo::PropertiesProxy::builder(&client)
+            .destination(service.unique_name().unwrap().to_owned())?
+            .path("/org/zbus/Greeter")?
+            .build()
+            .await?;
+        let iface = crate::names::InterfaceName::from_static_str_unchecked("org.zbus.Greeter");
+        assert_eq!(
+            properties.get(iface, "Mood").await.unwrap_err(),
+            crate::fdo::Error::UnknownProperty(
+                "Unknown property 'Mood' on interface 'org.zbus.Greeter'".to_string()
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn struct_return() {
+        block_on(test_struct_return()).unwrap();
+    }
+
+    async fn test_struct_return() -> Result<()> {
+        struct Versions;
+
+        #[zbus::dbus_interface(name = "org.zbus.Versions")]
+        impl Versions {
+            fn split(&self) -> (u32, String) {
+                (3, "beta".into())
+            }
+
+            #[dbus_interface(struct_return)]
+            fn single(&self) -> (u32, String) {
+                (3, "beta".into())
+            }
+
+            #[dbus_interface(struct_return)]
+            async fn fallible(&self) -> crate::fdo::Result<(u32, String)> {
+                Ok((3, "beta".into()))
+            }
+        }
+
+        let service = crate::ConnectionBuilder::session()?
+            .serve_at("/org/zbus/Versions", Versions)?
+            .build()
+            .await?;
+        let client = crate::Connection::session().await?;
+        for (method, signature) in [("Split", "us"), ("Single", "(us)"), ("Fallible", "(us)")] {
+            let reply = client
+                .call_method(
+                    service.unique_name().map(|n| n.to_owned()),
+                    "/org/zbus/Versions",
+                    Some("org.zbus.Versions"),
+                    method,
+                    &(),
+                )
+                .await?;
+            assert_eq!(reply.body_signature()?, signature);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn structured_error_reply() {
+        block_on(test_structured_error_reply()).unwrap();
+    }
+
+    async fn test_structured_error_reply() -> Result<()> {
+        use crate::{Error, MessageStream, MessageType};
+        use futures_util::StreamExt;
+
+        let service = crate::Connection::session().await?;
+        let mut calls = MessageStream::from(&service);
+        let client = crate::Connection::session().await?;
+        let serve = async {
+            let mut replied = 0;
+            while replied < 2 {
+                let call = calls.next().await.unwrap()?;
+                if call.message_type() != MessageType::MethodCall
+                    || call.interface().map(|i| i == "org.zbus.Shop") != Some(true)
+                {
+                    continue;
+                }
+                let name = "org.zbus.Shop.Error.OutOfStock";
+                if call.member().unwrap() == "Buy" {
+                    // With a description first.
+                    service
+                        .reply_error(&call, name, &("No apples left", 3u32))
+                        .await?;
+                } else {
+                    service.reply_error(&call, name, &(3u32, "days")).await?;
+                }
+                replied += 1;
+            }
+
+            Ok::<_, Error>(())
+        };
+        let call = |method| {
+            client.call_method(
+                service.unique_name(),
+                "/org/zbus/Shop",
+                Some("org.zbus.Shop"),
+                method,
+                &(),
+            )
+        };
+        let calls = async { (call("Buy").await, call("Restock").await) };
+        let (served, (bought, restocked)) = futures_util::join!(serve, calls);
+        served?;
+
+        match bought {
+            Err(Error::MethodError(name, description, reply)) => {
+                assert_eq!(name.as_str(), "org.zbus.Shop.Error.OutOfStock");
+                assert_eq!(description.as_deref(), Some("No apples left"));
+                assert_eq!(reply.body::<(&str, u32)>()?, ("No apples left", 3));
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+        // Without a description, the details are only in the reply.
+        match restocked {
+            Err(Error::MethodError(_, description, reply)) => {
+                assert_eq!(description, None);
+                assert_eq!(reply.body::<(u32, &str)>()?, (3, "days"));
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn big_endian_messages() {
+        block_on(test_big_endian_messages()).unwrap();
+    }
+
+    // Over a p2p connection, since the bus would convert the messages to its own byte order.
+    #[cfg(unix)]
+    async fn test_big_endian_messages() -> Result<()> {
+        use crate::{
+            utils::unix_p2p_pipe_with, EndianSig, MatchRule, MessageBuilder, MessageStream,
+        };
+        use futures_util::StreamExt;
+
+        struct Greeter;
+
+        #[zbus::dbus_interface(name = "org.zbus.BigEndian")]
+        impl Greeter {
+            fn greet(&self, name: &str, count: u32) -> String {
+                format!("{}x Hello {}!", count, name)
+            }
+        }
+
+        let (client, service) =
+            unix_p2p_pipe_with(Ok, |server| server.serve_at("/org/zbus/BigEndian", Greeter))
+                .await?;
+
+        // Method calls are dispatched to the interface, whatever their byte order.
+        let call = MessageBuilder::method_call("/org/zbus/BigEndian", "Greet")?
+            .interface("org.zbus.BigEndian")?
+            .endian(EndianSig::Big)
+            .build(&("zbus", 0x0102_0304u32))?;
+        let reply = client.send_message_with_reply(call).await?.reply().await?;
+        assert_eq!(reply.body::<&str>()?, "16909060x Hello zbus!");
+
+        // Signals are matched on their arguments, whatever their byte order.
+        let rule = MatchRule::builder()
+            .msg_type(crate::MessageType::Signal)
+            .interface("org.zbus.BigEndian")?
+            .member("Greeted")?
+            .arg(0, "zbus")?
+            .build();
+        let mut stream = MessageStream::for_match_rule(rule, &client, None).await?;
+        for name in ["dbus", "zbus"] {
+            let signal =
+                MessageBuilder::signal("/org/zbus/BigEndian", "org.zbus.BigEndian", "Greeted")?
+                    .endian(EndianSig::Big)
+                    .build(&(name, 0x0102_0304u32))?;
+            service.send_message(signal).await?;
+        }
+        let signal = stream.next().await.unwrap()?;
+        assert_eq!(signal.primary_header().endian_sig(), EndianSig::Big);
+        assert_eq!(signal.body::<(&str, u32)>()?, ("zbus", 0x0102_0304));
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn access_policy() {
+        block_on(test_access_policy()).unwrap();
+    }
+
+    async fn test_access_policy() -> Result<()> {
+        #[derive(Debug)]
+        struct NoReset;
+
+        #[async_trait::async_trait]
+        impl crate::AccessPolicy for NoReset {
+            async fn check(
+                &self,
+                _conn: &crate::Connection,
+                msg: &crate::Message,
+                interface: &crate::names::InterfaceName<'_>,
+            ) -> crate::fdo::Result<()> {
+                if *interface != "org.zbus.Counter" {
+                    return Ok(());
+                }
+                match msg.member() {
+                    Some(member) if member == "Reset" || member == "Set" => Err(
+                        crate::fdo::Error::AccessDenied("resetting isn't allowed".into()),
+                    ),
+                    _ => Ok(()),
+                }
+            }
+        }
+
+        struct Counter(u32);
+
+        #[zbus::dbus_interface(name = "org.zbus.Counter")]
+        impl Counter {
+            fn increment(&mut self) -> u32 {
+                self.0 += 1;
+                self.0
+            }
+
+            fn reset(&mut self) {
+                self.0 = 0;
+            }
+
+            #[dbus_interface(property)]
+            fn count(&self) -> u32 {
+                self.0
+            }
+
+            #[dbus_interface(property)]
+            fn set_count(&mut self, count: u32) {
+                self.0 = count;
+            }
+        }
+
+        let service = crate::ConnectionBuilder::session()?
+            .serve_at("/org/zbus/Counter", Counter(0))?
+            .access_policy(NoReset)
+            .build()
+            .await?;
+        let client = crate::Connection::session().await?;
+        let call = |method| {
+            client.call_method(
+                service.unique_name().map(|n| n.to_owned()),
+                "/org/zbus/Counter",
+                Some("org.zbus.Counter"),
+                method,
+                &(),
+            )
+        };
+
+        let count: u32 = call("Increment").await?.body()?;
+        assert_eq!(count, 1);
+        let err = call("Reset").await.unwrap_err();
+        assert!(matches!(
+            err,
+            crate::Error::MethodError(ref name, _, _)
+                if name.as_str() == "org.freedesktop.DBus.Error.AccessDenied"
+        ));
+        let count: u32 = call("Increment").await?.body()?;
+        assert_eq!(count, 2);
+
+        // The policy is told which interface the call resolves to.
+        let is_denied = |res: Result<Arc<crate::Message>>| {
+            matches!(
+                res,
+                Err(crate::Error::MethodError(ref name, _, _))
+                    if name.as_str() == "org.freedesktop.DBus.Error.AccessDenied"
+            )
+        };
+        let reset = client
+            .call_method(
+                service.unique_name().map(|n| n.to_owned()),
+                "/org/zbus/Counter",
+                None::<()>,
+                "Reset",
+                &(),
+            )
+            .await;
+        assert!(is_denied(reset));
+        let proxy = crate::Proxy::new(
+            &client,
+            service.unique_name().unwrap().to_owned(),
+            "/org/zbus/Counter",
+            "org.zbus.Counter",
+        )
+        .await?;
+        assert_eq!(proxy.get_property::<u32>("Count").await?, 2);
+        let set = proxy
+            .properties_proxy()
+            .call_method(
+                "Set",
+                &("org.zbus.Counter", "Count", zvariant::Value::from(0u32)),
+            )
+            .await;
+        assert!(is_denied(set));
+        assert_eq!(call("Increment").await?.body::<u32>()?, 3);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn caller_credentials() {
+        block_on(test_caller_credentials()).unwrap();
+    }
+
+    async fn test_caller_credentials() -> Result<()> {
+        struct Whoami;
+
+        #[zbus::dbus_interface(name = "org.zbus.Whoami")]
+        impl Whoami {
+            async fn process_id(
+                &self,
+                #[zbus(connection)] conn: &Connection,
+                #[zbus(header)] hdr: crate::MessageHeader<'_>,
+            ) -> crate::fdo::Result<u32> {
+                let credentials = conn.caller_credentials(&hdr).await?;
+
+                credentials
+                    .process_id()
+                    .ok_or_else(|| crate::fdo::Error::Failed("no process ID".into()))
+            }
+        }
+
+        let service = crate::ConnectionBuilder::session()?
+            .serve_at("/org/zbus/Whoami", Whoami)?
+            .build()
+            .await?;
+        let client = crate::Connection::session().await?;
+        let pid: u32 = client
+            .call_method(
+                service.unique_name(),
+                "/org/zbus/Whoami",
+                Some("org.zbus.Whoami"),
+                "ProcessId",
+                &(),
+            )
+            .await?
+            .body()?;
+        assert_eq!(pid, std::process::id());
+
+        // The same, through the typed methods of the bus proxy.
+        let proxy = crate::fdo::DBusProxy::new(&client).await?;
+        let name = client.unique_name().unwrap().clone();
+        let credentials = proxy
+            .get_connection_credentials(name.clone().into())
+            .await?;
+        assert_eq!(credentials.process_id(), Some(pid));
+        let pid = proxy
+            .get_connection_unix_process_id(name.clone().into())
+            .await?;
+        assert_eq!(pid, std::process::id());
+        #[cfg(unix)]
+        {
+            let uid = proxy.get_connection_unix_user(name.into()).await?;
+            assert_eq!(credentials.unix_user_id(), Some(uid));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn bus_capabilities() {
+        block_on(test_bus_capabilities()).unwrap();
+    }
+
+    async fn test_bus_capabilities() -> Result<()> {
+        let conn = crate::Connection::session().await?;
+        let proxy = crate::fdo::DBusProxy::new(&conn).await?;
+
+        let features = proxy.features().await?;
+        assert!(features.iter().all(|f| !f.is_empty()));
+        let interfaces = proxy.interfaces().await?;
+        // Only the optional interfaces are listed.
+        assert!(!interfaces
+            .iter()
+            .any(|i| i.as_str() == "org.freedesktop.DBus"));
+        if interfaces
+            .iter()
+            .any(|i| i.as_str() == "org.freedesktop.DBus.Monitoring")
+        {
+            crate::fdo::MonitoringProxy::new(&conn).await?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn bus_stats() {
+        block_on(test_bus_stats()).unwrap();
+    }
+
+    async fn test_bus_stats() -> Result<()> {
+        let conn = crate::Connection::session().await?;
+        let interfaces = crate::fdo::DBusProxy::new(&conn)
+            .await?
+            .interfaces()
+            .await?;
+        if !interfaces
+            .iter()
+            .any(|i| i.as_str() == "org.freedesktop.DBus.Debug.Stats")
+        {
+            // The bus wasn't built with statistics support.
+            return Ok(());
+        }
+        let proxy = crate::fdo::StatsProxy::new(&conn).await?;
+        let rule = crate::MatchRule::builder()
+            .msg_type(crate::MessageType::Signal)
+            .interface("org.zbus.Stats")?
+            .build();
+        conn.add_match(rule).await?;
+
+        let stats = proxy.get_stats().await?;
+        assert!(stats.active_connections().unwrap() >= 1);
+        assert!(stats.match_rules().unwrap() >= 1);
+
+        let name = conn.unique_name().unwrap();
+        let stats = proxy.get_connection_stats(name.into()).await?;
+        assert_eq!(stats.unique_name().unwrap().as_str(), name.as_str());
+        assert!(stats.match_rules().unwrap() >= 1);
+        assert!(stats.bus_names().unwrap() >= 1);
+
+        let rules = proxy.get_all_match_rules().await?;
+        let (_, rules) = rules.iter().find(|(n, _)| *n == name).unwrap();
+        assert!(rules.iter().any(|r| r.contains("org.zbus.Stats")));
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn peer_on_any_path() {
+        block_on(test_peer_on_any_path()).unwrap();
+    }
+
+    async fn test_peer_on_any_path() -> Result<()> {
+        struct Nothing;
+
+        #[zbus::dbus_interface(name = "org.zbus.Nothing")]
+        impl Nothing {}
+
+        let service = crate::ConnectionBuilder::session()?
+            .serve_at("/org/zbus/Nothing", Nothing)?
+            .build()
+            .await?;
+        let client = crate::Connection::session().await?;
+        let peer = crate::fdo::PeerProxy::builder(&client)
+            .destination(service.unique_name().unwrap())?
+            .path("/org/zbus/Nowhere")?
+            .build()
+            .await?;
+        peer.ping().await?;
+        let id = peer.get_machine_id().await?;
+        assert_eq!(id.len(), 32);
+        assert_eq!(peer.get_machine_id().await?, id);
+
+        // Only the Peer interface is there.
+        let introspectable = crate::fdo::IntrospectableProxy::builder(&client)
+            .destination(service.unique_name().unwrap())?
+            .path("/org/zbus/Nowhere")?
+            .build()
+            .await?;
+        assert!(matches!(
+            introspectable.introspect().await,
+            Err(crate::fdo::Error::UnknownObject(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn properties_proxy() {
+        block_on(test_properties_proxy()).unwrap();
+    }
+
+    async fn test_properties_proxy() -> Result<()> {
+        use futures_util::StreamExt;
+
+        struct Thermostat(u32);
+
+        #[zbus::dbus_interface(name = "org.zbus.Thermostat")]
+        impl Thermostat {
+            #[dbus_interface(property)]
+            fn target(&self) -> u32 {
+                self.0
+            }
+
+            #[dbus_interface(property)]
+            fn set_target(&mut self, target: u32) {
+                self.0 = target;
+            }
+        }
+
+        let service = crate::ConnectionBuilder::session()?
+            .serve_at("/org/zbus/Thermostat", Thermostat(20))?
+            .build()
+            .await?;
+        let client = crate::Connection::session().await?;
+        let properties = crate::fdo::PropertiesProxy::builder(&client)
+            .destination(service.unique_name().unwrap())?
+            .path("/org/zbus/Thermostat")?
+            .build()
+            .await?;
+        let interface = crate::names::InterfaceName::from_static_str("org.zbus.Thermostat")?;
+        let mut changes = properties.receive_properties_changed().await?;
+
+        let target: u32 = properties.get_as(interface.clone(), "Target").await?;
+        assert_eq!(target, 20);
+        properties
+            .set_value(interface.clone(), "Target", 22u32)
+            .await?;
+        let target: u32 = properties.get_as(interface.clone(), "Target").await?;
+        assert_eq!(target, 22);
+        assert!(properties
+            .get_as::<String>(interface.clone(), "Target")
+            .await
+            .is_err());
+
+        let change = changes.next().await.unwrap();
+        let args = change.args()?;
+        assert_eq!(args.interface_name(), &interface);
+        assert_eq!(
+            args.changed_properties().get("Target"),
+            Some(&zvariant::Value::from(22u32))
+        );
+        assert!(args.invalidated_properties().is_empty());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "quick-xml")]
+    #[test]
+    #[timeout(15000)]
+    fn tree_walker() {
+        block_on(test_tree_walker()).unwrap();
+    }
+
+    #[cfg(feature = "quick-xml")]
+    async fn test_tree_walker() -> Result<()> {
+        use futures_util::StreamExt;
+
+        struct Leaf;
+
+        #[zbus::dbus_interface(name = "org.zbus.Leaf")]
+        impl Leaf {}
+
+        let service = crate::ConnectionBuilder::session()?
+            .serve_at("/org/zbus/Tree/A", Leaf)?
+            .serve_at("/org/zbus/Tree/A/B", Leaf)?
+            .serve_at("/org/zbus/Tree/C/D/E", Leaf)?
+            .build()
+            .await?;
+        let client = crate::Connection::session().await?;
+        let walker = crate::TreeWalker::new(&client, service.unique_name().unwrap(), "/org/zbus")?
+            .max_concurrent(2);
+
+        let mut paths: Vec<_> = walker
+            .clone()
+            .stream()
+            .map(|res| res.map(|(path, _)| path.to_string()))
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<_>>()?;
+        paths.sort();
+        assert_eq!(
+            paths,
+            [
+                "/org/zbus",
+                "/org/zbus/Tree",
+                "/org/zbus/Tree/A",
+                "/org/zbus/Tree/A/B",
+                "/org/zbus/Tree/C",
+                "/org/zbus/Tree/C/D",
+                "/org/zbus/Tree/C/D/E",
+            ]
+        );
+
+        let tree = walker.max_depth(2).tree().await?;
+        let tree_node = &tree.nodes()[0];
+        assert_eq!(tree_node.name(), Some("Tree"));
+        let a = tree_node
+            .nodes()
+            .iter()
+            .find(|n| n.name() == Some("A"))
+            .unwrap();
+        assert!(a.interfaces().iter().any(|i| i.name() == "org.zbus.Leaf"));
+        // `B` is beyond the maximum depth, so it's only known by name.
+        let b = &a.nodes()[0];
+        assert_eq!(b.name(), Some("B"));
+        assert!(b.interfaces().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn weak_signal_context() {
+        block_on(test_weak_signal_context()).unwrap();
+    }
+
+    async fn test_weak_signal_context() -> Result<()> {
+        struct Emitter {
+            ctxt: crate::WeakSignalContext<'static>,
+        }
+
+        #[zbus::dbus_interface(name = "org.zbus.Emitter")]
+        impl Emitter {
+            async fn emit(&self) -> crate::fdo::Result<()> {
+                let ctxt = self
+                    .ctxt
+                    .upgrade()
+                    .ok_or_else(|| crate::fdo::Error::Failed("connection gone".into()))?;
+                Self::emitted(&ctxt).await?;
+
+                Ok(())
+            }
+
+            #[dbus_interface(signal)]
+            async fn emitted(ctxt: &SignalContext<'_>) -> Result<()>;
+        }
+
+        let service = crate::Connection::session().await?;
+        let ctxt = SignalContext::new(&service, "/org/zbus/Emitter")?;
+        let emitter = Emitter {
+            ctxt: ctxt.downgrade(),
+        };
+        drop(ctxt);
+        service
+            .object_server()
+            .at("/org/zbus/Emitter", emitter)
+            .await?;
+
+        let client = crate::Connection::session().await?;
+        client
+            .call_method(
+                service.unique_name(),
+                "/org/zbus/Emitter",
+                Some("org.zbus.Emitter"),
+                "Emit",
+                &(),
+            )
+            .await?;
+
+        // The interface doesn't keep its connection alive, once the (background) dispatching of
+        // the call is over.
+        let weak = service.downgrade();
+        assert!(weak.upgrade().is_some());
+        drop(service);
+        while weak.upgrade().is_some() {
+            crate::abstractions::timeout::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn custom_error() {
+        block_on(test_custom_error()).unwrap();
+    }
+
+    async fn test_custom_error() -> Result<()> {
+        #[derive(Debug)]
+        enum ShopError {
+            OutOfStock(String),
+            Closed,
+        }
+
+        // Implemented by hand, relying on the default reply.
+        impl crate::DBusError for ShopError {
+            fn name(&self) -> crate::names::ErrorName<'_> {
+                crate::names::ErrorName::from_static_str_unchecked(match self {
+                    ShopError::OutOfStock(_) => "org.zbus.Shop.Error.OutOfStock",
+                    ShopError::Closed => "org.zbus.Shop.Error.Closed",
+                })
+            }
+
+            fn description(&self) -> Option<&str> {
+                match self {
+                    ShopError::OutOfStock(item) => Some(item),
+                    ShopError::Closed => None,
+                }
+            }
+        }
+
+        struct Shop;
+
+        #[zbus::dbus_interface(name = "org.zbus.Shop")]
+        impl Shop {
+            fn buy(&self, item: &str) -> std::result::Result<u32, ShopError> {
+                match item {
+                    "bread" => Ok(2),
+                    "milk" => Err(ShopError::OutOfStock(item.to_string())),
+                    _ => Err(ShopError::Closed),
+                }
+            }
+        }
+
+        let service = crate::ConnectionBuilder::session()?
+            .serve_at("/org/zbus/Shop", Shop)?
+            .build()
+            .await?;
+        let client = crate::Connection::session().await?;
+        let buy = |item: &'static str| {
+            let client = &client;
+            let service = &service;
+
+            async move {
+                client
+                    .call_method(
+                        service.unique_name(),
+                        "/org/zbus/Shop",
+                        Some("org.zbus.Shop"),
+                        "Buy",
+                        &item,
+                    )
+                    .await
+            }
+        };
+
+        let price: u32 = buy("bread").await?.body()?;
+        assert_eq!(price, 2);
+        match buy("milk").await.unwrap_err() {
+            crate::Error::MethodError(name, description, _) => {
+                assert_eq!(name.as_str(), "org.zbus.Shop.Error.OutOfStock");
+                assert_eq!(description.as_deref(), Some("milk"));
+            }
+            e => panic!("unexpected error: {}", e),
+        }
+        match buy("caviar").await.unwrap_err() {
+            crate::Error::MethodError(name, description, _) => {
+                assert_eq!(name.as_str(), "org.zbus.Shop.Error.Closed");
+                assert_eq!(description, None);
+            }
+            e => panic!("unexpected error: {}", e),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn dispatch_stats() {
+        block_on(test_dispatch_stats()).unwrap();
+    }
+
+    async fn test_dispatch_stats() -> Result<()> {
+        use crate::names::{InterfaceName, MemberName};
+        use futures_util::TryStreamExt;
+
+        #[derive(Debug)]
+        struct Observer(Mutex<std::sync::mpsc::Sender<(Option<String>, Option<String>)>>);
+
+        impl crate::DispatchObserver for Observer {
+            fn dispatched(&self, _msg: &crate::Message, outcome: &crate::DispatchOutcome<'_>) {
+                let interface = outcome.interface().map(|i| i.to_string());
+                let error = outcome.error().map(|e| e.to_string());
+                self.0.lock().unwrap().send((interface, error)).unwrap();
+            }
+        }
+
+        struct Worker;
+
+        #[zbus::dbus_interface(name = "org.zbus.Worker")]
+        impl Worker {
+            fn work(&self) {}
+
+            fn fail(&self) -> crate::fdo::Result<()> {
+                Err(crate::fdo::Error::Failed("no luck".into()))
+            }
+
+            async fn slow(&self) {
+                crate::abstractions::timeout::sleep(std::time::Duration::from_millis(500)).await;
+            }
+        }
+
+        let (tx, rx) = channel();
+        let service = crate::ConnectionBuilder::session()?
+            .serve_at("/org/zbus/Worker", Worker)?
+            .dispatch_observer(Observer(Mutex::new(tx)))
+            .build()
+            .await?;
+        let client = crate::Connection::session().await?;
+        let call = |method| {
+            client.call_method(
+                service.unique_name().map(|n| n.to_owned()),
+                "/org/zbus/Worker",
+                Some("org.zbus.Worker"),
+                method,
+                &(),
+            )
+        };
+
+        // The observer is notified once the call is accounted for, right after the reply is sent.
+        let worker = Some("org.zbus.Worker".to_string());
+        call("Work").await?;
+        assert_eq!(rx.recv().unwrap(), (worker.clone(), None));
+        call("Work").await?;
+        assert_eq!(rx.recv().unwrap(), (worker.clone(), None));
+        // An error replied to a call being dispatched, but from outside the object server, isn't
+        // a failure of the dispatch.
+        let mut stream = crate::MessageStream::from(&service);
+        let reply_busy = async {
+            while let Some(msg) = stream.try_next().await? {
+                if msg.member().map_or(false, |m| m == "Slow") {
+                    service
+                        .reply_error(&msg, "org.zbus.Worker.Error.Busy", &())
+                        .await?;
+                    break;
+                }
+            }
+
+            Ok::<_, crate::Error>(())
+        };
+        let (slow, busy) = futures_util::future::join(call("Slow"), reply_busy).await;
+        busy?;
+        slow.unwrap_err();
+        assert_eq!(rx.recv().unwrap(), (worker.clone(), None));
+        drop(stream);
+        call("Fail").await.unwrap_err();
+        let failed = Some("org.freedesktop.DBus.Error.Failed".to_string());
+        assert_eq!(rx.recv().unwrap(), (worker, failed));
+        call("Idle").await.unwrap_err();
+        let unknown = Some("org.freedesktop.DBus.Error.UnknownMethod".to_string());
+        assert_eq!(rx.recv().unwrap(), (None, unknown.clone()));
+        // Failures are accounted for even when no error is replied.
+        client
+            .call_method_with_flags(
+                service.unique_name().map(|n| n.to_owned()),
+                "/org/zbus/Worker",
+                Some("org.zbus.Worker"),
+                "Idle",
+                crate::MethodFlags::NoReplyExpected.into(),
+                &(),
+            )
+            .await?;
+        assert_eq!(rx.recv().unwrap(), (None, unknown));
+
+        let stats = service.object_server().dispatch_stats();
+        assert_eq!(stats.calls(), 6);
+        assert_eq!(stats.errors(), 3);
+        assert_eq!(stats.in_flight(), 0);
+        let worker = InterfaceName::from_static_str("org.zbus.Worker")?;
+        let work = stats
+            .member(&worker, &MemberName::from_static_str("Work")?)
+            .unwrap();
+        assert_eq!((work.calls(), work.errors()), (2, 0));
+        assert!(work.max_time() <= work.total_time());
+        let fail = stats
+            .member(&worker, &MemberName::from_static_str("Fail")?)
+            .unwrap();
+        assert_eq!((fail.calls(), fail.errors()), (1, 1));
+        // Unknown members aren't accounted for individually.
+        assert_eq!(stats.members().count(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn fallible_property() {
+        block_on(test_fallible_property()).unwrap();
+    }
+
+    async fn test_fallible_property() -> Result<()> {
+        use futures_util::StreamExt;
+
+        struct Dimmer {
+            level: u8,
+        }
+
+        #[zbus::dbus_interface(name = "org.zbus.Dimmer")]
+        impl Dimmer {
+            #[dbus_interface(property)]
+            fn level(&self) -> u8 {
+                self.level
+            }
+
+            #[dbus_interface(property)]
+            fn set_level(&mut self, level: u8) -> crate::fdo::Result<()> {
+                if level > 10 {
+                    return Err(crate::fdo::Error::InvalidArgs(format!(
+                        "level {level} out of range"
+                    )));
+                }
+                self.level = level;
+
+                Ok(())
+            }
+
+            #[dbus_interface(property)]
+            async fn temperature(&self) -> Result<u8> {
+                Err(crate::Error::Unsupported)
+            }
+
+            #[dbus_interface(property)]
+            async fn set_temperature(&self, _temperature: u8) -> crate::fdo::Result<()> {
+                Err(crate::fdo::Error::NotSupported("no thermostat".into()))
+            }
+        }
+
+        let service = crate::ConnectionBuilder::session()?
+            .serve_at("/org/zbus/Dimmer", Dimmer { level: 5 })?
+            .build()
+            .await?;
+        let client = Connection::session().await?;
+        let properties = crate::fdo::PropertiesProxy::builder(&client)
+            .destination(service.unique_name().unwrap().to_owned())?
+            .path("/org/zbus/Dimmer")?
+            .build()
+            .await?;
+        let mut changes = properties.receive_properties_changed().await?;
+        let iface = zbus_names::InterfaceName::try_from("org.zbus.Dimmer")?;
+
+        // Rejected values result in an error reply and no change.
+        let err = properties
+            .set(iface.as_ref(), "Level", &42u8.into())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, crate::fdo::Error::InvalidArgs(_)), "{}", err);
+        properties.set(iface.as_ref(), "Level", &7u8.into()).await?;
+        let signal = changes.next().await.unwrap();
+        let args = signal.args()?;
+        assert_eq!(
+            args.changed_properties().get("Level"),
+            Some(&zvariant::Value::from(7u8))
+        );
+
+        let err = properties
+            .set(iface.as_ref(), "Temperature", &20u8.into())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, crate::fdo::Error::NotSupported(_)), "{}", err);
+
+        // Failing getters result in an error reply too, and are skipped by `GetAll`.
+        properties
+            .get(iface.as_ref(), "Temperature")
+            .await
+            .unwrap_err();
+        let all = properties.get_all(iface.as_ref()).await?;
+        assert_eq!(all.len(), 1);
+        assert_eq!(u8::try_from(&all["Level"])?, 7);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn header_arg() {
+        block_on(test_header_arg()).unwrap();
+    }
+
+    async fn test_header_arg() -> Result<()> {
+        #[derive(Default)]
+        struct Counter {
+            calls: HashMap<String, u32>,
+        }
+
+        #[zbus::dbus_interface(name = "org.zbus.Counter")]
+        impl Counter {
+            // Counts the calls per caller.
+            fn count(
+                &mut self,
+                #[zbus(header)] hdr: crate::MessageHeader<'_>,
+            ) -> crate::fdo::Result<u32> {
+                let sender = hdr
+                    .sender()?
+                    .ok_or_else(|| crate::fdo::Error::Failed("no sender".into()))?;
+                let calls = self.calls.entry(sender.to_string()).or_default();
+                *calls += 1;
+
+                Ok(*calls)
+            }
+
+            fn whoami(
+                &self,
+                #[zbus(header)] hdr: &crate::MessageHeader<'_>,
+            ) -> crate::fdo::Result<(String, String, bool)> {
+                let sender = hdr.sender()?.map(ToString::to_string).unwrap_or_default();
+                let path = hdr.path()?.map(ToString::to_string).unwrap_or_default();
+                let no_auto_start = hdr.primary().flags().contains(MessageFlags::NoAutoStart);
+
+                Ok((sender, path, no_auto_start))
+            }
+        }
+
+        let service = crate::ConnectionBuilder::session()?
+            .serve_at("/org/zbus/Counter", Counter::default())?
+            .build()
+            .await?;
+        let service_name = service.unique_name().unwrap().to_owned();
+        let mut clients = vec![];
+        for _ in 0..2 {
+            let client = Connection::session().await?;
+            let proxy: crate::Proxy<'_> = crate::ProxyBuilder::new_bare(&client)
+                .destination(service_name.clone())?
+                .path("/org/zbus/Counter")?
+                .interface("org.zbus.Counter")?
+                .build()
+                .await?;
+            clients.push((client, proxy));
+        }
+
+        let (first, first_proxy) = &clients[0];
+        let (_, second_proxy) = &clients[1];
+        assert_eq!(first_proxy.call::<_, _, u32>("Count", &()).await?, 1);
+        assert_eq!(first_proxy.call::<_, _, u32>("Count", &()).await?, 2);
+        assert_eq!(second_proxy.call::<_, _, u32>("Count", &()).await?, 1);
+
+        let (sender, path, no_auto_start): (String, String, bool) =
+            first_proxy.call("Whoami", &()).await?;
+        assert_eq!(sender, first.unique_name().unwrap().as_str());
+        assert_eq!(path, "/org/zbus/Counter");
+        assert!(!no_auto_start);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn property_emits_changed_signal() {
+        block_on(test_property_emits_changed_signal()).unwrap();
+    }
+
+    async fn test_property_emits_changed_signal() -> Result<()> {
+        use futures_util::StreamExt;
+
+        #[derive(Default)]
+        struct Settings {
+            mode: u32,
+            level: u32,
+            volume: u32,
+        }
+
+        #[zbus::dbus_interface(name = "org.zbus.Settings")]
+        impl Settings {
+            #[dbus_interface(property(emits_changed_signal = "false"))]
+            fn mode(&self) -> u32 {
+                self.mode
+            }
+
+            #[dbus_interface(property)]
+            fn set_mode(&mut self, mode: u32) {
+                self.mode = mode;
+            }
+
+            #[dbus_interface(property(emits_changed_signal = "invalidates"))]
+            fn level(&self) -> u32 {
+                self.level
+            }
+
+            #[dbus_interface(property)]
+            fn set_level(&mut self, level: u32) {
+                self.level = level;
+            }
+
+            #[dbus_interface(property)]
+            fn volume(&self) -> u32 {
+                self.volume
+            }
+
+            #[dbus_interface(property)]
+            fn set_volume(&mut self, volume: u32) {
+                self.volume = volume;
+            }
+        }
+
+        let service = crate::ConnectionBuilder::session()?
+            .serve_at("/org/zbus/Settings", Settings::default())?
+            .build()
+            .await?;
+        let client = Connection::session().await?;
+        let properties = crate::fdo::PropertiesProxy::builder(&client)
+            .destination(service.unique_name().unwrap().to_owned())?
+            .path("/org/zbus/Settings")?
+            .build()
+            .await?;
+        let mut changes = properties.receive_properties_changed().await?;
+        let iface = zbus_names::InterfaceName::try_from("org.zbus.Settings")?;
+
+        properties.set(iface.as_ref(), "Mode", &1u32.into()).await?;
+        properties
+            .set(iface.as_ref(), "Level", &2u32.into())
+            .await?;
+        properties
+            .set(iface.as_ref(), "Volume", &3u32.into())
+            .await?;
+
+        // No signal for "Mode", only invalidation for "Level".
+        let signal = changes.next().await.unwrap();
+        let args = signal.args()?;
+        assert!(args.changed_properties().is_empty());
+        assert_eq!(args.invalidated_properties(), &["Level"]);
+        let signal = changes.next().await.unwrap();
+        let args = signal.args()?;
+        let changed: Vec<_> = args.changed_properties().keys().collect();
+        assert_eq!(changed, [&"Volume"]);
+        assert!(args.invalidated_properties().is_empty());
+
+        let xml = crate::fdo::IntrospectableProxy::builder(&client)
+            .destination(service.unique_name().unwrap().to_owned())?
+            .path("/org/zbus/Settings")?
+            .build()
+            .await?
+            .introspect()
+            .await?;
+        assert!(xml.contains(
+            r#"<annotation name="org.freedesktop.DBus.Property.EmitsChangedSignal" value="false"/>"#
+        ));
+        assert!(xml.contains(
+            r#"<annotation name="org.freedesktop.DBus.Property.EmitsChangedSignal" value="invalidates"/>"#
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn signal_context_arg() {
+        block_on(test_signal_context_arg()).unwrap();
+    }
+
+    async fn test_signal_context_arg() -> Result<()> {
+        use futures_util::StreamExt;
+
+        struct Bell;
+
+        #[zbus::dbus_interface(name = "org.zbus.Bell")]
+        impl Bell {
+            async fn ring(
+                &self,
+                #[zbus(signal_context)] ctxt: &SignalContext<'_>,
+                times: u32,
+            ) -> zbus::fdo::Result<()> {
+                for i in 0..times {
+                    Self::rang(ctxt, i).await?;
+                }
+
+                Ok(())
+            }
+
+            async fn ring_once(&self, #[zbus(signal_context)] ctxt: SignalContext<'_>) {
+                Self::rang(&ctxt, 0).await.unwrap();
+            }
+
+            #[dbus_interface(signal)]
+            async fn rang(ctxt: &SignalContext<'_>, time: u32) -> zbus::Result<()>;
+        }
+
+        let service = crate::ConnectionBuilder::session()?
+            .serve_at("/org/zbus/Bell", Bell)?
+            .build()
+            .await?;
+        let client = Connection::session().await?;
+        let proxy = zbus::Proxy::new(
+            &client,
+            service.unique_name().unwrap(),
+            "/org/zbus/Bell",
+            "org.zbus.Bell",
+        )
+        .await?;
+        let mut rang = proxy.receive_signal("Rang").await?;
+
+        proxy.call::<_, _, ()>("Ring", &(2u32)).await?;
+        proxy.call::<_, _, ()>("RingOnce", &()).await?;
+        for expected in [0u32, 1, 0] {
+            let msg = rang.next().await.unwrap();
+            assert_eq!(msg.path().unwrap(), "/org/zbus/Bell");
+            assert_eq!(msg.body::<u32>()?, expected);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn interface_ref() {
+        block_on(test_interface_ref()).unwrap();
+    }
+
+    async fn test_interface_ref() -> Result<()> {
+        use futures_util::StreamExt;
+
+        struct Thermometer(i32);
+
+        #[zbus::dbus_interface(name = "org.zbus.Thermometer")]
+        impl Thermometer {
+            #[dbus_interface(property)]
+            fn temperature(&self) -> i32 {
+                self.0
+            }
+        }
+
+        #[zbus::dbus_proxy(
+            interface = "org.zbus.Thermometer",
+            default_path = "/org/zbus/Thermometer"
+        )]
+        trait Thermometer {
+            #[dbus_proxy(property)]
+            fn temperature(&self) -> zbus::Result<i32>;
+        }
+
+        let service = crate::ConnectionBuilder::session()?
+            .serve_at("/org/zbus/Thermometer", Thermometer(20))?
+            .build()
+            .await?;
+        let client = Connection::session().await?;
+        let proxy = ThermometerProxy::builder(&client)
+            .destination(service.unique_name().unwrap().to_owned())?
+            .build()
+            .await?;
+        let mut changes = proxy.receive_temperature_changed().await;
+        assert_eq!(changes.next().await.unwrap().get().await?, 20);
+
+        // Mutate the interface from outside of its handlers and signal the change.
+        let iface_ref = service
+            .object_server()
+            .interface::<_, Thermometer>("/org/zbus/Thermometer")
+            .await?;
+        assert_eq!(iface_ref.signal_context().path(), "/org/zbus/Thermometer");
+        {
+            let mut iface = iface_ref.get_mut().await;
+            iface.0 = 25;
+            iface
+                .temperature_changed(iface_ref.signal_context())
+                .await?;
+        }
+        assert_eq!(iface_ref.get().await.0, 25);
+        assert_eq!(changes.next().await.unwrap().get().await?, 25);
+        assert_eq!(proxy.cached_temperature()?, Some(25));
+
+        // A different interface type is not found.
+        struct Other;
+
+        #[zbus::dbus_interface(name = "org.zbus.Other")]
+        impl Other {}
+
+        assert_eq!(
+            service
+                .object_server()
+                .interface::<_, Other>("/org/zbus/Thermometer")
+                .await
+                .err(),
+            Some(zbus::Error::InterfaceNotFound)
+        );
+
+        Ok(())
+    }
+
     #[test]
     #[timeout(15000)]
     fn issue_260() {
@@ -899,7 +2988,7 @@ mod tests {
             "org.zbus.Issue260",
         )
         .await?
-        .call("Whatever", &())
+        .call::<_, _, ()>("Whatever", &())
         .await?;
         Ok(())
     }
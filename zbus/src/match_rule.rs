@@ -3,7 +3,7 @@ use std::{convert::TryFrom, ops::Deref};
 
 use serde::{de, Deserialize, Serialize};
 use static_assertions::assert_impl_all;
-use zvariant::Structure;
+use zvariant::{Structure, Value};
 
 use crate::{
     names::{BusName, InterfaceName, MemberName, UniqueName},
@@ -273,7 +273,7 @@ impl<'m> MatchRule<'m> {
             match path_spec {
                 MatchRulePathSpec::Path(path) if path != &msg_path => return Ok(false),
                 MatchRulePathSpec::PathNamespace(path_ns)
-                    if !msg_path.starts_with(path_ns.as_str()) =>
+                    if !path_in_namespace(&msg_path, path_ns) =>
                 {
                     return Ok(false);
                 }
@@ -281,21 +281,8 @@ impl<'m> MatchRule<'m> {
             }
         }
 
-        // The arg0 namespace.
-        if let Some(arg0_ns) = self.arg0ns() {
-            if let Ok(arg0) = msg.body_unchecked::<BusName<'_>>() {
-                match arg0.strip_prefix(arg0_ns.as_str()) {
-                    None => return Ok(false),
-                    Some(s) if !s.is_empty() && !s.starts_with('.') => return Ok(false),
-                    _ => (),
-                }
-            } else {
-                return Ok(false);
-            }
-        }
-
         // Args
-        if self.args().is_empty() && self.arg_paths().is_empty() {
+        if self.arg0ns().is_none() && self.args().is_empty() && self.arg_paths().is_empty() {
             return Ok(true);
         }
         let structure = match msg.body::<Structure<'_>>() {
@@ -304,6 +291,18 @@ impl<'m> MatchRule<'m> {
         };
         let args = structure.fields();
 
+        // The arg0 namespace.
+        if let Some(arg0_ns) = self.arg0ns() {
+            match args.first().map(<&str>::try_from) {
+                Some(Ok(arg0)) => match arg0.strip_prefix(arg0_ns.as_str()) {
+                    None => return Ok(false),
+                    Some(s) if !s.is_empty() && !s.starts_with('.') => return Ok(false),
+                    _ => (),
+                },
+                _ => return Ok(false),
+            }
+        }
+
         for (i, arg) in self.args() {
             match args.get(*i as usize) {
                 Some(msg_arg) => match <&str>::try_from(msg_arg) {
@@ -317,13 +316,13 @@ impl<'m> MatchRule<'m> {
 
         // Path args
         for (i, path) in self.arg_paths() {
-            match args.get(*i as usize) {
-                Some(msg_arg) => match <ObjectPath<'_>>::try_from(msg_arg) {
-                    Ok(msg_arg) if *path != msg_arg => return Ok(false),
-                    Ok(_) => (),
-                    Err(_) => return Ok(false),
-                },
-                None => return Ok(false),
+            let msg_arg = match args.get(*i as usize) {
+                Some(Value::Str(s)) => s.as_str(),
+                Some(Value::ObjectPath(p)) => p.as_str(),
+                _ => return Ok(false),
+            };
+            if !arg_path_matches(path, msg_arg) {
+                return Ok(false);
             }
         }
 
@@ -331,6 +330,29 @@ impl<'m> MatchRule<'m> {
     }
 }
 
+/// Whether `path` is `namespace` itself or one of its descendants.
+fn path_in_namespace(path: &ObjectPath<'_>, namespace: &ObjectPath<'_>) -> bool {
+    let namespace = namespace.as_str();
+    if namespace == "/" {
+        return true;
+    }
+
+    match path.as_str().strip_prefix(namespace) {
+        Some(rest) => rest.is_empty() || rest.starts_with('/'),
+        None => false,
+    }
+}
+
+/// The `argNpath` semantics: either both are equal, or one of them ends with `/` and is a prefix
+/// of the other.
+fn arg_path_matches(rule_path: &ObjectPath<'_>, arg: &str) -> bool {
+    let rule_path = rule_path.as_str();
+
+    rule_path == arg
+        || (rule_path.ends_with('/') && arg.starts_with(rule_path))
+        || (arg.ends_with('/') && rule_path.starts_with(arg))
+}
+
 impl ToString for MatchRule<'_> {
     fn to_string(&self) -> String {
         let mut s = String::new();
@@ -1,5 +1,5 @@
 use std::{
-    convert::{Into, TryFrom, TryInto},
+    convert::{Into, TryInto},
     fmt,
     io::Cursor,
 };
@@ -10,6 +10,7 @@ use std::{
     sync::{Arc, RwLock},
 };
 
+use enumflags2::BitFlags;
 use static_assertions::assert_impl_all;
 use zbus_names::{BusName, ErrorName, InterfaceName, MemberName, UniqueName};
 
@@ -17,20 +18,39 @@ use zbus_names::{BusName, ErrorName, InterfaceName, MemberName, UniqueName};
 use crate::OwnedFd;
 use crate::{
     utils::padding_for_8_bytes,
-    zvariant::{DynamicType, EncodingContext, ObjectPath, Signature, Type},
-    EndianSig, Error, MessageBuilder, MessageField, MessageFieldCode, MessageFields, MessageHeader,
-    MessagePrimaryHeader, MessageType, QuickMessageFields, Result, MIN_MESSAGE_SIZE,
-    NATIVE_ENDIAN_SIG,
+    with_dbus_context,
+    zvariant::{DynamicType, ObjectPath, Signature, Type},
+    Error, MessageBuilder, MessageField, MessageFieldCode, MessageFields, MessageFlags,
+    MessageHeader, MessagePrimaryHeader, MessageType, QuickMessageFields, Result, MIN_MESSAGE_SIZE,
 };
 
 #[cfg(unix)]
 const LOCK_PANIC_MSG: &str = "lock poisoned";
 
-macro_rules! dbus_context {
-    ($n_bytes_before: expr) => {
-        EncodingContext::<byteorder::NativeEndian>::new_dbus($n_bytes_before)
-    };
+// Create a span for handling `$msg`, with the details identifying it as fields. The serial allows
+// correlating the spans of a method call and its reply, across connections.
+macro_rules! message_span {
+    ($name:literal, $msg:expr) => {{
+        let msg: &$crate::Message = $msg;
+        let header = msg.header().ok();
+        let header = header.as_ref();
+
+        tracing::debug_span!(
+            $name,
+            serial = msg.primary_header().serial_num(),
+            reply_serial = msg.reply_serial(),
+            msg_type = ?msg.message_type(),
+            sender = header.and_then(|h| h.sender().ok().flatten()).map(|s| s.as_str()),
+            destination = header
+                .and_then(|h| h.destination().ok().flatten())
+                .map(|d| d.as_str()),
+            path = msg.path().as_ref().map(|p| p.as_str()),
+            interface = msg.interface().as_ref().map(|i| i.as_str()),
+            member = msg.member().as_ref().map(|m| m.as_str()),
+        )
+    }};
 }
+pub(crate) use message_span;
 
 #[cfg(unix)]
 #[derive(Debug, Eq, PartialEq)]
@@ -66,7 +86,11 @@ impl MessageSequence {
 /// **Note**: The message owns the received FDs and will close them when dropped. You can call
 /// [`take_fds`] after deserializing to `RawFD` using [`body`] if you want to take the ownership.
 ///
+/// Messages can be in either byte order. Received messages are decoded according to their
+/// [`EndianSig`], and you can build big-endian messages with [`MessageBuilder::endian`].
+///
 /// [`body`]: #method.body
+/// [`EndianSig`]: enum.EndianSig.html
 /// [`take_fds`]: #method.take_fds
 /// [`Connection`]: struct.Connection#method.call_method
 #[derive(Clone)]
@@ -82,7 +106,6 @@ pub struct Message {
 
 assert_impl_all!(Message: Send, Sync, Unpin);
 
-// TODO: Handle non-native byte order: https://github.com/dbus2/zbus/issues/19
 impl Message {
     /// Create a message of type [`MessageType::MethodCall`].
     ///
@@ -200,8 +223,9 @@ impl Message {
     ///
     /// The `fds` parameter is only available on unix. It specifies the file descriptors that
     /// accompany the message. On the wire, values of the UNIX_FD types store the index of the
-    /// corresponding file descriptor in this vector. Passing an empty vector on a message that
-    /// has UNIX_FD will result in an error.
+    /// corresponding file descriptor in this vector. The number of file descriptors must match the
+    /// one given by the `UNIX_FDS` header field of the message, or [`Error::InvalidField`] is
+    /// returned.
     ///
     /// **Note:** Since the constructed message is not construct by zbus, the receive sequence,
     /// which can be acquired from [`Message::recv_position`], is not applicable and hence set
@@ -213,30 +237,112 @@ impl Message {
     pub unsafe fn from_bytes(bytes: Vec<u8>, #[cfg(unix)] fds: Vec<OwnedFd>) -> Result<Self> {
         Self::from_raw_parts(
             bytes,
+            // The UNIX_FD values of the body are indices in the FDs, so they must all be there.
             #[cfg(unix)]
-            fds,
+            |count| {
+                if count == fds.len() {
+                    Ok(fds)
+                } else {
+                    Err(Error::InvalidField)
+                }
+            },
             0,
         )
     }
 
-    /// Create a message from its full contents
+    /// Create a message from bytes, as given by [`Message::to_bytes`].
+    ///
+    /// Unlike [`Message::from_bytes`], the message is fully validated: `bytes` must hold exactly
+    /// one message, with the header fields required by its type and a body matching its
+    /// signature. Since file descriptors can't be carried in bytes, messages with `UNIX_FDS` are
+    /// rejected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use zbus::{Message, MessageBuilder};
+    /// # (|| -> zbus::Result<()> {
+    /// let msg = MessageBuilder::signal("/org/zbus/Test", "org.zbus.Test", "Ping")?.build(&(42u32,))?;
+    /// let bytes = msg.to_bytes();
+    ///
+    /// // Persist `bytes`, or send them over some channel..
+    ///
+    /// let msg = Message::from_slice(&bytes)?;
+    /// assert_eq!(msg.member().unwrap(), "Ping");
+    /// assert_eq!(msg.body::<u32>()?, 42);
+    /// # Ok(()) })().unwrap()
+    /// ```
+    pub fn from_slice(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < MIN_MESSAGE_SIZE {
+            return Err(zvariant::Error::OutOfBounds.into());
+        }
+        let (primary_header, fields_len) = MessagePrimaryHeader::read(bytes)?;
+        let header_len = MIN_MESSAGE_SIZE + fields_len as usize;
+        let total_len =
+            header_len + padding_for_8_bytes(header_len) + primary_header.body_len() as usize;
+        if bytes.len() < total_len {
+            return Err(zvariant::Error::OutOfBounds.into());
+        }
+        if bytes.len() > total_len {
+            return Err(Error::ExcessData);
+        }
+
+        let msg = Self::from_raw_parts(
+            bytes.to_vec(),
+            #[cfg(unix)]
+            |_| Ok(vec![]),
+            0,
+        )?;
+        if msg.unix_fds().unwrap_or(0) != 0 {
+            return Err(Error::InvalidHeaderField(MessageFieldCode::UnixFDs));
+        }
+        let required: &[MessageFieldCode] = match msg.message_type() {
+            MessageType::Invalid => return Err(Error::InvalidField),
+            MessageType::MethodCall => &[MessageFieldCode::Path, MessageFieldCode::Member],
+            MessageType::MethodReturn => &[MessageFieldCode::ReplySerial],
+            MessageType::Error => &[MessageFieldCode::ErrorName, MessageFieldCode::ReplySerial],
+            MessageType::Signal => &[
+                MessageFieldCode::Path,
+                MessageFieldCode::Interface,
+                MessageFieldCode::Member,
+            ],
+        };
+        let header = msg.header()?;
+        if let Some(code) = required
+            .iter()
+            .find(|code| header.fields().get_field(**code).is_none())
+        {
+            return Err(Error::MissingHeaderField(*code));
+        }
+        match msg.body_signature() {
+            Ok(_) => {
+                msg.body::<zvariant::Structure<'_>>()?;
+            }
+            Err(Error::NoBodySignature) if primary_header.body_len() == 0 => (),
+            Err(e) => return Err(e),
+        }
+
+        Ok(msg)
+    }
+
+    /// Create a message from its full contents.
+    ///
+    /// `take_fds` is given the number of FDs the message declares, and returns them.
     pub(crate) fn from_raw_parts(
         bytes: Vec<u8>,
-        #[cfg(unix)] fds: Vec<OwnedFd>,
+        #[cfg(unix)] take_fds: impl FnOnce(usize) -> Result<Vec<OwnedFd>>,
         recv_seq: u64,
     ) -> Result<Self> {
-        if EndianSig::try_from(bytes[0])? != NATIVE_ENDIAN_SIG {
-            return Err(Error::IncorrectEndian);
-        }
-
         let (primary_header, fields_len) = MessagePrimaryHeader::read(&bytes)?;
-        let header = zvariant::from_slice(&bytes, dbus_context!(0))?;
-        #[cfg(unix)]
-        let fds = Arc::new(RwLock::new(Fds::Owned(fds)));
+        let header = with_dbus_context!(primary_header.endian_sig(), 0, |ctxt| {
+            zvariant::from_slice(&bytes, ctxt)
+        })?;
 
         let header_len = MIN_MESSAGE_SIZE + fields_len as usize;
         let body_offset = header_len + padding_for_8_bytes(header_len);
         let quick_fields = QuickMessageFields::new(&bytes, &header)?;
+        #[cfg(unix)]
+        let fds = take_fds(quick_fields.unix_fds().unwrap_or(0) as usize)?;
 
         Ok(Self {
             primary_header,
@@ -244,7 +350,7 @@ impl Message {
             bytes,
             body_offset,
             #[cfg(unix)]
-            fds,
+            fds: Arc::new(RwLock::new(Fds::Owned(fds))),
             recv_seq: MessageSequence { recv_seq },
         })
     }
@@ -286,7 +392,7 @@ impl Message {
             .ok_or(Error::NoBodySignature)?
         {
             MessageField::Signature(signature) => Ok(signature),
-            _ => Err(Error::InvalidField),
+            _ => Err(Error::InvalidHeaderField(MessageFieldCode::Signature)),
         }
     }
 
@@ -301,24 +407,33 @@ impl Message {
         modifier(&mut self.primary_header)?;
 
         let mut cursor = Cursor::new(&mut self.bytes);
-        zvariant::to_writer(&mut cursor, dbus_context!(0), &self.primary_header)
-            .map(|_| ())
-            .map_err(Error::from)
+        with_dbus_context!(self.primary_header.endian_sig(), 0, |ctxt| {
+            zvariant::to_writer(&mut cursor, ctxt, &self.primary_header)
+        })
+        .map(|_| ())
+        .map_err(Error::from)
     }
 
     /// Deserialize the header.
     ///
     /// Note: prefer using the direct access methods if possible; they are more efficient.
     pub fn header(&self) -> Result<MessageHeader<'_>> {
-        zvariant::from_slice(&self.bytes, dbus_context!(0)).map_err(Error::from)
+        with_dbus_context!(self.primary_header.endian_sig(), 0, |ctxt| {
+            zvariant::from_slice(&self.bytes, ctxt)
+        })
+        .map_err(Error::from)
     }
 
     /// Deserialize the fields.
     ///
     /// Note: prefer using the direct access methods if possible; they are more efficient.
     pub fn fields(&self) -> Result<MessageFields<'_>> {
-        let ctxt = dbus_context!(crate::PRIMARY_HEADER_SIZE);
-        zvariant::from_slice(&self.bytes[crate::PRIMARY_HEADER_SIZE..], ctxt).map_err(Error::from)
+        with_dbus_context!(
+            self.primary_header.endian_sig(),
+            crate::PRIMARY_HEADER_SIZE,
+            |ctxt| zvariant::from_slice(&self.bytes[crate::PRIMARY_HEADER_SIZE..], ctxt)
+        )
+        .map_err(Error::from)
     }
 
     /// The message type.
@@ -326,6 +441,34 @@ impl Message {
         self.primary_header.msg_type()
     }
 
+    /// The flags of the message.
+    ///
+    /// See [`MessageFlags`] documentation for the meaning of the flags.
+    pub fn flags(&self) -> BitFlags<MessageFlags> {
+        self.primary_header.flags()
+    }
+
+    /// If the message is an error.
+    pub fn is_error(&self) -> bool {
+        self.message_type() == MessageType::Error
+    }
+
+    /// If the message is a reply (a method return or an error) to the method call of serial
+    /// number `serial`.
+    pub fn is_reply_to(&self, serial: u32) -> bool {
+        matches!(
+            self.message_type(),
+            MessageType::MethodReturn | MessageType::Error
+        ) && self.reply_serial() == Some(serial)
+    }
+
+    /// If the message is the signal `member` of the interface `interface`.
+    pub fn is_signal_for(&self, interface: &str, member: &str) -> bool {
+        self.message_type() == MessageType::Signal
+            && self.interface().map(|i| i == interface).unwrap_or(false)
+            && self.member().map(|m| m == member).unwrap_or(false)
+    }
+
     /// The object to send a call to, or the object a signal is emitted from.
     pub fn path(&self) -> Option<ObjectPath<'_>> {
         self.quick_fields.path(self)
@@ -341,35 +484,57 @@ impl Message {
         self.quick_fields.member(self)
     }
 
+    /// The name of the error that occurred, for errors.
+    pub fn error_name(&self) -> Option<ErrorName<'_>> {
+        self.quick_fields.error_name(self)
+    }
+
     /// The serial number of the message this message is a reply to.
     pub fn reply_serial(&self) -> Option<u32> {
         self.quick_fields.reply_serial()
     }
 
+    /// The name of the connection this message is intended for.
+    pub fn destination(&self) -> Option<BusName<'_>> {
+        self.quick_fields.destination(self)
+    }
+
+    /// Unique name of the sending connection.
+    pub fn sender(&self) -> Option<UniqueName<'_>> {
+        self.quick_fields.sender(self)
+    }
+
+    /// The number of Unix file descriptors that accompany the message.
+    pub fn unix_fds(&self) -> Option<u32> {
+        self.quick_fields.unix_fds()
+    }
+
     /// Deserialize the body (without checking signature matching).
     pub fn body_unchecked<'d, 'm: 'd, B>(&'m self) -> Result<B>
     where
         B: serde::de::Deserialize<'d> + Type,
     {
-        {
+        with_dbus_context!(self.primary_header.endian_sig(), 0, |ctxt| {
             #[cfg(unix)]
             {
-                zvariant::from_slice_fds(
-                    &self.bytes[self.body_offset..],
-                    Some(&self.fds()),
-                    dbus_context!(0),
-                )
+                zvariant::from_slice_fds(&self.bytes[self.body_offset..], Some(&self.fds()), ctxt)
             }
             #[cfg(not(unix))]
             {
-                zvariant::from_slice(&self.bytes[self.body_offset..], dbus_context!(0))
+                zvariant::from_slice(&self.bytes[self.body_offset..], ctxt)
             }
-        }
+        })
         .map_err(Error::from)
     }
 
     /// Deserialize the body using the contained signature.
     ///
+    /// The body can be deserialized into types borrowing from the message, such as `&str`, `&[u8]`
+    /// or [`Value<'_>`], in which case no data is copied. The returned value then can't outlive the
+    /// message.
+    ///
+    /// [`Value<'_>`]: enum@zvariant::Value
+    ///
     /// # Example
     ///
     /// ```
@@ -400,13 +565,13 @@ impl Message {
             Err(e) => return Err(e),
         };
 
-        {
+        with_dbus_context!(self.primary_header.endian_sig(), 0, |ctxt| {
             #[cfg(unix)]
             {
                 zvariant::from_slice_fds_for_dynamic_signature(
                     &self.bytes[self.body_offset..],
                     Some(&self.fds()),
-                    dbus_context!(0),
+                    ctxt,
                     &body_sig,
                 )
             }
@@ -414,11 +579,11 @@ impl Message {
             {
                 zvariant::from_slice_for_dynamic_signature(
                     &self.bytes[self.body_offset..],
-                    dbus_context!(0),
+                    ctxt,
                     &body_sig,
                 )
             }
-        }
+        })
         .map_err(Error::from)
     }
 
@@ -435,6 +600,13 @@ impl Message {
         &self.bytes
     }
 
+    /// The byte encoding of the message, to create it back with [`Message::from_slice`].
+    ///
+    /// **Note:** The file descriptors of the message, if any, are not part of it.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.bytes.clone()
+    }
+
     /// Get a reference to the byte encoding of the body of the message.
     pub fn body_as_bytes(&self) -> Result<&[u8]> {
         Ok(&self.bytes[self.body_offset..])
@@ -554,7 +726,7 @@ mod tests {
     #[cfg(unix)]
     use super::Fds;
     use super::Message;
-    use crate::Error;
+    use crate::{zvariant::Value, EndianSig, Error, MessageBuilder, MessageFieldCode};
 
     #[test]
     fn test() {
@@ -598,4 +770,293 @@ mod tests {
         .unwrap();
         assert_eq!(e.to_string(), "Error org.freedesktop.zbus.Error: kaboom!");
     }
+
+    #[test]
+    fn header_fields() {
+        let m = Message::method(
+            Some(":1.72"),
+            Some("org.zbus.Test"),
+            "/org/zbus/Test",
+            Some("org.zbus.Test"),
+            "Do",
+            &("foo", 1u32),
+        )
+        .unwrap();
+        assert_eq!(m.sender().unwrap(), ":1.72");
+        assert_eq!(m.destination().unwrap(), "org.zbus.Test");
+        assert_eq!(m.path().unwrap(), "/org/zbus/Test");
+        assert_eq!(m.interface().unwrap(), "org.zbus.Test");
+        assert_eq!(m.member().unwrap(), "Do");
+        assert_eq!(m.error_name(), None);
+        assert_eq!(m.reply_serial(), None);
+        assert_eq!(m.body_signature().unwrap(), "su");
+        assert_eq!(m.unix_fds(), None);
+
+        let mut m = m;
+        m.modify_primary_header(|primary| {
+            primary.serial_num_or_init(|| 7);
+
+            Ok(())
+        })
+        .unwrap();
+        let e = Message::method_error(Some(":1.73"), &m, "org.zbus.Error", &("kaboom!",)).unwrap();
+        assert_eq!(e.sender().unwrap(), ":1.73");
+        assert_eq!(e.destination().unwrap(), ":1.72");
+        assert_eq!(e.error_name().unwrap(), "org.zbus.Error");
+        assert_eq!(e.reply_serial(), Some(7));
+        assert_eq!(e.path(), None);
+
+        #[cfg(unix)]
+        {
+            let stdout = std::io::stdout();
+            let m = MessageBuilder::signal("/", "org.zbus.Test", "Fd")
+                .unwrap()
+                .build(&(Fd::from(&stdout),))
+                .unwrap();
+            assert_eq!(m.unix_fds(), Some(1));
+            assert_eq!(m.body_signature().unwrap(), "h");
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn received_fds() {
+        use std::os::unix::io::{FromRawFd, IntoRawFd};
+        use zvariant::OwnedFd;
+
+        let stdout = std::io::stdout();
+        let m = MessageBuilder::signal("/", "org.zbus.Test", "Fds")
+            .unwrap()
+            .build(&(Fd::from(&stdout), "stdout"))
+            .unwrap();
+        let bytes = m.as_bytes().to_vec();
+        let open = || {
+            let file = std::fs::File::open("/dev/null").unwrap();
+
+            unsafe { OwnedFd::from_raw_fd(file.into_raw_fd()) }
+        };
+
+        // The count of FDs must match the `UNIX_FDS` header field.
+        for fds in [vec![], vec![open(), open()]] {
+            let res = unsafe { Message::from_bytes(bytes.clone(), fds) };
+            assert!(matches!(res, Err(Error::InvalidField)));
+        }
+
+        let fd = open();
+        let raw_fd = fd.as_raw_fd();
+        let m = unsafe { Message::from_bytes(bytes, vec![fd]) }.unwrap();
+        assert_eq!(m.unix_fds(), Some(1));
+        let (fd, name): (Fd, String) = m.body().unwrap();
+        assert_eq!((fd.as_raw_fd(), name.as_str()), (raw_fd, "stdout"));
+
+        let fds = m.take_fds();
+        assert_eq!(fds.len(), 1);
+        assert_eq!(fds[0].as_raw_fd(), raw_fd);
+        assert!(m.take_fds().is_empty());
+        // The message still resolves the FDs, now owned by the caller.
+        assert_eq!(m.body::<(Fd, &str)>().unwrap().0.as_raw_fd(), raw_fd);
+    }
+
+    #[test]
+    fn to_from_bytes() {
+        let m = MessageBuilder::method_call("/org/zbus/Test", "Do")
+            .unwrap()
+            .destination("org.zbus.Test")
+            .unwrap()
+            .build(&("foo", vec![1u32, 2]))
+            .unwrap();
+        let bytes = m.to_bytes();
+        assert_eq!(bytes, m.as_bytes());
+        let body_offset = bytes.len() - m.body_as_bytes().unwrap().len();
+        let parsed = Message::from_slice(&bytes).unwrap();
+        assert_eq!(parsed.as_bytes(), m.as_bytes());
+        assert_eq!(parsed.member().unwrap(), "Do");
+        assert_eq!(
+            parsed.body::<(&str, Vec<u32>)>().unwrap(),
+            ("foo", vec![1, 2])
+        );
+
+        // Without a body.
+        let m = MessageBuilder::signal("/", "org.zbus.Test", "Done")
+            .unwrap()
+            .build(&())
+            .unwrap();
+        Message::from_slice(&m.to_bytes()).unwrap();
+
+        // Truncated or followed by other bytes.
+        assert!(matches!(
+            Message::from_slice(&bytes[..8]),
+            Err(Error::Variant(zvariant::Error::OutOfBounds))
+        ));
+        assert!(matches!(
+            Message::from_slice(&bytes[..bytes.len() - 1]),
+            Err(Error::Variant(zvariant::Error::OutOfBounds))
+        ));
+        let mut longer = bytes.clone();
+        longer.push(0);
+        assert!(matches!(
+            Message::from_slice(&longer),
+            Err(Error::ExcessData)
+        ));
+
+        // A body not matching the signature.
+        let mut invalid = bytes.clone();
+        invalid[body_offset..body_offset + 4].copy_from_slice(&[0xff; 4]);
+        assert!(Message::from_slice(&invalid).is_err());
+
+        // Without the fields required by the type.
+        let m = MessageBuilder::new(crate::MessageType::MethodCall)
+            .build(&())
+            .unwrap();
+        assert!(matches!(
+            Message::from_slice(&m.to_bytes()),
+            Err(Error::MissingHeaderField(MessageFieldCode::Path))
+        ));
+
+        // With FDs.
+        #[cfg(unix)]
+        {
+            let stdout = std::io::stdout();
+            let m = MessageBuilder::signal("/", "org.zbus.Test", "Fd")
+                .unwrap()
+                .build(&(Fd::from(&stdout),))
+                .unwrap();
+            assert!(matches!(
+                Message::from_slice(&m.to_bytes()),
+                Err(Error::InvalidHeaderField(MessageFieldCode::UnixFDs))
+            ));
+        }
+    }
+
+    #[test]
+    fn predicates() {
+        let mut call = MessageBuilder::method_call("/org/zbus/Test", "Do")
+            .unwrap()
+            .interface("org.zbus.Test")
+            .unwrap()
+            .build(&())
+            .unwrap();
+        call.modify_primary_header(|primary| {
+            primary.serial_num_or_init(|| 7);
+
+            Ok(())
+        })
+        .unwrap();
+        assert!(!call.is_error());
+        assert!(!call.is_reply_to(7));
+        assert!(!call.is_signal_for("org.zbus.Test", "Do"));
+
+        let reply = Message::method_reply(None::<()>, &call, &()).unwrap();
+        assert!(!reply.is_error());
+        assert!(reply.is_reply_to(7));
+        assert!(!reply.is_reply_to(8));
+
+        let error = Message::method_error(None::<()>, &call, "org.zbus.Error", &()).unwrap();
+        assert!(error.is_error());
+        assert!(error.is_reply_to(7));
+
+        let signal = MessageBuilder::signal("/org/zbus/Test", "org.zbus.Test", "Done")
+            .unwrap()
+            .build(&())
+            .unwrap();
+        assert!(signal.is_signal_for("org.zbus.Test", "Done"));
+        assert!(!signal.is_signal_for("org.zbus.Test", "Do"));
+        assert!(!signal.is_signal_for("org.zbus.Other", "Done"));
+        assert!(!signal.is_reply_to(7));
+    }
+
+    #[test]
+    fn array_too_long() {
+        let m = MessageBuilder::signal("/", "org.zbus.Test", "Array")
+            .unwrap()
+            .endian(EndianSig::Little)
+            .build(&(vec![0u8; 4],))
+            .unwrap();
+        let mut bytes = m.to_bytes();
+        let body_offset = bytes.len() - m.body_as_bytes().unwrap().len();
+        let len = 64 * 1024 * 1024 + 1;
+        bytes[body_offset..body_offset + 4].copy_from_slice(&(len as u32).to_le_bytes());
+        let m = unsafe {
+            Message::from_bytes(
+                bytes,
+                #[cfg(unix)]
+                vec![],
+            )
+        }
+        .unwrap();
+        assert_eq!(
+            m.body::<Vec<u8>>().unwrap_err(),
+            Error::Variant(zvariant::Error::ArrayTooLong(len))
+        );
+    }
+
+    #[test]
+    fn borrowed_body() {
+        let bytes: &[u8] = &[1, 2, 3];
+        let m = MessageBuilder::signal("/", "org.zbus.Test", "Borrowed")
+            .unwrap()
+            .build(&("foo", bytes, Value::from("bar")))
+            .unwrap();
+        let body = m.body_as_bytes().unwrap().as_ptr_range();
+        let borrowed = |s: &[u8]| body.contains(&s.as_ptr());
+
+        let (s, b, v): (&str, &[u8], Value<'_>) = m.body().unwrap();
+        assert_eq!((s, b), ("foo", bytes));
+        assert!(borrowed(s.as_bytes()));
+        assert!(borrowed(b));
+        match v {
+            Value::Str(v) => {
+                assert_eq!(v, "bar");
+                assert!(borrowed(v.as_bytes()));
+            }
+            v => panic!("unexpected value: {:?}", v),
+        }
+    }
+
+    #[test]
+    fn big_endian() {
+        let body = (0x1234_5678u32, "foo", vec![1i64, -2]);
+        let m = MessageBuilder::signal("/org/zbus/test", "org.zbus.Test", "Hi")
+            .unwrap()
+            .endian(EndianSig::Big)
+            .build(&body)
+            .unwrap();
+        assert_eq!(m.primary_header().endian_sig(), EndianSig::Big);
+        assert_eq!(m.as_bytes()[0], b'B');
+        // The body length in the primary header is big-endian too.
+        assert_eq!(
+            &m.as_bytes()[4..8],
+            &m.primary_header().body_len().to_be_bytes()
+        );
+        assert_eq!(&m.body_as_bytes().unwrap()[..4], &[0x12, 0x34, 0x56, 0x78]);
+        assert_eq!(m.body::<(u32, String, Vec<i64>)>().unwrap().2, vec![1, -2]);
+
+        // Setting the serial, as the connection does on sending, keeps the byte order.
+        let mut m = m;
+        m.modify_primary_header(|primary| {
+            primary.serial_num_or_init(|| 42);
+
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(&m.as_bytes()[8..12], &42u32.to_be_bytes());
+
+        // Parsing the bytes honors the byte order.
+        let parsed = unsafe {
+            Message::from_bytes(
+                m.as_bytes().to_vec(),
+                #[cfg(unix)]
+                vec![],
+            )
+        }
+        .unwrap();
+        let header = parsed.header().unwrap();
+        assert_eq!(header.primary().serial_num(), Some(&42));
+        assert_eq!(header.path().unwrap().unwrap().as_str(), "/org/zbus/test");
+        assert_eq!(parsed.member().unwrap(), "Hi");
+        assert_eq!(
+            parsed.body::<(u32, String, Vec<i64>)>().unwrap(),
+            (0x1234_5678, "foo".into(), vec![1, -2])
+        );
+    }
 }
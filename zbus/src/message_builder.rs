@@ -16,9 +16,10 @@ use zbus_names::{BusName, ErrorName, InterfaceName, MemberName, UniqueName};
 
 use crate::{
     utils::padding_for_8_bytes,
-    zvariant::{DynamicType, EncodingContext, ObjectPath, Signature},
-    Error, Message, MessageField, MessageFieldCode, MessageFields, MessageFlags, MessageHeader,
-    MessagePrimaryHeader, MessageSequence, MessageType, QuickMessageFields, Result,
+    with_dbus_context,
+    zvariant::{DynamicType, ObjectPath, Signature},
+    EndianSig, Error, Message, MessageField, MessageFieldCode, MessageFields, MessageFlags,
+    MessageHeader, MessagePrimaryHeader, MessageSequence, MessageType, QuickMessageFields, Result,
     MAX_MESSAGE_SIZE,
 };
 
@@ -28,12 +29,6 @@ type BuildGenericResult = Vec<RawFd>;
 #[cfg(not(unix))]
 type BuildGenericResult = ();
 
-macro_rules! dbus_context {
-    ($n_bytes_before: expr) => {
-        EncodingContext::<byteorder::NativeEndian>::new_dbus($n_bytes_before)
-    };
-}
-
 /// A builder for [`Message`]
 #[derive(Debug, Clone)]
 pub struct MessageBuilder<'a> {
@@ -41,7 +36,28 @@ pub struct MessageBuilder<'a> {
 }
 
 impl<'a> MessageBuilder<'a> {
-    fn new(msg_type: MessageType) -> Self {
+    /// Create a message of the given type, with no header fields set.
+    ///
+    /// Unlike the other constructors, this lets you craft any message, e.g to test how a peer
+    /// handles unexpected traffic. It's up to you to set the header fields the type of message
+    /// requires, and no checks are made at build time:
+    ///
+    /// ```
+    /// use zbus::{MessageBuilder, MessageType};
+    ///
+    /// let msg = MessageBuilder::new(MessageType::Error)
+    ///     .error_name("org.freedesktop.DBus.Error.Failed")?
+    ///     .reply_serial(42)
+    ///     .destination(":1.1")?
+    ///     .build(&("Something went wrong",))?;
+    /// assert_eq!(msg.reply_serial(), Some(42));
+    /// # Ok::<(), zbus::Error>(())
+    /// ```
+    ///
+    /// The serial number of the message is set by the [`Connection`] it's sent over.
+    ///
+    /// [`Connection`]: crate::Connection
+    pub fn new(msg_type: MessageType) -> Self {
         let primary = MessagePrimaryHeader::new(msg_type, 0);
         let fields = MessageFields::new();
         let header = MessageHeader::new(primary, fields);
@@ -97,18 +113,38 @@ impl<'a> MessageBuilder<'a> {
     ///
     /// See [`MessageFlags`] documentation for the meaning of the flags.
     ///
+    /// Several flags can be added at once, by combining them with `|`.
+    ///
     /// The function will return an error if invalid flags are given for the message type.
-    pub fn with_flags(mut self, flag: MessageFlags) -> Result<Self> {
+    pub fn with_flags<F>(mut self, flags: F) -> Result<Self>
+    where
+        F: Into<BitFlags<MessageFlags>>,
+    {
+        let flags = flags.into();
         if self.header.message_type()? != MessageType::MethodCall
-            && BitFlags::from_flag(flag).contains(MessageFlags::NoReplyExpected)
+            && flags.contains(MessageFlags::NoReplyExpected)
         {
             return Err(Error::InvalidField);
         }
-        let flags = self.header.primary().flags() | flag;
+        let flags = self.header.primary().flags() | flags;
         self.header.primary_mut().set_flags(flags);
         Ok(self)
     }
 
+    /// Set the byte order of the message.
+    ///
+    /// By default, messages are encoded in the native byte order of the target. Peers must accept
+    /// both byte orders, but some bus implementations (typically on embedded targets) only speak
+    /// big-endian.
+    ///
+    /// If you build the message with [`MessageBuilder::build_raw_body`], the body bytes must be in
+    /// this byte order.
+    pub fn endian(mut self, endian: EndianSig) -> Self {
+        self.header.primary_mut().set_endian_sig(endian);
+
+        self
+    }
+
     /// Set the unique name of the sending connection.
     pub fn sender<'s: 'a, S>(mut self, sender: S) -> Result<Self>
     where
@@ -157,7 +193,8 @@ impl<'a> MessageBuilder<'a> {
         Ok(self)
     }
 
-    fn error_name<'e: 'a, E>(mut self, error: E) -> Result<Self>
+    /// Set the name of the error, for messages of type [`MessageType::Error`].
+    pub fn error_name<'e: 'a, E>(mut self, error: E) -> Result<Self>
     where
         E: TryInto<ErrorName<'e>>,
         E::Error: Into<Error>,
@@ -180,11 +217,21 @@ impl<'a> MessageBuilder<'a> {
         Ok(self)
     }
 
-    fn reply_to(mut self, reply_to: &MessageHeader<'_>) -> Result<Self> {
-        let serial = reply_to.primary().serial_num().ok_or(Error::MissingField)?;
+    /// Set the serial number of the message this is a reply to.
+    ///
+    /// [`MessageBuilder::method_return`] and [`MessageBuilder::error`] set it from the header of
+    /// the method call.
+    pub fn reply_serial(mut self, serial: u32) -> Self {
         self.header
             .fields_mut()
-            .replace(MessageField::ReplySerial(*serial));
+            .replace(MessageField::ReplySerial(serial));
+
+        self
+    }
+
+    fn reply_to(mut self, reply_to: &MessageHeader<'_>) -> Result<Self> {
+        let serial = reply_to.primary().serial_num().ok_or(Error::MissingField)?;
+        self = self.reply_serial(*serial);
 
         if let Some(sender) = reply_to.sender()? {
             self.destination(sender.to_owned())
@@ -207,35 +254,36 @@ impl<'a> MessageBuilder<'a> {
     where
         B: serde::ser::Serialize + DynamicType,
     {
-        let ctxt = dbus_context!(0);
-
-        // Note: this iterates the body twice, but we prefer efficient handling of large messages
-        // to efficient handling of ones that are complex to serialize.
-        #[cfg(unix)]
-        let (body_len, fds_len) = zvariant::serialized_size_fds(ctxt, body)?;
-        #[cfg(not(unix))]
-        let body_len = zvariant::serialized_size(ctxt, body)?;
-
-        let signature = body.dynamic_signature();
-
-        self.build_generic(
-            signature,
-            body_len,
-            move |cursor| {
-                #[cfg(unix)]
-                {
-                    let (_, fds) = zvariant::to_writer_fds(cursor, ctxt, body)?;
-                    Ok::<Vec<RawFd>, Error>(fds)
-                }
-                #[cfg(not(unix))]
-                {
-                    zvariant::to_writer(cursor, ctxt, body)?;
-                    Ok::<(), Error>(())
-                }
-            },
+        let endian = self.header.primary().endian_sig();
+        with_dbus_context!(endian, 0, |ctxt| {
+            // Note: this iterates the body twice, but we prefer efficient handling of large
+            // messages to efficient handling of ones that are complex to serialize.
             #[cfg(unix)]
-            fds_len,
-        )
+            let (body_len, fds_len) = zvariant::serialized_size_fds(ctxt, body)?;
+            #[cfg(not(unix))]
+            let body_len = zvariant::serialized_size(ctxt, body)?;
+
+            let signature = body.dynamic_signature();
+
+            self.build_generic(
+                signature,
+                body_len,
+                move |cursor| {
+                    #[cfg(unix)]
+                    {
+                        let (_, fds) = zvariant::to_writer_fds(cursor, ctxt, body)?;
+                        Ok::<Vec<RawFd>, Error>(fds)
+                    }
+                    #[cfg(not(unix))]
+                    {
+                        zvariant::to_writer(cursor, ctxt, body)?;
+                        Ok::<(), Error>(())
+                    }
+                },
+                #[cfg(unix)]
+                fds_len,
+            )
+        })
     }
 
     /// Create a new message from a raw slice of bytes to populate the body with, rather than by
@@ -285,7 +333,6 @@ impl<'a> MessageBuilder<'a> {
     where
         WriteFunc: FnOnce(&mut Cursor<&mut Vec<u8>>) -> Result<BuildGenericResult>,
     {
-        let ctxt = dbus_context!(0);
         let mut header = self.header;
 
         if !signature.is_empty() {
@@ -307,7 +354,9 @@ impl<'a> MessageBuilder<'a> {
             }
         }
 
-        let hdr_len = zvariant::serialized_size(ctxt, &header)?;
+        let endian = header.primary().endian_sig();
+        let hdr_len =
+            with_dbus_context!(endian, 0, |ctxt| zvariant::serialized_size(ctxt, &header))?;
         // We need to align the body to 8-byte boundary.
         let body_padding = padding_for_8_bytes(hdr_len);
         let body_offset = hdr_len + body_padding;
@@ -318,7 +367,11 @@ impl<'a> MessageBuilder<'a> {
         let mut bytes: Vec<u8> = Vec::with_capacity(total_len);
         let mut cursor = Cursor::new(&mut bytes);
 
-        zvariant::to_writer(&mut cursor, ctxt, &header)?;
+        with_dbus_context!(endian, 0, |ctxt| zvariant::to_writer(
+            &mut cursor,
+            ctxt,
+            &header
+        ))?;
         for _ in 0..body_padding {
             cursor.write_all(&[0u8])?;
         }
@@ -328,7 +381,8 @@ impl<'a> MessageBuilder<'a> {
         write_body(&mut cursor)?;
 
         let primary_header = header.into_primary();
-        let header: MessageHeader<'_> = zvariant::from_slice(&bytes, ctxt)?;
+        let header: MessageHeader<'_> =
+            with_dbus_context!(endian, 0, |ctxt| zvariant::from_slice(&bytes, ctxt))?;
         let quick_fields = QuickMessageFields::new(&bytes, &header)?;
 
         Ok(Message {
@@ -357,7 +411,8 @@ impl<'m> From<MessageHeader<'m>> for MessageBuilder<'m> {
 #[cfg(test)]
 mod tests {
     use super::MessageBuilder;
-    use crate::Error;
+    use crate::{Error, MessageFlags, MessageType};
+    use enumflags2::BitFlags;
     use test_log::test;
 
     #[test]
@@ -378,4 +433,64 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_any_type() -> Result<(), Error> {
+        let msg = MessageBuilder::new(MessageType::MethodReturn)
+            .reply_serial(7)
+            .destination(":1.42")?
+            .build(&(1u32, "two"))?;
+        assert_eq!(msg.message_type(), MessageType::MethodReturn);
+        assert_eq!(msg.reply_serial(), Some(7));
+        let (one, two): (u32, String) = msg.body()?;
+        assert_eq!((one, two.as_str()), (1, "two"));
+
+        let msg = MessageBuilder::new(MessageType::Error)
+            .error_name("org.zbus.Error.Crafted")?
+            .reply_serial(8)
+            .build(&("crafted",))?;
+        match Error::from(msg) {
+            Error::MethodError(name, description, _) => {
+                assert_eq!(name, "org.zbus.Error.Crafted");
+                assert_eq!(description.as_deref(), Some("crafted"));
+            }
+            e => panic!("unexpected error: {}", e),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_flags() -> Result<(), Error> {
+        let msg = MessageBuilder::method_call("/", "Do")?
+            .with_flags(MessageFlags::NoReplyExpected)?
+            .with_flags(MessageFlags::NoAutoStart | MessageFlags::AllowInteractiveAuth)?
+            .build(&())?;
+        assert_eq!(msg.flags(), BitFlags::all());
+
+        let msg = MessageBuilder::signal("/", "org.zbus.Test", "Done")?.build(&())?;
+        assert!(msg.flags().is_empty());
+        assert!(matches!(
+            MessageBuilder::signal("/", "org.zbus.Test", "Done")?
+                .with_flags(MessageFlags::NoReplyExpected | MessageFlags::NoAutoStart),
+            Err(Error::InvalidField)
+        ));
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_fds() -> Result<(), Error> {
+        use std::os::unix::io::AsRawFd;
+        use zvariant::Fd;
+
+        let stdout = std::io::stdout();
+        let msg = MessageBuilder::signal("/", "org.zbus.Test", "Fds")?
+            .build(&(Fd::from(stdout.as_raw_fd()), "stdout"))?;
+        assert_eq!(msg.fds().len(), 1);
+        assert_eq!(msg.header()?.unix_fds()?, Some(1));
+
+        Ok(())
+    }
 }
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use static_assertions::assert_impl_all;
 use std::convert::{TryFrom, TryInto};
-use zbus_names::{InterfaceName, MemberName};
+use zbus_names::{BusName, ErrorName, InterfaceName, MemberName, UniqueName};
 use zvariant::{ObjectPath, Type};
 
 use crate::{Message, MessageField, MessageFieldCode, MessageHeader, Result};
@@ -152,7 +152,11 @@ pub(crate) struct QuickMessageFields {
     path: FieldPos,
     interface: FieldPos,
     member: FieldPos,
+    error_name: FieldPos,
+    destination: FieldPos,
+    sender: FieldPos,
     reply_serial: Option<u32>,
+    unix_fds: Option<u32>,
 }
 
 impl QuickMessageFields {
@@ -161,7 +165,11 @@ impl QuickMessageFields {
             path: FieldPos::new(buf, header.path()?),
             interface: FieldPos::new(buf, header.interface()?),
             member: FieldPos::new(buf, header.member()?),
+            error_name: FieldPos::new(buf, header.error_name()?),
+            destination: FieldPos::new(buf, header.destination()?),
+            sender: FieldPos::new(buf, header.sender()?),
             reply_serial: header.reply_serial()?,
+            unix_fds: header.unix_fds()?,
         })
     }
 
@@ -177,9 +185,25 @@ impl QuickMessageFields {
         self.member.read(msg.as_bytes())
     }
 
+    pub fn error_name<'m>(&self, msg: &'m Message) -> Option<ErrorName<'m>> {
+        self.error_name.read(msg.as_bytes())
+    }
+
+    pub fn destination<'m>(&self, msg: &'m Message) -> Option<BusName<'m>> {
+        self.destination.read(msg.as_bytes())
+    }
+
+    pub fn sender<'m>(&self, msg: &'m Message) -> Option<UniqueName<'m>> {
+        self.sender.read(msg.as_bytes())
+    }
+
     pub fn reply_serial(&self) -> Option<u32> {
         self.reply_serial
     }
+
+    pub fn unix_fds(&self) -> Option<u32> {
+        self.unix_fds
+    }
 }
 
 impl<'m> Default for MessageFields<'m> {
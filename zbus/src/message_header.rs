@@ -7,13 +7,14 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 
 use static_assertions::assert_impl_all;
 use zbus_names::{BusName, ErrorName, InterfaceName, MemberName, UniqueName};
-use zvariant::{EncodingContext, ObjectPath, Signature, Type};
+use zvariant::{ObjectPath, Signature, Type};
 
-use crate::{Error, MessageField, MessageFieldCode, MessageFields};
+use crate::{utils::padding_for_8_bytes, Error, MessageField, MessageFieldCode, MessageFields};
 
 pub(crate) const PRIMARY_HEADER_SIZE: usize = 12;
 pub(crate) const MIN_MESSAGE_SIZE: usize = PRIMARY_HEADER_SIZE + 4;
 pub(crate) const MAX_MESSAGE_SIZE: usize = 128 * 1024 * 1024; // 128 MiB
+pub(crate) const MAX_HEADER_FIELDS_SIZE: usize = 64 * 1024 * 1024; // 64 MiB, as any array
 
 /// D-Bus code for endianness.
 #[repr(u8)]
@@ -48,6 +49,27 @@ pub const NATIVE_ENDIAN_SIG: EndianSig = EndianSig::Big;
 /// Signature of the target's native endian.
 pub const NATIVE_ENDIAN_SIG: EndianSig = EndianSig::Little;
 
+// Evaluate `$body` with `$ctxt` bound to the D-Bus encoding context for the given byte order.
+//
+// The context types differ for each byte order, so `$body` is expanded once for each of them.
+macro_rules! with_dbus_context {
+    ($endian:expr, $n_bytes_before:expr, |$ctxt:ident| $body:expr) => {
+        match $endian {
+            $crate::EndianSig::Big => {
+                let $ctxt =
+                    zvariant::EncodingContext::<byteorder::BigEndian>::new_dbus($n_bytes_before);
+                $body
+            }
+            $crate::EndianSig::Little => {
+                let $ctxt =
+                    zvariant::EncodingContext::<byteorder::LittleEndian>::new_dbus($n_bytes_before);
+                $body
+            }
+        }
+    };
+}
+pub(crate) use with_dbus_context;
+
 /// Message header representing the D-Bus type of the message.
 #[repr(u8)]
 #[derive(Debug, Copy, Clone, Deserialize_repr, PartialEq, Eq, Hash, Serialize_repr, Type)]
@@ -172,9 +194,26 @@ impl MessagePrimaryHeader {
     }
 
     pub(crate) fn read(buf: &[u8]) -> Result<(MessagePrimaryHeader, u32), Error> {
-        let ctx = EncodingContext::<byteorder::NativeEndian>::new_dbus(0);
-        let primary_header = zvariant::from_slice(buf, ctx)?;
-        let fields_len = zvariant::from_slice(&buf[PRIMARY_HEADER_SIZE..], ctx)?;
+        // The first byte tells the byte order of everything else.
+        let endian = EndianSig::try_from(buf[0])?;
+        let (primary_header, fields_len): (MessagePrimaryHeader, u32) =
+            with_dbus_context!(endian, 0, |ctx| {
+                let primary_header = zvariant::from_slice(buf, ctx)?;
+                let fields_len = zvariant::from_slice(&buf[PRIMARY_HEADER_SIZE..], ctx)?;
+                Ok::<_, Error>((primary_header, fields_len))
+            })?;
+
+        // Enforce the limits of the specification before the rest of the message is even read.
+        let header_len = MIN_MESSAGE_SIZE + fields_len as usize;
+        let total_len =
+            header_len + padding_for_8_bytes(header_len) + primary_header.body_len() as usize;
+        if fields_len as usize > MAX_HEADER_FIELDS_SIZE {
+            return Err(Error::HeaderFieldsTooLarge(fields_len as usize));
+        }
+        if total_len > MAX_MESSAGE_SIZE {
+            return Err(Error::MessageTooLarge(total_len));
+        }
+
         Ok((primary_header, fields_len))
     }
 
@@ -273,7 +312,7 @@ macro_rules! get_field {
         #[allow(clippy::redundant_closure_call)]
         match $self.fields().get_field(MessageFieldCode::$kind) {
             Some(MessageField::$kind(value)) => Ok(Some($closure(value))),
-            Some(_) => Err(Error::InvalidField),
+            Some(_) => Err(Error::InvalidHeaderField(MessageFieldCode::$kind)),
             None => Ok(None),
         }
     };
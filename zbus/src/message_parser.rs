@@ -0,0 +1,413 @@
+#[cfg(unix)]
+use std::collections::VecDeque;
+use std::{io, mem, task::Poll};
+
+use static_assertions::assert_impl_all;
+#[cfg(unix)]
+use tracing::debug;
+
+#[cfg(unix)]
+use crate::OwnedFd;
+use crate::{
+    message_header::{MAX_MESSAGE_SIZE, MIN_MESSAGE_SIZE},
+    raw::PollRecvmsg,
+    utils::padding_for_8_bytes,
+    Error, Message, MessagePrimaryHeader, Result,
+};
+
+/// An incremental parser of D-Bus messages.
+///
+/// The parser is fed with the bytes received from a peer, in chunks of any size, and yields the
+/// messages as soon as they're complete. This is what connections use to read the messages off
+/// their socket, and it's meant for receiving messages over transports zbus doesn't support
+/// natively.
+///
+/// The limits of the D-Bus specification are enforced as soon as the fixed part of the header of
+/// a message is received: a message larger than 128 MiB results in [`Error::MessageTooLarge`] and
+/// header fields larger than 64 MiB in [`Error::HeaderFieldsTooLarge`].
+///
+/// On unix, the file descriptors received along the bytes are fed separately, with
+/// [`MessageParser::feed_fds`]. Each message takes as many of them as it declares, in the order
+/// they were fed. The ones left once a message is taken are closed if no bytes of the next message
+/// have been fed yet, since the file descriptors are received along the message they're for.
+///
+/// # Example
+///
+/// ```
+/// # use zbus::{MessageBuilder, MessageParser};
+/// # (|| -> zbus::Result<()> {
+/// let ping = MessageBuilder::signal("/org/zbus/Test", "org.zbus.Test", "Ping")?.build(&())?;
+/// let pong = MessageBuilder::signal("/org/zbus/Test", "org.zbus.Test", "Pong")?.build(&())?;
+/// let bytes = [ping.as_bytes(), pong.as_bytes()].concat();
+///
+/// let mut parser = MessageParser::new();
+/// let mut received = vec![];
+/// for chunk in bytes.chunks(7) {
+///     parser.feed(chunk);
+///     while let Some(msg) = parser.next_message()? {
+///         received.push(msg.member().unwrap().to_string());
+///     }
+/// }
+/// assert_eq!(received, ["Ping", "Pong"]);
+/// # Ok(()) })().unwrap()
+/// ```
+#[derive(Debug)]
+pub struct MessageParser {
+    buffer: Vec<u8>,
+    #[cfg(unix)]
+    fds: VecDeque<OwnedFd>,
+    max_message_size: usize,
+    #[cfg(unix)]
+    max_fds: usize,
+}
+
+assert_impl_all!(MessageParser: Send, Sync, Unpin);
+
+impl MessageParser {
+    /// Create a parser, with no bytes fed yet.
+    pub fn new() -> Self {
+        Self {
+            buffer: vec![],
+            #[cfg(unix)]
+            fds: VecDeque::new(),
+            max_message_size: MAX_MESSAGE_SIZE,
+            #[cfg(unix)]
+            max_fds: usize::MAX,
+        }
+    }
+
+    /// Feed the parser with the next bytes received.
+    pub fn feed(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Feed the parser with the next file descriptors received.
+    ///
+    /// This function is Unix-specific.
+    #[cfg(unix)]
+    pub fn feed_fds(&mut self, fds: Vec<OwnedFd>) {
+        self.fds.extend(fds);
+    }
+
+    /// The number of bytes missing to complete the next message.
+    ///
+    /// Until the fixed part of the header of the message is received, this is the number of bytes
+    /// missing for it. It's 0 if the next message is complete.
+    pub fn bytes_needed(&self) -> usize {
+        match self.message_len() {
+            Ok(Some(len)) => len.saturating_sub(self.buffer.len()),
+            Ok(None) => MIN_MESSAGE_SIZE - self.buffer.len(),
+            Err(_) => 0,
+        }
+    }
+
+    /// Take the next message, if it has been received completely.
+    ///
+    /// An error is returned if the bytes received don't make a valid message, in which case the
+    /// bytes fed afterwards can't be parsed either, since the message boundaries are lost.
+    pub fn next_message(&mut self) -> Result<Option<Message>> {
+        self.parse(0)
+    }
+
+    /// Set the maximum size of the messages.
+    ///
+    /// Values larger than the maximum allowed by the D-Bus specification are capped to it.
+    pub(crate) fn set_max_message_size(&mut self, max: usize) {
+        self.max_message_size = max.min(MAX_MESSAGE_SIZE);
+    }
+
+    /// Set the maximum number of file descriptors a message can carry.
+    #[cfg(unix)]
+    pub(crate) fn set_max_fds(&mut self, max: usize) {
+        self.max_fds = max;
+    }
+
+    /// Take over the bytes and file descriptors fed to `other`, keeping the limits of `self`.
+    pub(crate) fn take_input(&mut self, other: MessageParser) {
+        self.buffer = other.buffer;
+        #[cfg(unix)]
+        {
+            self.fds = other.fds;
+        }
+    }
+
+    /// Take the next message, with `recv_seq` as its position in the stream of messages received.
+    pub(crate) fn parse(&mut self, recv_seq: u64) -> Result<Option<Message>> {
+        let len = match self.message_len()? {
+            Some(len) if len <= self.buffer.len() => len,
+            _ => return Ok(None),
+        };
+        let rest = self.buffer.split_off(len);
+        let bytes = mem::replace(&mut self.buffer, rest);
+
+        let msg = Message::from_raw_parts(
+            bytes,
+            #[cfg(unix)]
+            |count| {
+                if count > self.max_fds {
+                    self.fds.clear();
+
+                    return Err(Error::TooManyFds(count));
+                }
+                if count > self.fds.len() {
+                    return Err(Error::InvalidField);
+                }
+
+                Ok(self.fds.drain(..count).collect())
+            },
+            recv_seq,
+        );
+        // Nothing past the message is received from sockets, so the FDs it didn't claim are
+        // extraneous. They're closed rather than kept around for messages that may never claim
+        // them, which would let the peer accumulate them.
+        #[cfg(unix)]
+        if self.buffer.is_empty() && !self.fds.is_empty() {
+            debug!(
+                "Closing {} file descriptor(s) not claimed by the message",
+                self.fds.len()
+            );
+            self.fds.clear();
+        }
+
+        msg.map(Some)
+    }
+
+    /// Receive the bytes missing to complete the next message with `recvmsg`, directly into the
+    /// buffer of the parser.
+    ///
+    /// Only as many bytes as needed are received, so the bytes and file descriptors of the next
+    /// messages are left to be received.
+    pub(crate) fn poll_recv<F>(&mut self, recvmsg: F) -> Poll<Result<()>>
+    where
+        F: FnOnce(&mut [u8]) -> PollRecvmsg,
+    {
+        let filled = self.buffer.len();
+        self.buffer.resize(filled + self.bytes_needed().max(1), 0);
+        let res = recvmsg(&mut self.buffer[filled..]);
+        let len = match res {
+            #[cfg(unix)]
+            Poll::Ready(Ok((len, fds))) => {
+                self.feed_fds(fds);
+                len
+            }
+            #[cfg(not(unix))]
+            Poll::Ready(Ok(len)) => len,
+            Poll::Ready(Err(e)) => {
+                self.buffer.truncate(filled);
+
+                return Poll::Ready(Err(e.into()));
+            }
+            Poll::Pending => {
+                self.buffer.truncate(filled);
+
+                return Poll::Pending;
+            }
+        };
+        self.buffer.truncate(filled + len);
+        #[cfg(unix)]
+        if self.fds.len() > self.max_fds {
+            let count = self.fds.len();
+            self.fds.clear();
+
+            return Poll::Ready(Err(Error::TooManyFds(count)));
+        }
+        if len == 0 {
+            return Poll::Ready(Err(Error::InputOutput(
+                io::Error::new(io::ErrorKind::UnexpectedEof, "failed to receive message").into(),
+            )));
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
+    // The full length of the next message, once the fixed part of its header is received.
+    fn message_len(&self) -> Result<Option<usize>> {
+        if self.buffer.len() < MIN_MESSAGE_SIZE {
+            return Ok(None);
+        }
+
+        let (primary_header, fields_len) = MessagePrimaryHeader::read(&self.buffer)?;
+        let header_len = MIN_MESSAGE_SIZE + fields_len as usize;
+        let body_padding = padding_for_8_bytes(header_len);
+        let body_len = primary_header.body_len() as usize;
+        let total_len = header_len + body_padding + body_len;
+        if total_len > self.max_message_size {
+            return Err(Error::MessageTooLarge(total_len));
+        }
+
+        Ok(Some(total_len))
+    }
+}
+
+impl Default for MessageParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MessageParser;
+    use crate::{message_header::MIN_MESSAGE_SIZE, EndianSig, Error, MessageBuilder};
+    use test_log::test;
+
+    #[test]
+    fn chunks() {
+        let msgs = ["First", "Second", "Third"].map(|member| {
+            MessageBuilder::signal("/org/zbus/Test", "org.zbus.Test", member)
+                .unwrap()
+                .build(&(member, 42u32))
+                .unwrap()
+        });
+        let bytes = msgs
+            .iter()
+            .flat_map(|m| m.as_bytes())
+            .copied()
+            .collect::<Vec<_>>();
+
+        for chunk_len in [1, 7, 64, bytes.len()] {
+            let mut parser = MessageParser::new();
+            assert_eq!(parser.bytes_needed(), MIN_MESSAGE_SIZE);
+            let mut received = vec![];
+            for chunk in bytes.chunks(chunk_len) {
+                parser.feed(chunk);
+                while let Some(msg) = parser.next_message().unwrap() {
+                    received.push(msg);
+                }
+            }
+            assert_eq!(parser.bytes_needed(), MIN_MESSAGE_SIZE);
+            assert_eq!(received.len(), msgs.len());
+            for (received, msg) in received.iter().zip(&msgs) {
+                assert_eq!(received.as_bytes(), msg.as_bytes());
+                assert_eq!(received.member(), msg.member());
+            }
+        }
+
+        // The missing bytes are known once the fixed part of the header is received.
+        let mut parser = MessageParser::new();
+        parser.feed(&bytes[..MIN_MESSAGE_SIZE - 1]);
+        assert_eq!(parser.bytes_needed(), 1);
+        parser.feed(&bytes[MIN_MESSAGE_SIZE - 1..MIN_MESSAGE_SIZE]);
+        let len = msgs[0].as_bytes().len();
+        assert_eq!(parser.bytes_needed(), len - MIN_MESSAGE_SIZE);
+        assert!(parser.next_message().unwrap().is_none());
+        parser.feed(&bytes[MIN_MESSAGE_SIZE..len]);
+        assert_eq!(parser.bytes_needed(), 0);
+        assert_eq!(
+            parser.next_message().unwrap().unwrap().member().unwrap(),
+            "First"
+        );
+    }
+
+    #[test]
+    fn too_large() {
+        let msg = MessageBuilder::signal("/org/zbus/Test", "org.zbus.Test", "Big")
+            .unwrap()
+            .build(&vec![0u8; 1024])
+            .unwrap();
+        let len = msg.as_bytes().len();
+        let mut parser = MessageParser::new();
+        parser.set_max_message_size(1024);
+        parser.feed(&msg.as_bytes()[..MIN_MESSAGE_SIZE]);
+        assert!(matches!(parser.next_message(), Err(Error::MessageTooLarge(l)) if l == len));
+    }
+
+    #[test]
+    fn spec_limits() {
+        let msg = MessageBuilder::signal("/org/zbus/Test", "org.zbus.Test", "Big")
+            .unwrap()
+            .endian(EndianSig::Little)
+            .build(&())
+            .unwrap();
+        let fixed = &msg.as_bytes()[..MIN_MESSAGE_SIZE];
+
+        // A body or header fields array larger than allowed is rejected as soon as the fixed part
+        // of the header is received.
+        let body_len = 128 * 1024 * 1024;
+        let fields_len = 64 * 1024 * 1024 + 8;
+        for (pos, len) in [(4, body_len), (12, fields_len)] {
+            let mut bytes = fixed.to_vec();
+            bytes[pos..pos + 4].copy_from_slice(&(len as u32).to_le_bytes());
+            let mut parser = MessageParser::new();
+            parser.feed(&bytes);
+            match parser.next_message() {
+                Err(Error::MessageTooLarge(size)) => {
+                    assert_eq!(pos, 4);
+                    assert!(size > body_len);
+                }
+                Err(Error::HeaderFieldsTooLarge(l)) => {
+                    assert_eq!(pos, 12);
+                    assert_eq!(l, fields_len);
+                }
+                res => panic!("unexpected result: {:?}", res.map(|_| ())),
+            }
+            assert_eq!(parser.bytes_needed(), 0);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn fds() {
+        use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd};
+        use zvariant::{Fd, OwnedFd};
+
+        let open = || {
+            let file = std::fs::File::open("/dev/null").unwrap();
+
+            unsafe { OwnedFd::from_raw_fd(file.into_raw_fd()) }
+        };
+        // The builder only keeps one of the same FDs, so they must be distinct.
+        let sent = [open(), open(), open()];
+        let msgs = [&sent[..2], &[], &sent[2..]].map(|fds| {
+            let fds: Vec<_> = fds.iter().map(Fd::from).collect();
+            MessageBuilder::signal("/org/zbus/Test", "org.zbus.Test", "Fds")
+                .unwrap()
+                .build(&(fds,))
+                .unwrap()
+        });
+
+        // The FDs of all the messages, received at once.
+        let mut parser = MessageParser::new();
+        for msg in &msgs {
+            parser.feed(msg.as_bytes());
+        }
+        let fds = vec![open(), open(), open()];
+        let raw_fds = fds.iter().map(|fd| fd.as_raw_fd()).collect::<Vec<_>>();
+        parser.feed_fds(fds);
+        let mut received = vec![];
+        while let Some(msg) = parser.next_message().unwrap() {
+            let (fds,): (Vec<Fd>,) = msg.body().unwrap();
+            received.extend(fds.iter().map(|fd| fd.as_raw_fd()));
+            assert_eq!(msg.take_fds().len(), fds.len());
+        }
+        assert_eq!(received, raw_fds);
+
+        // A message declaring more FDs than received.
+        let mut parser = MessageParser::new();
+        parser.feed(msgs[0].as_bytes());
+        parser.feed_fds(vec![open()]);
+        assert!(matches!(parser.next_message(), Err(Error::InvalidField)));
+
+        // The FDs not claimed by a message are not kept for the next ones.
+        let mut parser = MessageParser::new();
+        parser.feed(msgs[1].as_bytes());
+        parser.feed_fds(vec![open(), open()]);
+        assert!(parser.next_message().unwrap().is_some());
+        parser.feed(msgs[0].as_bytes());
+        assert!(matches!(parser.next_message(), Err(Error::InvalidField)));
+
+        // The limit applies to each message, not to all the FDs received.
+        let mut parser = MessageParser::new();
+        parser.set_max_fds(2);
+        for msg in &msgs {
+            parser.feed(msg.as_bytes());
+        }
+        parser.feed_fds(vec![open(), open(), open()]);
+        while parser.next_message().unwrap().is_some() {}
+        let mut parser = MessageParser::new();
+        parser.set_max_fds(1);
+        parser.feed(msgs[0].as_bytes());
+        parser.feed_fds(vec![open(), open()]);
+        assert!(matches!(parser.next_message(), Err(Error::TooManyFds(2))));
+    }
+}
@@ -5,7 +5,7 @@ use std::{
     task::{Context, Poll},
 };
 
-use async_broadcast::Receiver as ActiveReceiver;
+use async_broadcast::{Receiver as ActiveReceiver, TryRecvError};
 use futures_core::stream;
 use futures_util::stream::FusedStream;
 use ordered_stream::{OrderedStream, PollResult};
@@ -21,6 +21,10 @@ use crate::{
 ///
 /// You can convert a [`Connection`] to this type and back to [`Connection`].
 ///
+/// `MessageStream` is cheap to clone and each clone yields its own copy of every message, so you
+/// can hand out clones to different consumers. If the stream was created for a
+/// match rule, the rule stays registered until the last clone is dropped.
+///
 /// **NOTE**: You must ensure a `MessageStream` is continuously polled or you will experience hangs.
 /// If you don't need to continuously poll the `MessageStream` but need to keep it around for later
 /// use, keep the connection around and convert it into a `MessageStream` when needed. The
@@ -135,7 +139,7 @@ impl MessageStream {
         R::Error: Into<crate::Error>,
     {
         let rule = rule.try_into().map_err(Into::into)?;
-        let msg_receiver = conn.add_match(rule.clone(), max_queued).await?;
+        let msg_receiver = conn.subscribe(rule.clone(), max_queued).await?;
 
         Ok(Self::for_subscription_channel(
             msg_receiver,
@@ -144,9 +148,27 @@ impl MessageStream {
         ))
     }
 
+    /// Take the next message out of the queue, if any, without waiting.
+    ///
+    /// Returns `None` if no message is queued at the moment, or if the stream has ended (see
+    /// [`FusedStream::is_terminated`]). This is useful when the connection is driven from an
+    /// external event loop (see [`Connection::raw_fd`]).
+    pub fn try_receive(&mut self) -> Option<Result<Arc<Message>>> {
+        loop {
+            match self.inner.msg_receiver.try_recv() {
+                Ok(msg) => return Some(msg),
+                Err(TryRecvError::Overflowed(_)) => continue,
+                Err(TryRecvError::Empty) | Err(TryRecvError::Closed) => return None,
+            }
+        }
+    }
+
     /// The associated match rule, if any.
     pub fn match_rule(&self) -> Option<MatchRule<'_>> {
-        self.inner.match_rule.as_deref().cloned()
+        self.inner
+            .subscription
+            .as_ref()
+            .and_then(|s| s.rule.as_deref().cloned())
     }
 
     /// The maximum number of messages to queue for this stream.
@@ -171,11 +193,18 @@ impl MessageStream {
     ) -> Self {
         let conn_inner = conn.inner.clone();
 
+        let subscription = rule.map(|rule| {
+            Arc::new(Subscription {
+                conn_inner: conn_inner.clone(),
+                rule: Some(rule),
+            })
+        });
+
         Self {
             inner: Inner {
                 conn_inner,
                 msg_receiver,
-                match_rule: rule,
+                subscription,
             },
         }
     }
@@ -245,7 +274,7 @@ impl From<Connection> for MessageStream {
             inner: Inner {
                 conn_inner,
                 msg_receiver,
-                match_rule: None,
+                subscription: None,
             },
         }
     }
@@ -275,16 +304,23 @@ impl From<&MessageStream> for Connection {
 struct Inner {
     conn_inner: Arc<ConnectionInner>,
     msg_receiver: ActiveReceiver<Result<Arc<Message>>>,
-    match_rule: Option<OwnedMatchRule>,
+    // Shared by all the clones of the stream so the match rule is only removed once.
+    subscription: Option<Arc<Subscription>>,
 }
 
-impl Drop for Inner {
+#[derive(Debug)]
+struct Subscription {
+    conn_inner: Arc<ConnectionInner>,
+    rule: Option<OwnedMatchRule>,
+}
+
+impl Drop for Subscription {
     fn drop(&mut self) {
         let conn = Connection {
             inner: self.conn_inner.clone(),
         };
 
-        if let Some(rule) = self.match_rule.take() {
+        if let Some(rule) = self.rule.take() {
             conn.queue_remove_match(rule);
         }
     }
@@ -297,10 +333,71 @@ impl AsyncDrop for MessageStream {
             inner: self.inner.conn_inner.clone(),
         };
 
-        if let Some(rule) = self.inner.match_rule.take() {
+        // Only the last clone removes the rule.
+        let rule = self
+            .inner
+            .subscription
+            .take()
+            .and_then(|s| Arc::try_unwrap(s).ok())
+            .and_then(|mut s| s.rule.take());
+        if let Some(rule) = rule {
             if let Err(e) = conn.remove_match(rule).await {
                 warn!("Failed to remove match rule: {}", e);
             }
         }
     }
 }
+
+#[cfg(unix)]
+#[cfg(test)]
+mod tests {
+    use futures_util::{future::join, StreamExt, TryStreamExt};
+    use ntest::timeout;
+    use test_log::test;
+
+    use super::*;
+    use crate::{utils::unix_p2p_pipe, MessageType};
+
+    #[test]
+    #[timeout(15000)]
+    fn clones() {
+        crate::utils::block_on(test_clones()).unwrap();
+    }
+
+    async fn test_clones() -> Result<()> {
+        let (client, server) = unix_p2p_pipe().await?;
+
+        let rule = MatchRule::builder()
+            .msg_type(MessageType::Signal)
+            .member("Ping")?
+            .build();
+        let stream = MessageStream::for_match_rule(rule, &server, None).await?;
+        let clone = stream.clone();
+        assert_eq!(server.stats().await.match_rules(), 1);
+
+        for member in ["Pong", "Ping"] {
+            client
+                .emit_signal(None::<()>, "/", "org.zbus.Test", member, &())
+                .await?;
+        }
+        // Both streams get the message and can be used with the usual stream combinators.
+        let (msgs, clone_msgs) = join(
+            stream.clone().take(1).try_collect::<Vec<_>>(),
+            clone
+                .map_ok(|msg| msg.to_string())
+                .take(1)
+                .try_collect::<Vec<_>>(),
+        )
+        .await;
+        assert_eq!(msgs?[0].to_string(), "Signal Ping");
+        assert_eq!(clone_msgs?, ["Signal Ping"]);
+
+        // Dropping clones doesn't remove the rule, only dropping the last one does.
+        stream.clone().async_drop().await;
+        assert_eq!(server.stats().await.match_rules(), 1);
+        stream.async_drop().await;
+        assert_eq!(server.stats().await.match_rules(), 0);
+
+        Ok(())
+    }
+}
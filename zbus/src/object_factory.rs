@@ -0,0 +1,237 @@
+use std::{collections::HashMap, convert::TryFrom, sync::Arc};
+
+use zbus_names::InterfaceName;
+use zvariant::ObjectPath;
+
+use crate::{async_lock::RwLock, fdo, Interface, Result};
+
+/// What becomes of the objects created by a factory.
+///
+/// See [`ObjectServer::at_factory`] for details.
+///
+/// [`ObjectServer::at_factory`]: crate::ObjectServer::at_factory
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FactoryPolicy {
+    /// The object is kept at its path once created, just like the ones registered through
+    /// [`ObjectServer::at`], until it's removed.
+    ///
+    /// [`ObjectServer::at`]: crate::ObjectServer::at
+    Cache,
+    /// The object is dropped once the method call it was created for is handled, so a new one is
+    /// created for each call. The calls arriving for the same path in the meantime are served by
+    /// it too.
+    Discard,
+}
+
+type Create = dyn Fn(&ObjectPath<'_>, &HashMap<&str, &str>) -> fdo::Result<Arc<RwLock<dyn Interface>>>
+    + Send
+    + Sync;
+
+// A factory, creating the interface of the objects whose path matches a template.
+#[derive(derivative::Derivative)]
+#[derivative(Debug)]
+pub(crate) struct ObjectFactory {
+    template: String,
+    segments: Vec<Segment>,
+    name: InterfaceName<'static>,
+    policy: FactoryPolicy,
+    #[derivative(Debug = "ignore")]
+    create: Box<Create>,
+}
+
+#[derive(Debug)]
+enum Segment {
+    Literal(String),
+    // A `{name}` placeholder, matching any single element of the path.
+    Param(String),
+}
+
+impl ObjectFactory {
+    pub(crate) fn new<F, I>(template: &str, policy: FactoryPolicy, create: F) -> Result<Self>
+    where
+        I: Interface,
+        F: Fn(&ObjectPath<'_>, &HashMap<&str, &str>) -> fdo::Result<I> + Send + Sync + 'static,
+    {
+        let segments: Vec<_> = template
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(|s| match param_name(s) {
+                Some(name) => Segment::Param(name.to_string()),
+                None => Segment::Literal(s.to_string()),
+            })
+            .collect();
+        // The template must be a valid object path, once its placeholders are filled.
+        let path = template
+            .split('/')
+            .map(|s| param_name(s).map(|_| "_").unwrap_or(s))
+            .collect::<Vec<_>>()
+            .join("/");
+        ObjectPath::try_from(path.as_str())?;
+
+        Ok(Self {
+            template: template.to_string(),
+            segments,
+            name: I::name(),
+            policy,
+            create: Box::new(move |path, params| {
+                let iface = create(path, params)?;
+
+                Ok(Arc::new(RwLock::new(iface)))
+            }),
+        })
+    }
+
+    pub(crate) fn interface_name(&self) -> &InterfaceName<'static> {
+        &self.name
+    }
+
+    pub(crate) fn policy(&self) -> FactoryPolicy {
+        self.policy
+    }
+
+    // Get the values of the placeholders of the template, if `path` matches it.
+    pub(crate) fn matches<'p>(&self, path: &'p ObjectPath<'_>) -> Option<HashMap<&str, &'p str>> {
+        let mut elements = path.split('/').filter(|s| !s.is_empty());
+        let mut params = HashMap::new();
+        for segment in &self.segments {
+            let element = elements.next()?;
+            match segment {
+                Segment::Literal(literal) if literal != element => return None,
+                Segment::Literal(_) => (),
+                Segment::Param(name) => {
+                    params.insert(name.as_str(), element);
+                }
+            }
+        }
+
+        match elements.next() {
+            Some(_) => None,
+            None => Some(params),
+        }
+    }
+
+    pub(crate) fn create(
+        &self,
+        path: &ObjectPath<'_>,
+        params: &HashMap<&str, &str>,
+    ) -> fdo::Result<Arc<RwLock<dyn Interface>>> {
+        (self.create)(path, params)
+    }
+}
+
+// The factories, indexed by the elements of their template, so only the ones of the templates with
+// the shape of a path are looked at when matching it.
+#[derive(Debug, Default)]
+pub(crate) struct ObjectFactories {
+    factories: Vec<Arc<ObjectFactory>>,
+    literals: HashMap<String, ObjectFactories>,
+    param: Option<Box<ObjectFactories>>,
+}
+
+impl ObjectFactories {
+    // Add the factory, unless there is already one for its interface and template.
+    pub(crate) fn insert(&mut self, factory: ObjectFactory) -> bool {
+        let mut node = self;
+        for segment in &factory.segments {
+            node = match segment {
+                Segment::Literal(literal) => node.literals.entry(literal.clone()).or_default(),
+                Segment::Param(_) => node.param.get_or_insert_with(Default::default),
+            };
+        }
+        if node
+            .factories
+            .iter()
+            .any(|f| f.template == factory.template && f.name == factory.name)
+        {
+            return false;
+        }
+        node.factories.push(Arc::new(factory));
+
+        true
+    }
+
+    // Remove the factory for the interface and template, if any.
+    pub(crate) fn remove(&mut self, template: &str, name: &InterfaceName<'_>) -> bool {
+        let segments: Vec<_> = template.split('/').filter(|s| !s.is_empty()).collect();
+
+        self.remove_at(&segments, template, name)
+    }
+
+    fn remove_at(&mut self, segments: &[&str], template: &str, name: &InterfaceName<'_>) -> bool {
+        let (segment, rest) = match segments.split_first() {
+            Some(split) => split,
+            None => {
+                let len = self.factories.len();
+                self.factories
+                    .retain(|f| f.template != template || f.name != *name);
+
+                return self.factories.len() != len;
+            }
+        };
+        // Drop the branches left without factories along the way.
+        match param_name(segment) {
+            Some(_) => {
+                let param = match &mut self.param {
+                    Some(param) => param,
+                    None => return false,
+                };
+                let removed = param.remove_at(rest, template, name);
+                if param.is_empty() {
+                    self.param = None;
+                }
+
+                removed
+            }
+            None => {
+                let literal = match self.literals.get_mut(*segment) {
+                    Some(literal) => literal,
+                    None => return false,
+                };
+                let removed = literal.remove_at(rest, template, name);
+                if literal.is_empty() {
+                    self.literals.remove(*segment);
+                }
+
+                removed
+            }
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.factories.is_empty() && self.literals.is_empty() && self.param.is_none()
+    }
+
+    // Get the factories whose template `path` matches.
+    pub(crate) fn matching(&self, path: &ObjectPath<'_>) -> Vec<Arc<ObjectFactory>> {
+        let elements: Vec<_> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let mut matching = vec![];
+        self.collect_matching(&elements, &mut matching);
+
+        matching
+    }
+
+    fn collect_matching(&self, elements: &[&str], matching: &mut Vec<Arc<ObjectFactory>>) {
+        let (element, rest) = match elements.split_first() {
+            Some(split) => split,
+            None => {
+                matching.extend(self.factories.iter().cloned());
+
+                return;
+            }
+        };
+        if let Some(literal) = self.literals.get(*element) {
+            literal.collect_matching(rest, matching);
+        }
+        if let Some(param) = &self.param {
+            param.collect_matching(rest, matching);
+        }
+    }
+}
+
+// The name of the placeholder, if the element of the template is one.
+fn param_name(element: &str) -> Option<&str> {
+    element
+        .strip_prefix('{')?
+        .strip_suffix('}')
+        .filter(|name| !name.is_empty())
+}
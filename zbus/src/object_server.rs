@@ -1,22 +1,28 @@
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, BTreeMap, HashMap, HashSet},
     convert::TryInto,
     fmt::Write,
     marker::PhantomData,
     ops::{Deref, DerefMut},
     sync::Arc,
 };
-use tracing::{debug, instrument, trace};
+use tracing::{debug, instrument, trace, Instrument};
 
+use enumflags2::BitFlags;
 use static_assertions::assert_impl_all;
-use zbus_names::InterfaceName;
+use zbus_names::{InterfaceName, MemberName, WellKnownName};
 use zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Value};
 
 use crate::{
-    async_lock::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+    async_lock::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    dispatch_stats::{DispatchOutcome, DispatchStats, DispatchTracker},
     fdo,
-    fdo::{Introspectable, ManagedObjects, ObjectManager, Peer, Properties},
-    Connection, DispatchResult, Error, Interface, Message, Result, SignalContext, WeakConnection,
+    fdo::{Introspectable, ManagedObjects, ObjectManager, Peer, Properties, RequestNameFlags},
+    forwarder::Forwarder,
+    message_span,
+    object_factory::{FactoryPolicy, ObjectFactories, ObjectFactory},
+    Connection, DBusError, DispatchResult, Error, Interface, Message, MessageFlags, Proxy, Result,
+    SignalContext, WeakConnection,
 };
 
 /// Opaque structure that derefs to an `Interface` type.
@@ -94,17 +100,16 @@ where
         }
     }
 
-    /// Get a reference to the underlying interface.
+    /// Get a mutable reference to the underlying interface.
+    ///
+    /// This allows changing the state of the interface from outside of its method handlers. The
+    /// changes of properties are not signaled automatically though: use the generated
+    /// `<property_name>_changed` methods, along with [`InterfaceRef::signal_context`], for that.
     ///
     /// **WARNINGS:** Since the `ObjectServer` will not be able to access the interface in question
     /// until the return value of this method is dropped, it is highly recommended that the scope
     /// of the interface returned is restricted.
     ///
-    /// # Errors
-    ///
-    /// If the interface at this instance's path is not valid, `Error::InterfaceNotFound` error is
-    /// returned.
-    ///
     /// # Examples
     ///
     /// ```no_run
@@ -154,6 +159,9 @@ where
         }
     }
 
+    /// The signal context for the object path of the interface.
+    ///
+    /// This is to be used to emit the signals of the interface, including the property changes.
     pub fn signal_context(&self) -> &SignalContext<'static> {
         &self.ctxt
     }
@@ -176,6 +184,50 @@ pub(crate) struct Node {
     children: HashMap<String, Node>,
     #[derivative(Debug = "ignore")]
     interfaces: HashMap<InterfaceName<'static>, Arc<RwLock<dyn Interface>>>,
+    // The interfaces served for the descendants not having them (see `ObjectServer::at_fallback`).
+    #[derivative(Debug = "ignore")]
+    fallbacks: HashMap<InterfaceName<'static>, Arc<RwLock<dyn Interface>>>,
+    // The interfaces forwarded to other objects (see `ObjectServer::forward`).
+    forwards: HashMap<InterfaceName<'static>, Arc<Forwarder>>,
+    // The factories of the objects with a templated path (see `ObjectServer::at_factory`). Only
+    // the root node has any.
+    factories: ObjectFactories,
+}
+
+// Whether the interface is one of those all nodes have.
+fn is_standard_interface(name: &InterfaceName<'_>) -> bool {
+    *name == Peer::name() || *name == Introspectable::name() || *name == Properties::name()
+}
+
+// Ask the access policy of the connection, if any, whether the call to a method of `iface_name`
+// is allowed.
+async fn check_access(
+    connection: &Connection,
+    msg: &Message,
+    iface_name: &InterfaceName<'_>,
+) -> fdo::Result<()> {
+    let policy = match connection.access_policy() {
+        Some(policy) => policy,
+        None => return Ok(()),
+    };
+    if *iface_name != Properties::name() {
+        return policy.check(connection, msg, iface_name).await;
+    }
+
+    // The property accessors are about the interface given in the body.
+    let target = match msg.member().as_ref().map(|m| m.as_str()) {
+        Some("Get") => msg
+            .body::<(InterfaceName<'_>, &str)>()
+            .map(|(iface, _)| iface),
+        Some("Set") => msg
+            .body::<(InterfaceName<'_>, &str, Value<'_>)>()
+            .map(|(iface, _, _)| iface),
+        Some("GetAll") => msg.body::<InterfaceName<'_>>(),
+        _ => return policy.check(connection, msg, iface_name).await,
+    };
+    let target = target.map_err(|e| fdo::Error::InvalidArgs(e.to_string()))?;
+
+    policy.check(connection, msg, &target).await
 }
 
 impl Node {
@@ -210,6 +262,105 @@ impl Node {
         Some(node)
     }
 
+    // Get the child Node at path (if any), along with the ancestors of it that have fallback
+    // interfaces, the closest first.
+    fn get_child_with_fallbacks(&self, path: &ObjectPath<'_>) -> (Option<&Node>, Vec<&Node>) {
+        let mut node = self;
+        let mut fallbacks = vec![];
+
+        for i in path.split('/').skip(1) {
+            if i.is_empty() {
+                continue;
+            }
+            if !node.fallbacks.is_empty() {
+                fallbacks.insert(0, node);
+            }
+            match node.children.get(i) {
+                Some(n) => node = n,
+                None => return (None, fallbacks),
+            }
+        }
+
+        (Some(node), fallbacks)
+    }
+
+    // Get the interface of the object at path. If the object doesn't have it (or doesn't exist),
+    // the fallback interface of the closest ancestor having one is returned.
+    pub(crate) fn lookup_interface(
+        &self,
+        path: &ObjectPath<'_>,
+        interface_name: InterfaceName<'_>,
+    ) -> fdo::Result<Arc<RwLock<dyn Interface>>> {
+        let (node, fallbacks) = self.get_child_with_fallbacks(path);
+        let iface = match node {
+            Some(node) => node.interfaces.get(&interface_name),
+            // Peer calls are answered whatever the path, as the specification expects. The
+            // interface is stateless, so the one of the root node is as good as any.
+            None if interface_name == Peer::name() => self.interfaces.get(&interface_name),
+            None if fallbacks.is_empty() => {
+                return Err(fdo::Error::UnknownObject(format!(
+                    "Unknown object '{path}'"
+                )));
+            }
+            // Virtual objects get the standard interfaces too. These are stateless, so the ones of
+            // the root node are as good as any.
+            None if is_standard_interface(&interface_name) => self.interfaces.get(&interface_name),
+            None => None,
+        };
+
+        iface
+            .or_else(|| {
+                fallbacks
+                    .iter()
+                    .find_map(|n| n.fallbacks.get(&interface_name))
+            })
+            .cloned()
+            .ok_or_else(|| {
+                fdo::Error::UnknownInterface(format!(
+                    "Unknown interface '{interface_name}' at '{path}'"
+                ))
+            })
+    }
+
+    // Get the forwarder of the interface of the object at path, if it's forwarded.
+    pub(crate) fn lookup_forward(
+        &self,
+        path: &ObjectPath<'_>,
+        interface_name: &InterfaceName<'_>,
+    ) -> Option<Arc<Forwarder>> {
+        self.get_child(path)?.forwards.get(interface_name).cloned()
+    }
+
+    // Get all the interfaces of the object at path, including the fallback interfaces it gets from
+    // its ancestors, sorted by name.
+    fn interfaces(
+        &self,
+        path: &ObjectPath<'_>,
+    ) -> fdo::Result<BTreeMap<&InterfaceName<'static>, &Arc<RwLock<dyn Interface>>>> {
+        let (node, fallbacks) = self.get_child_with_fallbacks(path);
+        let mut interfaces = BTreeMap::new();
+        match node {
+            Some(node) => interfaces.extend(node.interfaces.iter()),
+            None if fallbacks.is_empty() => {
+                return Err(fdo::Error::UnknownObject(format!(
+                    "Unknown object '{path}'"
+                )));
+            }
+            None => interfaces.extend(
+                self.interfaces
+                    .iter()
+                    .filter(|(name, _)| is_standard_interface(name)),
+            ),
+        }
+        for fallback in fallbacks {
+            for (name, iface) in &fallback.fallbacks {
+                interfaces.entry(name).or_insert(iface);
+            }
+        }
+
+        Ok(interfaces)
+    }
+
     // Get the child Node at path. Optionally create one if it doesn't exist.
     // It also returns the path of parent node that implements ObjectManager (if any). If multiple
     // parents implement it (they shouldn't), then the closest one is returned.
@@ -259,25 +410,88 @@ impl Node {
         self.interfaces.remove(&interface_name).is_some()
     }
 
+    fn remove_forward(&mut self, interface_name: InterfaceName<'static>) -> bool {
+        self.forwards.remove(&interface_name).is_some()
+    }
+
+    // Remove all the interfaces (forwarded ones included), except for the ones all nodes have,
+    // returning their names.
+    fn remove_interfaces(&mut self) -> Vec<InterfaceName<'static>> {
+        let mut names: Vec<_> = self
+            .interfaces
+            .keys()
+            .filter(|k| !is_standard_interface(k))
+            .cloned()
+            .collect();
+        for name in &names {
+            self.interfaces.remove(name);
+        }
+        names.extend(self.forwards.drain().map(|(name, _)| name));
+
+        names
+    }
+
     fn is_empty(&self) -> bool {
-        !self.interfaces.keys().any(|k| {
-            *k != Peer::name()
-                && *k != Introspectable::name()
-                && *k != Properties::name()
-                && *k != ObjectManager::name()
-        })
+        self.forwards.is_empty()
+            && !self.interfaces.keys().any(|k| {
+                *k != Peer::name()
+                    && *k != Introspectable::name()
+                    && *k != Properties::name()
+                    && *k != ObjectManager::name()
+            })
+    }
+
+    // Whether the node has neither children, nor any interface besides the ones all nodes have.
+    fn is_unused(&self) -> bool {
+        self.children.is_empty()
+            && self.fallbacks.is_empty()
+            && self.factories.is_empty()
+            && self.forwards.is_empty()
+            && self.interfaces.keys().all(is_standard_interface)
     }
 
     fn remove_node(&mut self, node: &str) -> bool {
         self.children.remove(node).is_some()
     }
 
+    // Remove the descendant node at `path`, and then its ancestors, as long as they're unused.
+    // The nodes leading to other objects are kept.
+    fn prune(&mut self, path: &ObjectPath<'_>) {
+        let mut node_path = path.as_str();
+        while let Some((parent_path, name)) = node_path.rsplit_once('/') {
+            if name.is_empty() {
+                // Reached the root.
+                break;
+            }
+            let node = self
+                .get_child(&ObjectPath::from_str_unchecked(node_path))
+                .expect("node missing");
+            if !node.is_unused() {
+                break;
+            }
+
+            let parent_path = if parent_path.is_empty() {
+                "/"
+            } else {
+                parent_path
+            };
+            self.get_child_mut(&ObjectPath::from_str_unchecked(parent_path), false)
+                .0
+                .expect("parent node missing")
+                .remove_node(name);
+            node_path = parent_path;
+        }
+    }
+
     // Takes a closure so caller can avoid having to create an Arc & RwLock in case interface was
     // already added.
     fn at<F>(&mut self, name: InterfaceName<'static>, iface_creator: F) -> bool
     where
         F: FnOnce() -> Arc<RwLock<dyn Interface>>,
     {
+        if self.forwards.contains_key(&name) {
+            return false;
+        }
         match self.interfaces.entry(name) {
             Entry::Vacant(e) => e.insert(iface_creator()),
             Entry::Occupied(_) => return false,
@@ -286,48 +500,68 @@ impl Node {
         true
     }
 
-    #[async_recursion::async_recursion]
-    async fn introspect_to_writer<W: Write + Send>(&self, writer: &mut W, level: usize) {
-        if level == 0 {
-            writeln!(
-                writer,
-                r#"
-<!DOCTYPE node PUBLIC "-//freedesktop//DTD D-BUS Object Introspection 1.0//EN"
- "http://www.freedesktop.org/standards/dbus/1.0/introspect.dtd">
-<node>"#
-            )
-            .unwrap();
+    fn forward(&mut self, name: InterfaceName<'static>, forwarder: Arc<Forwarder>) -> bool {
+        if self.interfaces.contains_key(&name) {
+            return false;
         }
+        match self.forwards.entry(name) {
+            Entry::Vacant(e) => e.insert(forwarder),
+            Entry::Occupied(_) => return false,
+        };
 
-        for iface in self.interfaces.values() {
-            iface.read().await.introspect_to_writer(writer, level + 2);
-        }
+        true
+    }
 
-        for (path, node) in &self.children {
-            let level = level + 2;
-            writeln!(
-                writer,
-                "{:indent$}<node name=\"{}\">",
-                "",
-                path,
-                indent = level
-            )
-            .unwrap();
-            node.introspect_to_writer(writer, level).await;
-            writeln!(writer, "{:indent$}</node>", "", indent = level).unwrap();
-        }
+    fn at_fallback<F>(&mut self, name: InterfaceName<'static>, iface_creator: F) -> bool
+    where
+        F: FnOnce() -> Arc<RwLock<dyn Interface>>,
+    {
+        match self.fallbacks.entry(name) {
+            Entry::Vacant(e) => e.insert(iface_creator()),
+            Entry::Occupied(_) => return false,
+        };
 
-        if level == 0 {
-            writeln!(writer, "</node>").unwrap();
-        }
+        true
     }
 
-    pub(crate) async fn introspect(&self) -> String {
-        let mut xml = String::with_capacity(1024);
+    // Introspect the object at path, including the fallback interfaces it gets from its ancestors.
+    //
+    // Only the direct children are listed, as (empty) nodes. As per the D-Bus specification,
+    // peers introspect the children themselves, should they want to know more about them. The
+    // virtual children served by fallbacks are not known, so they can't be listed.
+    pub(crate) async fn introspect(&self, path: &ObjectPath<'_>) -> fdo::Result<String> {
+        let interfaces = self.interfaces(path)?;
+        let node = self.get_child(path);
+        let mut forwards: Vec<_> = node
+            .map(|node| node.forwards.iter().collect())
+            .unwrap_or_default();
+        forwards.sort_unstable_by_key(|(name, _)| *name);
+        let mut children: Vec<_> = node
+            .map(|node| node.children.keys().collect())
+            .unwrap_or_default();
+        children.sort_unstable();
 
-        self.introspect_to_writer(&mut xml, 0).await;
+        let mut xml = String::with_capacity(1024);
+        writeln!(
+            xml,
+            r#"
+<!DOCTYPE node PUBLIC "-//freedesktop//DTD D-BUS Object Introspection 1.0//EN"
+ "http://www.freedesktop.org/standards/dbus/1.0/introspect.dtd">
+<node>"#
+        )
+        .unwrap();
+        for iface in interfaces.values() {
+            iface.read().await.introspect_to_writer(&mut xml, 2);
+        }
+        for (_, forwarder) in forwards {
+            forwarder.introspect_to_writer(&mut xml, 2);
+        }
+        for name in children {
+            writeln!(xml, "  <node name=\"{name}\"/>").unwrap();
+        }
+        writeln!(xml, "</node>").unwrap();
 
-        xml
+        Ok(xml)
     }
 
     #[async_recursion::async_recursion]
@@ -346,7 +580,18 @@ impl Node {
                 let props = node.get_properties(iface_name.clone()).await;
                 interfaces.insert(iface_name.clone().into(), props);
             }
-            managed_objects.insert(node.path.clone(), interfaces);
+            for (iface_name, forwarder) in &node.forwards {
+                let props = forwarder.get_all().await.unwrap_or_else(|e| {
+                    debug!("Failed to get the properties of `{}`: {}", iface_name, e);
+
+                    HashMap::new()
+                });
+                interfaces.insert(iface_name.clone().into(), props);
+            }
+            // Intermediate nodes, only there to lead to their children, are not objects.
+            if !interfaces.is_empty() {
+                managed_objects.insert(node.path.clone(), interfaces);
+            }
             managed_objects.extend(node.get_managed_objects().await);
         }
 
@@ -373,6 +618,10 @@ impl Node {
 /// All object paths will have the standard interfaces implemented on your behalf, such as
 /// `org.freedesktop.DBus.Introspectable` or `org.freedesktop.DBus.Properties`.
 ///
+/// The method calls received on the associated connection are dispatched to the object server in
+/// the background, so there is no need to read the incoming messages yourself. Use
+/// [`Connection::stop_object_server`] to stop that, e.g for a graceful shutdown.
+///
 /// # Example
 ///
 /// This example exposes the `org.myiface.Example.Quit` method on the `/org/zbus/path`
@@ -427,6 +676,9 @@ impl Node {
 pub struct ObjectServer {
     conn: WeakConnection,
     root: RwLock<Node>,
+    // The well-known names bound to the object server.
+    names: Mutex<HashSet<WellKnownName<'static>>>,
+    tracker: DispatchTracker,
 }
 
 assert_impl_all!(ObjectServer: Send, Sync, Unpin);
@@ -437,6 +689,8 @@ impl ObjectServer {
         Self {
             conn: conn.into(),
             root: RwLock::new(Node::new("/".try_into().expect("zvariant bug"))),
+            names: Mutex::new(HashSet::new()),
+            tracker: DispatchTracker::default(),
         }
     }
 
@@ -449,7 +703,9 @@ impl ObjectServer {
     /// Typically you'd want your interfaces to be registered immediately after the associated
     /// connection is established and therefore use [`zbus::ConnectionBuilder::serve_at`] instead.
     /// However, there are situations where you'd need to register interfaces dynamically and that's
-    /// where this method becomes useful.
+    /// where this method becomes useful. It can be called at any time, including from the methods
+    /// of the served interfaces. If the object is under an [`ObjectManager`], the
+    /// `InterfacesAdded` signal is emitted.
     ///
     /// If the interface already exists at this path, returns false.
     pub async fn at<'p, P, I>(&self, path: P, iface: I) -> Result<bool>
@@ -522,7 +778,8 @@ impl ObjectServer {
     /// Unregister a D-Bus [`Interface`] at a given path.
     ///
     /// If there are no more interfaces left at that path, destroys the object as well.
-    /// Returns whether the object was destroyed.
+    /// Returns whether the object was destroyed. The objects below that path are not affected. If
+    /// the object is under an [`ObjectManager`], the `InterfacesRemoved` signal is emitted.
     pub async fn remove<'p, I, P>(&self, path: P) -> Result<bool>
     where
         I: Interface,
@@ -530,29 +787,510 @@ impl ObjectServer {
         P::Error: Into<Error>,
     {
         let path = path.try_into().map_err(Into::into)?;
+
+        self.remove_named(&path, I::name(), Node::remove_interface)
+            .await
+    }
+
+    // Same as `remove` but takes the name of the interface, and how to remove it from the node.
+    async fn remove_named(
+        &self,
+        path: &ObjectPath<'_>,
+        name: InterfaceName<'static>,
+        remove: fn(&mut Node, InterfaceName<'static>) -> bool,
+    ) -> Result<bool> {
         let mut root = self.root.write().await;
-        let (node, manager_path) = root.get_child_mut(&path, false);
+        let (node, manager_path) = root.get_child_mut(path, false);
         let node = node.ok_or(Error::InterfaceNotFound)?;
-        if !node.remove_interface(I::name()) {
+        if !remove(node, name.clone()) {
             return Err(Error::InterfaceNotFound);
         }
         if let Some(manager_path) = manager_path {
             let ctxt = SignalContext::new(&self.connection(), manager_path.clone())?;
-            ObjectManager::interfaces_removed(&ctxt, &path, &[I::name()]).await?;
-        }
-        if node.is_empty() {
-            let mut path_parts = path.rsplit('/').filter(|i| !i.is_empty());
-            let last_part = path_parts.next().unwrap();
-            let ppath = ObjectPath::from_string_unchecked(
-                path_parts.fold(String::new(), |a, p| format!("/{p}{a}")),
-            );
-            root.get_child_mut(&ppath, false)
-                .0
-                .unwrap()
-                .remove_node(last_part);
-            return Ok(true);
+            ObjectManager::interfaces_removed(&ctxt, path, &[name]).await?;
+        }
+        if !node.is_empty() {
+            return Ok(false);
+        }
+
+        root.prune(path);
+        self.release_names_if_unused(&root).await?;
+
+        Ok(true)
+    }
+
+    /// Unregister all the interfaces of the object at the given path.
+    ///
+    /// This is the same as calling [`ObjectServer::remove`] for each interface of the object,
+    /// except that a single `InterfacesRemoved` signal is emitted, if the object is managed by
+    /// an [`ObjectManager`]. The objects below that path are not affected.
+    ///
+    /// Returns whether an object was found at that path.
+    pub async fn remove_object<'p, P>(&self, path: P) -> Result<bool>
+    where
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+    {
+        let path = path.try_into().map_err(Into::into)?;
+        let mut root = self.root.write().await;
+        let (node, manager_path) = root.get_child_mut(&path, false);
+        let node = match node {
+            Some(node) if !node.is_empty() => node,
+            _ => return Ok(false),
+        };
+        let removed = node.remove_interfaces();
+        if let Some(manager_path) = manager_path {
+            let ctxt = SignalContext::new(&self.connection(), manager_path.clone())?;
+            let removed: Vec<_> = removed
+                .into_iter()
+                .filter(|name| *name != ObjectManager::name())
+                .collect();
+            ObjectManager::interfaces_removed(&ctxt, &path, &removed).await?;
+        }
+        root.prune(&path);
+        self.release_names_if_unused(&root).await?;
+
+        Ok(true)
+    }
+
+    /// Register a D-Bus [`Interface`] for the objects below a given path.
+    ///
+    /// The interface is served for all the descendants of the object at `path` that don't have
+    /// it (including the ones not registered at all), making them virtual objects. Since a single
+    /// interface instance serves all of them, its methods typically tell which object is called by
+    /// taking the message header (see the `#[zbus(header)]` attribute of [`dbus_interface`]). This
+    /// allows exposing many (possibly dynamic) objects without registering each one of them.
+    ///
+    /// The virtual objects also get the standard interfaces, and are introspected like any other
+    /// object. However, they're not listed as children in the introspection of their parent, since
+    /// the object server doesn't know about them. If several ancestors of an object have a
+    /// fallback for the same interface, the closest one is used.
+    ///
+    /// If a fallback for the interface already exists at this path, returns false.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::error::Error;
+    /// # use zbus::{block_on, dbus_interface, fdo, Connection, MessageHeader};
+    /// #
+    /// struct Device;
+    ///
+    /// #[dbus_interface(name = "org.example.Device")]
+    /// impl Device {
+    ///     fn name(&self, #[zbus(header)] header: &MessageHeader<'_>) -> fdo::Result<String> {
+    ///         let path = header.path()?.ok_or(zbus::Error::MissingField)?;
+    ///         // e.g `sda` for `/org/example/devices/sda`.
+    ///         let name = path.rsplit('/').next().unwrap_or_default();
+    ///
+    ///         Ok(name.to_string())
+    ///     }
+    /// }
+    ///
+    /// # block_on(async {
+    /// let connection = Connection::session().await?;
+    /// connection
+    ///     .object_server()
+    ///     .at_fallback("/org/example/devices", Device)
+    ///     .await?;
+    /// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+    /// # })?;
+    /// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+    /// ```
+    ///
+    /// [`dbus_interface`]: crate::dbus_interface
+    pub async fn at_fallback<'p, P, I>(&self, path: P, iface: I) -> Result<bool>
+    where
+        I: Interface,
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+    {
+        let path = path.try_into().map_err(Into::into)?;
+        let mut root = self.root.write().await;
+        let node = root.get_child_mut(&path, true).0.unwrap();
+
+        Ok(node.at_fallback(I::name(), move || Arc::new(RwLock::new(iface))))
+    }
+
+    /// Unregister a D-Bus [`Interface`] registered with [`ObjectServer::at_fallback`].
+    ///
+    /// Returns `Ok(false)` if there is no fallback for the interface at that path.
+    pub async fn remove_fallback<'p, I, P>(&self, path: P) -> Result<bool>
+    where
+        I: Interface,
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+    {
+        let path = path.try_into().map_err(Into::into)?;
+        let mut root = self.root.write().await;
+        let node = match root.get_child_mut(&path, false).0 {
+            Some(node) => node,
+            None => return Ok(false),
+        };
+        if node.fallbacks.remove(&I::name()).is_none() {
+            return Ok(false);
+        }
+
+        root.prune(&path);
+        self.release_names_if_unused(&root).await?;
+
+        Ok(true)
+    }
+
+    /// Forward the method calls for an interface at a given path to another object.
+    ///
+    /// The interface of `proxy` is served at `path`, by relaying all the method calls and property
+    /// accesses it receives to the object of `proxy` (i.e its destination and path), and the
+    /// replies back to the callers. Since the proxy can be on another connection, this allows
+    /// composing gateway or bridging services, e.g exposing an object of a remote bus locally.
+    /// The introspection data of the interface is taken from the forwarded object when
+    /// registering.
+    ///
+    /// Only method calls are forwarded: the signals of the forwarded object are not relayed. The
+    /// calls without an `INTERFACE` field are not forwarded either, as the methods of the forwarded
+    /// interface are not known locally. Since the properties are read through the proxy, it's best
+    /// built without a property cache, so they're always read from the forwarded object.
+    ///
+    /// If the interface already exists at this path, returns false.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::error::Error;
+    /// # use zbus::{block_on, CacheProperties, Connection, ProxyBuilder};
+    /// #
+    /// # block_on(async {
+    /// let system = Connection::system().await?;
+    /// let session = Connection::session().await?;
+    ///
+    /// // Expose the hostname service of the system bus on the session bus.
+    /// let proxy = ProxyBuilder::new_bare(&system)
+    ///     .destination("org.freedesktop.hostname1")?
+    ///     .path("/org/freedesktop/hostname1")?
+    ///     .interface("org.freedesktop.hostname1")?
+    ///     .cache_properties(CacheProperties::No)
+    ///     .build()
+    ///     .await?;
+    /// session
+    ///     .object_server()
+    ///     .forward("/org/freedesktop/hostname1", proxy)
+    ///     .await?;
+    /// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+    /// # })?;
+    /// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+    /// ```
+    pub async fn forward<'p, P>(&self, path: P, proxy: Proxy<'static>) -> Result<bool>
+    where
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+    {
+        let path = path.try_into().map_err(Into::into)?;
+        let forwarder = Arc::new(Forwarder::new(proxy).await);
+        let name = forwarder.interface_name();
+        let mut root = self.root().write().await;
+        let (node, manager_path) = root.get_child_mut(&path, true);
+        if !node.unwrap().forward(name.clone(), forwarder.clone()) {
+            return Ok(false);
         }
-        Ok(false)
+        if let Some(manager_path) = manager_path {
+            let ctxt = SignalContext::new(&self.connection(), manager_path.clone())?;
+            let owned_props = forwarder.get_all().await.unwrap_or_else(|e| {
+                debug!("Failed to get the properties of `{}`: {}", name, e);
+
+                HashMap::new()
+            });
+            let props = owned_props
+                .iter()
+                .map(|(k, v)| (k.as_str(), Value::from(v)))
+                .collect();
+            let mut interfaces = HashMap::new();
+            interfaces.insert(name, props);
+
+            ObjectManager::interfaces_added(&ctxt, &path, &interfaces).await?;
+        }
+
+        Ok(true)
+    }
+
+    /// Stop forwarding an interface registered with [`ObjectServer::forward`].
+    ///
+    /// This is the same as [`ObjectServer::remove`], except that the interface is given by name.
+    /// Only the forwarded interfaces are removed this way, not the ones served locally.
+    pub async fn remove_forward<'p, 'i, P, I>(&self, path: P, interface: I) -> Result<bool>
+    where
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+        I: TryInto<InterfaceName<'i>>,
+        I::Error: Into<Error>,
+    {
+        let path = path.try_into().map_err(Into::into)?;
+        let interface = interface.try_into().map_err(Into::into)?;
+
+        self.remove_named(&path, interface.into_owned(), Node::remove_forward)
+            .await
+    }
+
+    /// Register a factory, creating a D-Bus [`Interface`] for the objects whose path matches a
+    /// template.
+    ///
+    /// The template is an object path, some elements of which are placeholders of the form
+    /// `{name}`, each matching any single element, e.g `/org/example/Item/{id}`. When a method call
+    /// arrives for a matching path whose object doesn't have the interface, `factory` is called
+    /// with the path and the values of the placeholders (by name) to create it. This allows
+    /// serving per-entity objects without registering each one of them upfront. An error returned
+    /// by the factory (e.g [`fdo::Error::UnknownObject`] for an entity that doesn't exist) is sent
+    /// back to the caller as the reply.
+    ///
+    /// With [`FactoryPolicy::Cache`], the created object is then registered at its path, just like
+    /// through [`ObjectServer::at`], and the factory isn't called for it anymore until it's
+    /// removed. With [`FactoryPolicy::Discard`], the object only serves the call it's created for.
+    ///
+    /// The objects that are yet to be created are not listed as children in the introspection of
+    /// their parent, since the object server doesn't know about them.
+    ///
+    /// If a factory for the interface already exists for this template, returns false.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::error::Error;
+    /// # use zbus::{block_on, dbus_interface, fdo, Connection, FactoryPolicy};
+    /// #
+    /// struct Item {
+    ///     id: u32,
+    /// }
+    ///
+    /// #[dbus_interface(name = "org.example.Item")]
+    /// impl Item {
+    ///     #[dbus_interface(property)]
+    ///     fn id(&self) -> u32 {
+    ///         self.id
+    ///     }
+    /// }
+    ///
+    /// # block_on(async {
+    /// let connection = Connection::session().await?;
+    /// connection
+    ///     .object_server()
+    ///     .at_factory(
+    ///         "/org/example/Item/{id}",
+    ///         FactoryPolicy::Cache,
+    ///         |path, params| match params["id"].parse() {
+    ///             Ok(id) => Ok(Item { id }),
+    ///             Err(_) => Err(fdo::Error::UnknownObject(format!("Unknown object '{}'", path))),
+    ///         },
+    ///     )
+    ///     .await?;
+    /// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+    /// # })?;
+    /// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+    /// ```
+    pub async fn at_factory<F, I>(
+        &self,
+        template: &str,
+        policy: FactoryPolicy,
+        factory: F,
+    ) -> Result<bool>
+    where
+        I: Interface,
+        F: Fn(&ObjectPath<'_>, &HashMap<&str, &str>) -> fdo::Result<I> + Send + Sync + 'static,
+    {
+        let factory = ObjectFactory::new(template, policy, factory)?;
+
+        Ok(self.root.write().await.factories.insert(factory))
+    }
+
+    /// Unregister a factory registered with [`ObjectServer::at_factory`].
+    ///
+    /// The objects it created and that are still registered are not affected.
+    ///
+    /// Returns `Ok(false)` if there is no factory for the interface with this template.
+    pub async fn remove_factory<I>(&self, template: &str) -> Result<bool>
+    where
+        I: Interface,
+    {
+        let mut root = self.root.write().await;
+        if !root.factories.remove(template, &I::name()) {
+            return Ok(false);
+        }
+
+        self.release_names_if_unused(&root).await?;
+
+        Ok(true)
+    }
+
+    /// Get the statistics about the method calls dispatched so far.
+    ///
+    /// This gives the number of calls handled, the ones that got an error as reply, the ones being
+    /// dispatched and the time spent handling the calls to each member of the interfaces, so
+    /// services can be monitored and slow handlers found. See also [`DispatchObserver`] to get
+    /// notified of each call.
+    ///
+    /// [`DispatchObserver`]: crate::DispatchObserver
+    pub fn dispatch_stats(&self) -> DispatchStats {
+        self.tracker.stats()
+    }
+
+    // Create the interfaces the factories matching `path` provide, that the object doesn't have
+    // yet. Returns the ones to discard once the call is handled.
+    async fn run_factories(
+        &self,
+        path: &ObjectPath<'_>,
+    ) -> fdo::Result<Vec<(InterfaceName<'static>, Arc<RwLock<dyn Interface>>)>> {
+        let factories = {
+            let root = self.root.read().await;
+            let node = root.get_child(path);
+            let mut factories = root.factories.matching(path);
+            factories.retain(|factory| {
+                let name = factory.interface_name();
+
+                !node.map_or(false, |n| {
+                    n.interfaces.contains_key(name) || n.forwards.contains_key(name)
+                })
+            });
+
+            factories
+        };
+
+        // The factories are run without the root lock held, so they can use the object server.
+        let mut created = vec![];
+        for factory in factories {
+            if let Some(params) = factory.matches(path) {
+                let iface = factory.create(path, &params)?;
+                created.push((factory.interface_name().clone(), factory.policy(), iface));
+            }
+        }
+
+        let mut discarded = vec![];
+        for (name, policy, iface) in created {
+            match policy {
+                FactoryPolicy::Cache => {
+                    self.at_ready(path.clone(), name, move || iface).await?;
+                }
+                FactoryPolicy::Discard => {
+                    let mut root = self.root.write().await;
+                    let node = root.get_child_mut(path, true).0.unwrap();
+                    if node.at(name.clone(), || iface.clone()) {
+                        discarded.push((name, iface));
+                    }
+                }
+            }
+        }
+
+        Ok(discarded)
+    }
+
+    // Remove the interfaces created for a single call by `run_factories`.
+    async fn discard(
+        &self,
+        path: &ObjectPath<'_>,
+        discarded: Vec<(InterfaceName<'static>, Arc<RwLock<dyn Interface>>)>,
+    ) {
+        if discarded.is_empty() {
+            return;
+        }
+
+        let mut root = self.root.write().await;
+        let node = match root.get_child_mut(path, false).0 {
+            Some(node) => node,
+            None => return,
+        };
+        for (name, iface) in discarded {
+            // Unless it was replaced in the meantime.
+            if node
+                .interfaces
+                .get(&name)
+                .map_or(false, |i| Arc::ptr_eq(i, &iface))
+            {
+                node.interfaces.remove(&name);
+            }
+        }
+        root.prune(path);
+    }
+
+    /// Register a well-known name on the bus, bound to the object server.
+    ///
+    /// This is the same as [`Connection::request_name`], except that the name goes along with the
+    /// objects served: it's released once the removal of an interface leaves the object server
+    /// empty (see [`ObjectServer::remove`] and [`ObjectServer::remove_object`]). A name requested
+    /// while nothing is served is hence kept until objects are served and then all removed, or
+    /// until it's released explicitly. Since the bus releases the names of a connection when it's
+    /// closed, the names are also released when the object server goes away with its connection.
+    /// See also [`ConnectionBuilder::serve_name`] to request the name as part of the connection
+    /// setup.
+    ///
+    /// Requesting a name that is already bound to the object server does nothing.
+    ///
+    /// If the name is lost to another peer (see [`RequestNameFlags::AllowReplacement`]), the
+    /// method calls addressed to that name are no longer dispatched, until it's acquired again.
+    ///
+    /// [`ConnectionBuilder::serve_name`]: crate::ConnectionBuilder::serve_name
+    /// [`RequestNameFlags::AllowReplacement`]: crate::fdo::RequestNameFlags::AllowReplacement
+    pub async fn request_name<'w, W>(&self, well_known_name: W) -> Result<()>
+    where
+        W: TryInto<WellKnownName<'w>>,
+        W::Error: Into<Error>,
+    {
+        self.request_name_with_flags(
+            well_known_name,
+            RequestNameFlags::ReplaceExisting | RequestNameFlags::DoNotQueue,
+        )
+        .await
+    }
+
+    pub(crate) async fn request_name_with_flags<'w, W>(
+        &self,
+        well_known_name: W,
+        flags: BitFlags<RequestNameFlags>,
+    ) -> Result<()>
+    where
+        W: TryInto<WellKnownName<'w>>,
+        W::Error: Into<Error>,
+    {
+        let well_known_name = well_known_name.try_into().map_err(Into::into)?;
+        let mut names = self.names.lock().await;
+        if names.contains(&well_known_name) {
+            return Ok(());
+        }
+        self.connection()
+            .request_name_with_flags(well_known_name.clone(), flags)
+            .await?;
+        names.insert(well_known_name.into_owned());
+
+        Ok(())
+    }
+
+    /// Release a well-known name bound to the object server.
+    ///
+    /// Returns `Ok(false)` if the name was not requested through [`ObjectServer::request_name`]
+    /// (or already released). Otherwise, this is the same as [`Connection::release_name`].
+    pub async fn release_name<'w, W>(&self, well_known_name: W) -> Result<bool>
+    where
+        W: TryInto<WellKnownName<'w>>,
+        W::Error: Into<Error>,
+    {
+        let well_known_name = well_known_name.try_into().map_err(Into::into)?;
+        if !self.names.lock().await.remove(&well_known_name.to_owned()) {
+            return Ok(false);
+        }
+
+        self.connection().release_name(well_known_name).await
+    }
+
+    // Release the names bound to the object server once there is nothing left to serve.
+    async fn release_names_if_unused(&self, root: &Node) -> Result<()> {
+        if !root.is_unused() {
+            return Ok(());
+        }
+
+        let names: Vec<_> = self.names.lock().await.drain().collect();
+        let conn = self.connection();
+        for name in names {
+            trace!("Releasing `{}`, as the object server is now empty", name);
+            conn.release_name(name).await?;
+        }
+
+        Ok(())
     }
 
     /// Get the interface at the given path.
@@ -634,46 +1372,98 @@ impl ObjectServer {
         &self,
         connection: &Connection,
         msg: &Message,
-    ) -> fdo::Result<Result<()>> {
+    ) -> fdo::Result<(InterfaceName<'static>, Result<()>)> {
         let path = msg
             .path()
             .ok_or_else(|| fdo::Error::Failed("Missing object path".into()))?;
-        let iface_name = msg
-            .interface()
-            // TODO: In the absence of an INTERFACE field, if two or more interfaces on the same
-            // object have a method with the same name, it is undefined which of those
-            // methods will be invoked. Implementations may choose to either return an
-            // error, or deliver the message as though it had an arbitrary one of those
-            // interfaces.
-            .ok_or_else(|| fdo::Error::Failed("Missing interface".into()))?;
         let member = msg
             .member()
-            .ok_or_else(|| fdo::Error::Failed("Missing member".into()))?;
+            .ok_or_else(|| fdo::Error::UnknownMethod("Missing member".into()))?;
+
+        let discarded = self.run_factories(&path).await?;
+        let res = self
+            .dispatch_to_object(connection, msg, &path, member.as_ref())
+            .await;
+        self.discard(&path, discarded).await;
+
+        res
+    }
 
+    // Call the method on the object at path.
+    async fn dispatch_to_object(
+        &self,
+        connection: &Connection,
+        msg: &Message,
+        path: &ObjectPath<'_>,
+        member: MemberName<'_>,
+    ) -> fdo::Result<(InterfaceName<'static>, Result<()>)> {
         // Ensure the root lock isn't held while dispatching the message. That
         // way, the object server can be mutated during that time.
-        let iface = {
+        let ifaces = {
             let root = self.root.read().await;
-            let node = root
-                .get_child(&path)
-                .ok_or_else(|| fdo::Error::UnknownObject(format!("Unknown object '{path}'")))?;
+            match msg.interface() {
+                Some(iface_name) => {
+                    if let Some(forwarder) = root.lookup_forward(path, &iface_name) {
+                        drop(root);
+                        check_access(connection, msg, &iface_name).await?;
+                        let res = forwarder.forward_call(connection, msg).await;
+
+                        return Ok((iface_name.to_owned(), res));
+                    }
 
-            node.interface_lock(iface_name.as_ref()).ok_or_else(|| {
-                fdo::Error::UnknownInterface(format!("Unknown interface '{iface_name}'"))
-            })?
+                    let iface = root.lookup_interface(path, iface_name.as_ref())?;
+
+                    vec![(iface_name.to_owned(), iface)]
+                }
+                // In the absence of an INTERFACE field, the call is delivered to the first
+                // interface (by name) of the object that has the method, as the D-Bus
+                // specification leaves it to the implementations to pick one.
+                None => root
+                    .interfaces(path)?
+                    .into_iter()
+                    .map(|(name, iface)| (name.clone(), iface.clone()))
+                    .collect(),
+            }
         };
 
+        for (iface_name, iface) in &ifaces {
+            if let Some(res) = self
+                .dispatch_to_interface(iface_name, iface, connection, msg, member.clone())
+                .await?
+            {
+                return Ok((iface_name.clone(), res));
+            }
+        }
+
+        Err(fdo::Error::UnknownMethod(match &*ifaces {
+            [(iface_name, _)] if msg.interface().is_some() => {
+                format!("Unknown method '{member}' on interface '{iface_name}'")
+            }
+            _ => format!("Unknown method '{member}' at '{path}'"),
+        }))
+    }
+
+    // Call the method on the interface, returning `None` if it doesn't have it.
+    //
+    // The access policy is checked once the method is found, so it's told the interface the call
+    // resolved to, even in the absence of an `INTERFACE` field.
+    async fn dispatch_to_interface(
+        &self,
+        iface_name: &InterfaceName<'_>,
+        iface: &RwLock<dyn Interface>,
+        connection: &Connection,
+        msg: &Message,
+        member: MemberName<'_>,
+    ) -> fdo::Result<Option<Result<()>>> {
         trace!("acquiring read lock on interface `{}`", iface_name);
         let read_lock = iface.read().await;
         trace!("acquired read lock on interface `{}`", iface_name);
-        match read_lock.call(self, connection, msg, member.as_ref()) {
-            DispatchResult::NotFound => {
-                return Err(fdo::Error::UnknownMethod(format!(
-                    "Unknown method '{member}'"
-                )));
-            }
+        match read_lock.call(self, connection, msg, member.clone()) {
+            DispatchResult::NotFound => return Ok(None),
             DispatchResult::Async(f) => {
-                return Ok(f.await);
+                check_access(connection, msg, iface_name).await?;
+
+                return Ok(Some(f.await));
             }
             DispatchResult::RequiresMut => {}
         }
@@ -681,30 +1471,61 @@ impl ObjectServer {
         trace!("acquiring write lock on interface `{}`", iface_name);
         let mut write_lock = iface.write().await;
         trace!("acquired write lock on interface `{}`", iface_name);
-        match write_lock.call_mut(self, connection, msg, member.as_ref()) {
+        match write_lock.call_mut(self, connection, msg, member) {
             DispatchResult::NotFound => {}
             DispatchResult::RequiresMut => {}
             DispatchResult::Async(f) => {
-                return Ok(f.await);
+                check_access(connection, msg, iface_name).await?;
+
+                return Ok(Some(f.await));
             }
         }
-        drop(write_lock);
-        Err(fdo::Error::UnknownMethod(format!(
-            "Unknown method '{member}'"
-        )))
+
+        Ok(None)
     }
 
     #[instrument(skip(self, connection))]
     async fn dispatch_method_call(&self, connection: &Connection, msg: &Message) -> Result<()> {
-        match self.dispatch_method_call_try(connection, msg).await {
+        let mut call = self.tracker.begin(msg);
+        let dispatch = call.dispatch(self.dispatch_method_call_try(connection, msg));
+        let (interface, res) = match dispatch.await {
             Err(e) => {
-                let hdr = msg.header()?;
-                debug!("Returning error: {}", e);
-                connection.reply_dbus_error(&hdr, e).await?;
-                Ok(())
+                call.failed(e.name());
+
+                (None, self.reply_dispatch_error(connection, msg, e).await)
             }
-            Ok(r) => r,
+            Ok((interface, r)) => (Some(interface), r),
+        };
+        let member = msg.member();
+        let (error, elapsed) = self.tracker.end(call, interface.as_ref(), member.as_ref());
+        if let Some(observer) = connection.dispatch_observer() {
+            let outcome = DispatchOutcome::new(interface.as_ref(), error.as_ref(), elapsed);
+            observer.dispatched(msg, &outcome);
         }
+
+        res
+    }
+
+    // Reply the error to the method call, unless no reply is expected.
+    async fn reply_dispatch_error(
+        &self,
+        connection: &Connection,
+        msg: &Message,
+        e: fdo::Error,
+    ) -> Result<()> {
+        let hdr = msg.header()?;
+        if hdr
+            .primary()
+            .flags()
+            .contains(MessageFlags::NoReplyExpected)
+        {
+            debug!("Not returning error, as no reply is expected: {}", e);
+            return Ok(());
+        }
+        debug!("Returning error: {}", e);
+        connection.reply_dbus_error(&hdr, e).await?;
+
+        Ok(())
     }
 
     /// Dispatch an incoming message to a registered interface.
@@ -722,7 +1543,9 @@ impl ObjectServer {
     #[instrument(skip(self))]
     pub(crate) async fn dispatch_message(&self, msg: &Message) -> Result<bool> {
         let conn = self.connection();
-        self.dispatch_method_call(&conn, msg).await?;
+        let dispatch = self.dispatch_method_call(&conn, msg);
+        let dispatch = dispatch.instrument(message_span!("handle method call", msg));
+        dispatch.await?;
         trace!("Handled: {}", msg);
 
         Ok(true)
@@ -13,16 +13,18 @@ use std::{
     pin::Pin,
     sync::{Arc, RwLock, RwLockReadGuard},
     task::{Context, Poll},
+    time::Duration,
 };
-use tracing::{debug, info_span, instrument, trace, Instrument};
+use tracing::{debug, info_span, instrument, trace, trace_span, Instrument};
 
 use zbus_names::{BusName, InterfaceName, MemberName, UniqueName};
 use zvariant::{ObjectPath, OwnedValue, Str, Value};
 
 use crate::{
     fdo::{self, IntrospectableProxy, NameOwnerChanged, PropertiesChangedStream, PropertiesProxy},
-    AsyncDrop, CacheProperties, Connection, Error, Executor, MatchRule, Message, MessageFlags,
-    MessageSequence, MessageStream, MessageType, OwnedMatchRule, ProxyBuilder, Result, Task,
+    AsyncDrop, CacheProperties, Connection, Error, Executor, MatchRule, MatchRulePathSpec, Message,
+    MessageFlags, MessageSequence, MessageStream, MessageType, OwnedMatchRule, ProxyBuilder,
+    Result, Task,
 };
 
 /// A client-side interface proxy.
@@ -78,18 +80,34 @@ pub(crate) struct ProxyInnerStatic {
     dest_owner_change_match_rule: OnceCell<OwnedMatchRule>,
 }
 
+// The property cache, along with the task keeping it up to date.
+type PropertyCache = Arc<OnceCell<(Arc<PropertiesCache>, Task<()>)>>;
+
+// The tracked owner of the destination, along with the task tracking it.
+type DestOwner = (Arc<RwLock<Option<UniqueName<'static>>>>, Task<()>);
+
 #[derive(Debug)]
 pub(crate) struct ProxyInner<'a> {
     inner_without_borrows: ProxyInnerStatic,
-    pub(crate) destination: BusName<'a>,
+    pub(crate) destination: Option<BusName<'a>>,
     pub(crate) path: ObjectPath<'a>,
     pub(crate) interface: InterfaceName<'a>,
 
     /// Cache of property values.
-    property_cache: Option<OnceCell<(Arc<PropertiesCache>, Task<()>)>>,
+    property_cache: Option<PropertyCache>,
+    /// Whether the property cache is to be populated before the proxy is used.
+    cache_upfront: bool,
+    /// The tracked owner of the destination, once requested through `Proxy::current_owner`.
+    dest_owner: OnceCell<DestOwner>,
     /// Set of properties which do not get cached, by name.
     /// This overrides proxy-level caching behavior.
     uncached_properties: HashSet<Str<'a>>,
+    /// Maximum number of signals queued in signal streams, if not the connection's default.
+    pub(crate) max_queued_signals: Option<usize>,
+    /// Timeout for method calls, if not the connection's default.
+    method_timeout: Option<Duration>,
+    /// Whether the property cache reads the `EmitsChangedSignal` annotations.
+    honor_emits_changed_signal: bool,
 }
 
 impl Drop for ProxyInnerStatic {
@@ -198,6 +216,8 @@ where
 
 /// A [`stream::Stream`] implementation that yields property change notifications.
 ///
+/// The first item is yielded right away, for the current value of the property.
+///
 /// Use [`Proxy::receive_property_changed`] to create an instance of this type.
 #[derive(derivative::Derivative)]
 #[derivative(Debug)]
@@ -205,6 +225,8 @@ pub struct PropertyStream<'a, T> {
     name: &'a str,
     proxy: Proxy<'a>,
     changed_listener: EventListener,
+    // Whether the item for the current value was yielded already.
+    seeded: bool,
     phantom: std::marker::PhantomData<T>,
 }
 
@@ -221,16 +243,21 @@ where
             // With no cache, we will get no updates; return immediately
             None => return Poll::Ready(None),
         };
-        ready!(Pin::new(&mut m.changed_listener).poll(cx));
-
-        m.changed_listener = properties
-            .values
-            .read()
-            .expect("lock poisoned")
-            .get(m.name)
-            .expect("PropertyStream with no corresponding property")
-            .event
-            .listen();
+        if m.seeded {
+            ready!(Pin::new(&mut m.changed_listener).poll(cx));
+
+            m.changed_listener = properties
+                .values
+                .read()
+                .expect("lock poisoned")
+                .get(m.name)
+                .expect("PropertyStream with no corresponding property")
+                .event
+                .listen();
+        } else {
+            // Changes from now on are caught by the listener created along with the stream.
+            m.seeded = true;
+        }
 
         Poll::Ready(Some(PropertyChanged {
             name: m.name,
@@ -245,6 +272,10 @@ where
 pub(crate) struct PropertiesCache {
     values: RwLock<HashMap<String, PropertyValue>>,
     caching_result: RwLock<CachingResult>,
+    // Properties whose changes are not signaled, as announced in the introspection data.
+    non_emitting: OnceCell<HashSet<String>>,
+    // Properties that never change, as announced in the introspection data.
+    immortal: OnceCell<HashSet<String>>,
 }
 
 #[derive(Debug)]
@@ -260,19 +291,27 @@ impl PropertiesCache {
         interface: InterfaceName<'static>,
         executor: &Executor<'_>,
         uncached_properties: HashSet<zvariant::Str<'static>>,
+        honor_emits_changed_signal: bool,
     ) -> (Arc<Self>, Task<()>) {
         let cache = Arc::new(PropertiesCache {
             values: Default::default(),
             caching_result: RwLock::new(CachingResult::Caching {
                 ready: Event::new(),
             }),
+            non_emitting: OnceCell::new(),
+            immortal: OnceCell::new(),
         });
 
         let cache_clone = cache.clone();
         let task_name = format!("{interface} proxy caching");
         let proxy_caching = async move {
             let result = cache_clone
-                .init(proxy, interface, uncached_properties)
+                .init(
+                    proxy,
+                    interface,
+                    uncached_properties,
+                    honor_emits_changed_signal,
+                )
                 .await;
             let (prop_changes, interface, uncached_properties) = {
                 let mut caching_result = cache_clone.caching_result.write().expect("lock poisoned");
@@ -317,6 +356,7 @@ impl PropertiesCache {
         proxy: PropertiesProxy<'static>,
         interface: InterfaceName<'static>,
         uncached_properties: HashSet<zvariant::Str<'static>>,
+        honor_emits_changed_signal: bool,
     ) -> Result<(
         PropertiesChangedStream<'static>,
         InterfaceName<'static>,
@@ -324,12 +364,17 @@ impl PropertiesCache {
     )> {
         use ordered_stream::OrderedStreamExt;
 
+        if honor_emits_changed_signal {
+            #[cfg(feature = "quick-xml")]
+            self.read_annotations(&proxy, &interface).await;
+        }
+
         let prop_changes = proxy.receive_properties_changed().await?.map(Either::Left);
 
         let get_all = proxy
             .connection()
             .call_method_raw(
-                Some(proxy.destination()),
+                proxy.optional_destination(),
                 proxy.path(),
                 Some(proxy.interface()),
                 "GetAll",
@@ -377,6 +422,71 @@ impl PropertiesCache {
         Ok((prop_changes, interface, uncached_properties))
     }
 
+    // Find out which properties don't emit `PropertiesChanged` or never change, through the
+    // `org.freedesktop.DBus.Property.EmitsChangedSignal` annotation.
+    #[cfg(feature = "quick-xml")]
+    async fn read_annotations(&self, proxy: &PropertiesProxy<'_>, interface: &InterfaceName<'_>) {
+        use crate::quick_xml::{EmitsChangedSignal, Node};
+
+        let xml = match proxy
+            .connection()
+            .introspect(proxy.optional_destination(), proxy.path())
+            .await
+        {
+            Ok(xml) => xml,
+            Err(e) => {
+                // Not a problem, we'll just assume all properties emit `PropertiesChanged`.
+                debug!("Failed to introspect {}: {e}", proxy.path());
+
+                return;
+            }
+        };
+        let node = match Node::from_reader(xml.as_bytes()) {
+            Ok(node) => node,
+            Err(e) => {
+                debug!("Failed to parse introspection of {}: {e}", proxy.path());
+
+                return;
+            }
+        };
+        let iface = match node.interfaces().iter().find(|i| i.name() == *interface) {
+            Some(iface) => iface,
+            None => return,
+        };
+
+        let mut non_emitting = HashSet::new();
+        let mut immortal = HashSet::new();
+        for property in iface.properties() {
+            let name = property.name();
+            match iface.property_emits_changed_signal(&name) {
+                Some(EmitsChangedSignal::False) => {
+                    non_emitting.insert(name.to_string());
+                }
+                Some(EmitsChangedSignal::Const) => {
+                    immortal.insert(name.to_string());
+                }
+                _ => (),
+            }
+        }
+        trace!("Not caching {non_emitting:?} and never invalidating {immortal:?} of {interface}");
+        let _ = self.non_emitting.set(non_emitting);
+        let _ = self.immortal.set(immortal);
+    }
+
+    /// Whether the peer notifies us of the changes of `property_name`, so it can be cached.
+    fn emits_changes(&self, property_name: &str) -> bool {
+        self.non_emitting
+            .get()
+            .map_or(true, |names| !names.contains(property_name))
+    }
+
+    /// Whether `property_name` never changes, so its cached value never gets stale.
+    fn is_immortal(&self, property_name: &str) -> bool {
+        self.immortal
+            .get()
+            .map_or(false, |names| names.contains(property_name))
+    }
+
     // new() runs this in a task it spawns for keeping the cache in sync.
     #[instrument(skip_all)]
     async fn keep_updated(
@@ -414,7 +524,7 @@ impl PropertiesCache {
         let mut values = self.values.write().expect("lock poisoned");
 
         for inval in invalidated {
-            if uncached_properties.contains(&Str::from(inval)) {
+            if uncached_properties.contains(&Str::from(inval)) || !self.emits_changes(inval) {
                 debug!(
                     "Ignoring invalidation of uncached property `{}.{}`",
                     interface, inval
@@ -425,12 +535,15 @@ impl PropertiesCache {
 
             if let Some(entry) = values.get_mut(inval) {
                 entry.value = None;
+                entry.updates += 1;
                 entry.event.notify(usize::MAX);
             }
         }
 
         for (property_name, value) in changed {
-            if uncached_properties.contains(&Str::from(*property_name)) {
+            if uncached_properties.contains(&Str::from(*property_name))
+                || !self.emits_changes(property_name)
+            {
                 debug!(
                     "Ignoring update of uncached property `{}.{}`",
                     interface, property_name
@@ -444,10 +557,57 @@ impl PropertiesCache {
                 .or_insert_with(PropertyValue::default);
 
             entry.value = Some(OwnedValue::from(value));
+            entry.updates += 1;
+            entry.event.notify(usize::MAX);
+        }
+    }
+
+    /// Invalidate all the cached values, e.g because another peer now owns the destination.
+    ///
+    /// The values of the properties that never change are kept.
+    fn invalidate_all(&self) {
+        let mut values = self.values.write().expect("lock poisoned");
+
+        for (_, entry) in values
+            .iter_mut()
+            .filter(|(name, _)| !self.is_immortal(name))
+        {
+            entry.value = None;
+            entry.updates += 1;
             entry.event.notify(usize::MAX);
         }
     }
 
+    /// The number of change notifications received so far for `property_name`.
+    ///
+    /// Read this before fetching the value of a property from the peer, to pass it to
+    /// `store_fetched` afterwards.
+    fn updates(&self, property_name: &str) -> u64 {
+        self.values
+            .read()
+            .expect("lock poisoned")
+            .get(property_name)
+            .map(|e| e.updates)
+            .unwrap_or(0)
+    }
+
+    /// Cache the value of `property_name` fetched from the peer.
+    ///
+    /// Nothing is done if a change notification was received for the property in the meantime
+    /// (i-e since `updates` was read), as the fetched value might be outdated already. Neither is
+    /// anything done for properties whose changes are not signaled by the peer.
+    fn store_fetched(&self, property_name: &str, updates: u64, value: OwnedValue) {
+        if !self.emits_changes(property_name) {
+            return;
+        }
+        let mut values = self.values.write().expect("lock poisoned");
+        let entry = values.entry(property_name.to_string()).or_default();
+        if entry.updates == updates {
+            trace!("Property `{property_name}` fetched");
+            entry.value = Some(value);
+        }
+    }
+
     /// Wait for the cache to be populated and return any error encountered during population
     pub(crate) async fn ready(&self) -> Result<()> {
         let listener = match &*self.caching_result.read().expect("lock poisoned") {
@@ -467,18 +627,23 @@ impl PropertiesCache {
 }
 
 impl<'a> ProxyInner<'a> {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         conn: Connection,
-        destination: BusName<'a>,
+        destination: Option<BusName<'a>>,
         path: ObjectPath<'a>,
         interface: InterfaceName<'a>,
         cache: CacheProperties,
         uncached_properties: HashSet<Str<'a>>,
+        max_queued_signals: Option<usize>,
+        method_timeout: Option<Duration>,
+        honor_emits_changed_signal: bool,
     ) -> Self {
         let property_cache = match cache {
-            CacheProperties::Yes | CacheProperties::Lazily => Some(OnceCell::new()),
+            CacheProperties::Yes | CacheProperties::Lazily => Some(Arc::new(OnceCell::new())),
             CacheProperties::No => None,
         };
+        let cache_upfront = cache == CacheProperties::Yes;
         Self {
             inner_without_borrows: ProxyInnerStatic {
                 conn,
@@ -488,7 +653,12 @@ impl<'a> ProxyInner<'a> {
             path,
             interface,
             property_cache,
+            cache_upfront,
+            dest_owner: OnceCell::new(),
             uncached_properties,
+            max_queued_signals,
+            method_timeout,
+            honor_emits_changed_signal,
         }
     }
 
@@ -502,8 +672,8 @@ impl<'a> ProxyInner<'a> {
         }
 
         let well_known_name = match &self.destination {
-            BusName::WellKnown(well_known_name) => well_known_name,
-            BusName::Unique(_) => return Ok(()),
+            Some(BusName::WellKnown(well_known_name)) => well_known_name,
+            Some(BusName::Unique(_)) | None => return Ok(()),
         };
 
         if self
@@ -528,7 +698,7 @@ impl<'a> ProxyInner<'a> {
             .to_owned()
             .into();
 
-        conn.add_match(
+        conn.subscribe(
             signal_rule.clone(),
             Some(MAX_NAME_OWNER_CHANGED_SIGNALS_QUEUED),
         )
@@ -604,8 +774,31 @@ impl<'a> Proxy<'a> {
     }
 
     /// Get a reference to the destination service name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the proxy has no destination, which is only possible on peer-to-peer connections.
+    /// Use [`Proxy::optional_destination`] for such proxies.
     pub fn destination(&self) -> &BusName<'_> {
-        &self.inner.destination
+        self.optional_destination()
+            .expect("proxy without a destination")
+    }
+
+    /// Get a reference to the destination service name, if any.
+    ///
+    /// This is `None` for proxies built without a destination on peer-to-peer connections, where
+    /// messages go straight to the peer.
+    pub fn optional_destination(&self) -> Option<&BusName<'_>> {
+        self.inner.destination.as_ref()
+    }
+
+    // The destination, if it's a name on the bus, i-e if the peer can be told by its name.
+    pub(crate) fn bus_destination(&self) -> Option<&BusName<'_>> {
+        if self.connection().is_bus() {
+            self.optional_destination()
+        } else {
+            None
+        }
     }
 
     /// Get a reference to the object path.
@@ -624,7 +817,7 @@ impl<'a> Proxy<'a> {
     /// result.
     pub async fn introspect(&self) -> fdo::Result<String> {
         let proxy = IntrospectableProxy::builder(&self.inner.inner_without_borrows.conn)
-            .destination(&self.inner.destination)?
+            .same_destination_as(self)
             .path(&self.inner.path)?
             .build()
             .await?;
@@ -632,36 +825,58 @@ impl<'a> Proxy<'a> {
         proxy.introspect().await
     }
 
-    fn properties_proxy(&self) -> PropertiesProxy<'_> {
-        PropertiesProxy::builder(&self.inner.inner_without_borrows.conn)
-            // Safe because already checked earlier
-            .destination(self.inner.destination.as_ref())
-            .unwrap()
+    /// Introspect the associated object, and return the parsed description.
+    ///
+    /// Same as [`Proxy::introspect`], except that the XML is parsed into a [`Node`] tree, giving
+    /// access to the interfaces (along with their methods, signals and properties) and the child
+    /// nodes of the object.
+    ///
+    /// [`Node`]: crate::quick_xml::Node
+    #[cfg(feature = "quick-xml")]
+    pub async fn introspect_node(&self) -> Result<crate::quick_xml::Node<'static>> {
+        let xml = self.introspect().await?;
+
+        crate::quick_xml::Node::from_reader(xml.as_bytes())
+    }
+
+    pub(crate) fn properties_proxy(&self) -> PropertiesProxy<'_> {
+        let builder = PropertiesProxy::builder(&self.inner.inner_without_borrows.conn)
+            .same_destination_as(self)
             // Safe because already checked earlier
             .path(self.inner.path.as_ref())
             .unwrap()
             // does not have properties
-            .cache_properties(CacheProperties::No)
+            .cache_properties(CacheProperties::No);
+
+        self.with_method_timeout(builder)
             .build_internal()
             .unwrap()
             .into()
     }
 
     fn owned_properties_proxy(&self) -> PropertiesProxy<'static> {
-        PropertiesProxy::builder(&self.inner.inner_without_borrows.conn)
-            // Safe because already checked earlier
-            .destination(self.inner.destination.to_owned())
-            .unwrap()
+        let builder = PropertiesProxy::builder(&self.inner.inner_without_borrows.conn)
+            .same_destination_as(self)
             // Safe because already checked earlier
             .path(self.inner.path.to_owned())
             .unwrap()
             // does not have properties
-            .cache_properties(CacheProperties::No)
+            .cache_properties(CacheProperties::No);
+
+        self.with_method_timeout(builder)
             .build_internal()
             .unwrap()
             .into()
     }
 
+    // Property access must not stall any longer than our own method calls.
+    fn with_method_timeout<'b, T>(&self, builder: ProxyBuilder<'b, T>) -> ProxyBuilder<'b, T> {
+        match self.inner.method_timeout {
+            Some(timeout) => builder.method_timeout(timeout),
+            None => builder,
+        }
+    }
+
     /// Get the cache, starting it in the background if needed.
     ///
     /// Use PropertiesCache::ready() to wait for the cache to be populated and to get any errors
@@ -682,12 +897,25 @@ impl<'a> Proxy<'a> {
                 .collect();
             let executor = self.connection().executor();
 
-            PropertiesCache::new(proxy, interface, executor, uncached_properties)
+            PropertiesCache::new(
+                proxy,
+                interface,
+                executor,
+                uncached_properties,
+                self.inner.honor_emits_changed_signal,
+            )
         });
 
         Some(cache)
     }
 
+    // Start populating the cache, if that was deferred by `ProxyBuilder::build_lazy`.
+    fn start_deferred_caching(&self) {
+        if self.inner.cache_upfront {
+            self.get_property_cache();
+        }
+    }
+
     /// Get the cached value of the property `property_name`.
     ///
     /// This returns `None` if the property is not in the cache.  This could be because the cache
@@ -714,11 +942,12 @@ impl<'a> Proxy<'a> {
         &'p self,
         property_name: &'p str,
     ) -> Option<impl Deref<Target = Value<'static>> + 'p> {
+        self.start_deferred_caching();
         if let Some(values) = self
             .inner
             .property_cache
             .as_ref()
-            .and_then(OnceCell::get)
+            .and_then(|cache| cache.get())
             .map(|c| c.0.values.read().expect("lock poisoned"))
         {
             // ensure that the property is in the cache.
@@ -764,22 +993,89 @@ impl<'a> Proxy<'a> {
     ///
     /// Get the property value from the cache (if caching is enabled) or call the
     /// `Get` method of the `org.freedesktop.DBus.Properties` interface.
+    ///
+    /// If caching is enabled, a value that had to be fetched with `Get` (e.g because the property
+    /// was invalidated) is cached as well, so it's only fetched again after the next change
+    /// notification from the peer.
+    ///
+    /// The cache can be told to honor the `org.freedesktop.DBus.Property.EmitsChangedSignal`
+    /// annotations of the properties, through [`ProxyBuilder::honor_emits_changed_signal`].
     pub async fn get_property<T>(&self, property_name: &str) -> Result<T>
     where
         T: TryFrom<OwnedValue>,
         T::Error: Into<Error>,
     {
-        if let Some(cache) = self.get_property_cache() {
+        let cache = self.get_property_cache();
+        if let Some(cache) = cache {
             cache.ready().await?;
         }
         if let Some(value) = self.cached_property(property_name)? {
             return Ok(value);
         }
 
+        let cache = cache.filter(|_| {
+            !self
+                .inner
+                .uncached_properties
+                .contains(&Str::from(property_name))
+        });
+        let updates = cache.map(|cache| cache.updates(property_name));
         let value = self.get_proxy_property(property_name).await?;
+        if let (Some(cache), Some(updates)) = (cache, updates) {
+            cache.store_fetched(property_name, updates, value.clone());
+        }
+
         value.try_into().map_err(Into::into)
     }
 
+    /// Get all the properties of the interface at once, as a `T`.
+    ///
+    /// This calls the `GetAll` method of the `org.freedesktop.DBus.Properties` interface and
+    /// deserializes the resulting `a{sv}` dictionary, so you get a consistent snapshot of the
+    /// interface in a single round-trip. The property cache isn't used nor updated.
+    ///
+    /// `T` is typically a struct deriving [`zvariant::DeserializeDict`] and [`zvariant::Type`]:
+    ///
+    /// ```no_run
+    /// # zbus::block_on(async {
+    /// use zbus::{zvariant::{DeserializeDict, Type}, Connection, Proxy};
+    ///
+    /// #[derive(DeserializeDict, Type, Debug)]
+    /// #[zvariant(signature = "dict", rename_all = "PascalCase")]
+    /// struct Unit {
+    ///     id: String,
+    ///     active_state: String,
+    ///     // Make a property optional if the peer doesn't always provide it.
+    ///     description: Option<String>,
+    /// }
+    ///
+    /// let conn = Connection::system().await?;
+    /// let proxy = Proxy::new(
+    ///     &conn,
+    ///     "org.freedesktop.systemd1",
+    ///     "/org/freedesktop/systemd1/unit/dbus_2eservice",
+    ///     "org.freedesktop.systemd1.Unit",
+    /// )
+    /// .await?;
+    /// let unit: Unit = proxy.get_all_as().await?;
+    /// println!(
+    ///     "{} ({}) is {}",
+    ///     unit.id,
+    ///     unit.description.as_deref().unwrap_or("no description"),
+    ///     unit.active_state,
+    /// );
+    /// # Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    pub async fn get_all_as<T>(&self) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned + zvariant::Type,
+    {
+        self.properties_proxy()
+            .call("GetAll", &(self.interface(),))
+            .await
+    }
+
     /// Set the property `property_name`.
     ///
     /// Effectively, call the `Set` method of the `org.freedesktop.DBus.Properties` interface.
@@ -792,6 +1088,16 @@ impl<'a> Proxy<'a> {
             .await
     }
 
+    /// The timeout for the method calls made through the proxy, if any.
+    ///
+    /// This is the one set through [`ProxyBuilder::method_timeout`] or, in its absence, the
+    /// [default one of the connection](Connection::method_timeout).
+    pub fn method_timeout(&self) -> Option<Duration> {
+        self.inner
+            .method_timeout
+            .or_else(|| self.inner.inner_without_borrows.conn.method_timeout())
+    }
+
     /// Call a method and return the reply.
     ///
     /// Typically, you would want to use [`call`] method instead. Use this method if you need to
@@ -805,17 +1111,70 @@ impl<'a> Proxy<'a> {
         M::Error: Into<Error>,
         B: serde::ser::Serialize + zvariant::DynamicType,
     {
-        self.inner
+        self.send_method_call(method_name, BitFlags::empty(), None, body)
+            .await
+            .map(|reply| reply.expect("no reply"))
+    }
+
+    /// Call a method and return the reply, waiting for it at most `timeout`.
+    ///
+    /// Same as [`Proxy::call_method`], except that the given `timeout` is used instead of the
+    /// [default one](Proxy::method_timeout). If no reply arrives in time, [`Error::Timeout`] is
+    /// returned.
+    pub async fn call_method_with_timeout<'m, M, B>(
+        &self,
+        method_name: M,
+        timeout: Duration,
+        body: &B,
+    ) -> Result<Arc<Message>>
+    where
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+        B: serde::ser::Serialize + zvariant::DynamicType,
+    {
+        self.send_method_call(method_name, BitFlags::empty(), Some(timeout), body)
+            .await
+            .map(|reply| reply.expect("no reply"))
+    }
+
+    // Send a method call and, unless no reply is expected, wait for the reply for no longer than
+    // `timeout` or the default timeout of the proxy.
+    async fn send_method_call<'m, M, B>(
+        &self,
+        method_name: M,
+        flags: BitFlags<MessageFlags>,
+        timeout: Option<Duration>,
+        body: &B,
+    ) -> Result<Option<Arc<Message>>>
+    where
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+        B: serde::ser::Serialize + zvariant::DynamicType,
+    {
+        self.start_deferred_caching();
+        let call = self
+            .inner
             .inner_without_borrows
             .conn
-            .call_method(
-                Some(&self.inner.destination),
-                self.inner.path.as_str(),
-                Some(&self.inner.interface),
+            .call_method_raw(
+                self.optional_destination(),
+                self.path(),
+                Some(self.interface()),
                 method_name,
+                flags,
                 body,
             )
-            .await
+            .await?;
+        match call {
+            Some(mut call) => {
+                if let Some(timeout) = timeout.or(self.inner.method_timeout) {
+                    call.set_timeout(timeout);
+                }
+
+                call.reply().await.map(Some)
+            }
+            None => Ok(None),
+        }
     }
 
     /// Call a method and return the reply body.
@@ -835,6 +1194,30 @@ impl<'a> Proxy<'a> {
         reply.body()
     }
 
+    /// Call a method and return the reply body, waiting for the reply at most `timeout`.
+    ///
+    /// Same as [`Proxy::call`], except that the given `timeout` is used instead of the
+    /// [default one](Proxy::method_timeout). If no reply arrives in time, [`Error::Timeout`] is
+    /// returned.
+    pub async fn call_with_timeout<'m, M, B, R>(
+        &self,
+        method_name: M,
+        timeout: Duration,
+        body: &B,
+    ) -> Result<R>
+    where
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+        B: serde::ser::Serialize + zvariant::DynamicType,
+        R: serde::de::DeserializeOwned + zvariant::Type,
+    {
+        let reply = self
+            .call_method_with_timeout(method_name, timeout, body)
+            .await?;
+
+        reply.body()
+    }
+
     /// Call a method and return the reply body, optionally supplying a set of
     /// method flags to control the way the method call message is sent and handled.
     ///
@@ -861,20 +1244,10 @@ impl<'a> Proxy<'a> {
             .map(MessageFlags::from)
             .collect::<BitFlags<_>>();
         match self
-            .inner
-            .inner_without_borrows
-            .conn
-            .call_method_raw(
-                Some(self.destination()),
-                self.path(),
-                Some(self.interface()),
-                method_name,
-                flags,
-                body,
-            )
+            .send_method_call(method_name, flags, None, body)
             .await?
         {
-            Some(reply) => reply.await?.body().map(Some),
+            Some(reply) => reply.body().map(Some),
             None => Ok(None),
         }
     }
@@ -919,27 +1292,137 @@ impl<'a> Proxy<'a> {
         M: TryInto<MemberName<'m>>,
         M::Error: Into<Error>,
     {
-        let signal_name = signal_name.try_into().map_err(Into::into)?;
-        self.receive_signals(Some(signal_name), args).await
+        let mut rule_builder = MatchRule::builder().member(signal_name)?;
+        for (i, arg) in args {
+            rule_builder = rule_builder.arg(*i, arg.to_string())?;
+        }
+
+        self.receive_signals_matching(rule_builder.build()).await
     }
 
-    async fn receive_signals<'m>(
+    /// Same as [`Proxy::receive_signal`] but with all the filters of `rule`.
+    ///
+    /// Unlike [`Proxy::receive_signal_with_args`], this allows the use of the `arg0namespace`,
+    /// `argNpath` and `path_namespace` elements of match rules, so that e.g. listening for the
+    /// `NameOwnerChanged` signal of all names under a specific namespace doesn't wake up the
+    /// process for every name on the bus. The filters are applied both by the bus and on the
+    /// client side, so that messages from other subscriptions on the same connection are not
+    /// delivered to the stream either.
+    ///
+    /// The member of `rule` (if set) is the name of the signal to receive. The message type,
+    /// sender and interface of `rule` are always replaced by those of the proxy, while its path is
+    /// used unless `rule` already has a path or a path namespace set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # zbus::block_on(async {
+    /// use futures_util::StreamExt;
+    /// use zbus::{fdo::NameOwnerChanged, Connection, MatchRule, Proxy};
+    ///
+    /// let conn = Connection::session().await?;
+    /// let proxy = Proxy::new(
+    ///     &conn,
+    ///     "org.freedesktop.DBus",
+    ///     "/org/freedesktop/DBus",
+    ///     "org.freedesktop.DBus",
+    /// )
+    /// .await?;
+    /// let rule = MatchRule::builder()
+    ///     .member("NameOwnerChanged")?
+    ///     .arg0ns("org.freedesktop.zbus.ProxyMatchDocTest")?
+    ///     .build();
+    /// let mut stream = proxy.receive_signals_matching(rule).await?;
+    ///
+    /// conn.request_name("org.freedesktop.zbus.ProxyMatchDocTest.Name").await?;
+    /// let signal = NameOwnerChanged::from_message(stream.next().await.unwrap()).unwrap();
+    /// assert_eq!(signal.args()?.name(), "org.freedesktop.zbus.ProxyMatchDocTest.Name");
+    /// # Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    pub async fn receive_signals_matching<'m>(
         &self,
-        signal_name: Option<MemberName<'m>>,
-        args: &[(u8, &str)],
+        rule: MatchRule<'m>,
     ) -> Result<SignalStream<'m>> {
+        self.start_deferred_caching();
         self.inner.subscribe_dest_owner_change().await?;
 
-        SignalStream::new(self.clone(), signal_name, args).await
+        SignalStream::new(self.clone(), rule).await
     }
 
     /// Create a stream for all signals emitted by this service.
     pub async fn receive_all_signals(&self) -> Result<SignalStream<'static>> {
-        self.receive_signals(None, &[]).await
+        self.receive_signals_matching(MatchRule::builder().build())
+            .await
+    }
+
+    /// Call `handler` for each signal named `signal_name`, until the returned guard is dropped.
+    ///
+    /// The handler is run in a task spawned on the executor of the connection, one signal after
+    /// the other. The match rule for the signal is managed for you, the same way as for
+    /// [`Proxy::receive_signal`]: it's removed once the handler is disconnected and no other
+    /// stream needs it.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # zbus::block_on(async {
+    /// use zbus::{Connection, Proxy};
+    ///
+    /// let conn = Connection::session().await?;
+    /// let proxy = Proxy::new(
+    ///     &conn,
+    ///     "org.freedesktop.DBus",
+    ///     "/org/freedesktop/DBus",
+    ///     "org.freedesktop.DBus",
+    /// )
+    /// .await?;
+    /// let handler = proxy
+    ///     .connect_signal("NameOwnerChanged", |msg| async move {
+    ///         println!("Name owner changed: {}", msg);
+    ///     })
+    ///     .await?;
+    ///
+    /// // Keep `handler` around for as long as you want to be notified..
+    /// handler.disconnect();
+    /// # Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    pub async fn connect_signal<'m, M, F, Fut>(
+        &self,
+        signal_name: M,
+        mut handler: F,
+    ) -> Result<SignalHandler>
+    where
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+        F: FnMut(Arc<Message>) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        use futures_util::StreamExt;
+
+        let signal_name = signal_name.try_into().map_err(Into::into)?.to_owned();
+        let task_name = format!("`{}` signal handler", signal_name);
+        let mut stream = self.receive_signal(signal_name).await?;
+        let task = self.connection().executor().spawn(
+            async move {
+                while let Some(msg) = stream.next().await {
+                    handler(msg).await;
+                }
+            }
+            .instrument(trace_span!("{}", task_name)),
+            &task_name,
+        );
+
+        Ok(SignalHandler { task })
     }
 
     /// Get a stream to receive property changed events.
     ///
+    /// The stream is seeded with the current value of the property: the first event is yielded
+    /// right away, without waiting for the property to change. This makes it easy to keep
+    /// something in sync with a property, with a single loop.
+    ///
     /// Note that zbus doesn't queue the updates. If the listener is slower than the receiver, it
     /// will only receive the last update.
     ///
@@ -963,6 +1446,7 @@ impl<'a> Proxy<'a> {
             name,
             proxy: self.clone(),
             changed_listener,
+            seeded: false,
             phantom: std::marker::PhantomData,
         }
     }
@@ -978,15 +1462,19 @@ impl<'a> Proxy<'a> {
     ///
     /// Note that zbus doesn't queue the updates. If the listener is slower than the receiver, it
     /// will only receive the last update.
+    ///
+    /// On a peer-to-peer connection, there are no names to own and [`Error::Unsupported`] is
+    /// returned.
     pub async fn receive_owner_changed(&self) -> Result<OwnerChangedStream<'_>> {
         use futures_util::StreamExt;
+        let name = self.bus_destination().ok_or(Error::Unsupported)?;
         let dbus_proxy = fdo::DBusProxy::builder(self.connection())
             .cache_properties(CacheProperties::No)
             .build()
             .await?;
         Ok(OwnerChangedStream {
             stream: dbus_proxy
-                .receive_name_owner_changed_with_args(&[(0, self.destination().as_str())])
+                .receive_name_owner_changed_with_args(&[(0, name.as_str())])
                 .await?
                 .map(Box::new(move |signal| {
                     let args = signal.args().unwrap();
@@ -994,15 +1482,174 @@ impl<'a> Proxy<'a> {
 
                     new_owner
                 })),
-            name: self.destination().clone(),
+            name: name.clone(),
         })
     }
+
+    /// The unique name of the peer currently owning the destination, if any.
+    ///
+    /// The first call looks the owner up on the bus and starts tracking the `NameOwnerChanged`
+    /// signal for the destination in the background, so that the following calls are answered
+    /// right away. Whenever the owner changes (e.g the service restarted), all the cached property
+    /// values are invalidated, so they won't be stale values from the previous owner, and the
+    /// proxy's signal streams only yield the signals emitted by the new owner. Use
+    /// [`Proxy::receive_owner_changed`] to be notified of such changes.
+    ///
+    /// On a peer-to-peer connection, there are no names to own and this always returns `None`.
+    pub async fn current_owner(&self) -> Result<Option<UniqueName<'static>>> {
+        if let Some((owner, _)) = self.inner.dest_owner.get() {
+            return Ok(owner.read().expect("lock poisoned").clone());
+        }
+        let conn = self.connection();
+        let name = match self.bus_destination() {
+            Some(name) => name.to_owned(),
+            // Names don't mean much outside the bus context.
+            None => return Ok(None),
+        };
+        let (current_owner, mut owner_changes) = lookup_owner(conn, &name).await?;
+        let owner = Arc::new(RwLock::new(current_owner.clone()));
+        let tracked_owner = owner.clone();
+        let property_cache = self.inner.property_cache.clone();
+        let task_name = format!("{name} owner tracking");
+        let tracking = async move {
+            use futures_util::StreamExt;
+
+            while let Some(msg) = owner_changes.next().await {
+                let signal = match msg.ok().and_then(NameOwnerChanged::from_message) {
+                    Some(signal) => signal,
+                    None => continue,
+                };
+                let new_owner = match signal.args() {
+                    Ok(args) => args.new_owner().as_ref().map(UniqueName::to_owned),
+                    Err(_) => continue,
+                };
+                trace!("Owner of {name} changed to {new_owner:?}");
+                *tracked_owner.write().expect("lock poisoned") = new_owner;
+
+                if let Some((cache, _)) = property_cache.as_ref().and_then(|cache| cache.get()) {
+                    cache.invalidate_all();
+                }
+            }
+        }
+        .instrument(trace_span!("{}", task_name));
+        let task = conn.executor().spawn(tracking, &task_name);
+
+        match self.inner.dest_owner.set((owner, task)) {
+            Ok(()) => Ok(current_owner),
+            // We raced another call, whose tracking we keep.
+            Err(_) => Ok(self
+                .inner
+                .dest_owner
+                .get()
+                .expect("owner tracking not initialized")
+                .0
+                .read()
+                .expect("lock poisoned")
+                .clone()),
+        }
+    }
+}
+
+/// Look up the current owner of `name`, subscribing to its `NameOwnerChanged` signal beforehand.
+///
+/// The returned stream yields the owner changes that happened after the lookup.
+async fn lookup_owner(
+    conn: &Connection,
+    name: &BusName<'_>,
+) -> Result<(Option<UniqueName<'static>>, MessageStream)> {
+    use ordered_stream::OrderedStreamExt;
+
+    let name_owner_changed_rule = MatchRule::builder()
+        .msg_type(MessageType::Signal)
+        .sender("org.freedesktop.DBus")?
+        .path("/org/freedesktop/DBus")?
+        .interface("org.freedesktop.DBus")?
+        .member("NameOwnerChanged")?
+        .add_arg(name.as_str())?
+        .build();
+    let name_owner_changed_stream = MessageStream::for_match_rule(
+        name_owner_changed_rule,
+        conn,
+        Some(MAX_NAME_OWNER_CHANGED_SIGNALS_QUEUED),
+    )
+    .await?
+    .map(Either::Left);
+
+    let get_name_owner = conn
+        .call_method_raw(
+            Some("org.freedesktop.DBus"),
+            "/org/freedesktop/DBus",
+            Some("org.freedesktop.DBus"),
+            "GetNameOwner",
+            BitFlags::empty(),
+            name,
+        )
+        .await
+        .map(|r| FromFuture::from(r.expect("no reply")).map(Either::Right))?;
+
+    let mut join = join_streams(name_owner_changed_stream, get_name_owner);
+
+    let mut owner = loop {
+        match join.next().await {
+            Some(Either::Left(Ok(msg))) => {
+                let signal = NameOwnerChanged::from_message(msg)
+                    .expect("`NameOwnerChanged` signal stream got wrong message");
+                {
+                    break signal
+                        .args()
+                        // SAFETY: The filtering code couldn't have let this through if
+                        // args were not in order.
+                        .expect("`NameOwnerChanged` signal has no args")
+                        .new_owner()
+                        .as_ref()
+                        .map(UniqueName::to_owned);
+                }
+            }
+            Some(Either::Left(Err(_))) => (),
+            Some(Either::Right(Ok(response))) => {
+                break Some(response.body::<UniqueName<'_>>()?.to_owned())
+            }
+            Some(Either::Right(Err(e))) => {
+                // Probably the name is not owned. Not a problem but let's still log it.
+                debug!("Failed to get owner of {name}: {e}");
+
+                break None;
+            }
+            None => {
+                return Err(Error::InputOutput(
+                    std::io::Error::new(std::io::ErrorKind::BrokenPipe, "connection closed").into(),
+                ))
+            }
+        }
+    };
+
+    // Let's take into account any buffered NameOwnerChanged signal.
+    let (stream, _, queued) = join.into_inner();
+    if let Some(msg) = queued.and_then(|e| match e.0 {
+        Either::Left(Ok(msg)) => Some(msg),
+        Either::Left(Err(_)) | Either::Right(_) => None,
+    }) {
+        if let Some(signal) = NameOwnerChanged::from_message(msg) {
+            if let Ok(args) = signal.args() {
+                match (args.name(), args.new_owner().deref()) {
+                    (n, Some(new_owner)) if n == name => {
+                        owner = Some(new_owner.to_owned());
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    Ok((owner, stream.into_inner()))
 }
 
 #[derive(Debug, Default)]
 struct PropertyValue {
     value: Option<OwnedValue>,
     event: Event,
+    // Number of change notifications (updates & invalidations) received for this property.
+    updates: u64,
 }
 
 /// Flags to use with [`Proxy::call_with_flags`].
@@ -1099,125 +1746,40 @@ impl<'a> SignalStream<'a> {
         self.signal_name.as_ref()
     }
 
-    async fn new(
-        proxy: Proxy<'_>,
-        signal_name: Option<MemberName<'a>>,
-        args: &[(u8, &str)],
-    ) -> Result<SignalStream<'a>> {
-        let mut rule_builder = MatchRule::builder()
-            .msg_type(MessageType::Signal)
-            .sender(proxy.destination())?
-            .path(proxy.path())?
-            .interface(proxy.interface())?;
-        if let Some(name) = &signal_name {
-            rule_builder = rule_builder.member(name)?;
-        }
-        for (i, arg) in args {
-            rule_builder = rule_builder.arg(*i, *arg)?;
+    async fn new(proxy: Proxy<'_>, mut rule: MatchRule<'a>) -> Result<SignalStream<'a>> {
+        rule.msg_type = Some(MessageType::Signal);
+        rule.sender = proxy.bus_destination().map(|d| d.to_owned());
+        rule.interface = Some(proxy.interface().to_owned());
+        if rule.path_spec.is_none() {
+            rule.path_spec = Some(MatchRulePathSpec::Path(proxy.path().to_owned()));
         }
-        let signal_rule: OwnedMatchRule = rule_builder.build().to_owned().into();
+        let signal_name = rule.member.clone();
+        let signal_rule: OwnedMatchRule = rule.into_owned().into();
         let conn = proxy.connection();
+        let max_queued = proxy.inner.max_queued_signals;
 
-        let (src_unique_name, stream) = match proxy.destination().to_owned() {
-            BusName::Unique(name) => (
+        let (src_unique_name, stream) = match proxy.bus_destination().map(|d| d.to_owned()) {
+            // On peer-to-peer connections, signals come without a sender.
+            None => (
+                None,
+                join_streams(
+                    MessageStream::for_match_rule(signal_rule, conn, max_queued).await?,
+                    None,
+                ),
+            ),
+            Some(BusName::Unique(name)) => (
                 Some(name),
                 join_streams(
-                    MessageStream::for_match_rule(signal_rule, conn, None).await?,
+                    MessageStream::for_match_rule(signal_rule, conn, max_queued).await?,
                     None,
                 ),
             ),
-            BusName::WellKnown(name) => {
-                use ordered_stream::OrderedStreamExt;
-
-                let name_owner_changed_rule = MatchRule::builder()
-                    .msg_type(MessageType::Signal)
-                    .sender("org.freedesktop.DBus")?
-                    .path("/org/freedesktop/DBus")?
-                    .interface("org.freedesktop.DBus")?
-                    .member("NameOwnerChanged")?
-                    .add_arg(name.as_str())?
-                    .build();
-                let name_owner_changed_stream = MessageStream::for_match_rule(
-                    name_owner_changed_rule,
-                    conn,
-                    Some(MAX_NAME_OWNER_CHANGED_SIGNALS_QUEUED),
-                )
-                .await?
-                .map(Either::Left);
-
-                let get_name_owner = conn
-                    .call_method_raw(
-                        Some("org.freedesktop.DBus"),
-                        "/org/freedesktop/DBus",
-                        Some("org.freedesktop.DBus"),
-                        "GetNameOwner",
-                        BitFlags::empty(),
-                        &name,
-                    )
-                    .await
-                    .map(|r| FromFuture::from(r.expect("no reply")).map(Either::Right))?;
-
-                let mut join = join_streams(name_owner_changed_stream, get_name_owner);
-
-                let mut src_unique_name = loop {
-                    match join.next().await {
-                        Some(Either::Left(Ok(msg))) => {
-                            let signal = NameOwnerChanged::from_message(msg)
-                                .expect("`NameOwnerChanged` signal stream got wrong message");
-                            {
-                                break signal
-                                    .args()
-                                    // SAFETY: The filtering code couldn't have let this through if
-                                    // args were not in order.
-                                    .expect("`NameOwnerChanged` signal has no args")
-                                    .new_owner()
-                                    .as_ref()
-                                    .map(UniqueName::to_owned);
-                            }
-                        }
-                        Some(Either::Left(Err(_))) => (),
-                        Some(Either::Right(Ok(response))) => {
-                            break Some(response.body::<UniqueName<'_>>()?.to_owned())
-                        }
-                        Some(Either::Right(Err(e))) => {
-                            // Probably the name is not owned. Not a problem but let's still log it.
-                            debug!("Failed to get owner of {name}: {e}");
-
-                            break None;
-                        }
-                        None => {
-                            return Err(Error::InputOutput(
-                                std::io::Error::new(
-                                    std::io::ErrorKind::BrokenPipe,
-                                    "connection closed",
-                                )
-                                .into(),
-                            ))
-                        }
-                    }
-                };
-
-                // Let's take into account any buffered NameOwnerChanged signal.
-                let (stream, _, queued) = join.into_inner();
-                if let Some(msg) = queued.and_then(|e| match e.0 {
-                    Either::Left(Ok(msg)) => Some(msg),
-                    Either::Left(Err(_)) | Either::Right(_) => None,
-                }) {
-                    if let Some(signal) = NameOwnerChanged::from_message(msg) {
-                        if let Ok(args) = signal.args() {
-                            match (args.name(), args.new_owner().deref()) {
-                                (BusName::WellKnown(n), Some(new_owner)) if n == &name => {
-                                    src_unique_name = Some(new_owner.to_owned());
-                                }
-                                _ => (),
-                            }
-                        }
-                    }
-                }
-                let name_owner_changed_stream = stream.into_inner();
+            Some(dest @ BusName::WellKnown(_)) => {
+                let (src_unique_name, name_owner_changed_stream) =
+                    lookup_owner(conn, &dest).await?;
 
                 let stream = join_streams(
-                    MessageStream::for_match_rule(signal_rule, conn, None).await?,
+                    MessageStream::for_match_rule(signal_rule, conn, max_queued).await?,
                     Some(name_owner_changed_stream),
                 );
 
@@ -1309,6 +1871,26 @@ impl AsyncDrop for SignalStream<'_> {
     }
 }
 
+/// A signal handler registered with [`Proxy::connect_signal`].
+///
+/// The handler stays connected for as long as this guard is around.
+#[derive(Debug)]
+#[must_use = "the handler is disconnected when dropped"]
+pub struct SignalHandler {
+    #[allow(unused)]
+    task: Task<()>,
+}
+
+assert_impl_all!(SignalHandler: Send, Sync, Unpin);
+
+impl SignalHandler {
+    /// Disconnect the handler.
+    ///
+    /// This is the same as dropping `self`: the handler won't be called anymore, even for the
+    /// signals already received.
+    pub fn disconnect(self) {}
+}
+
 impl<'a> From<crate::blocking::Proxy<'a>> for Proxy<'a> {
     fn from(proxy: crate::blocking::Proxy<'a>) -> Self {
         proxy.into_inner()
@@ -1389,6 +1971,686 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[timeout(15000)]
+    fn shared_signal_subscription() {
+        block_on(test_shared_signal_subscription()).unwrap();
+    }
+
+    async fn test_shared_signal_subscription() -> Result<()> {
+        let conn = Connection::session().await?;
+        let service_conn = Connection::session().await?;
+        let service_name = service_conn.unique_name().unwrap().clone();
+
+        let proxy = || {
+            ProxyBuilder::new_bare(&conn)
+                .destination(service_name.clone())
+                .unwrap()
+                .path("/org/zbus/SharedSubscription")
+                .unwrap()
+                .interface("org.zbus.SharedSubscription")
+                .unwrap()
+                .cache_properties(CacheProperties::No)
+                .build()
+        };
+        let (proxy1, proxy2): (Proxy<'_>, Proxy<'_>) = (proxy().await?, proxy().await?);
+        let match_rules = conn.stats().await.match_rules();
+
+        // Both proxies subscribing to the same signal results in a single match rule.
+        let mut stream1 = proxy1.receive_signal("Ping").await?;
+        let mut stream2 = proxy2.receive_signal("Ping").await?;
+        assert_eq!(conn.stats().await.match_rules(), match_rules + 1);
+
+        service_conn
+            .emit_signal(
+                None::<()>,
+                "/org/zbus/SharedSubscription",
+                "org.zbus.SharedSubscription",
+                "Ping",
+                &(),
+            )
+            .await?;
+        for stream in [&mut stream1, &mut stream2] {
+            let msg = stream.next().await.unwrap();
+            assert_eq!(msg.member().unwrap(), "Ping");
+        }
+
+        // The rule is only removed once the last subscriber is gone.
+        stream1.async_drop().await;
+        assert_eq!(conn.stats().await.match_rules(), match_rules + 1);
+        stream2.async_drop().await;
+        assert_eq!(conn.stats().await.match_rules(), match_rules);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn signal_match_rule_filters() {
+        block_on(test_signal_match_rule_filters()).unwrap();
+    }
+
+    async fn test_signal_match_rule_filters() -> Result<()> {
+        let conn = Connection::session().await?;
+        let service_conn = Connection::session().await?;
+        let service_name = service_conn.unique_name().unwrap().clone();
+
+        let proxy: Proxy<'_> = ProxyBuilder::new_bare(&conn)
+            .destination(service_name)?
+            .path("/org/zbus/Filtered")?
+            .interface("org.zbus.Filtered")?
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await?;
+        // A broader subscription on the same connection, to ensure the filtered stream doesn't get
+        // the messages the bus delivers for it.
+        let all_rule = MatchRule::builder()
+            .member("Ping")?
+            .path_namespace("/")?
+            .build();
+        let mut all_stream = proxy.receive_signals_matching(all_rule).await?;
+        let rule = MatchRule::builder()
+            .member("Ping")?
+            .path_namespace("/org/zbus/Filtered")?
+            .arg0ns("org.zbus.a")?
+            .arg_path(1, "/org/zbus/Filtered")?
+            .build();
+        let mut stream = proxy.receive_signals_matching(rule).await?;
+
+        let signals = [
+            // Not in the path namespace, even though the path has it as a string prefix.
+            ("/org/zbus/FilteredNot", "org.zbus.a", "/org/zbus/Filtered"),
+            // Not in the arg0 namespace.
+            ("/org/zbus/Filtered", "org.zbus.ab", "/org/zbus/Filtered"),
+            // Not matching the arg1 path.
+            ("/org/zbus/Filtered/child", "org.zbus.a.b", "/org/zbusy/"),
+            // Matches all, the arg1 path being a namespace of the one in the rule.
+            ("/org/zbus/Filtered/child", "org.zbus.a.b", "/org/zbus/"),
+        ];
+        for (path, arg0, arg1) in signals {
+            service_conn
+                .emit_signal(None::<()>, path, "org.zbus.Filtered", "Ping", &(arg0, arg1))
+                .await?;
+        }
+        for (_, _, arg1) in signals {
+            let msg = all_stream.next().await.unwrap();
+            let (_, path): (&str, &str) = msg.body()?;
+            assert_eq!(path, arg1);
+        }
+
+        let msg = stream.next().await.unwrap();
+        let (arg0, path): (&str, &str) = msg.body()?;
+        assert_eq!(arg0, "org.zbus.a.b");
+        assert_eq!(path, "/org/zbus/");
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn fetched_property_cached() {
+        block_on(test_fetched_property_cached()).unwrap();
+    }
+
+    async fn test_fetched_property_cached() -> Result<()> {
+        use std::sync::atomic::{AtomicUsize, Ordering::SeqCst};
+
+        struct Counter {
+            value: u32,
+            reads: Arc<AtomicUsize>,
+        }
+
+        #[dbus_interface(name = "org.zbus.FetchedPropertyCached")]
+        impl Counter {
+            #[dbus_interface(property)]
+            fn value(&self) -> u32 {
+                self.reads.fetch_add(1, SeqCst);
+
+                self.value
+            }
+        }
+
+        let reads = Arc::new(AtomicUsize::new(0));
+        let counter = Counter {
+            value: 1,
+            reads: reads.clone(),
+        };
+        let service = ConnectionBuilder::session()?
+            .serve_at("/org/zbus/FetchedPropertyCached", counter)?
+            .build()
+            .await?;
+        let conn = Connection::session().await?;
+        let proxy = Proxy::new(
+            &conn,
+            service.unique_name().unwrap(),
+            "/org/zbus/FetchedPropertyCached",
+            "org.zbus.FetchedPropertyCached",
+        )
+        .await?;
+
+        // The first read populates the cache, the following ones are served from it.
+        assert_eq!(proxy.get_property::<u32>("Value").await?, 1);
+        assert_eq!(proxy.get_property::<u32>("Value").await?, 1);
+        assert_eq!(reads.load(SeqCst), 1);
+
+        let mut changes = proxy.receive_property_changed::<u32>("Value").await;
+        assert_eq!(changes.next().await.unwrap().get().await?, 1);
+        let iface_ref = service
+            .object_server()
+            .interface::<_, Counter>("/org/zbus/FetchedPropertyCached")
+            .await?;
+        {
+            let mut iface = iface_ref.get_mut().await;
+            iface.value = 2;
+            iface.value_invalidate(iface_ref.signal_context()).await?;
+        }
+        changes.next().await.unwrap();
+        assert_eq!(proxy.cached_property::<u32>("Value")?, None);
+
+        // Once fetched again, the invalidated value is cached until the next notification.
+        assert_eq!(proxy.get_property::<u32>("Value").await?, 2);
+        assert_eq!(proxy.get_property::<u32>("Value").await?, 2);
+        assert_eq!(proxy.cached_property::<u32>("Value")?, Some(2));
+        assert_eq!(reads.load(SeqCst), 2);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "quick-xml")]
+    #[test]
+    #[timeout(15000)]
+    fn emits_changed_signal_annotation() {
+        block_on(test_emits_changed_signal_annotation()).unwrap();
+    }
+
+    #[cfg(feature = "quick-xml")]
+    async fn test_emits_changed_signal_annotation() -> Result<()> {
+        use futures_util::TryStreamExt;
+
+        const XML: &str = r#"
+<node>
+  <interface name="org.zbus.EmitsChangedSignal">
+    <property name="Load" type="u" access="read">
+      <annotation name="org.freedesktop.DBus.Property.EmitsChangedSignal" value="false"/>
+    </property>
+    <property name="Id" type="s" access="read">
+      <annotation name="org.freedesktop.DBus.Property.EmitsChangedSignal" value="const"/>
+    </property>
+  </interface>
+</node>
+"#;
+
+        // A bare service, since our object server doesn't announce the annotation.
+        let service = Connection::session().await?;
+        let mut calls = MessageStream::from(&service);
+        let responder = service.clone();
+        let _responder_task = service.executor().spawn(
+            async move {
+                let mut load = 0u32;
+                while let Some(call) = calls.try_next().await? {
+                    let member = match call.member() {
+                        Some(member) => member,
+                        None => continue,
+                    };
+                    load += 1;
+                    match member.as_str() {
+                        "Introspect" => responder.reply(&call, &XML).await?,
+                        "GetAll" => {
+                            let mut props = HashMap::new();
+                            props.insert("Load", Value::from(load));
+                            props.insert("Id", Value::from("fixed"));
+                            responder.reply(&call, &props).await?
+                        }
+                        "Get" => responder.reply(&call, &Value::from(load)).await?,
+                        _ => continue,
+                    };
+                }
+
+                Ok::<_, Error>(())
+            },
+            "responder",
+        );
+
+        let conn = Connection::session().await?;
+        let proxy = ProxyBuilder::<'_, Proxy<'_>>::new_bare(&conn)
+            .destination(service.unique_name().unwrap().to_owned())?
+            .path("/org/zbus/EmitsChangedSignal")?
+            .interface("org.zbus.EmitsChangedSignal")?
+            .cache_properties(CacheProperties::Yes)
+            .honor_emits_changed_signal(true)
+            .build()
+            .await?;
+
+        // The cache would never know the load changed, so it's always fetched from the service.
+        let load = proxy.get_property::<u32>("Load").await?;
+        assert_eq!(proxy.cached_property::<u32>("Load")?, None);
+        assert!(proxy.get_property::<u32>("Load").await? > load);
+        assert_eq!(proxy.cached_property::<String>("Id")?.unwrap(), "fixed");
+
+        // Constant values are here to stay.
+        proxy
+            .get_property_cache()
+            .expect("no property cache")
+            .invalidate_all();
+        assert_eq!(proxy.cached_property::<String>("Id")?.unwrap(), "fixed");
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn current_owner() {
+        block_on(test_current_owner()).unwrap();
+    }
+
+    async fn test_current_owner() -> Result<()> {
+        struct Service(u32);
+
+        #[dbus_interface(name = "org.zbus.CurrentOwner")]
+        impl Service {
+            #[dbus_interface(property)]
+            fn generation(&self) -> u32 {
+                self.0
+            }
+        }
+
+        async fn wait_for_owner(proxy: &Proxy<'_>, owner: Option<&UniqueName<'_>>) -> Result<()> {
+            while proxy.current_owner().await?.as_ref() != owner {
+                crate::abstractions::timeout::sleep(std::time::Duration::from_millis(10)).await;
+            }
+
+            Ok(())
+        }
+
+        let name = "org.zbus.CurrentOwner";
+        let conn = Connection::session().await?;
+        let proxy = Proxy::new(&conn, name, "/org/zbus/CurrentOwner", name).await?;
+        assert_eq!(proxy.current_owner().await?, None);
+
+        let service = ConnectionBuilder::session()?
+            .serve_at("/org/zbus/CurrentOwner", Service(1))?
+            .name(name)?
+            .build()
+            .await?;
+        wait_for_owner(&proxy, service.unique_name().map(|n| &**n)).await?;
+        assert_eq!(proxy.get_property::<u32>("Generation").await?, 1);
+        assert_eq!(proxy.cached_property::<u32>("Generation")?, Some(1));
+
+        // A restarted service doesn't announce its properties, the stale values must go.
+        drop(service);
+        wait_for_owner(&proxy, None).await?;
+        assert_eq!(proxy.cached_property::<u32>("Generation")?, None);
+        let service = ConnectionBuilder::session()?
+            .serve_at("/org/zbus/CurrentOwner", Service(2))?
+            .name(name)?
+            .build()
+            .await?;
+        wait_for_owner(&proxy, service.unique_name().map(|n| &**n)).await?;
+        assert_eq!(proxy.get_property::<u32>("Generation").await?, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn build_lazy() {
+        block_on(test_build_lazy()).unwrap();
+    }
+
+    async fn test_build_lazy() -> Result<()> {
+        struct Service;
+
+        #[dbus_interface(name = "org.zbus.Lazy")]
+        impl Service {
+            fn ping(&self) -> u32 {
+                42
+            }
+
+            #[dbus_interface(property)]
+            fn answer(&self) -> u32 {
+                42
+            }
+        }
+
+        let name = "org.zbus.Lazy";
+        let conn = Connection::session().await?;
+        let builder = ProxyBuilder::<Proxy<'_>>::new_bare(&conn)
+            .destination(name)?
+            .path("/org/zbus/Lazy")?
+            .interface(name)?
+            .cache_properties(CacheProperties::Yes);
+
+        let stats = conn.stats().await;
+        let proxy = builder.clone().build_lazy()?;
+        let lazy_stats = conn.stats().await;
+        assert_eq!(lazy_stats.msgs_sent(), stats.msgs_sent());
+        assert_eq!(lazy_stats.match_rules(), stats.match_rules());
+        assert!(proxy.inner.property_cache.as_ref().unwrap().get().is_none());
+        // Unlike lazy building, caching upfront fails without the service around.
+        builder.build().await.unwrap_err();
+
+        let _service = ConnectionBuilder::session()?
+            .serve_at("/org/zbus/Lazy", Service)?
+            .name(name)?
+            .build()
+            .await?;
+
+        // The first use starts caching.
+        assert_eq!(proxy.call::<_, _, u32>("Ping", &()).await?, 42);
+        proxy
+            .get_property_cache()
+            .expect("properties cache not initialized")
+            .ready()
+            .await?;
+        assert_eq!(proxy.cached_property::<u32>("Answer")?, Some(42));
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn method_timeout() {
+        block_on(test_method_timeout()).unwrap();
+    }
+
+    async fn test_method_timeout() -> Result<()> {
+        // The service doesn't serve any objects, so it never replies to method calls.
+        let service = Connection::session().await?;
+        let conn = Connection::session().await?;
+        let proxy: Proxy<'_> = ProxyBuilder::new_bare(&conn)
+            .destination(service.unique_name().unwrap().to_owned())?
+            .path("/org/zbus/Silent")?
+            .interface("org.zbus.Silent")?
+            .cache_properties(CacheProperties::No)
+            .method_timeout(Duration::from_millis(100))
+            .build()
+            .await?;
+        assert_eq!(proxy.method_timeout(), Some(Duration::from_millis(100)));
+
+        let res = proxy.call::<_, _, ()>("Hang", &()).await;
+        assert_eq!(res.unwrap_err(), Error::Timeout);
+        let res = proxy.get_property::<u32>("Hung").await;
+        assert_eq!(res.unwrap_err(), Error::Timeout);
+        let res = proxy
+            .call_with_timeout::<_, _, ()>("Hang", Duration::from_millis(10), &())
+            .await;
+        assert_eq!(res.unwrap_err(), Error::Timeout);
+
+        // Calls that timed out don't linger around.
+        assert_eq!(conn.stats().await.method_calls_in_flight(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn get_all_as() {
+        block_on(test_get_all_as()).unwrap();
+    }
+
+    async fn test_get_all_as() -> Result<()> {
+        use zvariant::{DeserializeDict, Type};
+
+        struct Device;
+
+        #[dbus_interface(name = "org.zbus.GetAllAs")]
+        impl Device {
+            #[dbus_interface(property)]
+            fn name(&self) -> &str {
+                "sensor"
+            }
+
+            #[dbus_interface(property)]
+            fn sample_rate(&self) -> u32 {
+                100
+            }
+        }
+
+        #[derive(DeserializeDict, Type, Debug, PartialEq)]
+        #[zvariant(signature = "dict", rename_all = "PascalCase")]
+        struct DeviceProperties {
+            name: String,
+            sample_rate: u32,
+            vendor: Option<String>,
+        }
+
+        #[derive(DeserializeDict, Type, Debug)]
+        #[zvariant(signature = "dict", rename_all = "PascalCase")]
+        struct Incompatible {
+            #[allow(unused)]
+            vendor: String,
+        }
+
+        let service = ConnectionBuilder::session()?
+            .serve_at("/org/zbus/GetAllAs", Device)?
+            .build()
+            .await?;
+        let conn = Connection::session().await?;
+        let proxy = ProxyBuilder::<'_, Proxy<'_>>::new_bare(&conn)
+            .destination(service.unique_name().unwrap().to_owned())?
+            .path("/org/zbus/GetAllAs")?
+            .interface("org.zbus.GetAllAs")?
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await?;
+
+        let props: DeviceProperties = proxy.get_all_as().await?;
+        assert_eq!(
+            props,
+            DeviceProperties {
+                name: "sensor".into(),
+                sample_rate: 100,
+                vendor: None,
+            }
+        );
+        // A required field the interface doesn't provide is a deserialization error.
+        assert!(proxy.get_all_as::<Incompatible>().await.is_err());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "quick-xml")]
+    #[test]
+    #[timeout(15000)]
+    fn introspect_node() {
+        block_on(test_introspect_node()).unwrap();
+    }
+
+    #[cfg(feature = "quick-xml")]
+    async fn test_introspect_node() -> Result<()> {
+        struct Pinger;
+
+        #[dbus_interface(name = "org.zbus.IntrospectNode")]
+        impl Pinger {
+            fn ping(&self) {}
+        }
+
+        let service = ConnectionBuilder::session()?
+            .serve_at("/org/zbus/IntrospectNode", Pinger)?
+            .serve_at("/org/zbus/IntrospectNode/Child", Pinger)?
+            .serve_at("/org/zbus/IntrospectNode/Child/GrandChild", Pinger)?
+            .serve_at("/org/zbus/IntrospectNode/Another/GrandChild", Pinger)?
+            .build()
+            .await?;
+        let conn = Connection::session().await?;
+        let proxy = Proxy::new(
+            &conn,
+            service.unique_name().unwrap(),
+            "/org/zbus/IntrospectNode",
+            "org.zbus.IntrospectNode",
+        )
+        .await?;
+
+        let node = proxy.introspect_node().await?;
+        let iface = node
+            .interfaces()
+            .iter()
+            .find(|i| i.name() == "org.zbus.IntrospectNode")
+            .unwrap();
+        assert_eq!(iface.methods().len(), 1);
+        assert_eq!(iface.methods()[0].name(), "Ping");
+        let interfaces: Vec<_> = node.interfaces().iter().map(|i| i.name()).collect();
+        assert_eq!(
+            interfaces,
+            [
+                "org.freedesktop.DBus.Introspectable",
+                "org.freedesktop.DBus.Peer",
+                "org.freedesktop.DBus.Properties",
+                "org.zbus.IntrospectNode",
+            ]
+        );
+
+        // Only the direct children are listed, including the intermediate ones, without details.
+        let children: Vec<_> = node.nodes().iter().map(|n| n.name()).collect();
+        assert_eq!(children, [Some("Another"), Some("Child")]);
+        for child in node.nodes() {
+            assert!(child.interfaces().is_empty());
+            assert!(child.nodes().is_empty());
+        }
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn p2p() {
+        block_on(test_p2p()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_p2p() -> Result<()> {
+        use crate::utils::unix_p2p_pipe_with;
+
+        struct Greeter;
+
+        #[dbus_interface(name = "org.zbus.p2p.Greeter")]
+        impl Greeter {
+            fn greet(&self, name: &str) -> String {
+                format!("Hello {name}")
+            }
+
+            #[dbus_interface(property)]
+            fn greeting(&self) -> &str {
+                "Hello"
+            }
+        }
+
+        // The default service is meaningless without a bus, but harmless.
+        #[dbus_proxy(
+            gen_blocking = false,
+            interface = "org.zbus.p2p.Greeter",
+            default_service = "org.zbus.p2p.Greeter",
+            default_path = "/org/zbus/Greeter"
+        )]
+        trait Greeter {
+            fn greet(&self, name: &str) -> Result<String>;
+
+            #[dbus_proxy(property)]
+            fn greeting(&self) -> Result<String>;
+
+            #[dbus_proxy(signal)]
+            fn greeted(&self, name: &str) -> Result<()>;
+        }
+
+        let (client, server) =
+            unix_p2p_pipe_with(Ok, |server| server.serve_at("/org/zbus/Greeter", Greeter)).await?;
+
+        let proxy = GreeterProxy::new(&client).await?;
+        assert_eq!(proxy.destination(), "org.zbus.p2p.Greeter");
+        assert_eq!(proxy.greet("p2p").await?, "Hello p2p");
+        assert_eq!(proxy.greeting().await?, "Hello");
+        assert_eq!(proxy.current_owner().await?, None);
+
+        // A bare proxy doesn't need a destination at all.
+        let bare = ProxyBuilder::<'_, Proxy<'_>>::new_bare(&client)
+            .path("/org/zbus/Greeter")?
+            .interface("org.zbus.p2p.Greeter")?
+            .build()
+            .await?;
+        assert_eq!(bare.optional_destination(), None);
+        assert_eq!(
+            bare.call::<_, _, String>("Greet", &"bare").await?,
+            "Hello bare"
+        );
+
+        // Signals from the peer come without a sender.
+        let mut greeted = proxy.receive_greeted().await?;
+        server
+            .emit_signal(
+                None::<()>,
+                "/org/zbus/Greeter",
+                "org.zbus.p2p.Greeter",
+                "Greeted",
+                &"p2p",
+            )
+            .await?;
+        assert_eq!(greeted.next().await.unwrap().args()?.name(), &"p2p");
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn signal_handler() {
+        block_on(test_signal_handler()).unwrap();
+    }
+
+    async fn test_signal_handler() -> Result<()> {
+        #[dbus_proxy(
+            gen_blocking = false,
+            interface = "org.zbus.SignalHandler",
+            default_path = "/org/zbus/SignalHandler"
+        )]
+        trait Counter {
+            #[dbus_proxy(signal)]
+            fn count(&self, value: u32) -> Result<()>;
+        }
+
+        let service = Connection::session().await?;
+        let conn = Connection::session().await?;
+        let proxy = CounterProxy::builder(&conn)
+            .destination(service.unique_name().unwrap())?
+            .build()
+            .await?;
+        let match_rules = conn.stats().await.match_rules();
+
+        let (tx, mut rx) = async_broadcast::broadcast(4);
+        let handler = proxy
+            .connect_count(move |signal| {
+                let tx = tx.clone();
+
+                async move {
+                    let value = signal.args().unwrap().value;
+                    tx.broadcast(value).await.unwrap();
+                }
+            })
+            .await?;
+        assert_eq!(conn.stats().await.match_rules(), match_rules + 1);
+
+        for value in 1..3u32 {
+            service
+                .emit_signal(
+                    None::<()>,
+                    "/org/zbus/SignalHandler",
+                    "org.zbus.SignalHandler",
+                    "Count",
+                    &value,
+                )
+                .await?;
+            assert_eq!(rx.recv().await.unwrap(), value);
+        }
+
+        // Disconnecting drops the handler (and its sender) and removes the match rule.
+        handler.disconnect();
+        assert!(rx.recv().await.is_err());
+        while conn.stats().await.match_rules() != match_rules {
+            crate::abstractions::timeout::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        Ok(())
+    }
+
     #[test]
     #[timeout(15000)]
     fn signal_stream_deadlock() {
@@ -1,4 +1,4 @@
-use std::{collections::HashSet, convert::TryInto, marker::PhantomData, sync::Arc};
+use std::{collections::HashSet, convert::TryInto, marker::PhantomData, sync::Arc, time::Duration};
 
 use static_assertions::assert_impl_all;
 use zbus_names::{BusName, InterfaceName};
@@ -11,7 +11,7 @@ use crate::{Connection, Error, Proxy, ProxyInner, Result};
 #[non_exhaustive]
 pub enum CacheProperties {
     /// Cache properties. The properties will be cached upfront as part of the proxy
-    /// creation.
+    /// creation, or on first use of the proxy if it's built with [`ProxyBuilder::build_lazy`].
     Yes,
     /// Don't cache properties.
     No,
@@ -30,6 +30,9 @@ pub struct ProxyBuilder<'a, T = ()> {
     proxy_type: PhantomData<T>,
     cache: CacheProperties,
     uncached_properties: Option<HashSet<Str<'a>>>,
+    max_queued_signals: Option<usize>,
+    method_timeout: Option<Duration>,
+    honor_emits_changed_signal: bool,
 }
 
 impl<'a, T> Clone for ProxyBuilder<'a, T> {
@@ -41,6 +44,9 @@ impl<'a, T> Clone for ProxyBuilder<'a, T> {
             interface: self.interface.clone(),
             cache: self.cache,
             uncached_properties: self.uncached_properties.clone(),
+            max_queued_signals: self.max_queued_signals,
+            method_timeout: self.method_timeout,
+            honor_emits_changed_signal: self.honor_emits_changed_signal,
             proxy_type: PhantomData,
         }
     }
@@ -59,6 +65,9 @@ impl<'a, T> ProxyBuilder<'a, T> {
             interface: None,
             cache: CacheProperties::default(),
             uncached_properties: None,
+            max_queued_signals: None,
+            method_timeout: None,
+            honor_emits_changed_signal: false,
             proxy_type: PhantomData,
         }
     }
@@ -66,6 +75,11 @@ impl<'a, T> ProxyBuilder<'a, T> {
 
 impl<'a, T> ProxyBuilder<'a, T> {
     /// Set the proxy destination address.
+    ///
+    /// The destination is required on bus connections. On peer-to-peer connections, there is no
+    /// one to route the messages and it's optional: method calls are then sent without a
+    /// `DESTINATION` header. Either way, signals from the peer are matched by path and interface
+    /// only, since they come without a sender.
     pub fn destination<D>(mut self, destination: D) -> Result<Self>
     where
         D: TryInto<BusName<'a>>,
@@ -111,15 +125,67 @@ impl<'a, T> ProxyBuilder<'a, T> {
         self
     }
 
+    /// Set the maximum number of signals to queue in each signal stream of the proxy.
+    ///
+    /// Once a stream's queue is full, receiving more messages on the connection waits for the
+    /// stream to be read from. Signal streams subscribing to the same signals share their queue,
+    /// which ends up with the biggest of the requested sizes.
+    ///
+    /// By default, the [connection's limit][`crate::ConnectionBuilder::max_queued`] is used.
+    #[must_use]
+    pub fn max_queued_signals(mut self, max: usize) -> Self {
+        self.max_queued_signals = Some(max);
+
+        self
+    }
+
+    /// Set the default timeout for the method calls made through the proxy.
+    ///
+    /// If no reply arrives in time, the call fails with [`Error::Timeout`]. This overrides the
+    /// [connection's timeout][`crate::ConnectionBuilder::method_timeout`], if any, and can itself
+    /// be overridden for individual calls with [`Proxy::call_with_timeout`].
+    #[must_use]
+    pub fn method_timeout(mut self, timeout: Duration) -> Self {
+        self.method_timeout = Some(timeout);
+
+        self
+    }
+
+    /// Honor the `org.freedesktop.DBus.Property.EmitsChangedSignal` annotations of the properties.
+    ///
+    /// If enabled, the object is introspected when the property cache is populated, so that the
+    /// properties announced as not emitting `PropertiesChanged` are always fetched from the peer,
+    /// while the `const` ones stay cached even if the destination changes owner. Since that costs
+    /// an extra call to the peer, it's disabled by default and all properties are assumed to emit
+    /// `PropertiesChanged`.
+    #[cfg(feature = "quick-xml")]
+    #[must_use]
+    pub fn honor_emits_changed_signal(mut self, honor: bool) -> Self {
+        self.honor_emits_changed_signal = honor;
+
+        self
+    }
+
+    // Target the same peer as another proxy, i-e no one on a peer-to-peer connection.
+    pub(crate) fn same_destination_as(mut self, proxy: &Proxy<'_>) -> Self {
+        self.destination = proxy.optional_destination().map(|d| d.to_owned());
+
+        self
+    }
+
     pub(crate) fn build_internal(self) -> Result<Proxy<'a>> {
         let conn = self.conn;
-        let destination = self
-            .destination
-            .ok_or(Error::MissingParameter("destination"))?;
+        let destination = self.destination;
+        if destination.is_none() && conn.is_bus() {
+            return Err(Error::MissingParameter("destination"));
+        }
         let path = self.path.ok_or(Error::MissingParameter("path"))?;
         let interface = self.interface.ok_or(Error::MissingParameter("interface"))?;
         let cache = self.cache;
         let uncached_properties = self.uncached_properties.unwrap_or_default();
+        let max_queued_signals = self.max_queued_signals;
+        let method_timeout = self.method_timeout;
+        let honor_emits_changed_signal = self.honor_emits_changed_signal;
 
         Ok(Proxy {
             inner: Arc::new(ProxyInner::new(
@@ -129,6 +195,9 @@ impl<'a, T> ProxyBuilder<'a, T> {
                 interface,
                 cache,
                 uncached_properties,
+                max_queued_signals,
+                method_timeout,
+                honor_emits_changed_signal,
             )),
         })
     }
@@ -156,6 +225,25 @@ impl<'a, T> ProxyBuilder<'a, T> {
 
         Ok(proxy.into())
     }
+
+    /// Build a proxy from the builder, without any communication with the peer.
+    ///
+    /// Unlike [`ProxyBuilder::build`], this never waits on the bus. With [`CacheProperties::Yes`],
+    /// the population of the properties cache is deferred to the first use of the proxy (a method
+    /// call, a signal subscription or a property access) and happens in the background. This
+    /// makes it cheap to create many proxies upfront, e.g at application startup, even if only a
+    /// few of them end up being used.
+    ///
+    /// # Errors
+    ///
+    /// If the builder is lacking the necessary parameters to build a proxy,
+    /// [`Error::MissingParameter`] is returned.
+    pub fn build_lazy(self) -> Result<T>
+    where
+        T: From<Proxy<'a>>,
+    {
+        self.build_internal().map(Into::into)
+    }
 }
 
 impl<'a, T> ProxyBuilder<'a, T>
@@ -174,6 +262,9 @@ where
             ),
             cache: CacheProperties::default(),
             uncached_properties: None,
+            max_queued_signals: None,
+            method_timeout: None,
+            honor_emits_changed_signal: false,
             proxy_type: PhantomData,
         }
     }
@@ -191,6 +282,21 @@ pub trait ProxyDefault {
     const PATH: &'static str;
 }
 
+/// Trait for the D-Bus interface a proxy is for.
+///
+/// Unlike [`ProxyDefault`], the trait is implemented by the [`dbus_proxy`] macro for all proxies,
+/// even those without a default destination or path. This allows creating proxies of a given type
+/// generically, e.g. with [`ObjectManagerProxy::managed_proxies`].
+///
+/// [`dbus_proxy`]: attr.dbus_proxy.html
+/// [`ObjectManagerProxy::managed_proxies`]: crate::fdo::ObjectManagerProxy::managed_proxies
+pub trait ProxyInterface {
+    /// The name of the interface.
+    const INTERFACE: &'static str;
+    /// The properties of the interface which should be excluded from caching.
+    const UNCACHED_PROPERTIES: &'static [&'static str];
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -212,12 +318,16 @@ mod tests {
             .unwrap()
             .interface("org.freedesktop.Interface")
             .unwrap()
-            .cache_properties(CacheProperties::No);
+            .cache_properties(CacheProperties::No)
+            .max_queued_signals(2)
+            .method_timeout(Duration::from_secs(5));
         assert!(matches!(
             builder.clone().destination.unwrap(),
             BusName::Unique(_),
         ));
         let proxy = builder.build().await.unwrap();
-        assert!(matches!(proxy.inner.destination, BusName::Unique(_)));
+        assert!(matches!(proxy.inner.destination, Some(BusName::Unique(_))));
+        assert_eq!(proxy.inner.max_queued_signals, Some(2));
+        assert_eq!(proxy.method_timeout(), Some(Duration::from_secs(5)));
     }
 }
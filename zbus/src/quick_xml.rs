@@ -181,6 +181,39 @@ impl PropertyAccess {
     }
 }
 
+/// The value of the standard `org.freedesktop.DBus.Property.EmitsChangedSignal` annotation.
+///
+/// It tells whether the `PropertiesChanged` signal is emitted when a property changes. It can be
+/// set on a property, or on the whole interface as the default for its properties.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EmitsChangedSignal {
+    /// The signal is emitted with the new value.
+    #[default]
+    True,
+    /// The signal is emitted but the value is not included.
+    Invalidates,
+    /// The property never changes during the lifetime of the object.
+    Const,
+    /// The signal is not guaranteed to be emitted.
+    False,
+}
+
+impl EmitsChangedSignal {
+    const ANNOTATION: &'static str = "org.freedesktop.DBus.Property.EmitsChangedSignal";
+
+    fn from_annotations(annotations: &[Annotation]) -> Option<Self> {
+        let annotation = annotations.iter().find(|a| a.name() == Self::ANNOTATION)?;
+
+        match annotation.value() {
+            "true" => Some(Self::True),
+            "invalidates" => Some(Self::Invalidates),
+            "const" => Some(Self::Const),
+            "false" => Some(Self::False),
+            _ => None,
+        }
+    }
+}
+
 /// A property
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Property<'a> {
@@ -218,6 +251,15 @@ impl<'a> Property<'a> {
     pub fn annotations(&self) -> &[Annotation] {
         &self.annotations
     }
+
+    /// Returns the value of the `org.freedesktop.DBus.Property.EmitsChangedSignal` annotation of
+    /// the property, if set to a valid value.
+    ///
+    /// Use [`Interface::property_emits_changed_signal`] to take the interface default into
+    /// account.
+    pub fn emits_changed_signal(&self) -> Option<EmitsChangedSignal> {
+        EmitsChangedSignal::from_annotations(&self.annotations)
+    }
 }
 
 /// An interface
@@ -263,6 +305,21 @@ impl<'a> Interface<'a> {
     pub fn annotations(&self) -> &[Annotation] {
         &self.annotations
     }
+
+    /// Returns whether the `PropertiesChanged` signal is emitted for the given property.
+    ///
+    /// The annotation of the property takes precedence over the one of the interface. Returns
+    /// `None` if the interface has no such property.
+    pub fn property_emits_changed_signal(&self, property_name: &str) -> Option<EmitsChangedSignal> {
+        let property = self.properties.iter().find(|p| p.name() == property_name)?;
+
+        Some(
+            property
+                .emits_changed_signal()
+                .or_else(|| EmitsChangedSignal::from_annotations(&self.annotations))
+                .unwrap_or_default(),
+        )
+    }
 }
 
 /// An introspection tree node (typically the root of the XML document).
@@ -320,6 +377,14 @@ impl<'a> Node<'a> {
     pub fn interfaces(&self) -> &[Interface<'a>] {
         &self.interfaces
     }
+
+    pub(crate) fn nodes_mut(&mut self) -> &mut Vec<Node<'a>> {
+        &mut self.nodes
+    }
+
+    pub(crate) fn set_name(&mut self, name: Option<String>) {
+        self.name = name;
+    }
 }
 
 impl<'a> TryFrom<&'a str> for Node<'a> {
@@ -338,7 +403,7 @@ mod tests {
     use std::{convert::TryInto, error::Error};
     use test_log::test;
 
-    use super::{ArgDirection, Node};
+    use super::{ArgDirection, EmitsChangedSignal, Node};
 
     static EXAMPLE: &str = r##"
 <!DOCTYPE node PUBLIC "-//freedesktop//DTD D-BUS Object Introspection 1.0//EN"
@@ -390,4 +455,48 @@ mod tests {
         node.to_writer(&mut writer).unwrap();
         Ok(())
     }
+    #[test]
+    fn emits_changed_signal() -> Result<(), Box<dyn Error>> {
+        let node = Node::from_reader(
+            r#"
+<node>
+  <interface name="com.example.Annotated">
+    <annotation name="org.freedesktop.DBus.Property.EmitsChangedSignal" value="invalidates"/>
+    <property name="Default" type="u" access="read"/>
+    <property name="Id" type="s" access="read">
+      <annotation name="org.freedesktop.DBus.Property.EmitsChangedSignal" value="const"/>
+    </property>
+    <property name="Load" type="d" access="read">
+      <annotation name="org.freedesktop.DBus.Property.EmitsChangedSignal" value="false"/>
+    </property>
+  </interface>
+  <interface name="com.example.Plain">
+    <property name="Level" type="u" access="read"/>
+  </interface>
+</node>
+"#
+            .as_bytes(),
+        )?;
+        let annotated = &node.interfaces()[0];
+        assert_eq!(annotated.properties()[0].emits_changed_signal(), None);
+        assert_eq!(
+            annotated.property_emits_changed_signal("Default"),
+            Some(EmitsChangedSignal::Invalidates)
+        );
+        assert_eq!(
+            annotated.property_emits_changed_signal("Id"),
+            Some(EmitsChangedSignal::Const)
+        );
+        assert_eq!(
+            annotated.property_emits_changed_signal("Load"),
+            Some(EmitsChangedSignal::False)
+        );
+        assert_eq!(annotated.property_emits_changed_signal("Unknown"), None);
+        assert_eq!(
+            node.interfaces()[1].property_emits_changed_signal("Level"),
+            Some(EmitsChangedSignal::True)
+        );
+
+        Ok(())
+    }
 }
@@ -6,18 +6,12 @@ use std::{
 };
 
 use event_listener::{Event, EventListener};
+use futures_core::ready;
 
-#[cfg(unix)]
-use crate::OwnedFd;
 use crate::{
-    message_header::{MAX_MESSAGE_SIZE, MIN_MESSAGE_SIZE},
-    raw::Socket,
-    utils::padding_for_8_bytes,
-    Message, MessagePrimaryHeader,
+    raw::Socket, ConnectionStats, Message, MessageParser, MessageType, OutgoingQueuePolicy,
 };
 
-use futures_core::ready;
-
 /// A low-level representation of a D-Bus connection
 ///
 /// This wrapper is agnostic on the actual transport, using the `Socket` trait
@@ -32,30 +26,134 @@ pub struct Connection<S> {
     #[derivative(Debug = "ignore")]
     socket: S,
     event: Event,
-    raw_in_buffer: Vec<u8>,
-    #[cfg(unix)]
-    raw_in_fds: Vec<OwnedFd>,
-    raw_in_pos: usize,
+    parser: MessageParser,
     out_pos: usize,
     out_msgs: VecDeque<Arc<Message>>,
     prev_seq: u64,
+    max_out_queued: Option<usize>,
+    out_queue_policy: OutgoingQueuePolicy,
+    msgs_sent: u64,
+    bytes_sent: u64,
+    bytes_received: u64,
 }
 
 impl<S: Socket> Connection<S> {
     pub(crate) fn new(socket: S, raw_in_buffer: Vec<u8>) -> Connection<S> {
+        let mut parser = MessageParser::new();
+        parser.feed(&raw_in_buffer);
+
         Connection {
             socket,
             event: Event::new(),
-            raw_in_pos: raw_in_buffer.len(),
-            raw_in_buffer,
-            #[cfg(unix)]
-            raw_in_fds: vec![],
+            parser,
             out_pos: 0,
             out_msgs: VecDeque::new(),
             prev_seq: 0,
+            max_out_queued: None,
+            out_queue_policy: OutgoingQueuePolicy::default(),
+            msgs_sent: 0,
+            bytes_sent: 0,
+            bytes_received: 0,
+        }
+    }
+
+    /// The traffic statistics of this connection.
+    ///
+    /// Only the counters known at this level are filled in.
+    pub(crate) fn stats(&self) -> ConnectionStats {
+        ConnectionStats {
+            msgs_sent: self.msgs_sent,
+            bytes_sent: self.bytes_sent,
+            msgs_received: self.prev_seq,
+            bytes_received: self.bytes_received,
+            outgoing_queued: self.out_msgs.len(),
+            ..Default::default()
         }
     }
 
+    /// Take over the socket of `conn`, a newly established connection to the same peer.
+    ///
+    /// The configured limits and the statistics are kept but the messages waiting in the outgoing
+    /// queue are not sent, since they were meant for the previous connection. They're returned
+    /// instead, for the caller to fail the method calls among them.
+    pub(crate) fn replace_socket(&mut self, conn: Connection<S>) -> VecDeque<Arc<Message>> {
+        self.socket = conn.socket;
+        self.parser.take_input(conn.parser);
+        self.out_pos = 0;
+        self.event.notify(usize::MAX);
+
+        std::mem::take(&mut self.out_msgs)
+    }
+
+    /// Set the maximum size of incoming messages.
+    ///
+    /// Values larger than the maximum allowed by the D-Bus specification are capped to it.
+    pub(crate) fn set_max_message_size(&mut self, max: usize) {
+        self.parser.set_max_message_size(max);
+    }
+
+    /// Set the maximum number of file descriptors an incoming message can carry.
+    #[cfg(unix)]
+    pub(crate) fn set_max_fds(&mut self, max: usize) {
+        self.parser.set_max_fds(max);
+    }
+
+    /// Set the maximum number of messages waiting in the outgoing queue.
+    ///
+    /// The queue always has room for at least one message.
+    pub(crate) fn set_max_out_queued(&mut self, max: usize) {
+        self.max_out_queued = Some(max.max(1));
+    }
+
+    /// What to do when the outgoing queue is full.
+    pub(crate) fn out_queue_policy(&self) -> OutgoingQueuePolicy {
+        self.out_queue_policy
+    }
+
+    /// Set what to do when the outgoing queue is full.
+    pub(crate) fn set_out_queue_policy(&mut self, policy: OutgoingQueuePolicy) {
+        self.out_queue_policy = policy;
+    }
+
+    /// The position of the oldest signal in the outgoing queue that isn't being written out
+    /// already.
+    fn oldest_queued_signal(&self) -> Option<usize> {
+        let first = if self.out_pos > 0 { 1 } else { 0 };
+
+        self.out_msgs
+            .iter()
+            .skip(first)
+            .position(|msg| msg.message_type() == MessageType::Signal)
+            .map(|pos| pos + first)
+    }
+
+    /// If there's a signal in the outgoing queue that can be dropped to make room.
+    pub(crate) fn has_queued_signal(&self) -> bool {
+        self.oldest_queued_signal().is_some()
+    }
+
+    /// Drop the oldest signal in the outgoing queue that isn't being written out already.
+    ///
+    /// Method calls, replies and errors are never dropped, since their peers would be left
+    /// waiting. Returns `false` if there is no signal to drop.
+    pub(crate) fn drop_oldest_queued_signal(&mut self) -> bool {
+        match self.oldest_queued_signal() {
+            Some(pos) => {
+                self.out_msgs.remove(pos);
+
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// If the outgoing queue has reached its maximum length.
+    pub(crate) fn is_out_queue_full(&self) -> bool {
+        self.max_out_queued
+            .map(|max| self.out_msgs.len() >= max)
+            .unwrap_or(false)
+    }
+
     /// Attempt to flush the outgoing buffer
     ///
     /// This will try to write as many messages as possible from the
@@ -70,16 +168,19 @@ impl<S: Socket> Connection<S> {
                 if data.is_empty() {
                     self.out_pos = 0;
                     self.out_msgs.pop_front();
+                    self.msgs_sent += 1;
                     break;
                 }
                 #[cfg(unix)]
                 let fds = if self.out_pos == 0 { msg.fds() } else { vec![] };
-                self.out_pos += ready!(self.socket.poll_sendmsg(
+                let written = ready!(self.socket.poll_sendmsg(
                     cx,
                     data,
                     #[cfg(unix)]
                     &fds,
                 ))?;
+                self.out_pos += written;
+                self.bytes_sent += written as u64;
             }
         }
         Poll::Ready(Ok(()))
@@ -103,89 +204,17 @@ impl<S: Socket> Connection<S> {
     /// `try_receive_message`.
     pub fn try_receive_message(&mut self, cx: &mut Context<'_>) -> Poll<crate::Result<Message>> {
         self.event.notify(usize::MAX);
-        if self.raw_in_pos < MIN_MESSAGE_SIZE {
-            self.raw_in_buffer.resize(MIN_MESSAGE_SIZE, 0);
-            // We don't have enough data to make a proper message header yet.
-            // Some partial read may be in raw_in_buffer, so we try to complete it
-            // until we have MIN_MESSAGE_SIZE bytes
-            //
-            // Given that MIN_MESSAGE_SIZE is 16, this codepath is actually extremely unlikely
-            // to be taken more than once
-            while self.raw_in_pos < MIN_MESSAGE_SIZE {
-                let res = ready!(self
-                    .socket
-                    .poll_recvmsg(cx, &mut self.raw_in_buffer[self.raw_in_pos..]))?;
-                let len = {
-                    #[cfg(unix)]
-                    {
-                        let (len, fds) = res;
-                        self.raw_in_fds.extend(fds);
-                        len
-                    }
-                    #[cfg(not(unix))]
-                    {
-                        res
-                    }
-                };
-                self.raw_in_pos += len;
-                if len == 0 {
-                    return Poll::Ready(Err(crate::Error::InputOutput(
-                        std::io::Error::new(
-                            std::io::ErrorKind::UnexpectedEof,
-                            "failed to receive message",
-                        )
-                        .into(),
-                    )));
-                }
-            }
-        }
-
-        let (primary_header, fields_len) = MessagePrimaryHeader::read(&self.raw_in_buffer)?;
-        let header_len = MIN_MESSAGE_SIZE + fields_len as usize;
-        let body_padding = padding_for_8_bytes(header_len);
-        let body_len = primary_header.body_len() as usize;
-        let total_len = header_len + body_padding + body_len;
-        if total_len > MAX_MESSAGE_SIZE {
-            return Poll::Ready(Err(crate::Error::ExcessData));
-        }
+        loop {
+            if let Some(msg) = self.parser.parse(self.prev_seq + 1)? {
+                self.prev_seq += 1;
+                self.bytes_received += msg.as_bytes().len() as u64;
 
-        // By this point we have a full primary header, so we know the exact length of the complete
-        // message.
-        self.raw_in_buffer.resize(total_len, 0);
+                return Poll::Ready(Ok(msg));
+            }
 
-        // Now we have an incomplete message; read the rest
-        while self.raw_in_buffer.len() > self.raw_in_pos {
-            let res = ready!(self
-                .socket
-                .poll_recvmsg(cx, &mut self.raw_in_buffer[self.raw_in_pos..]))?;
-            let read = {
-                #[cfg(unix)]
-                {
-                    let (read, fds) = res;
-                    self.raw_in_fds.extend(fds);
-                    read
-                }
-                #[cfg(not(unix))]
-                {
-                    res
-                }
-            };
-            self.raw_in_pos += read;
+            let socket = &mut self.socket;
+            ready!(self.parser.poll_recv(|buf| socket.poll_recvmsg(cx, buf)))?;
         }
-
-        // If we reach here, the message is complete; return it
-        self.raw_in_pos = 0;
-        let bytes = std::mem::take(&mut self.raw_in_buffer);
-        #[cfg(unix)]
-        let fds = std::mem::take(&mut self.raw_in_fds);
-        let seq = self.prev_seq + 1;
-        self.prev_seq = seq;
-        Poll::Ready(Message::from_raw_parts(
-            bytes,
-            #[cfg(unix)]
-            fds,
-            seq,
-        ))
     }
 
     /// Close the connection.
@@ -265,4 +294,41 @@ mod tests {
         let ret = poll_fn(|cx| conn1.try_receive_message(cx)).await.unwrap();
         assert_eq!(ret.to_string(), "Method call Test");
     }
+
+    #[test]
+    fn drop_oldest_queued_signal() {
+        #[cfg(not(feature = "tokio"))]
+        let (p0, _p1) = std::os::unix::net::UnixStream::pair()
+            .map(|(p0, p1)| (async_io::Async::new(p0).unwrap(), p1))
+            .unwrap();
+        #[cfg(feature = "tokio")]
+        let (p0, _p1) = crate::block_on(async { tokio::net::UnixStream::pair().unwrap() });
+
+        let mut conn = Connection::new(p0, vec![]);
+        let call = |member| Message::method(None::<()>, None::<()>, "/", None::<()>, member, &());
+        let signal =
+            |member| Message::signal(None::<()>, None::<()>, "/", "org.zbus.p2p", member, &());
+        for msg in [
+            call("Call"),
+            signal("First"),
+            call("Other"),
+            signal("Second"),
+        ] {
+            conn.enqueue_message(Arc::new(msg.unwrap()));
+        }
+
+        // Only the signals are dropped, oldest first.
+        let members = |conn: &Connection<_>| {
+            conn.out_msgs
+                .iter()
+                .map(|msg| msg.member().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert!(conn.drop_oldest_queued_signal());
+        assert_eq!(members(&conn), ["Call", "Other", "Second"]);
+        assert!(conn.drop_oldest_queued_signal());
+        assert!(!conn.has_queued_signal());
+        assert!(!conn.drop_oldest_queued_signal());
+        assert_eq!(members(&conn), ["Call", "Other"]);
+    }
 }
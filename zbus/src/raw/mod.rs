@@ -2,4 +2,5 @@ mod connection;
 mod socket;
 
 pub use connection::Connection;
+pub(crate) use socket::PollRecvmsg;
 pub use socket::Socket;
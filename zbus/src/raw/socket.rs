@@ -137,6 +137,50 @@ fn get_unix_uid(fd: &impl AsRawFd) -> io::Result<Option<u32>> {
     }
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn get_linux_security_label(fd: &impl AsRawFd) -> io::Result<Option<Vec<u8>>> {
+    use nix::libc;
+
+    let fd = fd.as_raw_fd();
+    let mut label = vec![0u8; 256];
+    loop {
+        let mut len = label.len() as libc::socklen_t;
+        // SAFETY: `label` is valid for writes of `len` bytes.
+        let ret = unsafe {
+            libc::getsockopt(
+                fd,
+                libc::SOL_SOCKET,
+                libc::SO_PEERSEC,
+                label.as_mut_ptr() as *mut libc::c_void,
+                &mut len,
+            )
+        };
+        if ret == 0 {
+            label.truncate(len as usize);
+            // The kernel may or may not include the trailing NUL but the D-Bus spec wants exactly
+            // one.
+            if let Some(nul) = label.iter().position(|b| *b == 0) {
+                label.truncate(nul);
+            }
+            if label.is_empty() {
+                return Ok(None);
+            }
+            label.push(0);
+
+            return Ok(Some(label));
+        }
+
+        let e = io::Error::last_os_error();
+        match e.raw_os_error() {
+            // The buffer was too small and `len` now contains the required size.
+            Some(libc::ERANGE) if len as usize > label.len() => label.resize(len as usize, 0),
+            // No LSM provides a label for this socket.
+            Some(libc::ENOPROTOOPT) | Some(libc::EINVAL) => return Ok(None),
+            _ => return Err(e),
+        }
+    }
+}
+
 // Send 0 byte as a separate SCM_CREDS message.
 #[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
 fn send_zero_byte(fd: &impl AsRawFd) -> io::Result<usize> {
@@ -152,11 +196,17 @@ fn send_zero_byte(fd: &impl AsRawFd) -> io::Result<usize> {
     .map_err(|e| e.into())
 }
 
+// Shut down both directions of the socket, without going through the async runtime.
+#[cfg(all(unix, feature = "tokio"))]
+fn shutdown_fd(fd: RawFd) -> io::Result<()> {
+    nix::sys::socket::shutdown(fd, nix::sys::socket::Shutdown::Both).map_err(|e| e.into())
+}
+
 #[cfg(unix)]
-type PollRecvmsg = Poll<io::Result<(usize, Vec<OwnedFd>)>>;
+pub(crate) type PollRecvmsg = Poll<io::Result<(usize, Vec<OwnedFd>)>>;
 
 #[cfg(not(unix))]
-type PollRecvmsg = Poll<io::Result<usize>>;
+pub(crate) type PollRecvmsg = Poll<io::Result<usize>>;
 
 /// Trait representing some transport layer over which the DBus protocol can be used
 ///
@@ -167,6 +217,11 @@ type PollRecvmsg = Poll<io::Result<usize>>;
 /// free to submit pull requests to add support for more runtimes to zbus itself so rust's orphan
 /// rules don't force the use of a wrapper struct (and to avoid duplicating the work across many
 /// projects).
+///
+/// Pass your implementation to [`crate::ConnectionBuilder::socket`] to run a connection over it.
+/// Transports that can't pass file descriptors should return `false` from
+/// [`Socket::can_pass_unix_fd`], and those that can't tell who the peer is need an authentication
+/// mechanism other than `EXTERNAL`.
 pub trait Socket: std::fmt::Debug + Send + Sync {
     /// Supports passing file descriptors.
     fn can_pass_unix_fd(&self) -> bool {
@@ -202,6 +257,15 @@ pub trait Socket: std::fmt::Debug + Send + Sync {
     /// After this call, it is valid for all reading and writing operations to fail.
     fn close(&self) -> io::Result<()>;
 
+    /// Return the underlying file descriptor, if any.
+    ///
+    /// This is used by [`crate::Connection::raw_fd`] to allow integrating the connection with
+    /// external event loops.
+    #[cfg(unix)]
+    fn raw_fd(&self) -> Option<RawFd> {
+        None
+    }
+
     /// Return the peer PID.
     fn peer_pid(&self) -> io::Result<Option<u32>> {
         Ok(None)
@@ -219,6 +283,14 @@ pub trait Socket: std::fmt::Debug + Send + Sync {
         Ok(None)
     }
 
+    /// Return the Linux security label of the peer, if any.
+    ///
+    /// This is the label that results from the `SO_PEERSEC` socket option, in the format described
+    /// by [`crate::fdo::ConnectionCredentials::linux_security_label`].
+    fn peer_security_label(&self) -> io::Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
     /// The dbus daemon on `freebsd` and `dragonfly` currently requires sending the zero byte
     /// as a separate message with SCM_CREDS, as part of the `EXTERNAL` authentication on unix
     /// sockets. This method is used by the authentication machinery in zbus to send this
@@ -256,6 +328,11 @@ impl Socket for Box<dyn Socket> {
         (**self).close()
     }
 
+    #[cfg(unix)]
+    fn raw_fd(&self) -> Option<RawFd> {
+        (**self).raw_fd()
+    }
+
     fn peer_pid(&self) -> io::Result<Option<u32>> {
         (**self).peer_pid()
     }
@@ -270,6 +347,10 @@ impl Socket for Box<dyn Socket> {
         (**self).uid()
     }
 
+    fn peer_security_label(&self) -> io::Result<Option<Vec<u8>>> {
+        (**self).peer_security_label()
+    }
+
     #[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
     fn send_zero_byte(&self) -> io::Result<Option<usize>> {
         (**self).send_zero_byte()
@@ -319,6 +400,10 @@ impl Socket for Async<UnixStream> {
         self.get_ref().shutdown(std::net::Shutdown::Both)
     }
 
+    fn raw_fd(&self) -> Option<RawFd> {
+        Some(self.as_raw_fd())
+    }
+
     fn peer_pid(&self) -> io::Result<Option<u32>> {
         get_unix_pid(self)
     }
@@ -328,6 +413,11 @@ impl Socket for Async<UnixStream> {
         get_unix_uid(self)
     }
 
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    fn peer_security_label(&self) -> io::Result<Option<Vec<u8>>> {
+        get_linux_security_label(self)
+    }
+
     #[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
     fn send_zero_byte(&self) -> io::Result<Option<usize>> {
         send_zero_byte(self).map(Some)
@@ -379,9 +469,13 @@ impl Socket for tokio::net::UnixStream {
     }
 
     fn close(&self) -> io::Result<()> {
-        // FIXME: This should call `tokio::net::UnixStream::poll_shutdown` but this method is not
-        // async-friendly. At the next API break, we should fix this.
-        Ok(())
+        // `tokio::net::UnixStream::poll_shutdown` is not usable here, as it's async and only shuts
+        // down the write half anyway.
+        shutdown_fd(self.as_raw_fd())
+    }
+
+    fn raw_fd(&self) -> Option<RawFd> {
+        Some(self.as_raw_fd())
     }
 
     fn peer_pid(&self) -> io::Result<Option<u32>> {
@@ -393,6 +487,11 @@ impl Socket for tokio::net::UnixStream {
         get_unix_uid(self)
     }
 
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    fn peer_security_label(&self) -> io::Result<Option<Vec<u8>>> {
+        get_linux_security_label(self)
+    }
+
     #[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
     fn send_zero_byte(&self) -> io::Result<Option<usize>> {
         send_zero_byte(self).map(Some)
@@ -524,6 +623,11 @@ impl Socket for Async<TcpStream> {
         self.get_ref().shutdown(std::net::Shutdown::Both)
     }
 
+    #[cfg(unix)]
+    fn raw_fd(&self) -> Option<RawFd> {
+        Some(self.as_raw_fd())
+    }
+
     #[cfg(windows)]
     fn peer_sid(&self) -> Option<String> {
         use crate::win32::{tcp_stream_get_peer_pid, ProcessToken};
@@ -579,11 +683,22 @@ impl Socket for tokio::net::TcpStream {
     }
 
     fn close(&self) -> io::Result<()> {
-        // FIXME: This should call `tokio::net::TcpStream::poll_shutdown` but this method is not
-        // async-friendly. At the next API break, we should fix this.
+        // FIXME: This should call `tokio::net::TcpStream::poll_shutdown` on Windows but this method
+        // is not async-friendly. At the next API break, we should fix this.
+        #[cfg(unix)]
+        {
+            shutdown_fd(self.as_raw_fd())
+        }
+
+        #[cfg(not(unix))]
         Ok(())
     }
 
+    #[cfg(unix)]
+    fn raw_fd(&self) -> Option<RawFd> {
+        Some(self.as_raw_fd())
+    }
+
     #[cfg(windows)]
     fn peer_sid(&self) -> Option<String> {
         use crate::win32::{socket_addr_get_pid, ProcessToken};
@@ -655,6 +770,10 @@ impl Socket for Async<vsock::VsockStream> {
     fn close(&self) -> io::Result<()> {
         self.get_ref().shutdown(std::net::Shutdown::Both)
     }
+
+    fn raw_fd(&self) -> Option<RawFd> {
+        Some(self.as_raw_fd())
+    }
 }
 
 #[cfg(feature = "tokio-vsock")]
@@ -700,4 +819,8 @@ impl Socket for tokio_vsock::VsockStream {
     fn close(&self) -> io::Result<()> {
         self.shutdown(std::net::Shutdown::Both)
     }
+
+    fn raw_fd(&self) -> Option<RawFd> {
+        Some(self.as_raw_fd())
+    }
 }
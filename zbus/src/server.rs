@@ -0,0 +1,313 @@
+#[cfg(not(feature = "tokio"))]
+use async_io::Async;
+use static_assertions::assert_impl_all;
+use std::convert::TryInto;
+#[cfg(not(feature = "tokio"))]
+use std::net::TcpListener;
+#[cfg(all(unix, not(feature = "tokio")))]
+use std::os::unix::net::UnixListener;
+#[cfg(feature = "tokio")]
+use tokio::net::TcpListener;
+#[cfg(all(unix, feature = "tokio"))]
+use tokio::net::UnixListener;
+use tracing::{debug, instrument};
+
+use crate::{
+    address::TcpAddress, Address, AuthMechanism, Connection, ConnectionBuilder, Error, Guid, Result,
+};
+
+#[derive(Debug)]
+enum Listener {
+    #[cfg(all(unix, not(feature = "tokio")))]
+    Unix(Async<UnixListener>),
+    #[cfg(all(unix, feature = "tokio"))]
+    Unix(UnixListener),
+    #[cfg(not(feature = "tokio"))]
+    Tcp(Async<TcpListener>),
+    #[cfg(feature = "tokio")]
+    Tcp(TcpListener),
+}
+
+/// A server accepting peer-to-peer connections.
+///
+/// The server listens on an [`Address`] and, for each client that connects, performs the server
+/// side of the authentication handshake and hands over the resulting peer-to-peer [`Connection`].
+/// The credentials of each peer are available through [`Connection::peer_credentials`].
+///
+/// Currently only `unix:path=` (on Unix) and `tcp:` addresses can be listened on. Since the
+/// `EXTERNAL` mechanism can't authenticate peers on TCP sockets, you'll want to enable another
+/// mechanism for those (see [`Server::auth_mechanisms`]).
+///
+/// The socket file of a `unix:path=` address is created by [`Server::bind`] and removed when the
+/// server is dropped.
+///
+/// # Example
+///
+/// ```no_run
+/// # use zbus::{dbus_interface, Server};
+/// use async_std::task::{block_on, spawn};
+///
+/// struct Greeter;
+///
+/// #[dbus_interface(name = "org.zbus.MyGreeter1")]
+/// impl Greeter {
+///     fn say_hello(&self, name: &str) -> String {
+///         format!("Hello {name}!")
+///     }
+/// }
+///
+/// # block_on(async {
+/// let server = Server::bind("unix:path=/tmp/my-service.socket").await?;
+/// loop {
+///     let builder = server.accept_builder().await?;
+///     // Perform the handshake in a task of its own, so a stalled client doesn't hold up the others.
+///     spawn(async move {
+///         // Set up the object server before the handshake, so no early method call is missed.
+///         let conn = builder
+///             .serve_at("/org/zbus/MyGreeter", Greeter)?
+///             .build()
+///             .await?;
+///         println!("{:?} connected", conn.peer_credentials().await?.unix_user_id());
+///
+///         // Serve the peer until it goes away.
+///         conn.closed().await;
+///
+///         Ok::<(), zbus::Error>(())
+///     });
+/// #     break;
+/// }
+/// #     Ok::<(), zbus::Error>(())
+/// # }).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct Server {
+    listener: Listener,
+    address: Address,
+    guid: Guid,
+    auth_mechanisms: Option<Vec<AuthMechanism>>,
+}
+
+assert_impl_all!(Server: Send, Sync, Unpin);
+
+impl Server {
+    /// Start listening on the given [D-Bus address].
+    ///
+    /// For `tcp:` addresses, a port of `0` asks the system to pick a free port. Use
+    /// [`Server::address`] to find out which one was picked.
+    ///
+    /// [D-Bus address]: https://dbus.freedesktop.org/doc/dbus-specification.html#addresses
+    pub async fn bind<A>(address: A) -> Result<Self>
+    where
+        A: TryInto<Address>,
+        A::Error: Into<Error>,
+    {
+        let address = address.try_into().map_err(Into::into)?;
+        let (listener, address) = match address {
+            #[cfg(unix)]
+            Address::Unix(path) => {
+                if path.to_str().map(|p| p.starts_with('\0')).unwrap_or(false) {
+                    return Err(Error::Address(
+                        "listening on abstract unix addresses is not supported".into(),
+                    ));
+                }
+
+                #[cfg(not(feature = "tokio"))]
+                let listener = Async::<UnixListener>::bind(&path)?;
+                #[cfg(feature = "tokio")]
+                let listener = UnixListener::bind(&path)?;
+
+                (Listener::Unix(listener), Address::Unix(path))
+            }
+            Address::Tcp(addr) => {
+                #[cfg(not(feature = "tokio"))]
+                let listener = Async::new(TcpListener::bind((addr.host(), addr.port()))?)?;
+                #[cfg(feature = "tokio")]
+                let listener = TcpListener::bind((addr.host(), addr.port())).await?;
+                #[cfg(not(feature = "tokio"))]
+                let local_addr = listener.get_ref().local_addr()?;
+                #[cfg(feature = "tokio")]
+                let local_addr = listener.local_addr()?;
+                let addr = TcpAddress {
+                    port: local_addr.port(),
+                    ..addr
+                };
+
+                (Listener::Tcp(listener), Address::Tcp(addr))
+            }
+            address => {
+                return Err(Error::Address(format!(
+                    "listening on `{address}` is not supported"
+                )))
+            }
+        };
+
+        Ok(Self {
+            listener,
+            address,
+            guid: Guid::generate(),
+            auth_mechanisms: None,
+        })
+    }
+
+    /// Specify the mechanisms clients can authenticate with.
+    ///
+    /// By default, only the `EXTERNAL` mechanism is accepted.
+    pub fn auth_mechanisms(mut self, auth_mechanisms: &[AuthMechanism]) -> Self {
+        self.auth_mechanisms = Some(auth_mechanisms.to_vec());
+
+        self
+    }
+
+    /// The address the server is listening on.
+    pub fn address(&self) -> &Address {
+        &self.address
+    }
+
+    /// The GUID of the server.
+    pub fn guid(&self) -> &Guid {
+        &self.guid
+    }
+
+    /// Wait for the next client and perform the handshake with it.
+    ///
+    /// If you want to serve objects on the connection, use [`Server::accept_builder`] instead, so
+    /// they're registered before the client can send any method call.
+    pub async fn accept(&self) -> Result<Connection> {
+        self.accept_builder().await?.build().await
+    }
+
+    /// Wait for the next client and return a builder for the connection to it.
+    ///
+    /// The builder is already set up for a server-side peer-to-peer connection. The handshake is
+    /// performed by [`ConnectionBuilder::build`].
+    #[instrument(skip(self))]
+    pub async fn accept_builder(&self) -> Result<ConnectionBuilder<'static>> {
+        let builder = match &self.listener {
+            #[cfg(unix)]
+            Listener::Unix(listener) => {
+                let (stream, _) = listener.accept().await?;
+                debug!("Accepted unix connection");
+
+                ConnectionBuilder::socket(stream)
+            }
+            Listener::Tcp(listener) => {
+                let (stream, addr) = listener.accept().await?;
+                debug!("Accepted TCP connection from {}", addr);
+
+                ConnectionBuilder::socket(stream)
+            }
+        };
+        let builder = builder.server(&self.guid).p2p();
+
+        Ok(match &self.auth_mechanisms {
+            Some(mechanisms) => builder.auth_mechanisms(mechanisms),
+            None => builder,
+        })
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Address::Unix(path) = &self.address {
+            if let Err(e) = std::fs::remove_file(path) {
+                debug!("Failed to remove the server socket {:?}: {}", path, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::future::join;
+    use ntest::timeout;
+    use test_log::test;
+
+    use super::*;
+
+    #[test]
+    #[timeout(15000)]
+    fn tcp_server() {
+        crate::utils::block_on(test_tcp_server()).unwrap();
+    }
+
+    async fn test_tcp_server() -> Result<()> {
+        let server = Server::bind("tcp:host=127.0.0.1,port=0")
+            .await?
+            .auth_mechanisms(&[AuthMechanism::Anonymous]);
+        let port = match server.address() {
+            Address::Tcp(addr) => addr.port(),
+            _ => unreachable!(),
+        };
+        assert_ne!(port, 0);
+
+        let client = ConnectionBuilder::address(server.address().clone())?
+            .auth_mechanisms(&[AuthMechanism::Anonymous])
+            .p2p()
+            .build();
+        let (client, server_conn) = join(client, server.accept()).await;
+        let (client, server_conn) = (client?, server_conn?);
+        assert_eq!(client.server_guid(), server.guid().as_str());
+        assert_eq!(server_conn.server_guid(), server.guid().as_str());
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn unix_server() {
+        crate::utils::block_on(test_unix_server()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_unix_server() -> Result<()> {
+        struct Greeter;
+
+        #[crate::dbus_interface(name = "org.zbus.Greeter")]
+        impl Greeter {
+            fn greet(&self, name: &str) -> String {
+                format!("Hello {name}")
+            }
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("zbus-server-test");
+        let address = format!("unix:path={}", path.display());
+        let server = Server::bind(address.as_str()).await?;
+
+        let server_future = async {
+            server
+                .accept_builder()
+                .await?
+                .serve_at("/org/zbus/Greeter", Greeter)?
+                .build()
+                .await
+        };
+        let client_future = ConnectionBuilder::address(address.as_str())?.p2p().build();
+        let (server_conn, client) = join(server_future, client_future).await;
+        let (server_conn, client) = (server_conn?, client?);
+
+        let credentials = server_conn.peer_credentials().await?;
+        assert_eq!(
+            credentials.unix_user_id(),
+            Some(nix::unistd::Uid::effective().as_raw())
+        );
+        let reply = client
+            .call_method(
+                None::<()>,
+                "/org/zbus/Greeter",
+                Some("org.zbus.Greeter"),
+                "Greet",
+                &"peer",
+            )
+            .await?;
+        assert_eq!(reply.body::<String>()?, "Hello peer");
+
+        // The socket file goes away with the server.
+        drop(server);
+        assert!(!path.exists());
+
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use zbus_names::BusName;
 
-use crate::{zvariant::ObjectPath, Connection, Error, Result};
+use crate::{zvariant::ObjectPath, Connection, Error, Result, WeakConnection};
 use std::convert::TryInto;
 
 /// A signal emission context.
@@ -84,4 +84,95 @@ impl<'s> SignalContext<'s> {
             destination: self.destination.map(|d| d.into_owned()),
         }
     }
+
+    /// Create a signal context holding a weak reference to the connection of `self`.
+    ///
+    /// See [`WeakSignalContext`] for details.
+    pub fn downgrade(&self) -> WeakSignalContext<'s> {
+        WeakSignalContext {
+            conn: self.conn.downgrade(),
+            path: self.path.clone(),
+            destination: self.destination.clone(),
+        }
+    }
+}
+
+/// A signal emission context, holding a weak reference to its connection.
+///
+/// Unlike [`SignalContext`], this doesn't keep the connection alive. Interfaces emitting signals
+/// from outside of their methods can keep one around without creating a reference cycle with the
+/// connection serving them, that would keep both from ever being released. The context is
+/// upgraded to a [`SignalContext`] to emit signals.
+///
+/// It's created with [`SignalContext::downgrade`].
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::error::Error;
+/// # use zbus::{block_on, dbus_interface, Connection, SignalContext, WeakSignalContext};
+/// #
+/// struct Clock {
+///     ctxt: WeakSignalContext<'static>,
+/// }
+///
+/// impl Clock {
+///     async fn tick(&self) -> zbus::Result<()> {
+///         match self.ctxt.upgrade() {
+///             Some(ctxt) => Self::ticked(&ctxt).await,
+///             // The connection is gone, so is the object.
+///             None => Ok(()),
+///         }
+///     }
+/// }
+///
+/// #[dbus_interface(name = "org.example.Clock")]
+/// impl Clock {
+///     #[dbus_interface(signal)]
+///     async fn ticked(ctxt: &SignalContext<'_>) -> zbus::Result<()>;
+/// }
+///
+/// # block_on(async {
+/// let connection = Connection::session().await?;
+/// let ctxt = SignalContext::new(&connection, "/org/example/Clock")?;
+/// let clock = Clock {
+///     ctxt: ctxt.downgrade(),
+/// };
+/// clock.tick().await?;
+/// connection
+///     .object_server()
+///     .at("/org/example/Clock", clock)
+///     .await?;
+/// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+/// # })?;
+/// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct WeakSignalContext<'s> {
+    conn: WeakConnection,
+    path: ObjectPath<'s>,
+    destination: Option<BusName<'s>>,
+}
+
+impl<'s> WeakSignalContext<'s> {
+    /// Upgrade to a [`SignalContext`].
+    ///
+    /// Returns `None` if the connection has been dropped.
+    pub fn upgrade(&self) -> Option<SignalContext<'s>> {
+        self.conn.upgrade().map(|conn| SignalContext {
+            conn,
+            path: self.path.clone(),
+            destination: self.destination.clone(),
+        })
+    }
+
+    /// Get a reference to the associated object path.
+    pub fn path(&self) -> &ObjectPath<'s> {
+        &self.path
+    }
+
+    /// Get a reference to the associated destination (if any).
+    pub fn destination(&self) -> Option<&BusName<'s>> {
+        self.destination.as_ref()
+    }
 }
@@ -1,28 +1,42 @@
 use std::{
     collections::HashMap,
     sync::{self, Arc},
+    time::Duration,
 };
 
 use futures_util::future::poll_fn;
-use tracing::{debug, instrument, trace};
+use tracing::{debug, instrument, trace, Instrument};
 
 use crate::{
-    async_lock::Mutex, raw::Connection as RawConnection, Executor, MsgBroadcaster, OwnedMatchRule,
-    Socket, Task,
+    async_lock::Mutex, interceptor::intercept_incoming, message_span,
+    raw::Connection as RawConnection, timeout::sleep, ConnectionEvent, Executor, Message,
+    MessageInterceptor, MsgBroadcaster, OwnedMatchRule, Socket, Task, WeakConnection,
 };
 
+const MIN_RECONNECT_DELAY: Duration = Duration::from_millis(100);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(10);
+
 #[derive(Debug)]
 pub(crate) struct SocketReader {
     raw_conn: Arc<sync::Mutex<RawConnection<Box<dyn Socket>>>>,
     senders: Arc<Mutex<HashMap<Option<OwnedMatchRule>, MsgBroadcaster>>>,
+    interceptors: Vec<Arc<dyn MessageInterceptor>>,
+    conn: WeakConnection,
 }
 
 impl SocketReader {
     pub fn new(
         raw_conn: Arc<sync::Mutex<RawConnection<Box<dyn Socket>>>>,
         senders: Arc<Mutex<HashMap<Option<OwnedMatchRule>, MsgBroadcaster>>>,
+        interceptors: Vec<Arc<dyn MessageInterceptor>>,
+        conn: WeakConnection,
     ) -> Self {
-        Self { raw_conn, senders }
+        Self {
+            raw_conn,
+            senders,
+            interceptors,
+            conn,
+        }
     }
 
     pub fn spawn(self, executor: &Executor<'_>) -> Task<()> {
@@ -42,49 +56,105 @@ impl SocketReader {
                 .await
                 .map(Arc::new)
             };
-            match &msg {
-                Ok(msg) => trace!("Message received on the socket: {:?}", msg),
-                Err(e) => trace!("Error reading from the socket: {:?}", e),
-            };
+            let msg = match msg {
+                Ok(msg) => {
+                    trace!("Message received on the socket: {:?}", msg);
+
+                    match intercept_incoming(&self.interceptors, msg) {
+                        Some(msg) => Ok(msg),
+                        None => {
+                            trace!("Message dropped by an interceptor");
 
-            let mut senders = self.senders.lock().await;
-            for (rule, sender) in &*senders {
-                if let Ok(msg) = &msg {
-                    if let Some(rule) = rule.as_ref() {
-                        match rule.matches(msg) {
-                            Ok(true) => (),
-                            Ok(false) => continue,
-                            Err(e) => {
-                                debug!("Error matching message against rule: {:?}", e);
-
-                                continue;
-                            }
+                            continue;
                         }
                     }
                 }
+                Err(e) => {
+                    trace!("Error reading from the socket: {:?}", e);
 
-                if let Err(e) = sender.broadcast(msg.clone()).await {
-                    // An error would be due to either of these:
-                    //
-                    // 1. the channel is closed.
-                    // 2. No active receivers.
-                    //
-                    // In either case, just log it.
-                    trace!(
-                        "Error broadcasting message to stream for `{:?}`: {:?}",
-                        rule,
-                        e
-                    );
+                    Err(e)
                 }
-            }
-            trace!("Broadcasted to all streams: {:?}", msg);
+            };
+
+            let dispatch = self.dispatch(&msg);
+            let dispatch = dispatch.instrument(match &msg {
+                Ok(msg) => message_span!("dispatch message", msg),
+                Err(_) => tracing::Span::none(),
+            });
+            dispatch.await;
 
             if msg.is_err() {
-                senders.clear();
+                if let Some(conn) = self.conn.upgrade() {
+                    conn.emit_event(ConnectionEvent::Disconnected);
+                }
+                if self.reconnect().await {
+                    continue;
+                }
+
+                self.senders.lock().await.clear();
+                if let Some(conn) = self.conn.upgrade() {
+                    conn.set_closed();
+                }
                 trace!("Socket reading task stopped");
 
                 return;
             }
         }
     }
+
+    // Broadcast `msg` to all the streams interested in it.
+    async fn dispatch(&self, msg: &crate::Result<Arc<Message>>) {
+        let senders = self.senders.lock().await;
+        for (rule, sender) in &*senders {
+            if let Ok(msg) = msg {
+                if let Some(rule) = rule.as_ref() {
+                    match rule.matches(msg) {
+                        Ok(true) => (),
+                        Ok(false) => continue,
+                        Err(e) => {
+                            debug!("Error matching message against rule: {:?}", e);
+
+                            continue;
+                        }
+                    }
+                }
+            }
+
+            if let Err(e) = sender.broadcast(msg.clone()).await {
+                // An error would be due to either of these:
+                //
+                // 1. the channel is closed.
+                // 2. No active receivers.
+                //
+                // In either case, just log it.
+                trace!(
+                    "Error broadcasting message to stream for `{:?}`: {:?}",
+                    rule,
+                    e
+                );
+            }
+        }
+        trace!("Broadcasted to all streams: {:?}", msg);
+    }
+
+    // Keep trying to establish the connection again, if automatic reconnection is enabled.
+    //
+    // Returns `false` if it's not enabled or the connection is dropped in the meantime.
+    async fn reconnect(&self) -> bool {
+        let mut delay = MIN_RECONNECT_DELAY;
+        loop {
+            let conn = match self.conn.upgrade() {
+                Some(conn) => conn,
+                None => return false,
+            };
+            match conn.reconnect().await {
+                Ok(reconnected) => return reconnected,
+                Err(e) => debug!("Failed to reconnect: {}", e),
+            }
+            drop(conn);
+
+            sleep(delay).await;
+            delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+        }
+    }
 }
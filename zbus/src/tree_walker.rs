@@ -0,0 +1,247 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    convert::{TryFrom, TryInto},
+    future::Future,
+    pin::Pin,
+};
+
+use futures_core::Stream;
+use futures_util::{
+    stream::{self, FuturesUnordered},
+    StreamExt,
+};
+use zbus_names::{BusName, OwnedBusName};
+use zvariant::{ObjectPath, OwnedObjectPath};
+
+use crate::{fdo::IntrospectableProxy, quick_xml::Node, Connection, Error, Result};
+
+/// Walks the object tree of a peer, introspecting each object.
+///
+/// Starting at a given path, the walker introspects the object and descends into its child nodes,
+/// introspecting several objects at once. Use [`TreeWalker::stream`] to get the objects as they're
+/// introspected, or [`TreeWalker::tree`] to get the whole tree at once:
+///
+/// ```no_run
+/// # zbus::block_on(async {
+/// use zbus::{Connection, TreeWalker};
+///
+/// let conn = Connection::system().await?;
+/// let tree = TreeWalker::new(&conn, "org.freedesktop.systemd1", "/org/freedesktop/systemd1")?
+///     .max_depth(2)
+///     .tree()
+///     .await?;
+/// for child in tree.nodes() {
+///     println!("{:?}: {} interface(s)", child.name(), child.interfaces().len());
+/// }
+/// # Ok::<(), zbus::Error>(())
+/// # }).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct TreeWalker {
+    conn: Connection,
+    destination: OwnedBusName,
+    path: OwnedObjectPath,
+    max_depth: Option<usize>,
+    max_concurrent: usize,
+}
+
+type Introspection =
+    Pin<Box<dyn Future<Output = (OwnedObjectPath, usize, Result<Node<'static>>)> + Send>>;
+
+impl TreeWalker {
+    /// Create a walker of the objects of `destination`, starting at `path`.
+    pub fn new<'d, 'p, D, P>(conn: &Connection, destination: D, path: P) -> Result<Self>
+    where
+        D: TryInto<BusName<'d>>,
+        D::Error: Into<Error>,
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+    {
+        let destination = destination.try_into().map_err(Into::into)?;
+        let path = path.try_into().map_err(Into::into)?;
+
+        Ok(Self {
+            conn: conn.clone(),
+            destination: destination.into(),
+            path: path.into(),
+            max_depth: None,
+            max_concurrent: 8,
+        })
+    }
+
+    /// Only descend `depth` levels below the starting path.
+    ///
+    /// A depth of 0 only introspects the object at the starting path. By default, the whole tree
+    /// is walked.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+
+        self
+    }
+
+    /// The maximum number of objects introspected at once, 8 by default.
+    pub fn max_concurrent(mut self, max: usize) -> Self {
+        self.max_concurrent = max.max(1);
+
+        self
+    }
+
+    /// Walk the tree, yielding the path and introspection data of each object.
+    ///
+    /// The objects are yielded as they're introspected, so a parent comes before its children but
+    /// the order is unspecified otherwise. Failing to introspect an object yields an error but
+    /// doesn't stop the walk, only its children are skipped. Each object is introspected once,
+    /// even if it's listed several times, and the child nodes with an absolute name are ignored,
+    /// since the D-Bus specification only allows one for the root node of the introspection data.
+    pub fn stream(
+        self,
+    ) -> impl Stream<Item = Result<(OwnedObjectPath, Node<'static>)>> + Send + Unpin {
+        let mut queue = VecDeque::new();
+        queue.push_back((self.path.clone(), 0));
+        let mut visited = HashSet::new();
+        visited.insert(self.path.clone());
+        let pending = FuturesUnordered::<Introspection>::new();
+
+        Box::pin(stream::unfold(
+            (self, queue, visited, pending),
+            |(walker, mut queue, mut visited, mut pending)| async move {
+                while pending.len() < walker.max_concurrent {
+                    match queue.pop_front() {
+                        Some((path, depth)) => pending.push(walker.introspect(path, depth)),
+                        None => break,
+                    }
+                }
+
+                let (path, depth, res) = pending.next().await?;
+                let descend = walker.max_depth.map(|max| depth < max).unwrap_or(true);
+                if let (Ok(node), true) = (&res, descend) {
+                    for child in node.nodes() {
+                        match child_path(&path, child) {
+                            Some(child_path) if visited.insert(child_path.clone()) => {
+                                queue.push_back((child_path, depth + 1));
+                            }
+                            _ => (),
+                        }
+                    }
+                }
+
+                Some((
+                    res.map(|node| (path, node)),
+                    (walker, queue, visited, pending),
+                ))
+            },
+        ))
+    }
+
+    /// Walk the tree, returning the introspection data of the object at the starting path, with
+    /// its child nodes replaced by their own introspection data, recursively.
+    ///
+    /// Unlike [`TreeWalker::stream`], this fails as soon as an object can't be introspected.
+    pub async fn tree(self) -> Result<Node<'static>> {
+        let root = self.path.clone();
+        let mut nodes = HashMap::new();
+        let mut stream = self.stream();
+        while let Some(res) = stream.next().await {
+            let (path, node) = res?;
+            nodes.insert(path, node);
+        }
+
+        assemble(&root, &mut nodes)
+            .ok_or_else(|| Error::Failure(format!("Failed to introspect `{root}`")))
+    }
+
+    fn introspect(&self, path: OwnedObjectPath, depth: usize) -> Introspection {
+        let conn = self.conn.clone();
+        let destination = self.destination.clone();
+
+        Box::pin(async move {
+            let res = async {
+                let xml = IntrospectableProxy::builder(&conn)
+                    .destination(destination)?
+                    .path(path.clone())?
+                    .build()
+                    .await?
+                    .introspect()
+                    .await?;
+
+                Node::from_reader(xml.as_bytes())
+            }
+            .await;
+
+            (path, depth, res)
+        })
+    }
+}
+
+// The path of a child node of the object at `parent`. Only relative names are valid for the child
+// nodes, so the ones with an absolute name are ignored.
+fn child_path(parent: &ObjectPath<'_>, child: &Node<'_>) -> Option<OwnedObjectPath> {
+    let name = child.name().filter(|name| !name.starts_with('/'))?;
+    let path = if parent.as_str() == "/" {
+        format!("/{name}")
+    } else {
+        format!("{parent}/{name}")
+    };
+
+    ObjectPath::try_from(path).ok().map(Into::into)
+}
+
+// Take the node at `path` out of `nodes`, with its children replaced by their own nodes.
+fn assemble(
+    path: &OwnedObjectPath,
+    nodes: &mut HashMap<OwnedObjectPath, Node<'static>>,
+) -> Option<Node<'static>> {
+    let mut node = nodes.remove(path)?;
+    for child in node.nodes_mut() {
+        let child_path = match child_path(path, child) {
+            Some(child_path) => child_path,
+            None => continue,
+        };
+        if let Some(mut introspected) = assemble(&child_path, nodes) {
+            introspected.set_name(child.name().map(String::from));
+            *child = introspected;
+        }
+    }
+
+    Some(node)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use test_log::test;
+    use zvariant::ObjectPath;
+
+    use super::child_path;
+    use crate::quick_xml::Node;
+
+    #[test]
+    fn child_paths() {
+        let node = Node::from_reader(
+            r#"<node><node name="child"/><node name="/org/other"/><node/></node>"#.as_bytes(),
+        )
+        .unwrap();
+        let paths: Vec<_> = node
+            .nodes()
+            .iter()
+            .map(|child| {
+                let root = child_path(&ObjectPath::try_from("/").unwrap(), child);
+                let parent = child_path(&ObjectPath::try_from("/org/zbus").unwrap(), child);
+
+                (root.map(|p| p.to_string()), parent.map(|p| p.to_string()))
+            })
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                (
+                    Some("/child".to_string()),
+                    Some("/org/zbus/child".to_string())
+                ),
+                (None, None),
+                (None, None),
+            ]
+        );
+    }
+}
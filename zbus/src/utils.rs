@@ -46,3 +46,44 @@ pub fn block_on<F: std::future::Future>(future: F) -> F::Output {
         });
     TOKIO_RT.block_on(future)
 }
+
+/// Connect a client and a server peer-to-peer over a Unix socket pair.
+///
+/// Returns the client and the server connections, in that order.
+#[cfg(all(test, unix))]
+pub(crate) async fn unix_p2p_pipe() -> crate::Result<(crate::Connection, crate::Connection)> {
+    unix_p2p_pipe_with(Ok, Ok).await
+}
+
+/// Same as [`unix_p2p_pipe`], with `client` and `server` setting up the builder of the respective
+/// connection first.
+#[cfg(all(test, unix))]
+pub(crate) async fn unix_p2p_pipe_with<C, S>(
+    client: C,
+    server: S,
+) -> crate::Result<(crate::Connection, crate::Connection)>
+where
+    C: FnOnce(
+        crate::ConnectionBuilder<'static>,
+    ) -> crate::Result<crate::ConnectionBuilder<'static>>,
+    S: FnOnce(
+        crate::ConnectionBuilder<'static>,
+    ) -> crate::Result<crate::ConnectionBuilder<'static>>,
+{
+    #[cfg(not(feature = "tokio"))]
+    use std::os::unix::net::UnixStream;
+    #[cfg(feature = "tokio")]
+    use tokio::net::UnixStream;
+
+    use crate::{ConnectionBuilder, Guid};
+
+    let (p0, p1) = UnixStream::pair().unwrap();
+    let client = client(ConnectionBuilder::unix_stream(p1).p2p())?;
+    let server = server(
+        ConnectionBuilder::unix_stream(p0)
+            .server(&Guid::generate())
+            .p2p(),
+    )?;
+
+    futures_util::try_join!(client.build(), server.build())
+}
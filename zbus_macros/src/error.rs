@@ -141,6 +141,24 @@ pub fn expand_derive(input: DeriveInput) -> Result<TokenStream, Error> {
         replies.extend(r);
     }
 
+    // The zbus error is the source of the zbus_error variant.
+    let error_impl = match &zbus_error_variant {
+        Some(ident) => quote! {
+            impl ::std::error::Error for #name {
+                fn source(&self) -> ::std::option::Option<&(dyn ::std::error::Error + 'static)> {
+                    #[allow(unreachable_patterns)]
+                    match self {
+                        Self::#ident(e) => ::std::option::Option::Some(e),
+                        _ => ::std::option::Option::None,
+                    }
+                }
+            }
+        },
+        None => quote! {
+            impl ::std::error::Error for #name {}
+        },
+    };
+
     let from_zbus_error_impl = zbus_error_variant
         .map(|ident| {
             quote! {
@@ -198,7 +216,7 @@ pub fn expand_derive(input: DeriveInput) -> Result<TokenStream, Error> {
 
         #display_impl
 
-        impl ::std::error::Error for #name {}
+        #error_impl
 
         #from_zbus_error_impl
     })
@@ -1,6 +1,6 @@
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use syn::{
     self, parse_quote, punctuated::Punctuated, spanned::Spanned, AngleBracketedGenericArguments,
     AttributeArgs, Error, FnArg, GenericArgument, ImplItem, ItemImpl, Lit::Str, Meta,
@@ -23,8 +23,13 @@ def_attrs! {
     pub MethodAttributes("method") {
         name str,
         signal none,
-        property none,
-        out_args [str]
+        property {
+            pub PropertyAttributes("property") {
+                emits_changed_signal str
+            }
+        },
+        out_args [str],
+        struct_return none
     };
 }
 
@@ -46,19 +51,21 @@ mod arg_attrs {
 use arg_attrs::ArgAttributes;
 
 #[derive(Debug)]
-struct Property<'a> {
+struct Property {
     read: bool,
     write: bool,
-    ty: Option<&'a Type>,
+    ty: Option<Type>,
+    emits_changed_signal: PropertyEmitsChangedSignal,
     doc_comments: TokenStream,
 }
 
-impl<'a> Property<'a> {
+impl Property {
     fn new() -> Self {
         Self {
             read: false,
             write: false,
             ty: None,
+            emits_changed_signal: PropertyEmitsChangedSignal::True,
             doc_comments: quote!(),
         }
     }
@@ -104,6 +111,46 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
             }
         };
 
+    // The setters of write-only properties must not signal the new value and the
+    // `emits_changed_signal` attribute may be given on either the getter or the setter, so we need
+    // to know upfront which properties have a getter and how their changes are signaled.
+    let mut readable_properties = HashSet::new();
+    let mut emits_changed_signals = HashMap::new();
+    for item in &input.items {
+        if let ImplItem::Method(m) = item {
+            let attrs = MethodAttributes::parse(&m.attrs)?;
+            let prop_attrs = match attrs.property {
+                Some(prop_attrs) => prop_attrs,
+                None => continue,
+            };
+            let is_getter = m.sig.inputs.len() == 1;
+            let name = attrs.name.unwrap_or_else(|| {
+                let name = m.sig.ident.to_string();
+                match name.strip_prefix("set_") {
+                    Some(name) if !is_getter => pascal_case(name),
+                    _ => pascal_case(&name),
+                }
+            });
+            if let Some(s) = &prop_attrs.emits_changed_signal {
+                let emits = PropertyEmitsChangedSignal::parse(s, m.span())?;
+                match emits_changed_signals.insert(name.clone(), emits) {
+                    Some(other) if other != emits => {
+                        return Err(Error::new_spanned(
+                            &m.sig,
+                            format!(
+                                "conflicting `emits_changed_signal` values for property `{name}`"
+                            ),
+                        ));
+                    }
+                    _ => (),
+                }
+            }
+            if is_getter {
+                readable_properties.insert(name);
+            }
+        }
+    }
+
     for method in &mut input.items {
         let method = match method {
             ImplItem::Method(m) => m,
@@ -138,9 +185,10 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
             .collect();
 
         let doc_comments = to_xml_docs(docs);
-        let is_property = attrs.property;
+        let is_property = attrs.property.is_some();
         let is_signal = attrs.signal;
         let out_args = attrs.out_args.as_deref();
+        let struct_return = attrs.struct_return;
         assert!(!is_property || !is_signal);
 
         let has_inputs = inputs.len() > 1;
@@ -164,7 +212,10 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
             quote! {}
         };
 
-        let handle_fallible_property = quote! { .map(|e| <#zbus::zvariant::Value as ::std::convert::From<_>>::from(e).to_owned()) };
+        let handle_fallible_property = quote! {
+            .map(|e| <#zbus::zvariant::Value as ::std::convert::From<_>>::from(e).to_owned())
+            .map_err(::std::convert::Into::<#zbus::fdo::Error>::into)
+        };
 
         let mut typed_inputs = inputs
             .iter()
@@ -185,14 +236,21 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
 
         let mut intro_args = quote!();
         intro_args.extend(introspect_input_args(&typed_inputs, is_signal));
-        let is_result_output = introspect_add_output_args(&mut intro_args, output, out_args)?;
+        let is_result_output =
+            introspect_add_output_args(&mut intro_args, output, out_args, struct_return)?;
 
         let (args_from_msg, args_names) = get_args_from_inputs(&typed_inputs, &zbus)?;
 
         clean_input_args(inputs);
 
+        // A tuple is returned as multiple out arguments, unless it's to be returned as a single
+        // structure, which is achieved by wrapping it into another tuple.
         let reply = if is_result_output {
-            let ret = quote!(r);
+            let ret = if struct_return {
+                quote!((r,))
+            } else {
+                quote!(r)
+            };
 
             quote!(match reply {
                 ::std::result::Result::Ok(r) => c.reply(m, &#ret).await,
@@ -201,6 +259,8 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
                     c.reply_dbus_error(&hdr, e).await
                 }
             })
+        } else if struct_return {
+            quote!(c.reply(m, &(reply,)).await)
         } else {
             quote!(c.reply(m, &reply).await)
         };
@@ -218,6 +278,10 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
             introspect.extend(doc_comments);
             introspect.extend(introspect_signal(&member_name, &intro_args));
             let signal_context = signal_context_arg.unwrap().pat;
+            // The return type can be left out of the declaration.
+            if let ReturnType::Default = output {
+                *output = parse_quote!(-> #zbus::Result<()>);
+            }
 
             method.block = parse_quote!({
                 #signal_context.connection().emit_signal(
@@ -238,21 +302,33 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
 
             let p = p.or_insert_with(Property::new);
             p.doc_comments.extend(doc_comments);
+            p.emits_changed_signal = emits_changed_signals
+                .get(&member_name)
+                .copied()
+                .unwrap_or_default();
             if has_inputs {
                 p.write = true;
 
+                let value_ty = &typed_inputs
+                    .first()
+                    .ok_or_else(|| Error::new_spanned(&inputs, "Expected a value argument"))?
+                    .ty;
+                if p.ty.is_none() {
+                    p.ty = Some((**value_ty).clone());
+                }
+
                 let set_call = if is_result_output {
                     quote!(self.#ident(val)#method_await)
                 } else if is_async {
                     quote!(
                             #zbus::export::futures_util::future::FutureExt::map(
                                 self.#ident(val),
-                                ::std::result::Result::Ok,
+                                ::std::result::Result::<_, #zbus::fdo::Error>::Ok,
                             )
                             .await
                     )
                 } else {
-                    quote!(::std::result::Result::Ok(self.#ident(val)))
+                    quote!(::std::result::Result::<_, #zbus::fdo::Error>::Ok(self.#ident(val)))
                 };
 
                 // * For reference arg, we convert from `&Value` (so `TryFrom<&Value<'_>>` is
@@ -266,11 +342,7 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
                 let value_to_owned = quote! {
                     ::zbus::zvariant::Value::from(zbus::zvariant::Value::to_owned(value))
                 };
-                let value_arg = match &*typed_inputs
-                    .first()
-                    .ok_or_else(|| Error::new_spanned(&inputs, "Expected a value argument"))?
-                    .ty
-                {
+                let value_arg = match &**value_ty {
                     Type::Reference(_) => quote!(value),
                     Type::Path(path) => path
                         .path
@@ -288,19 +360,32 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
                         .unwrap_or_else(|| value_to_owned.clone()),
                     _ => value_to_owned,
                 };
+                // The value of write-only properties is not for everyone to see and changes to
+                // constant or unsignaled properties are not to be announced at all.
+                let notify_change = if readable_properties.contains(&member_name)
+                    && matches!(
+                        p.emits_changed_signal,
+                        PropertyEmitsChangedSignal::True | PropertyEmitsChangedSignal::Invalidates
+                    ) {
+                    quote!(
+                        self
+                            .#prop_changed_method_name(&signal_context)
+                            .await
+                            .map(|_| set_result)
+                            .map_err(Into::into)
+                    )
+                } else {
+                    quote!(::std::result::Result::Ok(set_result))
+                };
                 let do_set = quote!({
                     let value = #value_arg;
                     match ::std::convert::TryInto::try_into(value) {
                         ::std::result::Result::Ok(val) => {
                             match #set_call {
-                                ::std::result::Result::Ok(set_result) => {
-                                    self
-                                        .#prop_changed_method_name(&signal_context)
-                                        .await
-                                        .map(|_| set_result)
-                                        .map_err(Into::into)
+                                ::std::result::Result::Ok(set_result) => #notify_change,
+                                ::std::result::Result::Err(e) => {
+                                    ::std::result::Result::Err(::std::convert::Into::<#zbus::fdo::Error>::into(e))
                                 }
-                                e => e,
                             }
                         }
                         ::std::result::Result::Err(e) => {
@@ -327,7 +412,8 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
                     let q = quote!(
                         #member_name => {
                             #zbus::DispatchResult::Async(::std::boxed::Box::pin(async move {
-                                #do_set
+                                let res: #zbus::fdo::Result<()> = #do_set;
+                                res.map_err(::std::convert::Into::into)
                             }))
                         }
                     );
@@ -336,7 +422,7 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
             } else {
                 let is_fallible_property = is_result_output;
 
-                p.ty = Some(get_property_type(output)?);
+                p.ty = Some(get_property_type(output)?.clone());
                 p.read = true;
                 let inner = if is_fallible_property {
                     quote!(self.#ident()#method_await#handle_fallible_property)
@@ -387,22 +473,36 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
                     quote!(self.#ident()#method_await)
                 };
 
-                let prop_changed_method = quote!(
-                    pub async fn #prop_changed_method_name(
-                        &self,
-                        signal_context: &#zbus::SignalContext<'_>,
-                    ) -> #zbus::Result<()> {
-                        let mut changed = ::std::collections::HashMap::new();
-                        let value = <#zbus::zvariant::Value as ::std::convert::From<_>>::from(#prop_value_handled);
-                        changed.insert(#member_name, &value);
-                        #zbus::fdo::Properties::properties_changed(
-                            signal_context,
-                            #zbus::names::InterfaceName::from_static_str_unchecked(#iface_name),
-                            &changed,
-                            &[],
-                        ).await
+                let prop_changed_method = match p.emits_changed_signal {
+                    PropertyEmitsChangedSignal::True => quote!(
+                        pub async fn #prop_changed_method_name(
+                            &self,
+                            signal_context: &#zbus::SignalContext<'_>,
+                        ) -> #zbus::Result<()> {
+                            let mut changed = ::std::collections::HashMap::new();
+                            let value = <#zbus::zvariant::Value as ::std::convert::From<_>>::from(#prop_value_handled);
+                            changed.insert(#member_name, &value);
+                            #zbus::fdo::Properties::properties_changed(
+                                signal_context,
+                                #zbus::names::InterfaceName::from_static_str_unchecked(#iface_name),
+                                &changed,
+                                &[],
+                            ).await
+                        }
+                    ),
+                    PropertyEmitsChangedSignal::Invalidates => quote!(
+                        pub async fn #prop_changed_method_name(
+                            &self,
+                            signal_context: &#zbus::SignalContext<'_>,
+                        ) -> #zbus::Result<()> {
+                            self.#prop_invalidate_method_name(signal_context).await
+                        }
+                    ),
+                    // Nothing to signal.
+                    PropertyEmitsChangedSignal::Const | PropertyEmitsChangedSignal::False => {
+                        continue;
                     }
-                );
+                };
                 generated_signals.extend(prop_changed_method);
 
                 let prop_invalidate_method = quote!(
@@ -610,9 +710,17 @@ fn get_args_from_inputs(
                 }
 
                 let header_arg = &input.pat;
+                // The header can be taken by value or by reference.
+                let header_ref = match (&*input.ty, pat_ident(input)) {
+                    (Type::Reference(_), Some(ident)) => quote! {
+                        let #header_arg = &#ident;
+                    },
+                    _ => quote!(),
+                };
 
                 header_arg_decl = Some(quote! {
                     let #header_arg = m.header()?;
+                    #header_ref
                 });
             } else if attrs.signal_context {
                 if signal_context_arg_decl.is_some() {
@@ -623,6 +731,13 @@ fn get_args_from_inputs(
                 }
 
                 let signal_context_arg = &input.pat;
+                // The context can be taken by value or by reference.
+                let signal_context_ref = match (&*input.ty, pat_ident(input)) {
+                    (Type::Reference(_), Some(ident)) => quote! {
+                        let #signal_context_arg = &#ident;
+                    },
+                    _ => quote!(),
+                };
 
                 signal_context_arg_decl = Some(quote! {
                     let #signal_context_arg = match m.path() {
@@ -635,6 +750,7 @@ fn get_args_from_inputs(
                             return c.reply_dbus_error(&hdr, err).await;
                         }
                     };
+                    #signal_context_ref
                 });
             } else {
                 args_names.push(pat_ident(input).unwrap());
@@ -777,6 +893,7 @@ fn introspect_add_output_args(
     args: &mut TokenStream,
     output: &ReturnType,
     arg_names: Option<&[String]>,
+    struct_return: bool,
 ) -> syn::Result<bool> {
     let mut is_result_output = false;
 
@@ -796,20 +913,44 @@ fn introspect_add_output_args(
             }
         }
 
-        if let Type::Tuple(t) = ty {
-            if let Some(arg_names) = arg_names {
-                if t.elems.len() != arg_names.len() {
-                    // Turn into error
-                    panic!("Number of out arg names different from out args specified")
-                }
+        let tys: Vec<&Type> = match ty {
+            Type::Tuple(t) if !struct_return => t.elems.iter().collect(),
+            Type::Tuple(t) if t.elems.is_empty() => {
+                return Err(Error::new_spanned(
+                    ty,
+                    "`struct_return` requires a non-empty tuple return type",
+                ));
             }
-            for i in 0..t.elems.len() {
-                let name = arg_names.map(|names| &names[i]);
-                args.extend(introspect_output_arg(&t.elems[i], name));
+            Type::Tuple(_) => vec![ty],
+            _ if struct_return => {
+                return Err(Error::new_spanned(
+                    ty,
+                    "`struct_return` requires a tuple return type",
+                ));
             }
-        } else {
-            args.extend(introspect_output_arg(ty, None));
+            _ => vec![ty],
+        };
+        if let Some(arg_names) = arg_names {
+            if tys.len() != arg_names.len() {
+                return Err(Error::new_spanned(
+                    ty,
+                    format!(
+                        "{} out argument names specified through `out_args` for {} out arguments",
+                        arg_names.len(),
+                        tys.len(),
+                    ),
+                ));
+            }
+        }
+        for (i, ty) in tys.into_iter().enumerate() {
+            let name = arg_names.map(|names| &names[i]);
+            args.extend(introspect_output_arg(ty, name));
         }
+    } else if struct_return {
+        return Err(Error::new_spanned(
+            output,
+            "`struct_return` requires a tuple return type",
+        ));
     }
 
     Ok(is_result_output)
@@ -840,7 +981,7 @@ fn get_property_type(output: &ReturnType) -> syn::Result<&Type> {
 
 fn introspect_properties(
     introspection: &mut TokenStream,
-    properties: BTreeMap<String, Property<'_>>,
+    properties: BTreeMap<String, Property>,
 ) -> syn::Result<()> {
     for (name, prop) in properties {
         let access = if prop.read && prop.write {
@@ -855,19 +996,39 @@ fn introspect_properties(
                 "property is neither readable nor writable",
             ));
         };
-        let ty = prop.ty.ok_or_else(|| {
-            Error::new_spanned(&name, "Write-only properties aren't supported yet")
-        })?;
+        // Both getters and setters give the type.
+        let ty = match prop.ty {
+            Some(ty) => ty,
+            None => return Err(Error::new_spanned(name, "property without a type")),
+        };
 
         let doc_comments = prop.doc_comments;
-        introspection.extend(quote!(
-            #doc_comments
-            ::std::writeln!(
-                writer,
-                "{:indent$}<property name=\"{}\" type=\"{}\" access=\"{}\"/>",
-                "", #name, <#ty>::signature(), #access, indent = level,
-            ).unwrap();
-        ));
+        if prop.emits_changed_signal == PropertyEmitsChangedSignal::True {
+            introspection.extend(quote!(
+                #doc_comments
+                ::std::writeln!(
+                    writer,
+                    "{:indent$}<property name=\"{}\" type=\"{}\" access=\"{}\"/>",
+                    "", #name, <#ty>::signature(), #access, indent = level,
+                ).unwrap();
+            ));
+        } else {
+            let emits_changed_signal = prop.emits_changed_signal.as_str();
+            introspection.extend(quote!(
+                #doc_comments
+                ::std::writeln!(
+                    writer,
+                    "{:indent$}<property name=\"{}\" type=\"{}\" access=\"{}\">",
+                    "", #name, <#ty>::signature(), #access, indent = level,
+                ).unwrap();
+                ::std::writeln!(
+                    writer,
+                    "{:indent$}<annotation name=\"org.freedesktop.DBus.Property.EmitsChangedSignal\" value=\"{}\"/>",
+                    "", #emits_changed_signal, indent = level + 2,
+                ).unwrap();
+                ::std::writeln!(writer, "{:indent$}</property>", "", indent = level).unwrap();
+            ));
+        }
     }
 
     Ok(())
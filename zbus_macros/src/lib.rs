@@ -27,6 +27,11 @@ mod utils;
 /// former doesn't take any argument and uses the default service name and path. The later allows
 /// you to specify non-default proxy arguments.
 ///
+/// When both proxies are generated, they can be converted into each other through `From`. Both
+/// share the same underlying proxy, so this is cheap and the property cache is kept. This comes in
+/// handy when most of your code is blocking but some async code needs to use the same proxy (or
+/// vice versa).
+///
 /// The following attributes are supported:
 ///
 /// * `interface` - the name of the D-Bus interface this proxy is for.
@@ -51,6 +56,10 @@ mod utils;
 ///   default to `false`. Please make sure to explicitly set either this attribute or the default
 ///   values, according to your needs.
 ///
+/// * `method_timeout` - the default timeout for the method calls made through the proxy, given in
+///   seconds or milliseconds (e.g `"30s"` or `"500ms"`). See `ProxyBuilder::method_timeout` for
+///   details.
+///
 /// Each trait method will be expanded to call to the associated D-Bus remote interface.
 ///
 /// Trait methods accept `dbus_proxy` attributes:
@@ -58,7 +67,7 @@ mod utils;
 /// * `name` - override the D-Bus name (pascal case form by default)
 ///
 /// * `property` - expose the method as a property. If the method takes an argument, it must be a
-///   setter, with a `set_` prefix. Otherwise, it's a getter. Additional sub-attributes exists to
+///   setter, with a `set_` prefix. Otherwise, it's a getter. Additional sub-attributes exist to
 ///   control specific property behaviors:
 ///   * `emits_changed_signal` - specifies how property changes are signaled. Valid values are those
 ///     documented in [DBus specifications][dbus_emits_changed_signal]:
@@ -77,7 +86,8 @@ mod utils;
 /// * `signal` - declare a signal just like a D-Bus method. Read the [Signals](#signals) section
 ///   below for details.
 ///
-/// * `no_reply` - declare a method call that does not wait for a reply.
+/// * `no_reply` - declare a method call that does not wait for a reply. The method returns as soon
+///   as the call is sent, so it must return `()` (see [`zbus::Proxy::call_noreply`]).
 ///
 /// * `no_autostart` - declare a method call that will not trigger the bus to automatically launch
 ///   the destination service if it is not already running.
@@ -96,6 +106,19 @@ mod utils;
 ///
 ///   NB: Any doc comments provided shall be appended to the ones added by the macro.
 ///
+/// The arguments of method calls accept `dbus_proxy` attributes as well, to let callers pass
+/// values of a different type than the one sent on the wire:
+///
+/// * `into` - the argument is converted into the given type through `Into`. For instance, with
+///   `#[dbus_proxy(into = "u32")] level: Level`, a `Level` is passed and a `u32` is sent.
+///
+/// * `try_into` - the argument is converted into the given type through `TryInto`. If the
+///   conversion fails, the method returns a [`zbus::Error::Variant`] error without calling the
+///   peer.
+///
+/// * `object_path` - shorthand for `try_into = "zbus::zvariant::ObjectPath<'_>"`, to pass object
+///   paths as strings, e.g `#[dbus_proxy(object_path)] path: &str`.
+///
 /// # Signals
 ///
 /// For each signal method declared, this macro will provide a method, named `receive_<method_name>`
@@ -105,6 +128,32 @@ mod utils;
 /// signal arguments. It also implements `Deref<Target = Message>` to allow easy access to the
 /// underlying [`zbus::Message`].
 ///
+/// The asynchronous proxy also gets a `connect_<method_name>` method, to register a handler called
+/// for each signal received (see [`zbus::Proxy::connect_signal`]). The handler is disconnected
+/// once the returned [`zbus::SignalHandler`] guard is dropped.
+///
+/// # File descriptors
+///
+/// Arguments, return values and signal arguments of D-Bus type `h` are declared with
+/// [`zvariant::Fd`] or [`zvariant::OwnedFd`]. The file descriptors are transparently passed
+/// through the file descriptor array of the messages (on Unix connections that support it). Use
+/// `Fd` (or `&OwnedFd`) to send a file descriptor you keep ownership of, and `OwnedFd` to receive
+/// one: it is a duplicate of the one in the message, owned by the caller and closed on drop.
+///
+/// ```no_run
+/// use zbus::{dbus_proxy, zvariant::OwnedFd, Result};
+///
+/// #[dbus_proxy(
+///     interface = "org.freedesktop.login1.Manager",
+///     default_service = "org.freedesktop.login1",
+///     default_path = "/org/freedesktop/login1"
+/// )]
+/// trait Manager {
+///     /// The lock is held until the returned file descriptor is closed.
+///     fn inhibit(&self, what: &str, who: &str, why: &str, mode: &str) -> Result<OwnedFd>;
+/// }
+/// ```
+///
 /// # Example
 ///
 /// ```no_run
@@ -189,9 +238,15 @@ mod utils;
 /// [`zbus::blocking::Proxy`]: https://docs.rs/zbus/3.0.0/zbus/blocking/struct.Proxy.html
 /// [`zbus::SignalStream`]: https://docs.rs/zbus/3.0.0/zbus/struct.SignalStream.html
 /// [`zbus::blocking::SignalIterator`]: https://docs.rs/zbus/3.0.0/zbus/blocking/struct.SignalIterator.html
+/// [`zbus::Proxy::call_noreply`]: https://docs.rs/zbus/3.0.0/zbus/struct.Proxy.html#method.call_noreply
+/// [`zbus::Proxy::connect_signal`]: https://docs.rs/zbus/3.0.0/zbus/struct.Proxy.html#method.connect_signal
+/// [`zbus::SignalHandler`]: https://docs.rs/zbus/3.0.0/zbus/struct.SignalHandler.html
 /// [`zbus::SignalReceiver::receive_for`]:
 /// https://docs.rs/zbus/3.0.0/zbus/struct.SignalReceiver.html#method.receive_for
 /// [`ObjectPath`]: https://docs.rs/zvariant/2.10.0/zvariant/struct.ObjectPath.html
+/// [`zbus::Error::Variant`]: https://docs.rs/zbus/3.0.0/zbus/enum.Error.html#variant.Variant
+/// [`zvariant::Fd`]: https://docs.rs/zvariant/3.0.0/zvariant/struct.Fd.html
+/// [`zvariant::OwnedFd`]: https://docs.rs/zvariant/3.0.0/zvariant/struct.OwnedFd.html
 /// [dbus_emits_changed_signal]: https://dbus.freedesktop.org/doc/dbus-specification.html#introspection-format
 #[proc_macro_attribute]
 pub fn dbus_proxy(attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -214,11 +269,31 @@ pub fn dbus_proxy(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///
 /// * `property` - expose the method as a property. If the method takes an argument, it must be a
 ///   setter, with a `set_` prefix. Otherwise, it's a getter. If it may fail, a property method must
-///   return `zbus::fdo::Result`.
+///   return a `Result` with an error type convertible into `zbus::fdo::Error`, such as
+///   `zbus::fdo::Result` or `zbus::Result`. The error is then sent back as the reply to the `Get`
+///   or `Set` call, which also allows setters to reject invalid values. Properties whose getter
+///   fails are left out of `GetAll` replies. A property with a setter but no getter is write-only:
+///   its value can't be read by peers and hence, setting it doesn't emit the "PropertiesChanged"
+///   signal. Additional sub-attributes exist to control specific property behaviors:
+///   * `emits_changed_signal` - specifies how property changes are signaled. It can be given on
+///     either the getter or the setter. Unless it's `"true"`, the corresponding
+///     `org.freedesktop.DBus.Property.EmitsChangedSignal` annotation is added to the introspection
+///     data. Valid values are those documented in [DBus specifications][dbus_emits_changed_signal]:
+///     * `"true"` - (default) change signal is emitted with the new value included.
+///     * `"invalidates"` - change signal is emitted, but the value is not included in the signal.
+///       The generated `_changed` method only invalidates the property.
+///     * `"const"` - property never changes, thus no signal is ever emitted for it. Neither the
+///       `_changed` nor the `_invalidate` method is generated and the setter (if any) doesn't emit
+///       any signal.
+///     * `"false"` - change signal is not emitted if the property changes. As for `"const"`, no
+///       signal emitting method is generated.
 ///
 /// * `signal` - the method is a "signal". It must be a method declaration (without body). Its code
 ///   block will be expanded to emit the signal from the object path associated with the interface
-///   instance.
+///   instance. The first argument is the [`SignalContext`] to emit the signal with, and the
+///   following ones are the arguments of the signal, which are also used for the introspection
+///   data, along with the doc comments. The return type can be omitted, in which case it defaults
+///   to `zbus::Result<()>`.
 ///
 ///   You can call a signal method from a an interface method, or from an [`ObjectServer::with`]
 ///   function.
@@ -227,33 +302,55 @@ pub fn dbus_proxy(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///   important. You can use `out_args` to specify their names.
 ///
 ///   In such case, your method must return a tuple containing
-///   your out arguments, in the same order as passed to `out_args`.
+///   your out arguments, in the same order as passed to `out_args`. A single name can also be
+///   given for a method returning a single value.
 ///
-/// The `struct_return` attribute (from zbus 1.x) is no longer supported. If you want to return a
-/// single structure from a method, declare it to return a tuple containing either a named structure
-/// or a nested tuple.
+/// * `struct_return` - Return the tuple returned by the method as a single structure out argument,
+///   rather than as multiple out arguments. If `out_args` is also given, it must then contain a
+///   single name. Alternatively, declare the method to return a tuple containing either a named
+///   structure or a nested tuple.
 ///
 /// Note: a `<property_name_in_snake_case>_changed` method is generated for each property: this
 /// method emits the "PropertiesChanged" signal for the associated property. The setter (if it
 /// exists) will automatically call this method. For instance, a property setter named `set_foo`
 /// will be called to set the property "Foo", and will emit the "PropertiesChanged" signal with the
 /// new value for "Foo". Other changes to the "Foo" property can be signaled manually with the
-/// generated `foo_changed` method. In addition, a `<property_name_in_snake_case>_invalidated`
+/// generated `foo_changed` method. In addition, a `<property_name_in_snake_case>_invalidate`
 /// method is also generated that much like `_changed` method, emits a "PropertyChanged" signal
 /// but does not send over the new value of the property along with it. It is usually best to avoid
 /// using this since it will force all interested peers to fetch the new value and hence result in
 /// excess traffic on the bus.
 ///
+/// [dbus_emits_changed_signal]: https://dbus.freedesktop.org/doc/dbus-specification.html#introspection-format
+///
+/// Methods (including property getters and setters) can be `async`. Each incoming method call is
+/// dispatched in its own task on the connection's executor and the reply is only sent once the
+/// method returns, so a method can await other D-Bus calls or I/O without holding up the handling
+/// of other messages. Keep in mind though that the interface instance is locked for the whole
+/// duration of the call: while methods taking `&self` can run concurrently, a method taking
+/// `&mut self` waits for all pending calls on the interface and holds off any new ones until it
+/// completes.
+///
+/// File descriptors can be passed in method arguments, return values and properties, using
+/// [`zvariant::Fd`] or [`zvariant::OwnedFd`]. A `Fd` argument is only valid for the duration of
+/// the call, since the file descriptor belongs to the message: take an `OwnedFd` (in methods or
+/// property setters) to keep it around. On the other hand, a returned `OwnedFd` is sent and then
+/// closed, while a property getter must return a `Fd` to a file descriptor the service keeps.
+///
 /// The method arguments support the following `zbus` attributes:
 ///
 /// * `object_server` - This marks the method argument to receive a reference to the
 ///   [`ObjectServer`] this method was called by.
 /// * `connection` - This marks the method argument to receive a reference to the [`Connection`] on
 ///   which the method call was received.
-/// * `header` - This marks the method argument to receive the message header associated with the
-///   D-Bus method call being handled.
-/// * `signal_context` - This marks the method argument to receive a [`SignalContext`] instance,
-///   which is needed for emitting signals the easy way.
+/// * `header` - This marks the method argument to receive the [`MessageHeader`] (by value or by
+///   reference) associated with the D-Bus method call being handled. It gives access to the unique
+///   name of the caller, the object path and the message flags, which comes in handy for keeping
+///   per-caller state or auditing the calls.
+/// * `signal_context` - This marks the method argument to receive a [`SignalContext`] instance (by
+///   value or by reference), for the connection and object path the method was called on. It's
+///   needed for emitting signals the easy way, through the associated functions generated for the
+///   `signal` methods.
 ///
 /// # Example
 ///
@@ -307,10 +404,20 @@ pub fn dbus_proxy(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///     #[dbus_interface(signal)]
 ///     async fn bye(signal_ctxt: &SignalContext<'_>, message: &str) -> zbus::Result<()>;
 ///
+///     // "Greeted" signal, with the return type left out.
+///     #[dbus_interface(signal)]
+///     async fn greeted(signal_ctxt: &SignalContext<'_>, name: &str, count: u64);
+///
 ///     #[dbus_interface(out_args("answer", "question"))]
 ///     fn meaning_of_life(&self) -> zbus::fdo::Result<(i32, String)> {
 ///         Ok((42, String::from("Meaning of life")))
 ///     }
+///
+///     // Returns a single `(is)` structure, rather than two out arguments.
+///     #[dbus_interface(struct_return, out_args("version"))]
+///     fn version(&self) -> (i32, String) {
+///         (3, String::from("beta"))
+///     }
 /// }
 ///
 /// # Ok::<_, Box<dyn Error + Send + Sync>>(())
@@ -323,7 +430,10 @@ pub fn dbus_proxy(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// [`Connection`]: https://docs.rs/zbus/3.0.0/zbus/struct.Connection.html
 /// [`Connection::emit_signal()`]: https://docs.rs/zbus/3.0.0/zbus/struct.Connection.html#method.emit_signal
 /// [`SignalContext`]: https://docs.rs/zbus/3.0.0/zbus/struct.SignalContext.html
+/// [`MessageHeader`]: https://docs.rs/zbus/3.0.0/zbus/struct.MessageHeader.html
 /// [`Interface`]: https://docs.rs/zbus/3.0.0/zbus/trait.Interface.html
+/// [`zvariant::Fd`]: https://docs.rs/zvariant/3.0.0/zvariant/struct.Fd.html
+/// [`zvariant::OwnedFd`]: https://docs.rs/zvariant/3.0.0/zvariant/struct.OwnedFd.html
 #[proc_macro_attribute]
 pub fn dbus_interface(attr: TokenStream, item: TokenStream) -> TokenStream {
     let args = parse_macro_input!(attr as AttributeArgs);
@@ -1,10 +1,10 @@
-use crate::utils::{pat_ident, typed_arg, zbus_path};
+use crate::utils::{pat_ident, typed_arg, zbus_path, PropertyEmitsChangedSignal};
 use proc_macro2::{Literal, Span, TokenStream};
 use quote::{format_ident, quote, quote_spanned, ToTokens};
 use regex::Regex;
 use syn::{
-    self, fold::Fold, parse_quote, spanned::Spanned, AttributeArgs, Error, FnArg, Ident, ItemTrait,
-    ReturnType, TraitItemMethod,
+    self, fold::Fold, parse_quote, punctuated::Punctuated, spanned::Spanned, AttributeArgs, Error,
+    FnArg, Ident, ItemTrait, PatType, ReturnType, Token, TraitItemMethod, Type,
 };
 use zvariant_utils::{case, def_attrs};
 
@@ -21,7 +21,8 @@ def_attrs! {
         async_name str,
         blocking_name str,
         gen_async bool,
-        gen_blocking bool
+        gen_blocking bool,
+        method_timeout str
     };
 
     pub MethodAttributes("method") {
@@ -41,6 +42,22 @@ def_attrs! {
     };
 }
 
+mod arg_attrs {
+    use zvariant_utils::def_attrs;
+
+    def_attrs! {
+        crate dbus_proxy;
+
+        pub ArgAttributes("argument") {
+            object_path none,
+            into str,
+            try_into str
+        };
+    }
+}
+
+use arg_attrs::ArgAttributes;
+
 struct AsyncOpts {
     blocking: bool,
     usage: TokenStream,
@@ -73,6 +90,7 @@ pub fn expand(args: AttributeArgs, input: ItemTrait) -> Result<TokenStream, Erro
         blocking_name,
         gen_async,
         gen_blocking,
+        method_timeout,
     } = ImplAttributes::parse_nested_metas(&args)?;
 
     let iface_name = match (interface, name) {
@@ -85,6 +103,16 @@ pub fn expand(args: AttributeArgs, input: ItemTrait) -> Result<TokenStream, Erro
     }?;
     let gen_async = gen_async.unwrap_or(true);
     let gen_blocking = gen_blocking.unwrap_or(true);
+    let method_timeout = method_timeout
+        .map(|timeout| {
+            parse_timeout_millis(&timeout).ok_or_else(|| {
+                syn::Error::new(
+                    input.span(),
+                    format!("invalid `method_timeout` value `{timeout}`, expected e.g `\"30s\"` or `\"500ms\"`"),
+                )
+            })
+        })
+        .transpose()?;
 
     // Some sanity checks
     assert!(
@@ -100,22 +128,26 @@ pub fn expand(args: AttributeArgs, input: ItemTrait) -> Result<TokenStream, Erro
         "Can't set asynchronous proxy's name if you disabled it. 😸",
     );
 
+    let blocking_name = blocking_name.unwrap_or_else(|| {
+        if gen_async {
+            format!("{}ProxyBlocking", input.ident)
+        } else {
+            // When only generating blocking proxy, there is no need for a suffix.
+            format!("{}Proxy", input.ident)
+        }
+    });
+    let async_name = async_name.unwrap_or_else(|| format!("{}Proxy", input.ident));
+
     let blocking_proxy = if gen_blocking {
-        let proxy_name = blocking_name.unwrap_or_else(|| {
-            if gen_async {
-                format!("{}ProxyBlocking", input.ident)
-            } else {
-                // When only generating blocking proxy, there is no need for a suffix.
-                format!("{}Proxy", input.ident)
-            }
-        });
+        let proxy_name = &blocking_name;
         create_proxy(
             &input,
             iface_name.as_deref(),
             assume_defaults,
             default_path.as_deref(),
             default_service.as_deref(),
-            &proxy_name,
+            method_timeout,
+            proxy_name,
             true,
             // Signal args structs are shared between the two proxies so always generate it for
             // async proxy only unless async proxy generation is disabled.
@@ -125,28 +157,66 @@ pub fn expand(args: AttributeArgs, input: ItemTrait) -> Result<TokenStream, Erro
         quote! {}
     };
     let async_proxy = if gen_async {
-        let proxy_name = async_name.unwrap_or_else(|| format!("{}Proxy", input.ident));
+        let proxy_name = &async_name;
         create_proxy(
             &input,
             iface_name.as_deref(),
             assume_defaults,
             default_path.as_deref(),
             default_service.as_deref(),
-            &proxy_name,
+            method_timeout,
+            proxy_name,
             false,
             true,
         )?
     } else {
         quote! {}
     };
+    let conversions = if gen_async && gen_blocking {
+        let zbus = zbus_path();
+        let async_name = Ident::new(&async_name, Span::call_site());
+        let blocking_name = Ident::new(&blocking_name, Span::call_site());
+
+        quote! {
+            impl<'c> ::std::convert::From<#async_name<'c>> for #blocking_name<'c> {
+                fn from(proxy: #async_name<'c>) -> Self {
+                    ::std::convert::From::from(proxy.into_inner())
+                }
+            }
+
+            impl<'c> ::std::convert::From<#blocking_name<'c>> for #async_name<'c> {
+                fn from(proxy: #blocking_name<'c>) -> Self {
+                    let proxy: #zbus::Proxy<'c> = proxy.into_inner().into_inner();
+
+                    ::std::convert::From::from(proxy)
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
 
     Ok(quote! {
         #blocking_proxy
 
         #async_proxy
+
+        #conversions
     })
 }
 
+// Parse a timeout in seconds (`"30s"`) or milliseconds (`"500ms"`), into milliseconds.
+fn parse_timeout_millis(timeout: &str) -> Option<u64> {
+    match timeout.strip_suffix("ms") {
+        Some(millis) => millis.parse().ok(),
+        None => timeout
+            .strip_suffix('s')?
+            .parse::<u64>()
+            .ok()?
+            .checked_mul(1000),
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn create_proxy(
     input: &ItemTrait,
@@ -154,6 +224,7 @@ pub fn create_proxy(
     assume_defaults: Option<bool>,
     default_path: Option<&str>,
     default_service: Option<&str>,
+    method_timeout: Option<u64>,
     proxy_name: &str,
     blocking: bool,
     gen_sig_args: bool,
@@ -218,6 +289,21 @@ pub fn create_proxy(
                 )
             });
 
+            if attrs.property.is_some() || attrs.signal {
+                if let Some(arg) = m
+                    .sig
+                    .inputs
+                    .iter()
+                    .filter_map(typed_arg)
+                    .find(|arg| arg.attrs.iter().any(|a| a.path.is_ident("dbus_proxy")))
+                {
+                    return Err(Error::new(
+                        arg.span(),
+                        "argument attributes are only supported on method calls",
+                    ));
+                }
+            }
+
             let m = if let Some(prop_attrs) = &attrs.property {
                 has_properties = true;
 
@@ -252,7 +338,7 @@ pub fn create_proxy(
 
                 method
             } else {
-                gen_proxy_method_call(&member_name, &method_name, m, &attrs, &async_opts)
+                gen_proxy_method_call(&member_name, &method_name, m, &attrs, &async_opts)?
             };
             methods.extend(m);
         }
@@ -273,6 +359,9 @@ pub fn create_proxy(
         (proxy, connection, builder)
     };
 
+    let method_timeout = method_timeout.map(|millis| {
+        quote! { .method_timeout(::std::time::Duration::from_millis(#millis)) }
+    });
     let (builder_new, proxydefault_impl, proxy_method_new) = match (&default_path, &default_service)
     {
         (None, None) => {
@@ -368,6 +457,11 @@ pub fn create_proxy(
     Ok(quote! {
         #proxydefault_impl
 
+        impl<'a> #zbus::ProxyInterface for #proxy_name<'a> {
+            const INTERFACE: &'static str = #iface_name;
+            const UNCACHED_PROPERTIES: &'static [&'static str] = &[#(#uncached_properties),*];
+        }
+
         #(#other_attrs)*
         #[derive(Clone, Debug)]
         pub struct #proxy_name<'c>(#proxy_struct<'c>);
@@ -377,7 +471,7 @@ pub fn create_proxy(
 
             /// Returns a customizable builder for this proxy.
             pub fn builder(conn: &#connection) -> #builder<'c, Self> {
-                let mut builder = #builder_new;
+                let mut builder = #builder_new #method_timeout;
                 if #has_properties {
                     let uncached = vec![#(#uncached_properties),*];
                     builder.cache_properties(#zbus::CacheProperties::default())
@@ -460,7 +554,7 @@ fn gen_proxy_method_call(
     m: &TraitItemMethod,
     attrs: &MethodAttributes,
     async_opts: &AsyncOpts,
-) -> TokenStream {
+) -> syn::Result<TokenStream> {
     let AsyncOpts {
         usage,
         wait,
@@ -479,6 +573,7 @@ fn gen_proxy_method_call(
         .filter_map(typed_arg)
         .filter_map(pat_ident)
         .collect();
+    let (inputs, conversions) = gen_arg_conversions(&m.sig.inputs)?;
 
     let proxy_object = attrs.object.as_ref().map(|o| {
         if *blocking {
@@ -503,35 +598,35 @@ fn gen_proxy_method_call(
 
     let method_flags = match (no_reply, no_autostart, allow_interactive_auth) {
         (true, false, false) => Some(quote!(::std::convert::Into::into(
-            zbus::MethodFlags::NoReplyExpected
+            #zbus::MethodFlags::NoReplyExpected
         ))),
         (false, true, false) => Some(quote!(::std::convert::Into::into(
-            zbus::MethodFlags::NoAutoStart
+            #zbus::MethodFlags::NoAutoStart
         ))),
         (false, false, true) => Some(quote!(::std::convert::Into::into(
-            zbus::MethodFlags::AllowInteractiveAuth
+            #zbus::MethodFlags::AllowInteractiveAuth
         ))),
 
         (true, true, false) => Some(quote!(
-            zbus::MethodFlags::NoReplyExpected | zbus::MethodFlags::NoAutoStart
+            #zbus::MethodFlags::NoReplyExpected | #zbus::MethodFlags::NoAutoStart
         )),
         (true, false, true) => Some(quote!(
-            zbus::MethodFlags::NoReplyExpected | zbus::MethodFlags::AllowInteractiveAuth
+            #zbus::MethodFlags::NoReplyExpected | #zbus::MethodFlags::AllowInteractiveAuth
         )),
         (false, true, true) => Some(quote!(
-            zbus::MethodFlags::NoAutoStart | zbus::MethodFlags::AllowInteractiveAuth
+            #zbus::MethodFlags::NoAutoStart | #zbus::MethodFlags::AllowInteractiveAuth
         )),
 
         (true, true, true) => Some(quote!(
-            zbus::MethodFlags::NoReplyExpected
-                | zbus::MethodFlags::NoAutoStart
-                | zbus::MethodFlags::AllowInteractiveAuth
+            #zbus::MethodFlags::NoReplyExpected
+                | #zbus::MethodFlags::NoAutoStart
+                | #zbus::MethodFlags::AllowInteractiveAuth
         )),
         _ => None,
     };
 
     let method = Ident::new(snake_case_name, Span::call_site());
-    let inputs = &m.sig.inputs;
+    let inputs = &inputs;
     let mut generics = m.sig.generics.clone();
     let where_clause = generics.where_clause.get_or_insert(parse_quote!(where));
     for param in generics
@@ -563,7 +658,7 @@ fn gen_proxy_method_call(
     }
     let (_, ty_generics, where_clause) = generics.split_for_impl();
 
-    if let Some(proxy_name) = proxy_object {
+    Ok(if let Some(proxy_name) = proxy_object {
         let proxy = Ident::new(&proxy_name, Span::call_site());
         let signature = quote! {
             fn #method#ty_generics(#inputs) -> #zbus::Result<#proxy<'c>>
@@ -573,6 +668,7 @@ fn gen_proxy_method_call(
         quote! {
             #(#other_attrs)*
             pub #usage #signature {
+                #conversions
                 let object_path: #zbus::zvariant::OwnedObjectPath =
                     self.0.call(
                         #method_name,
@@ -610,6 +706,7 @@ fn gen_proxy_method_call(
                 quote! {
                     #(#other_attrs)*
                     pub #usage #signature {
+                        #conversions
                         self.0.call_with_flags::<_, _, ()>(#method_name, #method_flags, #body)#wait?;
                         ::std::result::Result::Ok(())
                     }
@@ -618,6 +715,7 @@ fn gen_proxy_method_call(
                 quote! {
                     #(#other_attrs)*
                     pub #usage #signature {
+                        #conversions
                         let reply = self.0.call_with_flags(#method_name, #method_flags, #body)#wait?;
 
                         // SAFETY: This unwrap() cannot fail due to the guarantees in
@@ -634,41 +732,79 @@ fn gen_proxy_method_call(
             quote! {
                 #(#other_attrs)*
                 pub #usage #signature {
+                    #conversions
                     let reply = self.0.call(#method_name, #body)#wait?;
                     ::std::result::Result::Ok(reply)
                 }
             }
         }
-    }
+    })
 }
 
-/// Standard annotation `org.freedesktop.DBus.Property.EmitsChangedSignal`.
+/// Strip the argument attributes off `inputs` and generate the conversions they ask for.
 ///
-/// See <https://dbus.freedesktop.org/doc/dbus-specification.html#introspection-format>.
-#[derive(Debug, Default)]
-enum PropertyEmitsChangedSignal {
-    #[default]
-    True,
-    Invalidates,
-    Const,
-    False,
-}
+/// The conversions shadow the arguments with their converted value, so they must be put at the
+/// start of the method body.
+fn gen_arg_conversions(
+    inputs: &Punctuated<FnArg, Token![,]>,
+) -> syn::Result<(Punctuated<FnArg, Token![,]>, TokenStream)> {
+    let zbus = zbus_path();
+    let mut inputs = inputs.clone();
+    let mut conversions = quote!();
 
-impl PropertyEmitsChangedSignal {
-    fn parse(s: &str, span: Span) -> syn::Result<Self> {
-        use PropertyEmitsChangedSignal::*;
-
-        match s {
-            "true" => Ok(True),
-            "invalidates" => Ok(Invalidates),
-            "const" => Ok(Const),
-            "false" => Ok(False),
-            other => Err(syn::Error::new(
-                span,
-                format!("invalid value \"{other}\" for attribute `property(emits_changed_signal)`"),
-            )),
-        }
+    for arg in inputs.iter_mut() {
+        let arg = match arg {
+            FnArg::Typed(arg) => arg,
+            FnArg::Receiver(_) => continue,
+        };
+        let ArgAttributes {
+            object_path,
+            into,
+            try_into,
+        } = ArgAttributes::parse(&arg.attrs)?;
+        arg.attrs.retain(|a| !a.path.is_ident("dbus_proxy"));
+
+        let (ty, fallible) = match (object_path, into, try_into) {
+            (false, None, None) => continue,
+            (true, None, None) => (quote!(#zbus::zvariant::ObjectPath<'_>), true),
+            (false, Some(ty), None) => (parse_arg_type(&ty, arg)?, false),
+            (false, None, Some(ty)) => (parse_arg_type(&ty, arg)?, true),
+            _ => {
+                return Err(Error::new(
+                    arg.span(),
+                    "only one of `object_path`, `into` and `try_into` attributes can be used",
+                ))
+            }
+        };
+        let ident = pat_ident(arg).ok_or_else(|| {
+            Error::new(
+                arg.pat.span(),
+                "converted arguments must be plain identifiers",
+            )
+        })?;
+
+        conversions.extend(if fallible {
+            quote! {
+                let #ident: #ty = ::std::convert::TryInto::try_into(#ident).map_err(|e| {
+                    #zbus::Error::Variant(#zbus::zvariant::Error::Message(
+                        ::std::string::ToString::to_string(&e),
+                    ))
+                })?;
+            }
+        } else {
+            quote! {
+                let #ident: #ty = ::std::convert::Into::into(#ident);
+            }
+        });
     }
+
+    Ok((inputs, conversions))
+}
+
+fn parse_arg_type(ty: &str, arg: &PatType) -> syn::Result<TokenStream> {
+    syn::parse_str::<Type>(ty)
+        .map(ToTokens::into_token_stream)
+        .map_err(|e| Error::new(arg.span(), format!("invalid type `{ty}`: {e}")))
 }
 
 fn gen_proxy_property(
@@ -730,8 +866,9 @@ fn gen_proxy_property(
                 let (_, ty_generics, where_clause) = m.sig.generics.split_for_impl();
                 let receive = format_ident!("receive_{}_changed", method_name);
                 let gen_doc = format!(
-                    "Create a stream for the `{property_name}` property changes. \
-                This is a convenient wrapper around [`{proxy_name}::receive_property_changed`]."
+                    "Create a stream for the `{property_name}` property changes, starting with its \
+                current value. This is a convenient wrapper around \
+                [`{proxy_name}::receive_property_changed`]."
                 );
                 quote! {
                     #[doc = #gen_doc]
@@ -917,6 +1054,31 @@ fn gen_proxy_signal(
             }
         }
     };
+    let connect_signal = if *blocking {
+        quote!()
+    } else {
+        let connect_name = format_ident!("connect_{snake_case_name}");
+        let connect_gen_doc = format!(
+            "Call `handler` for each `{signal_name}` signal, until the returned guard is dropped.\n\
+                \n\
+                This a convenient wrapper around [`{proxy_path}::connect_signal`]\
+                (https://docs.rs/zbus/latest/zbus/struct.Proxy.html#method.connect_signal).",
+        );
+
+        quote! {
+            #[doc = #connect_gen_doc]
+            #(#other_attrs)*
+            pub async fn #connect_name<F, Fut>(&self, mut handler: F) -> #zbus::Result<#zbus::SignalHandler>
+            where
+                F: ::std::ops::FnMut(#signal_name_ident) -> Fut + ::std::marker::Send + 'static,
+                Fut: ::std::future::Future<Output = ()> + ::std::marker::Send + 'static,
+            {
+                self.0
+                    .connect_signal(#signal_name, move |msg| handler(#signal_name_ident(msg)))
+                    .await
+            }
+        }
+    };
     let receive_signal = quote! {
         #[doc = #receive_gen_doc]
         #(#other_attrs)*
@@ -926,6 +1088,8 @@ fn gen_proxy_signal(
         }
 
         #receive_signal_with_args
+
+        #connect_signal
     };
 
     let stream_gen_doc = format!(
@@ -34,6 +34,9 @@ fn test_proxy() {
         #[dbus_proxy(name = "CheckRENAMING")]
         fn check_renaming(&self) -> zbus::Result<Vec<u8>>;
 
+        #[dbus_proxy(no_reply, no_autostart)]
+        fn notify(&self, val: u32) -> zbus::Result<()>;
+
         #[dbus_proxy(property)]
         fn property(&self) -> fdo::Result<Vec<String>>;
 
@@ -92,6 +95,128 @@ fn test_proxy() {
     });
 }
 
+#[test]
+fn test_proxy_conversions() {
+    #[dbus_proxy(
+        interface = "org.freedesktop.zbus_macros.Conversions",
+        default_service = "org.freedesktop.zbus_macros",
+        default_path = "/org/freedesktop/zbus_macros/conversions"
+    )]
+    trait Conversions {
+        fn ping(&self) -> zbus::Result<()>;
+    }
+
+    let connection = zbus::blocking::Connection::session().unwrap();
+    let proxy = ConversionsProxyBlocking::builder(&connection)
+        .path("/org/freedesktop/zbus_macros/other")
+        .unwrap()
+        .build()
+        .unwrap();
+
+    // Both proxies share the same underlying proxy, customizations included.
+    let proxy = ConversionsProxy::from(proxy);
+    assert_eq!(proxy.path(), "/org/freedesktop/zbus_macros/other");
+    let proxy = ConversionsProxyBlocking::from(proxy);
+    assert_eq!(proxy.path(), "/org/freedesktop/zbus_macros/other");
+    assert_eq!(proxy.interface(), "org.freedesktop.zbus_macros.Conversions");
+}
+
+#[test]
+fn test_proxy_arg_conversions() {
+    use zbus::zvariant::ObjectPath;
+
+    struct Describer;
+
+    #[dbus_interface(name = "org.freedesktop.zbus_macros.ArgConversions")]
+    impl Describer {
+        fn describe(&self, path: ObjectPath<'_>, level: u8, id: u32) -> String {
+            format!("{path} {level} {id}")
+        }
+    }
+
+    enum Level {
+        Low,
+        High,
+    }
+
+    impl From<Level> for u8 {
+        fn from(level: Level) -> u8 {
+            match level {
+                Level::Low => 1,
+                Level::High => 10,
+            }
+        }
+    }
+
+    #[dbus_proxy(
+        interface = "org.freedesktop.zbus_macros.ArgConversions",
+        default_path = "/org/freedesktop/zbus_macros/arg_conversions",
+        gen_async = false
+    )]
+    trait ArgConversions {
+        fn describe(
+            &self,
+            #[dbus_proxy(object_path)] path: &str,
+            #[dbus_proxy(into = "u8")] level: Level,
+            #[dbus_proxy(try_into = "u32")] id: i64,
+        ) -> zbus::Result<String>;
+    }
+
+    let service = zbus::blocking::ConnectionBuilder::session()
+        .unwrap()
+        .serve_at("/org/freedesktop/zbus_macros/arg_conversions", Describer)
+        .unwrap()
+        .build()
+        .unwrap();
+    let connection = zbus::blocking::Connection::session().unwrap();
+    let proxy = ArgConversionsProxy::builder(&connection)
+        .destination(service.unique_name().unwrap())
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        proxy.describe("/org/zbus", Level::High, 42).unwrap(),
+        "/org/zbus 10 42"
+    );
+    assert_eq!(proxy.describe("/", Level::Low, 0).unwrap(), "/ 1 0");
+    // Conversion failures don't get as far as the service.
+    assert!(matches!(
+        proxy.describe("not a path", Level::Low, 0),
+        Err(zbus::Error::Variant(_))
+    ));
+    assert!(matches!(
+        proxy.describe("/", Level::Low, -1),
+        Err(zbus::Error::Variant(_))
+    ));
+}
+
+#[test]
+fn test_proxy_method_timeout() {
+    use std::time::Duration;
+
+    #[dbus_proxy(
+        interface = "org.freedesktop.zbus_macros.Timeout",
+        default_service = "org.freedesktop.zbus_macros",
+        default_path = "/org/freedesktop/zbus_macros/timeout",
+        method_timeout = "1500ms"
+    )]
+    trait Timeout {
+        fn ping(&self) -> zbus::Result<()>;
+    }
+
+    let connection = zbus::blocking::Connection::session().unwrap();
+    let proxy = TimeoutProxyBlocking::new(&connection).unwrap();
+    assert_eq!(proxy.method_timeout(), Some(Duration::from_millis(1500)));
+
+    // The builder allows overriding the default.
+    let proxy = TimeoutProxyBlocking::builder(&connection)
+        .method_timeout(Duration::from_secs(3))
+        .build()
+        .unwrap();
+    assert_eq!(proxy.method_timeout(), Some(Duration::from_secs(3)));
+}
+
 #[test]
 fn test_derive_error() {
     #[derive(Debug, DBusError)]
@@ -106,6 +231,22 @@ fn test_derive_error() {
             desc: String,
         },
     }
+
+    // The zbus error is the source of the `zbus_error` variant.
+    let e = Test::ZBus(zbus::Error::Timeout);
+    let source = std::error::Error::source(&e).unwrap();
+    assert_eq!(
+        source.downcast_ref::<zbus::Error>(),
+        Some(&zbus::Error::Timeout)
+    );
+    assert!(std::error::Error::source(&Test::SomeExcuse).is_none());
+
+    #[derive(Debug, DBusError)]
+    enum OnlyZBus {
+        #[dbus_error(zbus_error)]
+        ZBus(zbus::Error),
+    }
+    assert!(std::error::Error::source(&OnlyZBus::ZBus(zbus::Error::Timeout)).is_some());
 }
 
 #[test]
@@ -153,6 +294,21 @@ fn test_interface() {
             unimplemented!()
         }
 
+        #[dbus_interface(out_args("answer", "question"))]
+        fn named_output(&self) -> (u32, String) {
+            unimplemented!()
+        }
+
+        #[dbus_interface(out_args("count"))]
+        fn named_single_output(&self) -> u32 {
+            unimplemented!()
+        }
+
+        #[dbus_interface(struct_return, out_args("pair"))]
+        fn struct_output(&self) -> zbus::fdo::Result<(u32, String)> {
+            unimplemented!()
+        }
+
         #[dbus_interface(property)]
         fn my_custom_property(&self) -> MyCustomPropertyType {
             unimplemented!()
@@ -182,9 +338,33 @@ fn test_interface() {
             unimplemented!()
         }
 
+        #[dbus_interface(property)]
+        fn set_write_only(&mut self, _val: &str) {
+            unimplemented!()
+        }
+
+        #[dbus_interface(property(emits_changed_signal = "const"))]
+        fn const_prop(&self) -> u32 {
+            unimplemented!()
+        }
+
+        #[dbus_interface(property)]
+        fn invalidated_prop(&self) -> u32 {
+            unimplemented!()
+        }
+
+        #[dbus_interface(property(emits_changed_signal = "invalidates"))]
+        fn set_invalidated_prop(&mut self, _val: u32) {
+            unimplemented!()
+        }
+
         /// Emit a signal.
         #[dbus_interface(signal)]
         async fn signal(ctxt: &SignalContext<'_>, arg: u8, other: &str) -> zbus::Result<()>;
+
+        /// Emit a signal, declared without a return type.
+        #[dbus_interface(signal)]
+        async fn state_changed(ctxt: &SignalContext<'_>, state: u32);
     }
 
     const EXPECTED_XML: &str = r#"<interface name="org.freedesktop.zbus.Test">
@@ -204,6 +384,16 @@ fn test_interface() {
   <method name="PairOutput">
     <arg type="(us)" direction="out"/>
   </method>
+  <method name="NamedOutput">
+    <arg name="answer" type="u" direction="out"/>
+    <arg name="question" type="s" direction="out"/>
+  </method>
+  <method name="NamedSingleOutput">
+    <arg name="count" type="u" direction="out"/>
+  </method>
+  <method name="StructOutput">
+    <arg name="pair" type="(us)" direction="out"/>
+  </method>
   <method name="CheckVEC">
     <arg type="ay" direction="out"/>
   </method>
@@ -214,6 +404,18 @@ fn test_interface() {
     <arg name="arg" type="y"/>
     <arg name="other" type="s"/>
   </signal>
+  <!--
+   Emit a signal, declared without a return type.
+   -->
+  <signal name="StateChanged">
+    <arg name="state" type="u"/>
+  </signal>
+  <property name="ConstProp" type="u" access="read">
+    <annotation name="org.freedesktop.DBus.Property.EmitsChangedSignal" value="const"/>
+  </property>
+  <property name="InvalidatedProp" type="u" access="readwrite">
+    <annotation name="org.freedesktop.DBus.Property.EmitsChangedSignal" value="invalidates"/>
+  </property>
   <property name="MyCustomProperty" type="u" access="readwrite"/>
   <!--
    Testing my_prop documentation is reflected in XML.
@@ -221,6 +423,7 @@ fn test_interface() {
    And that too.
    -->
   <property name="MyProp" type="q" access="readwrite"/>
+  <property name="WriteOnly" type="s" access="write"/>
 </interface>
 "#;
     let t = Test {
@@ -244,6 +447,8 @@ fn test_interface() {
             let _ = t.call(&s, &c, &m, "StrU32".try_into().unwrap());
             let ctxt = SignalContext::new(&c, "/does/not/matter").unwrap();
             block_on(Test::<u32>::signal(&ctxt, 23, "ergo sum")).unwrap();
+            block_on(Test::<u32>::state_changed(&ctxt, 42)).unwrap();
+            block_on(t.invalidated_prop_changed(&ctxt)).unwrap();
         });
     }
 }
@@ -3,7 +3,7 @@ use std::fmt::{Display, Formatter};
 
 use zbus::{
     names::BusName,
-    quick_xml::{Arg, ArgDirection, Interface},
+    quick_xml::{Annotation, Arg, ArgDirection, Interface},
 };
 use zvariant::{
     Basic, ObjectPath, Signature, ARRAY_SIGNATURE_CHAR, DICT_ENTRY_SIG_END_CHAR,
@@ -33,6 +33,9 @@ impl<'i> Display for GenTrait<'i> {
             write!(f, ", assume_defaults = true")?;
         }
         writeln!(f, ")]")?;
+        if let Some(doc) = doc_string(iface.annotations()) {
+            write_doc(f, "", doc)?;
+        }
         writeln!(f, "trait {name} {{")?;
 
         let mut methods = iface.methods().to_vec();
@@ -42,6 +45,7 @@ impl<'i> Display for GenTrait<'i> {
             let name = to_identifier(&to_snakecase(m.name().as_str()));
             writeln!(f)?;
             writeln!(f, "    /// {} method", m.name())?;
+            write_member_doc(f, m.annotations(), m.args())?;
             if pascal_case(&name) != m.name().as_str() {
                 writeln!(f, "    #[dbus_proxy(name = \"{}\")]", m.name())?;
             }
@@ -55,6 +59,7 @@ impl<'i> Display for GenTrait<'i> {
             let name = to_identifier(&to_snakecase(signal.name().as_str()));
             writeln!(f)?;
             writeln!(f, "    /// {} signal", signal.name())?;
+            write_member_doc(f, signal.annotations(), signal.args())?;
             if pascal_case(&name) != signal.name().as_str() {
                 writeln!(f, "    #[dbus_proxy(signal, name = \"{}\")]", signal.name())?;
             } else {
@@ -70,6 +75,7 @@ impl<'i> Display for GenTrait<'i> {
 
             writeln!(f)?;
             writeln!(f, "    /// {} property", p.name())?;
+            write_member_doc(f, p.annotations(), &[])?;
             if pascal_case(&name) != p.name().as_str() {
                 writeln!(f, "    #[dbus_proxy(property, name = \"{}\")]", p.name())?;
             } else {
@@ -93,6 +99,320 @@ impl<'i> Display for GenTrait<'i> {
     }
 }
 
+pub struct GenServer<'i> {
+    pub interface: &'i Interface<'i>,
+}
+
+impl<'i> Display for GenServer<'i> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let iface = self.interface;
+        let idx = iface.name().rfind('.').unwrap() + 1;
+        let name = &iface.name()[idx..];
+
+        let mut methods = iface.methods().to_vec();
+        methods.sort_by(|a, b| a.name().partial_cmp(&b.name()).unwrap());
+        let mut signals = iface.signals().to_vec();
+        signals.sort_by(|a, b| a.name().partial_cmp(&b.name()).unwrap());
+        let mut props = iface.properties().to_vec();
+        props.sort_by(|a, b| a.name().partial_cmp(&b.name()).unwrap());
+
+        // The trait to implement.
+        if let Some(doc) = doc_string(iface.annotations()) {
+            write_doc(f, "", doc)?;
+        }
+        writeln!(f, "#[zbus::export::async_trait::async_trait]")?;
+        writeln!(f, "pub trait {name}: Send + Sync + 'static {{")?;
+        for m in &methods {
+            let method = ServerMethod::new(m.args());
+            let name = to_identifier(&to_snakecase(m.name().as_str()));
+            writeln!(f)?;
+            writeln!(f, "    /// {} method", m.name())?;
+            write_member_doc(f, m.annotations(), m.args())?;
+            writeln!(
+                f,
+                "    async fn {name}(&self{}) -> zbus::fdo::Result<{}>;",
+                method.params(),
+                method.output()
+            )?;
+        }
+        for p in &props {
+            let name = to_identifier(&to_snakecase(p.name().as_str()));
+            let ty = to_rust_type(p.ty(), false, false);
+            writeln!(f)?;
+            writeln!(f, "    /// {} property", p.name())?;
+            write_member_doc(f, p.annotations(), &[])?;
+            if p.access().read() {
+                writeln!(f, "    async fn {name}(&self) -> zbus::fdo::Result<{ty}>;")?;
+            }
+            if p.access().write() {
+                writeln!(
+                    f,
+                    "    async fn set_{name}(&mut self, value: {ty}) -> zbus::fdo::Result<()>;",
+                )?;
+            }
+        }
+        writeln!(f, "}}")?;
+        writeln!(f)?;
+
+        // The interface, serving the implementations of the trait.
+        writeln!(
+            f,
+            "/// Serves a [`{name}`] implementation as the `{}` interface.",
+            iface.name()
+        )?;
+        if !signals.is_empty() {
+            writeln!(f, "///")?;
+            writeln!(f, "/// This also provides the methods to emit its signals.")?;
+        }
+        writeln!(f, "pub struct {name}Server<T>(pub T);")?;
+        writeln!(f)?;
+        writeln!(f, "#[dbus_interface(name = \"{}\")]", iface.name())?;
+        writeln!(f, "impl<T: {name}> {name}Server<T> {{")?;
+        for m in &methods {
+            let method = ServerMethod::new(m.args());
+            let name = to_identifier(&to_snakecase(m.name().as_str()));
+            let mut attrs = vec![];
+            if pascal_case(&name) != m.name().as_str() {
+                attrs.push(format!("name = \"{}\"", m.name()));
+            }
+            if method.struct_return() {
+                attrs.push("struct_return".to_string());
+            }
+            if let Some(out_args) = method.out_args() {
+                attrs.push(format!("out_args({out_args})"));
+            }
+            writeln!(f)?;
+            writeln!(f, "    /// {} method", m.name())?;
+            if !attrs.is_empty() {
+                writeln!(f, "    #[dbus_interface({})]", attrs.join(", "))?;
+            }
+            writeln!(
+                f,
+                "    async fn {name}(&self{}) -> zbus::fdo::Result<{}> {{",
+                method.params(),
+                method.output()
+            )?;
+            writeln!(f, "        self.0.{name}({}).await", method.param_names())?;
+            writeln!(f, "    }}")?;
+        }
+        for signal in &signals {
+            let name = to_identifier(&to_snakecase(signal.name().as_str()));
+            let mut args = vec!["ctxt: &zbus::SignalContext<'_>".to_string()];
+            args.extend(named_args(signal.args()).map(|(arg, a)| {
+                let ty = to_rust_type(a.ty(), true, false);
+
+                format!("{arg}: {ty}")
+            }));
+            writeln!(f)?;
+            writeln!(f, "    /// {} signal", signal.name())?;
+            write_member_doc(f, signal.annotations(), signal.args())?;
+            if pascal_case(&name) != signal.name().as_str() {
+                writeln!(
+                    f,
+                    "    #[dbus_interface(signal, name = \"{}\")]",
+                    signal.name()
+                )?;
+            } else {
+                writeln!(f, "    #[dbus_interface(signal)]")?;
+            }
+            writeln!(
+                f,
+                "    async fn {name}({}) -> zbus::Result<()>;",
+                args.join(", ")
+            )?;
+        }
+        for p in &props {
+            let name = to_identifier(&to_snakecase(p.name().as_str()));
+            let ty = to_rust_type(p.ty(), false, false);
+            let mut attrs = String::from("property");
+            let emits_changed_signal = p
+                .annotations()
+                .iter()
+                .find(|a| a.name() == "org.freedesktop.DBus.Property.EmitsChangedSignal")
+                .map(|a| a.value())
+                .filter(|value| *value != "true");
+            if let Some(value) = emits_changed_signal {
+                attrs.push_str(&format!("(emits_changed_signal = \"{value}\")"));
+            }
+            if pascal_case(&name) != p.name().as_str() {
+                attrs.push_str(&format!(", name = \"{}\"", p.name()));
+            }
+            if p.access().read() {
+                writeln!(f)?;
+                writeln!(f, "    /// {} property", p.name())?;
+                writeln!(f, "    #[dbus_interface({attrs})]")?;
+                writeln!(
+                    f,
+                    "    async fn {name}(&self) -> zbus::fdo::Result<{ty}> {{"
+                )?;
+                writeln!(f, "        self.0.{name}().await")?;
+                writeln!(f, "    }}")?;
+            }
+            if p.access().write() {
+                writeln!(f)?;
+                writeln!(f, "    #[dbus_interface({attrs})]")?;
+                writeln!(
+                    f,
+                    "    async fn set_{name}(&mut self, value: {ty}) -> zbus::fdo::Result<()> {{"
+                )?;
+                writeln!(f, "        self.0.set_{name}(value).await")?;
+                writeln!(f, "    }}")?;
+            }
+        }
+        writeln!(f, "}}")
+    }
+}
+
+// The arguments of a method, as seen from the server side.
+struct ServerMethod<'a> {
+    inputs: Vec<(String, String)>,
+    outputs: Vec<&'a Arg>,
+}
+
+impl<'a> ServerMethod<'a> {
+    fn new(args: &'a [Arg]) -> Self {
+        let inputs = named_args(args)
+            .filter(|(_, a)| a.direction() != Some(ArgDirection::Out))
+            .map(|(name, a)| (name, to_rust_type(a.ty(), false, false)))
+            .collect();
+        let outputs = args
+            .iter()
+            .filter(|a| a.direction() == Some(ArgDirection::Out))
+            .collect();
+
+        Self { inputs, outputs }
+    }
+
+    // The parameters, following `&self`.
+    fn params(&self) -> String {
+        self.inputs
+            .iter()
+            .map(|(name, ty)| format!(", {name}: {ty}"))
+            .collect()
+    }
+
+    fn param_names(&self) -> String {
+        let names: Vec<_> = self.inputs.iter().map(|(name, _)| name.as_str()).collect();
+
+        names.join(", ")
+    }
+
+    fn output(&self) -> String {
+        let tys: Vec<_> = self
+            .outputs
+            .iter()
+            .map(|a| to_rust_type(a.ty(), false, false))
+            .collect();
+        match tys.len() {
+            1 => tys[0].to_string(),
+            _ => format!("({})", tys.join(", ")),
+        }
+    }
+
+    // A single structure out argument must not be returned as multiple out arguments.
+    fn struct_return(&self) -> bool {
+        match &*self.outputs {
+            [output] => output.ty().starts_with(STRUCT_SIG_START_CHAR),
+            _ => false,
+        }
+    }
+
+    // The names of the out arguments, if they're all named.
+    fn out_args(&self) -> Option<String> {
+        if self.outputs.is_empty() {
+            return None;
+        }
+        let names: Option<Vec<_>> = self
+            .outputs
+            .iter()
+            .map(|a| a.name().map(|name| format!("\"{name}\"")))
+            .collect();
+
+        names.map(|names| names.join(", "))
+    }
+}
+
+// Iterate over the arguments along with their identifiers, generated for the unnamed ones.
+fn named_args(args: &[Arg]) -> impl Iterator<Item = (String, &Arg)> {
+    args.iter().enumerate().map(|(i, a)| {
+        let name = match a.name() {
+            Some(name) => to_identifier(name),
+            None => format!("arg_{}", i + 1),
+        };
+
+        (name, a)
+    })
+}
+
+// The annotations commonly used to document the interfaces, in order of preference.
+static DOC_ANNOTATIONS: &[&str] = &["org.freedesktop.DBus.DocString", "org.gtk.GDBus.DocString"];
+
+fn doc_string(annotations: &[Annotation]) -> Option<&str> {
+    DOC_ANNOTATIONS.iter().find_map(|name| {
+        annotations
+            .iter()
+            .find(|a| a.name() == *name)
+            .map(|a| a.value().trim())
+            .filter(|doc| !doc.is_empty())
+    })
+}
+
+fn write_doc(f: &mut Formatter<'_>, indent: &str, doc: &str) -> std::fmt::Result {
+    for line in doc.lines().map(str::trim) {
+        if line.is_empty() {
+            writeln!(f, "{indent}///")?;
+        } else {
+            writeln!(f, "{indent}/// {line}")?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_member_doc(
+    f: &mut Formatter<'_>,
+    annotations: &[Annotation],
+    args: &[Arg],
+) -> std::fmt::Result {
+    if let Some(doc) = doc_string(annotations) {
+        writeln!(f, "    ///")?;
+        write_doc(f, "    ", doc)?;
+    }
+
+    let documented = args
+        .iter()
+        .filter_map(|a| Some((a.name()?, a.direction(), doc_string(a.annotations())?)))
+        .collect::<Vec<_>>();
+    if documented.is_empty() {
+        return Ok(());
+    }
+    writeln!(f, "    ///")?;
+    writeln!(f, "    /// # Arguments")?;
+    writeln!(f, "    ///")?;
+    for (name, direction, doc) in documented {
+        let name = to_identifier(name);
+        let direction = match direction {
+            Some(ArgDirection::Out) => " (out)",
+            None | Some(ArgDirection::In) => "",
+        };
+        let mut lines = doc.lines().map(str::trim);
+        writeln!(
+            f,
+            "    /// * `{name}`{direction} - {}",
+            lines.next().unwrap_or_default()
+        )?;
+        for line in lines {
+            if line.is_empty() {
+                writeln!(f, "    ///")?;
+            } else {
+                writeln!(f, "    ///   {line}")?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
 fn inputs_output_from_args(args: &[Arg]) -> (String, String) {
     let mut inputs = vec!["&self".to_string()];
     let mut output = vec![];
@@ -286,7 +606,7 @@ pub fn pascal_case(s: &str) -> String {
 mod tests {
     use std::{error::Error, result::Result};
 
-    use super::GenTrait;
+    use super::{GenServer, GenTrait};
     use zbus::quick_xml::Node;
 
     static EXAMPLE: &str = r##"
@@ -294,10 +614,18 @@ mod tests {
   "http://www.freedesktop.org/standards/dbus/1.0/introspect.dtd">
  <node name="/com/example/sample_object0">
    <interface name="com.example.SampleInterface0">
+     <annotation name="org.gtk.GDBus.DocString" value="A sample interface."/>
      <method name="Frobate">
+       <annotation name="org.freedesktop.DBus.DocString" value="Frobate the object.
+
+         Expect side effects."/>
        <arg name="foz" type="i"/>
-       <arg name="foo" type="i" direction="in"/>
-       <arg name="bar" type="s" direction="out"/>
+       <arg name="foo" type="i" direction="in">
+         <annotation name="org.freedesktop.DBus.DocString" value="How hard to frobate."/>
+       </arg>
+       <arg name="bar" type="s" direction="out">
+         <annotation name="org.freedesktop.DBus.DocString" value="The outcome."/>
+       </arg>
        <arg name="baz" type="a{us}" direction="out"/>
        <annotation name="org.freedesktop.DBus.Deprecated" value="true"/>
      </method>
@@ -308,6 +636,9 @@ mod tests {
      <method name="MogrifyMe">
        <arg name="bar" type="(iiav)" direction="in"/>
      </method>
+     <method name="GetPair">
+       <arg name="pair" type="(us)" direction="out"/>
+     </method>
      <signal name="Changed">
        <arg name="new_value" type="b"/>
      </signal>
@@ -315,7 +646,12 @@ mod tests {
        <arg name="new_value" type="b" direction="out"/>
        <arg name="new_value2" type="b" direction="out"/>
      </signal>
-     <property name="Bar" type="y" access="readwrite"/>
+     <property name="Bar" type="y" access="readwrite">
+       <annotation name="org.gtk.GDBus.DocString" value="The bar level."/>
+     </property>
+     <property name="Version" type="s" access="read">
+       <annotation name="org.freedesktop.DBus.Property.EmitsChangedSignal" value="const"/>
+     </property>
    </interface>
    <node name="child_of_sample_object"/>
    <node name="another_child_of_sample_object"/>
@@ -334,6 +670,64 @@ mod tests {
             }
         );
         println!("{t}");
+        assert!(t.contains("/// A sample interface.\ntrait SampleInterface0 {"));
+        assert!(t.contains(
+            "    /// Frobate method
+    ///
+    /// Frobate the object.
+    ///
+    /// Expect side effects.
+    ///
+    /// # Arguments
+    ///
+    /// * `foo` - How hard to frobate.
+    /// * `bar` (out) - The outcome.
+    fn frobate("
+        ));
+        assert!(t.contains("    /// Bar property\n    ///\n    /// The bar level.\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn gen_server() -> Result<(), Box<dyn Error>> {
+        let node = Node::from_reader(EXAMPLE.as_bytes())?;
+        let t = GenServer {
+            interface: &node.interfaces()[0],
+        }
+        .to_string();
+        assert!(t.contains(
+            "/// A sample interface.
+#[zbus::export::async_trait::async_trait]
+pub trait SampleInterface0: Send + Sync + 'static {"
+        ));
+        assert!(
+            t.contains("    async fn set_bar(&mut self, value: u8) -> zbus::fdo::Result<()>;\n")
+        );
+        assert!(t.contains(
+            "pub struct SampleInterface0Server<T>(pub T);
+
+#[dbus_interface(name = \"com.example.SampleInterface0\")]
+impl<T: SampleInterface0> SampleInterface0Server<T> {"
+        ));
+        assert!(t.contains(
+            "    #[dbus_interface(out_args(\"bar\", \"baz\"))]
+    async fn frobate(&self, foz: i32, foo: i32) -> \
+             zbus::fdo::Result<(String, std::collections::HashMap<u32, String>)> {
+        self.0.frobate(foz, foo).await
+    }"
+        ));
+        assert!(t.contains(
+            "    #[dbus_interface(struct_return, out_args(\"pair\"))]
+    async fn get_pair(&self) -> zbus::fdo::Result<(u32, String)> {"
+        ));
+        assert!(t.contains(
+            "    #[dbus_interface(signal)]
+    async fn changed(ctxt: &zbus::SignalContext<'_>, new_value: bool) -> zbus::Result<()>;"
+        ));
+        assert!(t.contains(
+            "    #[dbus_interface(property(emits_changed_signal = \"const\"))]
+    async fn version(&self) -> zbus::fdo::Result<String> {"
+        ));
         Ok(())
     }
 }
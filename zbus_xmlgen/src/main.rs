@@ -18,11 +18,21 @@ use zbus::{
 };
 
 mod gen;
-use gen::GenTrait;
+use gen::{GenServer, GenTrait};
 use zvariant::ObjectPath;
 
 fn main() -> Result<(), Box<dyn Error>> {
     let input_src;
+    let mut argv: Vec<String> = args().collect();
+    // Generate server-side code, rather than proxies.
+    let server = match argv.iter().position(|arg| arg == "--server") {
+        Some(i) => {
+            argv.remove(i);
+            true
+        }
+        None => false,
+    };
+    let args = || argv.iter().cloned();
 
     let proxy = |conn: Connection, service, path| -> zbus::blocking::fdo::IntrospectableProxy<'_> {
         ProxyBuilder::new(&conn)
@@ -98,16 +108,23 @@ fn main() -> Result<(), Box<dyn Error>> {
         None => {
             eprintln!(
                 r#"Usage:
-  zbus-xmlgen <interface.xml>
-  zbus-xmlgen --system|--session <service> <object_path>
-  zbus-xmlgen --address <address> <service> <object_path>
+  zbus-xmlgen [--server] <interface.xml>
+  zbus-xmlgen [--server] --system|--session <service> <object_path>
+  zbus-xmlgen [--server] --address <address> <service> <object_path>
+
+With `--server`, a trait to implement and the interface serving it are generated for each
+interface, instead of a client proxy.
 "#
             );
             return Ok(());
         }
     };
 
-    let mut process = match Command::new("rustfmt").stdin(Stdio::piped()).spawn() {
+    let mut process = match Command::new("rustfmt")
+        .args(["--edition", "2018"])
+        .stdin(Stdio::piped())
+        .spawn()
+    {
         Err(why) => panic!("couldn't spawn rustfmt: {}", why),
         Ok(process) => process,
     };
@@ -118,17 +135,22 @@ fn main() -> Result<(), Box<dyn Error>> {
         .iter()
         .partition(|&i| i.name().starts_with(fdo_iface_prefix));
 
+    let (kind, kinds) = if server {
+        ("server", "servers")
+    } else {
+        ("proxy", "proxies")
+    };
     if let Some((first_iface, following_ifaces)) = needed_ifaces.split_first() {
         if following_ifaces.is_empty() {
             writeln!(
                 rustfmt_stdin,
-                "//! # DBus interface proxy for: `{}`",
+                "//! # DBus interface {kind} for: `{}`",
                 first_iface.name()
             )?;
         } else {
             write!(
                 rustfmt_stdin,
-                "//! # DBus interface proxies for: `{}`",
+                "//! # DBus interface {kinds} for: `{}`",
                 first_iface.name()
             )?;
             for iface in following_ifaces {
@@ -138,6 +160,11 @@ fn main() -> Result<(), Box<dyn Error>> {
         }
     }
 
+    let (section, page) = if server {
+        ("Writing a server interface", "server")
+    } else {
+        ("Writing a client proxy", "client")
+    };
     write!(
         rustfmt_stdin,
         "//!
@@ -147,7 +174,7 @@ fn main() -> Result<(), Box<dyn Error>> {
          //! You may prefer to adapt it, instead of using it verbatim.
          //!
          //! More information can be found in the
-         //! [Writing a client proxy](https://dbus2.github.io/zbus/client.html)
+         //! [{section}](https://dbus2.github.io/zbus/{page}.html)
          //! section of the zbus documentation.
          //!
         ",
@@ -155,7 +182,24 @@ fn main() -> Result<(), Box<dyn Error>> {
         env!("CARGO_PKG_VERSION"),
         input_src,
     )?;
-    if !fdo_standard_ifaces.is_empty() {
+    if server {
+        write!(
+            rustfmt_stdin,
+            "//! Implement the generated traits, and serve the implementations through the
+             //! generated interfaces, e.g `ObjectServer::at(path, FooServer(MyFoo))`.
+             //!
+            "
+        )?;
+    }
+    if server && !fdo_standard_ifaces.is_empty() {
+        write!(
+            rustfmt_stdin,
+            "//! The standard DBus interfaces (`org.freedesktop.DBus.*`) are implemented by
+             //! the zbus `ObjectServer` itself, so `{}` did not generate code for them.
+            ",
+            env!("CARGO_BIN_NAME")
+        )?;
+    } else if !fdo_standard_ifaces.is_empty() {
         write!(rustfmt_stdin,
             "//! This DBus object implements
              //! [standard DBus interfaces](https://dbus.freedesktop.org/doc/dbus-specification.html),
@@ -175,20 +219,29 @@ fn main() -> Result<(), Box<dyn Error>> {
             env!("CARGO_BIN_NAME")
         )?;
     }
+    let import = if server {
+        "dbus_interface"
+    } else {
+        "dbus_proxy"
+    };
     write!(
         rustfmt_stdin,
         "
-        use zbus::dbus_proxy;
+        use zbus::{import};
         "
     )?;
     for iface in &needed_ifaces {
         writeln!(rustfmt_stdin)?;
-        let gen = GenTrait {
-            interface: iface,
-            service: service.as_ref(),
-            path: path.as_ref(),
-        }
-        .to_string();
+        let gen = if server {
+            GenServer { interface: iface }.to_string()
+        } else {
+            GenTrait {
+                interface: iface,
+                service: service.as_ref(),
+                path: path.as_ref(),
+            }
+            .to_string()
+        };
         rustfmt_stdin.write_all(gen.as_bytes())?;
     }
     process.wait()?;
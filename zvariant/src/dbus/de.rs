@@ -386,6 +386,9 @@ where
         de.0.container_depths = de.0.container_depths.inc_array()?;
 
         let len = B::read_u32(de.0.next_slice(4)?) as usize;
+        if len > MAX_ARRAY_LEN_DBUS {
+            return Err(Error::ArrayTooLong(len));
+        }
         let element_signature = de.0.sig_parser.next_signature()?;
         let element_alignment = alignment_for_signature(&element_signature, EncodingFormat::DBus)?;
         let mut element_signature_len = element_signature.len();
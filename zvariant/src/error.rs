@@ -63,6 +63,8 @@ pub enum Error {
     OutOfBounds,
     /// The maximum allowed depth for containers in encoding was exceeded.
     MaxDepthExceeded(MaxDepthExceeded),
+    /// An array (of the given length in bytes) exceeds the maximum length allowed by the format.
+    ArrayTooLong(usize),
 }
 
 assert_impl_all!(Error: Send, Sync, Unpin);
@@ -77,6 +79,7 @@ impl PartialEq for Error {
             (Error::PaddingNot0(p), Error::PaddingNot0(other)) => p == other,
             (Error::UnknownFd, Error::UnknownFd) => true,
             (Error::MaxDepthExceeded(max1), Error::MaxDepthExceeded(max2)) => max1 == max2,
+            (Error::ArrayTooLong(len1), Error::ArrayTooLong(len2)) => len1 == len2,
             (_, _) => false,
         }
     }
@@ -87,7 +90,7 @@ impl error::Error for Error {
         match self {
             #[allow(deprecated)]
             Error::Io(e) => Some(e),
-            Error::InputOutput(e) => Some(e),
+            Error::InputOutput(e) => Some(&**e),
             Error::Utf8(e) => Some(e),
             _ => None,
         }
@@ -122,6 +125,9 @@ impl fmt::Display for Error {
                 "Out of bounds range specified",
             ),
             Error::MaxDepthExceeded(max) => write!(f, "{max}"),
+            Error::ArrayTooLong(len) => {
+                write!(f, "Array of {len} bytes exceeds the maximum allowed length")
+            }
         }
     }
 }
@@ -146,6 +152,7 @@ impl Clone for Error {
             }
             Error::OutOfBounds => Error::OutOfBounds,
             Error::MaxDepthExceeded(max) => Error::MaxDepthExceeded(*max),
+            Error::ArrayTooLong(len) => Error::ArrayTooLong(*len),
         }
     }
 }
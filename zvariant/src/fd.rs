@@ -1,6 +1,6 @@
 use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 use static_assertions::assert_impl_all;
-use std::os::unix::io;
+use std::{convert::TryFrom, os::unix::io};
 
 use crate::{Basic, EncodingFormat, Signature, Type};
 
@@ -120,14 +120,30 @@ impl<'de> Deserialize<'de> for OwnedFd {
     where
         D: Deserializer<'de>,
     {
-        let fd = unsafe { libc::dup(i32::deserialize(deserializer)?) };
+        OwnedFd::dup(i32::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+impl OwnedFd {
+    fn dup(fd: io::RawFd) -> std::io::Result<Self> {
+        let fd = unsafe { libc::dup(fd) };
         if fd < 0 {
-            return Err(D::Error::custom(std::io::Error::last_os_error()));
+            return Err(std::io::Error::last_os_error());
         }
+
         Ok(OwnedFd { inner: fd })
     }
 }
 
+/// Duplicates the file descriptor, just like deserialization does.
+impl TryFrom<Fd> for OwnedFd {
+    type Error = crate::Error;
+
+    fn try_from(fd: Fd) -> Result<Self, Self::Error> {
+        OwnedFd::dup(fd.0).map_err(|e| crate::Error::InputOutput(e.into()))
+    }
+}
+
 impl io::FromRawFd for OwnedFd {
     unsafe fn from_raw_fd(fd: io::RawFd) -> Self {
         Self { inner: fd }
@@ -6,7 +6,7 @@ use crate::{
 };
 
 #[cfg(unix)]
-use crate::Fd;
+use crate::{Fd, OwnedFd};
 
 use std::{collections::HashMap, convert::TryFrom, hash::BuildHasher};
 
@@ -91,6 +91,29 @@ value_try_from!(Str, String);
 value_try_from_ref!(Str, str);
 value_try_from_ref_clone!(Str, String);
 
+// The file descriptor is duplicated since the value doesn't own it.
+#[cfg(unix)]
+impl<'a> TryFrom<Value<'a>> for OwnedFd {
+    type Error = Error;
+
+    fn try_from(value: Value<'a>) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+#[cfg(unix)]
+impl<'a> TryFrom<&'a Value<'_>> for OwnedFd {
+    type Error = Error;
+
+    fn try_from(value: &'a Value<'_>) -> Result<Self, Self::Error> {
+        if let Value::Fd(fd) = value {
+            Self::try_from(*fd)
+        } else {
+            Err(Error::IncorrectType)
+        }
+    }
+}
+
 impl<'a, T> TryFrom<Value<'a>> for Vec<T>
 where
     T: TryFrom<Value<'a>>,
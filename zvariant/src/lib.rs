@@ -350,6 +350,17 @@ mod tests {
         basic_type_test!(LE, DBus, Fd::from(42), 4, Fd, 4, Fd, 8);
         #[cfg(feature = "gvariant")]
         basic_type_test!(LE, GVariant, Fd::from(42), 4, Fd, 4, Fd, 6);
+
+        // Converting to an owned FD duplicates it.
+        use std::os::unix::io::AsRawFd;
+        let stdout = std::io::stdout();
+        let value = Value::from(Fd::from(&stdout));
+        let owned = crate::OwnedFd::try_from(&value).unwrap();
+        assert_ne!(owned.as_raw_fd(), stdout.as_raw_fd());
+        assert!(matches!(
+            crate::OwnedFd::try_from(Value::from(42_u8)),
+            Err(Error::IncorrectType)
+        ));
     }
 
     #[test]
@@ -8,7 +8,7 @@ use crate::{
 };
 
 #[cfg(unix)]
-use crate::Fd;
+use crate::{Fd, OwnedFd};
 
 #[cfg(feature = "gvariant")]
 use crate::Maybe;
@@ -78,6 +78,8 @@ ov_try_from!(Str<'static>);
 ov_try_from!(Structure<'static>);
 #[cfg(unix)]
 ov_try_from!(Fd);
+#[cfg(unix)]
+ov_try_from!(OwnedFd);
 
 ov_try_from_ref!(u8);
 ov_try_from_ref!(bool);
@@ -99,6 +101,8 @@ ov_try_from_ref!(&'a Structure<'a>);
 ov_try_from_ref!(&'a Maybe<'a>);
 #[cfg(unix)]
 ov_try_from_ref!(Fd);
+#[cfg(unix)]
+ov_try_from_ref!(OwnedFd);
 
 impl<'a, T> TryFrom<OwnedValue> for Vec<T>
 where
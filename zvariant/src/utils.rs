@@ -12,6 +12,8 @@ pub const ARRAY_SIGNATURE_CHAR: char = 'a';
 /// The prefix of ARRAY type signature, as a string. Provided for manual signature creation.
 pub const ARRAY_SIGNATURE_STR: &str = "a";
 pub(crate) const ARRAY_ALIGNMENT_DBUS: usize = 4;
+// The maximum length of an array in D-Bus format, in bytes (64 MiB).
+pub(crate) const MAX_ARRAY_LEN_DBUS: usize = 64 * 1024 * 1024;
 /// The opening character of STRUCT type signature. Provided for manual signature creation.
 pub const STRUCT_SIG_START_CHAR: char = '(';
 /// The closing character of STRUCT type signature. Provided for manual signature creation.